@@ -149,7 +149,7 @@ fn main() -> Result<(), IntersectError> {
         // draw active lines
         draw::set_draw_color(enums::Color::Red);
         for line_index in alg_data_b.0.get_active_lines().iter().flatten() {
-            let line = alg_data_b.0.get_lines()[*line_index];
+            let line = alg_data_b.0.get_line(*line_index).unwrap();
             draw::draw_line(
                 line.start.x as i32,
                 line.start.y as i32,
@@ -292,7 +292,7 @@ fn float_to_string(value: f64) -> String {
 fn generate_test(data: AlgoType) {
     let data = data.borrow_mut();
 
-    let lines = data.0.get_lines().iter().count();
+    let lines = data.0.line_count();
     println!("let _l:[[f64;4];{}]=[", lines);
     for (i, l) in data.0.get_lines().iter().enumerate() {
         print!(
@@ -370,7 +370,7 @@ fn print_results(data: AlgoType) {
         }
         println!("In total {} unique intersection points.", intersections);
 
-        let size = data.0.get_lines().len();
+        let size = data.0.line_count();
 
         println!(
                 "Made {} calls to intersect() for {} line segments. Brute force approach would need at least {} calls (n²/2)",
@@ -0,0 +1,320 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Statistics accumulated during the sweep.
+//!
+//! The handlers of this module implement [`SweepEventHandler`] and are meant for
+//! [`AlgorithmData::aggregate()`](crate::algorithm::AlgorithmData::aggregate), which hands
+//! every intersection to the handler without storing it:
+//! * [`IntersectionCounter`] counts the intersection points, the intersecting pairs and the
+//!   intersection points of every line
+//! * [`LayerCrossingCounter`] counts the intersection points between the layers of the lines
+//! * [`CutLengths`] measures the pieces the lines are cut into by the intersections
+//!
+//! Other aggregates are a matter of implementing [`SweepEventHandler`], the
+//! [`SweepContext`] gives access to the lines by index.
+
+use crate::handler::{SweepContext, SweepEventHandler};
+use crate::ulps_eq_c;
+use num_traits::Float;
+
+/// Counts the intersections, see the [module documentation](self)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntersectionCounter {
+    points: usize,
+    pairs: usize,
+    line_counts: Vec<usize>,
+}
+
+impl IntersectionCounter {
+    /// The number of intersection points
+    pub fn points(&self) -> usize {
+        self.points
+    }
+
+    /// The number of intersecting line pairs, a point where `n` lines meet counts as
+    /// `n * (n - 1) / 2` pairs
+    pub fn pairs(&self) -> usize {
+        self.pairs
+    }
+
+    /// The number of intersection points on each line, by line index. Lines after the last
+    /// intersecting line are not included.
+    pub fn line_counts(&self) -> &[usize] {
+        &self.line_counts
+    }
+}
+
+impl<T> SweepEventHandler<T> for IntersectionCounter
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn on_intersection(
+        &mut self,
+        _position: geo::Coordinate<T>,
+        lines: &[usize],
+        _context: &SweepContext<'_, T>,
+    ) {
+        self.points += 1;
+        self.pairs += lines.len() * lines.len().saturating_sub(1) / 2;
+        for line in lines.iter() {
+            if *line >= self.line_counts.len() {
+                self.line_counts.resize(*line + 1, 0);
+            }
+            self.line_counts[*line] += 1;
+        }
+    }
+}
+
+/// Counts the intersection points between layers, the layer of each line is given by a
+/// closure. A point is counted once for every pair of layers meeting there, lines of the
+/// same layer intersecting count for the pair `(layer, layer)`.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::aggregate::LayerCrossingCounter;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [0., 10., 10., 0.],
+///     [0., 1., 10., 1.],
+///     [0., 2., 10., 2.],
+/// ]);
+/// // the first two lines are roads, the others are rivers
+/// let mut counter = LayerCrossingCounter::new(|line| if line < 2 { 0 } else { 1 });
+/// AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .aggregate(&mut counter)?;
+/// assert_eq!(counter.counts(), [((0, 0), 1), ((0, 1), 4)]);
+/// assert_eq!(counter.count(1, 0), 4);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub struct LayerCrossingCounter<F>
+where
+    F: Fn(usize) -> usize,
+{
+    layer_of: F,
+    counts: ahash::AHashMap<(usize, usize), usize>,
+}
+
+impl<F> LayerCrossingCounter<F>
+where
+    F: Fn(usize) -> usize,
+{
+    pub fn new(layer_of: F) -> Self {
+        Self {
+            layer_of,
+            counts: ahash::AHashMap::default(),
+        }
+    }
+
+    /// The number of intersection points between two layers, in any order
+    pub fn count(&self, layer_a: usize, layer_b: usize) -> usize {
+        let key = (layer_a.min(layer_b), layer_a.max(layer_b));
+        self.counts.get(&key).copied().unwrap_or(0)
+    }
+
+    /// The counts of all the layer pairs that intersect, sorted by layer pair. The lower layer
+    /// comes first in each pair.
+    pub fn counts(&self) -> Vec<((usize, usize), usize)> {
+        let mut rv: Vec<_> = self.counts.iter().map(|(k, v)| (*k, *v)).collect();
+        rv.sort_unstable();
+        rv
+    }
+}
+
+impl<T, F> SweepEventHandler<T> for LayerCrossingCounter<F>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    F: Fn(usize) -> usize,
+{
+    fn on_intersection(
+        &mut self,
+        _position: geo::Coordinate<T>,
+        lines: &[usize],
+        _context: &SweepContext<'_, T>,
+    ) {
+        let layers: Vec<usize> = lines.iter().map(|l| (self.layer_of)(*l)).collect();
+        let mut pairs = Vec::<(usize, usize)>::new();
+        for (i, a) in layers.iter().enumerate() {
+            for b in layers.iter().skip(i + 1) {
+                pairs.push((*a.min(b), *a.max(b)));
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        for pair in pairs {
+            *self.counts.entry(pair).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Measures the pieces the lines are cut into by the intersection points, without storing
+/// the pieces. Zero length pieces, e.g. at intersections on an end point, are not counted.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::aggregate::CutLengths;
+/// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [2., -1., 2., 1.]]);
+/// let mut lengths = CutLengths::<f64>::default();
+/// AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .aggregate(&mut lengths)?;
+/// assert_eq!(lengths.pieces(), 4);
+/// assert_eq!(lengths.shortest(), Some(1.));
+/// assert_eq!(lengths.longest(), Some(8.));
+/// assert_eq!(lengths.total(), 12.);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct CutLengths<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pieces: usize,
+    total: T,
+    shortest: Option<T>,
+    longest: Option<T>,
+    // the last cut of every active line
+    last_cut: ahash::AHashMap<usize, geo::Coordinate<T>>,
+}
+
+impl<T> Default for CutLengths<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn default() -> Self {
+        Self {
+            pieces: 0,
+            total: T::zero(),
+            shortest: None,
+            longest: None,
+            last_cut: ahash::AHashMap::default(),
+        }
+    }
+}
+
+impl<T> CutLengths<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The number of pieces
+    pub fn pieces(&self) -> usize {
+        self.pieces
+    }
+
+    /// The total length of the pieces
+    pub fn total(&self) -> T {
+        self.total
+    }
+
+    /// The length of the shortest piece, None if there are no pieces
+    pub fn shortest(&self) -> Option<T> {
+        self.shortest
+    }
+
+    /// The length of the longest piece, None if there are no pieces
+    pub fn longest(&self) -> Option<T> {
+        self.longest
+    }
+
+    /// The mean length of the pieces, None if there are no pieces
+    pub fn mean(&self) -> Option<T> {
+        if self.pieces == 0 {
+            None
+        } else {
+            Some(self.total / T::from(self.pieces).unwrap())
+        }
+    }
+
+    /// Adds the piece of `line` from its last cut to `position`
+    fn cut(&mut self, line: usize, position: geo::Coordinate<T>) {
+        if let Some(last) = self.last_cut.insert(line, position) {
+            if ulps_eq_c(&last, &position) {
+                return;
+            }
+            let length = (position.x - last.x).hypot(position.y - last.y);
+            self.pieces += 1;
+            self.total = self.total + length;
+            self.shortest = Some(self.shortest.map_or(length, |s| s.min(length)));
+            self.longest = Some(self.longest.map_or(length, |l| l.max(length)));
+        }
+    }
+}
+
+impl<T> SweepEventHandler<T> for CutLengths<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn on_segment_start(&mut self, line: usize, context: &SweepContext<'_, T>) {
+        if let Some(l) = context.line(line) {
+            let _ = self.last_cut.insert(line, l.start);
+        }
+    }
+
+    fn on_segment_end(&mut self, line: usize, context: &SweepContext<'_, T>) {
+        if let Some(l) = context.line(line) {
+            self.cut(line, l.end);
+        }
+        let _ = self.last_cut.remove(&line);
+    }
+
+    fn on_intersection(
+        &mut self,
+        position: geo::Coordinate<T>,
+        lines: &[usize],
+        _context: &SweepContext<'_, T>,
+    ) {
+        for line in lines.iter() {
+            // lines ending at this point have already been removed
+            if self.last_cut.contains_key(line) {
+                self.cut(*line, position);
+            }
+        }
+    }
+}
@@ -43,13 +43,28 @@ License instead of this License. But first, please read <https://www.gnu.org/
 licenses /why-not-lgpl.html>.
  */
 
-use super::{intersect, ulps_eq_c};
+use super::{intersect_with_tolerance, Closedness, Tolerance};
+use crate::background::BackgroundCompute;
+use crate::collection::{into_geometry_collection, Provenance};
+use crate::diagnostic::{format_dump, json_number, json_string, Diagnostics};
+use crate::double;
+use crate::handler::{SweepContext, SweepEventHandler};
+use crate::intern::CoordinateInterner;
+use crate::list::{IntersectionList, Intersections};
+use crate::noding::{node_lines, NodedSegment};
+use crate::predicates;
+use crate::segments::SegmentStore;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{PendingEvent, SweepSnapshot};
+use crate::status::SweepStatus;
 use core::fmt;
-use num_traits::{Float, Zero};
+use num_traits::Float;
 use std::cmp;
-use std::convert::identity;
 use std::fmt::Debug;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 #[derive(Clone, Copy)]
 pub struct SiteEventKey<T>
@@ -58,6 +73,8 @@ where
     T::Epsilon: Copy,
 {
     pub pos: geo::Coordinate<T>,
+    // the tolerance used when comparing positions
+    tolerance: Tolerance<T>,
 }
 
 impl<T> SiteEventKey<T>
@@ -68,8 +85,14 @@ where
     pub fn new(x: T, y: T) -> Self {
         Self {
             pos: geo::Coordinate { x, y },
+            tolerance: Tolerance::default(),
         }
     }
+
+    /// Creates a key with a custom tolerance, only keys with the same tolerance should be compared.
+    pub fn with_tolerance(pos: geo::Coordinate<T>, tolerance: Tolerance<T>) -> Self {
+        Self { pos, tolerance }
+    }
 }
 
 impl<T> Debug for SiteEventKey<T>
@@ -91,8 +114,8 @@ where
     T::Epsilon: Copy,
 {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        if approx::ulps_eq!(&self.pos.y, &other.pos.y) {
-            if approx::ulps_eq!(&self.pos.x, &other.pos.x) {
+        if self.tolerance.ulps_eq(self.pos.y, other.pos.y) {
+            if self.tolerance.ulps_eq(self.pos.x, other.pos.x) {
                 Some(cmp::Ordering::Equal)
             } else {
                 self.pos.x.partial_cmp(&other.pos.x)
@@ -109,226 +132,7 @@ where
     T::Epsilon: Copy,
 {
     fn eq(&self, other: &Self) -> bool {
-        ulps_eq_c(&self.pos, &other.pos)
-    }
-}
-
-/// A container struct that keeps track of the lines around a pivot point.
-/// It only stores the lines with highest x value left of pivot point, and lines with lowest x
-/// value right of the point. Secondarily it prioritizes according to slope of the line, lines
-/// leaning towards pivot point have priority.
-struct MinMax<T>
-where
-    T: Float + approx::UlpsEq + geo::CoordFloat,
-    T::Epsilon: Copy,
-{
-    best_left: Option<T>,
-    slope: MinMaxSlope<T>,
-    best_right: Option<T>,
-}
-
-impl<T> MinMax<T>
-where
-    T: Sized + Float + approx::UlpsEq + geo::CoordFloat,
-    T::Epsilon: Copy,
-{
-    fn new() -> Self {
-        Self {
-            best_left: None,
-            best_right: None,
-            slope: MinMaxSlope::new(),
-        }
-    }
-
-    /// keep track of the candidates closest (on both sides) to pivot_x
-    fn update(&mut self, pivot_x: T, candidate_x: T, candidate_slope: T, candidate_index: usize) {
-        if candidate_x < pivot_x {
-            /*println!(
-                "Left:looking at {} candidate_x:{} candidate_slope:{}",
-                candidate_index, candidate_x, candidate_slope
-            );*/
-            // handle left side
-            if let Some(current_min) = self.best_left {
-                if approx::ulps_eq!(&current_min, &candidate_x) {
-                    self.slope
-                        .update_left(false, candidate_slope, candidate_index);
-                } else if current_min < candidate_x {
-                    // this candidate is better.
-                    self.slope
-                        .update_left(true, candidate_slope, candidate_index);
-                    self.best_left = Some(candidate_x);
-                } else {
-                    /*println!(
-                        "Left2:rejecting {} candidate_x:{} current_x:{} candidate_slope:{}, current_slope:{}",
-                        candidate_index, candidate_x, current_min, candidate_slope, self.slope.best_left.unwrap()
-                    )*/
-                }
-            } else {
-                // First sample
-                self.best_left = Some(candidate_x);
-                self.slope
-                    .update_left(false, candidate_slope, candidate_index);
-            }
-        } else if candidate_x > pivot_x {
-            /*println!(
-                "Right:looking at {} candidate_x:{} candidate_slope:{}",
-                candidate_index, candidate_x, candidate_slope
-            );*/
-            // handle right side
-            if let Some(current_max) = self.best_right {
-                if approx::ulps_eq!(&current_max, &candidate_x) {
-                    self.slope
-                        .update_right(false, candidate_slope, candidate_index);
-                } else if current_max > candidate_x {
-                    // this candidate is better.
-                    self.slope
-                        .update_right(true, candidate_slope, candidate_index);
-                    self.best_right = Some(candidate_x);
-                } else {
-                    /*println!(
-                        "Right2:rejecting {} candidate_x:{} current_x:{} candidate_slope:{}, current_slope:{}",
-                        candidate_index, candidate_x, current_max, candidate_slope, self.slope.best_right.unwrap()
-                    )*/
-                }
-            } else {
-                // First sample
-                self.best_right = Some(candidate_x);
-                self.slope
-                    .update_right(false, candidate_slope, candidate_index);
-            }
-        }
-    }
-
-    /// clear all data
-    fn clear(&mut self) {
-        self.best_left = None;
-        self.best_right = None;
-        self.slope.clear();
-    }
-}
-
-struct MinMaxSlope<T>
-where
-    T: Float + approx::UlpsEq + geo::CoordFloat,
-    T::Epsilon: Copy,
-{
-    best_left: Option<T>, // slope
-    candidates_left: Vec<usize>,
-
-    best_right: Option<T>, // slope
-    candidates_right: Vec<usize>,
-}
-
-impl<T> MinMaxSlope<T>
-where
-    T: Sized + Float + approx::UlpsEq + geo::CoordFloat,
-    T::Epsilon: Copy,
-{
-    fn new() -> Self {
-        Self {
-            best_left: None,
-            candidates_left: Vec::<usize>::new(),
-            best_right: None,
-            candidates_right: Vec::<usize>::new(),
-        }
-    }
-
-    /// sort candidates based on slope, keep only the ones with 'flattest' angle to the left and right
-    fn update_both(&mut self, candidate_index: usize, lines: &[geo::Line<T>]) {
-        let line = lines[candidate_index];
-        let candidate_slope = if approx::ulps_eq!(&line.end.y, &line.start.y) {
-            T::infinity()
-        } else {
-            (line.end.x - line.start.x) / (line.end.y - line.start.y)
-        };
-
-        self.update_left(false, candidate_slope, candidate_index);
-        self.update_right(false, candidate_slope, candidate_index);
-    }
-
-    /// sort candidates based on slope, keep only the best to the left -> lowest abs(negative slope value)
-    fn update_left(&mut self, clear: bool, candidate_slope: T, candidate_index: usize) {
-        if clear {
-            self.candidates_left.clear();
-            self.best_left = None;
-        }
-        /*println!(
-            "Left:looking at {} candidate_slope:{}",
-            candidate_index, candidate_slope
-        );*/
-        // handle left side
-        if let Some(current_slope) = self.best_left {
-            if approx::ulps_eq!(&current_slope, &candidate_slope) {
-                // this candidate is just as good as the others already found
-                self.candidates_left.push(candidate_index);
-            } else if candidate_slope < current_slope {
-                // this candidate is better.
-                self.candidates_left.clear();
-                self.candidates_left.push(candidate_index);
-                self.best_left = Some(candidate_slope);
-            } else {
-                /*println!(
-                    "Left1:rejecting {} candidate_slope:{}, current_slope:{}",
-                    candidate_index, candidate_slope, current_slope
-                )*/
-            }
-        } else {
-            // First sample
-            self.best_left = Some(candidate_slope);
-            self.candidates_left.push(candidate_index);
-        }
-        /*println!(
-            "Left: best slope is {:?} ,best_left:{}",
-            self.candidates_left,
-            self.best_left.unwrap()
-        )*/
-    }
-
-    /// sort candidates based on slope, keep only the best to the left -> lowest positive slope value
-    fn update_right(&mut self, clear: bool, candidate_slope: T, candidate_index: usize) {
-        /*println!(
-            "Right:looking at {} candidate_slope:{}",
-            candidate_index, candidate_slope
-        );*/
-        if clear {
-            self.candidates_right.clear();
-            self.best_right = None;
-        }
-        // handle right side
-        if let Some(current_slope) = self.best_right {
-            if approx::ulps_eq!(&current_slope, &candidate_slope) {
-                // this candidate is just as good as the others already found
-                self.candidates_right.push(candidate_index);
-            } else if candidate_slope > current_slope {
-                // this candidate is better.
-                self.candidates_right.clear();
-                self.candidates_right.push(candidate_index);
-                self.best_right = Some(candidate_slope);
-            } else {
-                /*println!(
-                    "Right1:rejecting {} candidate_slope:{}, current_slope:{}",
-                    candidate_index, candidate_slope, current_slope
-                )*/
-            }
-        } else {
-            // First sample
-            self.best_right = Some(candidate_slope);
-            self.candidates_right.push(candidate_index);
-        }
-        /*println!(
-            "Right: best slope is {:?} ,best_right:{}",
-            self.candidates_right,
-            self.best_right.unwrap()
-        )*/
-    }
-
-    /// clear all data
-    fn clear(&mut self) {
-        self.best_left = None;
-        self.candidates_left.clear();
-
-        self.best_right = None;
-        self.candidates_right.clear();
+        self.tolerance.ulps_eq_c(&self.pos, &other.pos)
     }
 }
 
@@ -387,47 +191,221 @@ where
     pub fn get_intersections(&self) -> &Option<Vec<usize>> {
         &self.intersection
     }
+
+    /// Moves the line lists of `other` into this event
+    fn merge(&mut self, other: SiteEvent<T>) {
+        fn append(to: &mut Option<Vec<usize>>, from: Option<Vec<usize>>) {
+            if let Some(mut from) = from {
+                if let Some(to) = to {
+                    to.append(&mut from);
+                } else {
+                    *to = Some(from);
+                }
+            }
+        }
+        append(&mut self.drop, other.drop);
+        append(&mut self.add, other.add);
+        append(&mut self.intersection, other.intersection);
+    }
 }
 
-/// Returns *one* point of intersection between the `sweepline` and `other`
-/// Second return value is the slope of the line
-fn sweepline_intersection<T>(sweepline: geo::Coordinate<T>, other: &geo::Line<T>) -> Option<(T, T)>
+/// An entry of the event queue heap
+struct QueueEntry<T>
 where
-    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
     T::Epsilon: Copy,
 {
-    // line equation: y=slope*x+d => d=y-slope*x => x = (y-d)/slope
-    let y1 = other.start.y;
-    let y2 = other.end.y;
-    let x1 = other.start.x;
-    let x2 = other.end.x;
-    if approx::ulps_eq!(&y1, &y2) {
-        // horizontal line: return to the point right of sweepline.x, if any
-        // Any point to the left are supposedly already handled.
-        if sweepline.x < x2 {
-            return Some((x2, T::zero()));
-        } else {
-            return None;
+    key: SiteEventKey<T>,
+    // insertion order, keeps the heap order total
+    sequence: usize,
+    event: SiteEvent<T>,
+}
+
+impl<T> Ord for QueueEntry<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        // reversed, so that std::collections::BinaryHeap pops the smallest key first
+        other
+            .key
+            .partial_cmp(&self.key)
+            .unwrap_or(cmp::Ordering::Equal)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl<T> PartialOrd for QueueEntry<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for QueueEntry<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<T> Eq for QueueEntry<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+}
+
+/// The queue of unhandled site events, a binary heap ordered by [`SiteEventKey`].
+/// Several events may be queued at the same position, they are merged lazily when the
+/// position is popped. So a push never has to search the queue.
+pub struct EventQueue<T>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    heap: std::collections::BinaryHeap<QueueEntry<T>>,
+    sequence: usize,
+}
+
+impl<T> Default for EventQueue<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn default() -> Self {
+        Self {
+            heap: std::collections::BinaryHeap::new(),
+            sequence: 0,
+        }
+    }
+}
+
+impl<T> EventQueue<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns the number of queued events, events at the same position are counted once
+    /// for each time they were pushed.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Iterates over the queued events, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&SiteEventKey<T>, &SiteEvent<T>)> {
+        self.heap.iter().map(|e| (&e.key, &e.event))
+    }
+
+    pub(crate) fn push(&mut self, key: SiteEventKey<T>, event: SiteEvent<T>) {
+        self.heap.push(QueueEntry {
+            key,
+            sequence: self.sequence,
+            event,
+        });
+        self.sequence += 1;
+    }
+
+    /// Moves many events into the queue at once, this is cheaper than pushing them one by one
+    pub(crate) fn extend(&mut self, events: Vec<(SiteEventKey<T>, SiteEvent<T>)>) {
+        let sequence = self.sequence;
+        self.sequence += events.len();
+        self.heap.extend(
+            events
+                .into_iter()
+                .enumerate()
+                .map(|(i, (key, event))| QueueEntry {
+                    key,
+                    sequence: sequence + i,
+                    event,
+                }),
+        );
+    }
+
+    /// Removes the first position from the queue, with all the events at that position merged
+    pub(crate) fn pop_pair(&mut self) -> Option<(SiteEventKey<T>, SiteEvent<T>)> {
+        let QueueEntry { key, mut event, .. } = self.heap.pop()?;
+        while self.heap.peek().map_or(false, |next| next.key == key) {
+            event.merge(self.heap.pop().unwrap().event);
+        }
+        Some((key, event))
+    }
+}
+
+/// The precomputed sort key of a line end point, used when the input lines are added.
+struct EndPointKey<T> {
+    y: T,
+    x: T,
+    line: u32,
+    is_drop: bool,
+}
+
+impl<T: Copy> EndPointKey<T> {
+    #[inline(always)]
+    fn new(pos: geo::Coordinate<T>, line: u32, is_drop: bool) -> Self
+    where
+        T: geo::CoordNum,
+    {
+        Self {
+            y: pos.y,
+            x: pos.x,
+            line,
+            is_drop,
         }
     }
 
-    if approx::ulps_eq!(&x1, &x2) {
-        return Some((x1, T::infinity()));
+    #[inline(always)]
+    fn line(&self) -> usize {
+        self.line as usize
+    }
+
+    #[inline(always)]
+    fn is_drop(&self) -> bool {
+        self.is_drop
     }
+}
+
+/// A transform applied to the input coordinates, see [`AlgorithmData::with_transform()`]
+type CoordinateTransform<T> = Box<dyn Fn(geo::Coordinate<T>) -> geo::Coordinate<T> + Send + Sync>;
+
+/// The number of events the background sweep handles between progress updates
+const BACKGROUND_EVENTS_PER_CHECK: usize = 1024;
+
+/// A future that is pending only on its first poll, after asking to be polled again. This
+/// lets the executor run other tasks in between, see [`AlgorithmData::compute_async()`].
+struct YieldNow(bool);
 
-    let slope: T = (y2 - y1) / (x2 - x1);
-    if slope.is_nan() {
-        panic!("D==0 should not happen!");
-        //return None;
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
     }
-    let d = y1 - slope * x1;
-    Some(((sweepline.y - d) / slope, slope))
 }
 
 /// Contains the data the sweep-line intersection algorithm needs to operate.
 /// Most of these containers are stored inside an Option. This makes it possible
 /// to take() them and make the borrow-checker happy.
-pub struct AlgorithmData<T>
+/// Each line can optionally carry a payload of type `P` (e.g. a feature id), see
+/// [`AlgorithmData::with_payload_lines()`].
+pub struct AlgorithmData<T, P = ()>
 where
     T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
     T::Epsilon: Copy,
@@ -436,28 +414,68 @@ where
     sweepline_pos: geo::Coordinate<T>,
     // Stop when first intersection is found
     stop_at_first_intersection: bool,
+    // Queue the end points unsorted, the event queue orders them as they are popped
+    lazy_setup: bool,
     // Allow start&end points to intersect
     // i.e. don't report them as an intersections.
     // An endpoint intersecting any other point of another line will still be
     // counted as an intersection.
     pub ignore_end_point_intersections: bool,
+    // Treat the lines as directed, only the end of one line meeting the start of another line
+    // is not an intersection
+    directed_end_points: bool,
+    // The tolerance used when comparing coordinates
+    tolerance: Tolerance<T>,
+    // Run the algorithm in f64 and round the results back to T
+    f64_upcast: bool,
+    // Use double-double arithmetic for intersection points and the sweep-line ordering
+    double_double: bool,
+    // Order the active lines with the interval filtered predicates
+    filtered_predicates: bool,
+    // Optional transform applied to every input coordinate
+    transform: Option<CoordinateTransform<T>>,
     // The unhandled events
-    site_events: Option<rb_tree::RBMap<SiteEventKey<T>, SiteEvent<T>>>,
+    site_events: Option<EventQueue<T>>,
     // The lines we are considering at any given point in time
     active_lines: Option<ahash::AHashSet<usize>>,
+    // The active lines, ordered left to right along the sweep-line
+    status: Option<SweepStatus>,
+    // The intersection point (if any) of every pair of lines already tested for
+    // intersection, keyed by (lower index, higher index)
+    pair_cache: ahash::AHashMap<(usize, usize), Option<geo::Coordinate<T>>>,
+    pair_cache_hits: usize,
+    // Optional interner snapping reported intersection points to already reported points
+    interner: Option<CoordinateInterner<T>>,
     // A list of intersection points and the line segments involved in each intersection
     result: Option<rb_tree::RBMap<SiteEventKey<T>, Vec<usize>>>,
     intersection_calls: usize,
-    // The 'best' lines surrounding the event point but not directly connected to the point.
-    neighbour_priority: Option<MinMax<T>>,
-    // The 'best' lines directly connected to the event point.
-    connected_priority: Option<MinMaxSlope<T>>,
+    // The number of events handled since the last reset
+    handled_events: usize,
+    // The number of intersection points reported since the last reset, kept or not
+    reported_intersections: usize,
     // The input geometry. These lines are re-arranged so that Line.start.y <= Line.end.y
     // These are never changed while the algorithm is running.
-    lines: Vec<geo::Line<T>>,
+    lines: SegmentStore<T>,
+    // True for the lines whose start and end points were swapped when re-arranged
+    reversed: Vec<bool>,
+    // The payloads of the lines, either empty or one for each line.
+    payloads: Vec<P>,
+    // Optional sink of the diagnostic dumps and the recent event history
+    diagnostics: Option<Diagnostics<T>>,
+    // Keep the intersections in `result`, they are dropped once handed to the event handler
+    // when aggregating
+    keep_results: bool,
 }
 
-impl<T> Default for AlgorithmData<T>
+/// The sweep-line containers taken out of an [`AlgorithmData`] while the events are handled
+type SweepContainers<T> = (
+    ahash::AHashSet<usize>,
+    SweepStatus,
+    EventQueue<T>,
+    rb_tree::RBMap<SiteEventKey<T>, Vec<usize>>,
+);
+
+impl<T, P> Default for AlgorithmData<T, P>
 where
     T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
     T::Epsilon: Copy,
@@ -469,19 +487,177 @@ where
                 y: -T::max_value(),
             },
             stop_at_first_intersection: false,
+            lazy_setup: false,
             ignore_end_point_intersections: false,
-            site_events: Some(rb_tree::RBMap::new()),
-            lines: Vec::<geo::Line<T>>::new(),
+            directed_end_points: false,
+            tolerance: Tolerance::default(),
+            f64_upcast: false,
+            double_double: false,
+            filtered_predicates: false,
+            transform: None,
+            site_events: Some(EventQueue::default()),
+            lines: SegmentStore::default(),
+            reversed: Vec::new(),
+            payloads: Vec::<P>::new(),
             result: Some(rb_tree::RBMap::new()),
             active_lines: Some(ahash::AHashSet::default()),
+            status: Some(SweepStatus::default()),
+            pair_cache: ahash::AHashMap::default(),
+            pair_cache_hits: 0,
+            interner: None,
             intersection_calls: 0,
-            neighbour_priority: Some(MinMax::new()),
-            connected_priority: Some(MinMaxSlope::new()),
+            handled_events: 0,
+            reported_intersections: 0,
+            diagnostics: None,
+            keep_results: true,
         }
     }
 }
 
+/// Shows the configuration and the amount of data the instance holds. With the
+/// `console_trace` feature the sizes of the event queue and the sweep-line status are shown
+/// too.
+impl<T, P> Debug for AlgorithmData<T, P>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("AlgorithmData");
+        let _ = s
+            .field("sweepline_pos", &self.sweepline_pos)
+            .field(
+                "stop_at_first_intersection",
+                &self.stop_at_first_intersection,
+            )
+            .field("lazy_setup", &self.lazy_setup)
+            .field(
+                "ignore_end_point_intersections",
+                &self.ignore_end_point_intersections,
+            )
+            .field("directed_end_points", &self.directed_end_points)
+            .field("tolerance", &self.tolerance)
+            .field("f64_upcast", &self.f64_upcast)
+            .field("double_double", &self.double_double)
+            .field("filtered_predicates", &self.filtered_predicates)
+            .field("transform", &self.transform.is_some())
+            .field("snap_radius", &self.interner.as_ref().map(|i| i.radius()))
+            .field("lines", &self.lines.len())
+            .field("payloads", &self.payloads.len())
+            .field("results", &self.result.as_ref().map(|r| r.len()))
+            .field("intersection_calls", &self.intersection_calls)
+            .field("handled_events", &self.handled_events)
+            .field("reported_intersections", &self.reported_intersections)
+            .field("pair_cache_hits", &self.pair_cache_hits)
+            .field("diagnostic_sink", &self.diagnostics.is_some());
+        #[cfg(feature = "console_trace")]
+        let _ = s
+            .field("site_events", &self.site_events.as_ref().map(|e| e.len()))
+            .field("active_lines", &self.active_lines.as_ref().map(|a| a.len()))
+            .field("status", &self.status.as_ref().map(|s| s.len()));
+        s.finish()
+    }
+}
+
 impl<T> AlgorithmData<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Creates an instance set up for the segments of one LineString.
+    /// Consecutive segments always share an end point, so end point intersections are
+    /// ignored. The line indices of the results are the segment indices of the LineString.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let line_string = geo::LineString::from(vec![(0., 0.), (10., 10.), (10., 0.), (0., 10.)]);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::for_linestring(&line_string)?
+    ///     .compute()?
+    ///     .collect();
+    /// assert_eq!(rv, vec![(geo::Coordinate { x: 5., y: 5. }, vec![0, 2])]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn for_linestring(line_string: &geo::LineString<T>) -> Result<Self, super::IntersectError> {
+        Self::for_linestring_with_closedness(line_string, Closedness::Infer)
+    }
+
+    /// Same as [`AlgorithmData::for_linestring()`] but the segments are created according to
+    /// `closedness`, see [`Closedness::segments()`]. A closed ring gets a closing segment
+    /// if its last point isn't ulps-equal to its first point.
+    /// ```
+    /// # use intersect2d::{algorithm::AlgorithmData, Closedness};
+    /// let line_string = geo::LineString::from(vec![(0., 0.), (10., 10.), (10., 0.), (0., 10.)]);
+    /// let rv: Vec<_> =
+    ///     AlgorithmData::<f64>::for_linestring_with_closedness(&line_string, Closedness::Closed)?
+    ///         .compute()?
+    ///         .collect();
+    /// assert_eq!(rv, vec![(geo::Coordinate { x: 5., y: 5. }, vec![0, 2])]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn for_linestring_with_closedness(
+        line_string: &geo::LineString<T>,
+        closedness: Closedness,
+    ) -> Result<Self, super::IntersectError> {
+        let mut rv = Self::default();
+        let _ = rv
+            .with_ignore_end_point_intersections(true)?
+            .with_lines(closedness.segments(line_string).into_iter())?;
+        Ok(rv)
+    }
+
+    /// Creates an instance set up for the segments of a number of closed rings (e.g. the
+    /// rings of a polygon). End point intersections are ignored like in
+    /// [`AlgorithmData::for_linestring()`], so rings only touching each other at a vertex
+    /// are not reported. The line indices of the results count the segments of all the rings,
+    /// in order.
+    pub fn for_rings<'a, I>(rings: I) -> Result<Self, super::IntersectError>
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a geo::LineString<T>>,
+    {
+        Self::for_rings_with_closedness(rings, Closedness::Infer)
+    }
+
+    /// Same as [`AlgorithmData::for_rings()`] but the segments of every ring are created
+    /// according to `closedness`, see [`Closedness::segments()`].
+    pub fn for_rings_with_closedness<'a, I>(
+        rings: I,
+        closedness: Closedness,
+    ) -> Result<Self, super::IntersectError>
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a geo::LineString<T>>,
+    {
+        let mut rv = Self::default();
+        let _ = rv.with_ignore_end_point_intersections(true)?.with_lines(
+            rings
+                .into_iter()
+                .flat_map(|r| closedness.segments(r).into_iter()),
+        )?;
+        Ok(rv)
+    }
+
+    /// Creates an instance set up for independent segments, every point where two segments
+    /// meet is reported, end points included.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [10., 0., 10., 10.]]);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::for_segments(lines)?.compute()?.collect();
+    /// assert_eq!(rv, vec![(geo::Coordinate { x: 10., y: 0. }, vec![0, 1])]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn for_segments<I>(lines: I) -> Result<Self, super::IntersectError>
+    where
+        I: IntoIterator<Item = geo::Line<T>>,
+    {
+        let mut rv = Self::default();
+        let _ = rv
+            .with_ignore_end_point_intersections(false)?
+            .with_lines(lines.into_iter())?;
+        Ok(rv)
+    }
+}
+
+impl<T, P> AlgorithmData<T, P>
 where
     T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
     T::Epsilon: Copy,
@@ -490,8 +666,30 @@ where
         &self.sweepline_pos
     }
 
-    pub fn get_lines(&self) -> &Vec<geo::Line<T>> {
-        &self.lines
+    /// Returns a copy of the (re-arranged) input lines.
+    /// The lines are stored in a structure-of-arrays form internally, so this converts every
+    /// line, use [`AlgorithmData::get_line()`] to look at single lines.
+    pub fn get_lines(&self) -> Vec<geo::Line<T>> {
+        self.lines.to_lines()
+    }
+
+    /// Returns the (re-arranged) input line at `index`
+    pub fn get_line(&self, index: usize) -> Option<geo::Line<T>> {
+        if index < self.lines.len() {
+            Some(self.lines.get(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of input lines
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns the payload of the line at `index`, if the lines were added with payloads
+    pub fn get_payload(&self, index: usize) -> Option<&P> {
+        self.payloads.get(index)
     }
 
     pub fn get_results(&self) -> &Option<rb_tree::RBMap<SiteEventKey<T>, Vec<usize>>> {
@@ -499,18 +697,9 @@ where
     }
 
     /// This removes the results from the AlgorithmData structure
-    #[allow(clippy::type_complexity)]
-    pub fn take_results<'a>(
-        &mut self,
-    ) -> Result<
-        Box<dyn ExactSizeIterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a>,
-        super::IntersectError,
-    >
-    where
-        T: 'a,
-    {
+    pub fn take_results(&mut self) -> Result<Intersections<T>, super::IntersectError> {
         if let Some(rv) = self.result.take() {
-            Ok(Box::new(rv.into_iter().map(|x| (x.0.pos, x.1))))
+            Ok(rv.into_iter().map(|x| (x.0.pos, x.1)).collect())
         } else {
             Err(super::IntersectError::ResultsAlreadyTaken(
                 "Results already taken from structure".to_string(),
@@ -518,7 +707,7 @@ where
         }
     }
 
-    pub fn get_site_events(&self) -> &Option<rb_tree::RBMap<SiteEventKey<T>, SiteEvent<T>>> {
+    pub fn get_site_events(&self) -> &Option<EventQueue<T>> {
         &self.site_events
     }
 
@@ -530,6 +719,49 @@ where
         self.intersection_calls
     }
 
+    /// Returns the number of events handled since the lines were added, or since the last
+    /// [`AlgorithmData::reset()`]
+    pub fn get_handled_events(&self) -> usize {
+        self.handled_events
+    }
+
+    /// Returns the number of times the sweep re-visited a pair of lines, and could use the
+    /// cached result instead of calculating the intersection again.
+    pub fn get_pair_cache_hits(&self) -> usize {
+        self.pair_cache_hits
+    }
+
+    /// Returns the cached intersection point of two lines, `None` if the pair was never tested.
+    /// `Some(None)` means that the lines were tested and found not to intersect.
+    pub fn get_cached_intersection(
+        &self,
+        a: usize,
+        b: usize,
+    ) -> Option<Option<geo::Coordinate<T>>> {
+        self.pair_cache.get(&(a.min(b), a.max(b))).copied()
+    }
+
+    /// Returns a multi line description of the configuration and the state of the instance,
+    /// the same as the alternate `Debug` output.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let _ = ad
+    ///     .with_ignore_end_point_intersections(true)?
+    ///     .with_lines(intersect2d::to_lines(&[[0., 0., 1., 1.]]).into_iter())?;
+    /// let state = ad.dump_state();
+    /// assert!(state.contains("ignore_end_point_intersections: true"));
+    /// assert!(state.contains("lines: 1"));
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn dump_state(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    pub fn get_tolerance(&self) -> &Tolerance<T> {
+        &self.tolerance
+    }
+
     pub fn with_stop_at_first_intersection(
         &mut self,
         value: bool,
@@ -538,6 +770,37 @@ where
         Ok(self)
     }
 
+    /// Skips the sorting of the end points when lines are added (or re-queued by
+    /// [`AlgorithmData::reset()`]). Normally all the end points are sorted upfront, with a
+    /// lazy setup they are moved into the event queue, a binary heap built in linear time,
+    /// and only ordered as the events are handled. A search stopping at the first intersection (see
+    /// [`AlgorithmData::with_stop_at_first_intersection()`]) that finds an intersection early
+    /// then doesn't pay for sorting all the events it never handles. A complete run is
+    /// usually a bit slower than with sorted end points. The results are the same.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 10.],
+    ///     [10., 0., 0., 10.],
+    ///     [0., 100., 10., 110.],
+    ///     [10., 100., 0., 110.],
+    /// ]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let rv = ad
+    ///     .with_lazy_setup(true)?
+    ///     .with_stop_at_first_intersection(true)?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?;
+    /// assert_eq!(rv.len(), 1);
+    /// // the events around the second intersection were never handled
+    /// assert_eq!(ad.get_handled_events(), 3);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_lazy_setup(&mut self, value: bool) -> Result<&mut Self, super::IntersectError> {
+        self.lazy_setup = value;
+        Ok(self)
+    }
+
     pub fn with_ignore_end_point_intersections(
         &mut self,
         value: bool,
@@ -546,85 +809,356 @@ where
         Ok(self)
     }
 
-    /// Add data to the input lines.
-    /// Sort the end point according to the order of SiteEventKey.
-    /// Populate the event queue
-    /// Todo: this duplicates functionality of 'with_ref_lines()', try to consolidate..
-    pub fn with_lines<I>(&mut self, input_iter: I) -> Result<&mut Self, super::IntersectError>
+    /// Treats the lines as directed, from `start` to `end` as they were added. Only a point
+    /// where exactly two lines meet, the end of one at the start of the other, is a benign
+    /// connection and not reported. Two lines starting, or ending, at the same point, and any
+    /// point where more than two lines meet, are reported, as well as every point in the
+    /// interior of a line. When set this replaces
+    /// [`AlgorithmData::with_ignore_end_point_intersections()`].
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 0.],
+    ///     [10., 0., 10., 10.],
+    ///     [0., 10., 10., 10.],
+    /// ]);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::default()
+    ///     .with_directed_end_points(true)?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?
+    ///     .collect();
+    /// // line 0 flows into line 1, but lines 1 and 2 both end at (10, 10)
+    /// assert_eq!(rv, vec![(geo::Coordinate { x: 10., y: 10. }, vec![1, 2])]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_directed_end_points(
+        &mut self,
+        value: bool,
+    ) -> Result<&mut Self, super::IntersectError> {
+        self.directed_end_points = value;
+        Ok(self)
+    }
+
+    /// Sets a sink for diagnostic dumps. A computation failing with
+    /// [`IntersectError::InternalError`](super::IntersectError::InternalError) writes a JSON
+    /// dump of the input, the options and the last handled events to the sink, see
+    /// [`crate::diagnostic`].
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let _ = ad
+    ///     .with_diagnostic_sink(std::io::sink())?
+    ///     .with_lines(intersect2d::to_lines(&[[0., 0., 1., 1.]]).into_iter())?;
+    /// assert!(ad.dump_state().contains("diagnostic_sink: true"));
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_diagnostic_sink<W>(&mut self, sink: W) -> Result<&mut Self, super::IntersectError>
     where
-        I: Iterator<Item = geo::Line<T>>,
+        W: std::io::Write + Send + 'static,
     {
-        let mut site_events = self.site_events.take().unwrap();
-
-        for (index, mut aline) in input_iter.enumerate() {
-            if !(aline.start.x.is_finite()
-                && aline.start.y.is_finite()
-                && aline.end.x.is_finite()
-                && aline.end.y.is_finite())
-            {
-                return Err(super::IntersectError::InvalidData(
-                    "Can't check for intersections on non-finite data".to_string(),
-                ));
-            }
-
-            // Re-arrange so that:
-            // SiteEvent.pos.start < SiteEvent.pos.end (primary ordering: pos.y, secondary: pos.x)
-            if !(SiteEventKey { pos: aline.start }).lt(&(SiteEventKey { pos: aline.end })) {
-                std::mem::swap(&mut aline.start, &mut aline.end);
-            };
-
-            self.lines.push(aline);
+        self.diagnostics = Some(Diagnostics::new(Box::new(sink)));
+        Ok(self)
+    }
 
-            let key_start = SiteEventKey { pos: aline.start };
-            let key_end = SiteEventKey { pos: aline.end };
+    /// Returns the JSON diagnostic dump, as written to the diagnostic sink, with `error` as
+    /// the error description. The event history is only recorded while a diagnostic sink
+    /// is set.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let _ = ad.with_lines(intersect2d::to_lines(&[[0., 0., 1., 1.]]).into_iter())?;
+    /// let dump = ad.diagnostic_dump("before the sweep");
+    /// assert!(dump.contains(r#""error":"before the sweep""#));
+    /// assert!(dump.contains(r#""segments":[[0.0,0.0,1.0,1.0]]"#));
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn diagnostic_dump(&self, error: &str) -> String {
+        let tolerance = format!(
+            "{{\"epsilon\":{},\"max_ulps\":{},\"mode\":{},\"precision\":{}}}",
+            json_number(self.tolerance.epsilon),
+            self.tolerance.max_ulps,
+            json_string(&format!("{:?}", self.tolerance.mode)),
+            json_string(&format!("{:?}", self.tolerance.precision))
+        );
+        let options = [
+            (
+                "stop_at_first_intersection",
+                self.stop_at_first_intersection.to_string(),
+            ),
+            (
+                "ignore_end_point_intersections",
+                self.ignore_end_point_intersections.to_string(),
+            ),
+            ("directed_end_points", self.directed_end_points.to_string()),
+            ("tolerance", tolerance),
+            ("f64_upcast", self.f64_upcast.to_string()),
+            ("double_double", self.double_double.to_string()),
+            ("filtered_predicates", self.filtered_predicates.to_string()),
+            ("transform", self.transform.is_some().to_string()),
+            (
+                "snap_radius",
+                self.interner
+                    .as_ref()
+                    .map_or("null".to_string(), |i| json_number(i.radius())),
+            ),
+        ];
+        format_dump(
+            error,
+            &options,
+            &self.sweepline_pos,
+            &self.lines.to_lines(),
+            self.diagnostics.as_ref(),
+        )
+    }
 
-            // start points goes into the site_event::add list
-            if let Some(mut event) = site_events.get_mut(&key_start) {
-                let mut lower = event.add.take().map_or(Vec::<usize>::new(), identity);
-                lower.push(index);
-                event.add = Some(lower);
-            } else {
-                let event = SiteEvent::<T>::with_add(&[index]);
-                let _ = site_events.insert(key_start, event);
+    /// Writes a diagnostic dump to the sink, if there is one, when `err` is an internal error
+    fn diagnose(&mut self, err: super::IntersectError) -> super::IntersectError {
+        if let super::IntersectError::InternalError(detail) = &err {
+            if self.diagnostics.is_some() {
+                let dump = self.diagnostic_dump(&format!("{}: {}", err, detail));
+                if let Some(diagnostics) = self.diagnostics.as_mut() {
+                    diagnostics.write(&dump);
+                }
             }
+        }
+        err
+    }
 
-            // end points goes into the site_event::drop list
-            if let Some(mut event) = site_events.get_mut(&key_end) {
-                let mut upper = event.drop.take().map_or(Vec::<usize>::new(), identity);
-                upper.push(index);
-                event.drop = Some(upper);
-            } else {
-                let event = SiteEvent::<T>::with_drop(&[index]);
-                let _ = site_events.insert(key_end, event);
+    /// Takes the sweep-line containers out of the instance, to make the borrow checker happy
+    /// by breaking the link between self and all the containers and their iterators.
+    fn take_containers(&mut self) -> Result<SweepContainers<T>, super::IntersectError> {
+        if self.result.is_none() {
+            return Err(super::IntersectError::ResultsAlreadyTaken(
+                "Results already taken from structure".to_string(),
+            ));
+        }
+        match (
+            self.active_lines.take(),
+            self.status.take(),
+            self.site_events.take(),
+            self.result.take(),
+        ) {
+            (Some(active_lines), Some(status), Some(site_events), Some(result)) => {
+                Ok((active_lines, status, site_events, result))
             }
+            _ => Err(self.diagnose(super::IntersectError::InternalError(
+                "The sweep-line containers are missing, an earlier computation was interrupted"
+                    .to_string(),
+            ))),
         }
+    }
 
+    /// Puts the borrowed containers back
+    fn restore_containers(&mut self, containers: SweepContainers<T>) {
+        let (active_lines, status, site_events, result) = containers;
         self.site_events = Some(site_events);
-        #[cfg(feature = "console_trace")]
-        self.debug();
+        self.active_lines = Some(active_lines);
+        self.status = Some(status);
+        self.result = Some(result);
+    }
+
+    /// Run the algorithm with all the internal predicates in f64, the results are rounded
+    /// back to `T` (and merged if they round to the same point). This makes f32 sweeps of
+    /// large coordinates much more robust. Only [`AlgorithmData::compute()`] honors this
+    /// setting.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f32>(&[
+    ///     [100000., 100000., 100010., 100010.],
+    ///     [100000., 100010., 100010., 100000.],
+    /// ]);
+    /// let rv: Vec<_> = AlgorithmData::<f32>::default()
+    ///     .with_f64_upcast(true)?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?
+    ///     .collect();
+    /// assert_eq!(rv, vec![(geo::Coordinate { x: 100005., y: 100005. }, vec![0, 1])]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_f64_upcast(&mut self, value: bool) -> Result<&mut Self, super::IntersectError> {
+        self.f64_upcast = value;
         Ok(self)
     }
 
-    /// Add data to the input lines.
-    /// Sort the end point according to the order of SiteEventKey.
-    /// Populate the event queue
-    /// TODO: is this worth keeping? AlgorithmData always keeps copies of the input geometry anyways
-    pub fn with_ref_lines<'a, I>(
+    /// Compute the intersection points, and the positions of the lines along the sweep-line,
+    /// in double-double arithmetic (see [`double`](crate::double)). This is roughly twice as
+    /// slow as plain floating point, but the intersection points are almost always correctly
+    /// rounded.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0.1, 0.3, 7.7, 3.1], [0.2, 5.9, 6.3, 0.7]]);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::default()
+    ///     .with_double_double(true)?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?
+    ///     .collect();
+    /// assert_eq!(rv.len(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_double_double(&mut self, value: bool) -> Result<&mut Self, super::IntersectError> {
+        self.double_double = value;
+        Ok(self)
+    }
+
+    /// Order the active lines along the sweep-line with
+    /// [`compare_x_at()`](crate::predicates::compare_x_at), deciding the order from the input
+    /// coordinates with interval arithmetic and an exact fallback, instead of comparing the
+    /// rounded x coordinates. Lines closer than the tolerance are still ordered by direction.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use intersect2d::{EqualityMode, Tolerance};
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0.1, 0.3, 7.7, 3.1], [0.2, 5.9, 6.3, 0.7]]);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::default()
+    ///     .with_tolerance(Tolerance::new(0.0, 0).with_equality_mode(EqualityMode::Absolute))?
+    ///     .with_filtered_predicates(true)?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?
+    ///     .collect();
+    /// assert_eq!(rv.len(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_filtered_predicates(
         &mut self,
-        input_iter: I,
-    ) -> Result<&mut Self, super::IntersectError>
+        value: bool,
+    ) -> Result<&mut Self, super::IntersectError> {
+        self.filtered_predicates = value;
+        Ok(self)
+    }
+
+    /// Set the tolerance used when comparing coordinates.
+    /// This must be done before any lines are added, see [`AlgorithmData::reset_with_tolerance()`]
+    /// for changing the tolerance of the lines already added.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use intersect2d::Tolerance;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [10.001, -5., 10.001, 5.]]);
+    /// let rv = AlgorithmData::<f64>::default()
+    ///     .with_tolerance(Tolerance::new(0.01, 4))?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?;
+    /// assert_eq!(rv.len(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_tolerance(
+        &mut self,
+        tolerance: Tolerance<T>,
+    ) -> Result<&mut Self, super::IntersectError> {
+        if !self.lines.is_empty() {
+            return Err(super::IntersectError::InvalidParameter(
+                "The tolerance must be set before any lines are added".to_string(),
+            ));
+        }
+        tolerance.validate()?;
+        self.tolerance = tolerance;
+        Ok(self)
+    }
+
+    /// Snap reported intersection points within `radius` of an already reported point to that
+    /// point. Repeated hits at (nearly) the same location then share one result entry whose
+    /// list of lines grows, instead of creating near-duplicate points.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// // three lines almost, but not exactly, meeting in one point
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 1., 2., 1.],
+    ///     [1., 0., 1., 2.],
+    ///     [0.0000001, 0., 2.0000001, 2.],
+    /// ]);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?
+    ///     .collect();
+    /// assert_eq!(rv.len(), 3);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::default()
+    ///     .with_snap_radius(1e-5)?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?
+    ///     .collect();
+    /// assert_eq!(rv.len(), 1);
+    /// assert_eq!(rv[0].1, vec![0, 1, 2]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_snap_radius(&mut self, radius: T) -> Result<&mut Self, super::IntersectError> {
+        self.interner = Some(CoordinateInterner::new(radius)?);
+        Ok(self)
+    }
+
+    /// Set a transform that is applied to every coordinate as the lines are added, e.g. a
+    /// re-projection or rescaling. The intersections (and [`AlgorithmData::get_lines()`])
+    /// are reported in the transformed coordinate space.
+    /// This must be done before any lines are added.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 1., 1.], [0., 1., 1., 0.]]);
+    /// let rv: Vec<_> = AlgorithmData::<f64>::default()
+    ///     .with_transform(|c| geo::Coordinate { x: c.x * 100.0, y: c.y * 10.0 })?
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?
+    ///     .collect();
+    /// assert_eq!(rv[0].0, geo::Coordinate { x: 50.0, y: 5.0 });
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_transform<F>(&mut self, transform: F) -> Result<&mut Self, super::IntersectError>
     where
-        T: 'a,
-        I: Iterator<Item = &'a geo::Line<T>>,
+        F: Fn(geo::Coordinate<T>) -> geo::Coordinate<T> + Send + Sync + 'static,
     {
-        let mut site_events = self.site_events.take().unwrap();
+        if !self.lines.is_empty() {
+            return Err(super::IntersectError::InvalidParameter(
+                "The transform must be set before any lines are added".to_string(),
+            ));
+        }
+        self.transform = Some(Box::new(transform));
+        Ok(self)
+    }
+
+    /// Applies the transform, if any, to an input line and rounds it to the precision model
+    #[inline(always)]
+    fn transform_line(&self, line: geo::Line<T>) -> geo::Line<T> {
+        let line = if let Some(transform) = &self.transform {
+            geo::Line {
+                start: transform(line.start),
+                end: transform(line.end),
+            }
+        } else {
+            line
+        };
+        self.tolerance.precision.make_precise_l(line)
+    }
 
-        for (index, aline) in input_iter.enumerate() {
+    /// Add data to the input lines.
+    /// Sort the end point according to the order of SiteEventKey.
+    /// Populate the event queue
+    pub fn with_lines<I>(&mut self, input_iter: I) -> Result<&mut Self, super::IntersectError>
+    where
+        I: Iterator<Item = geo::Line<T>>,
+    {
+        if !self.payloads.is_empty() {
+            return Err(super::IntersectError::InvalidParameter(
+                "Lines without payloads can't be added after lines with payloads".to_string(),
+            ));
+        }
+        self.add_lines(input_iter)
+    }
+
+    fn add_lines<I>(&mut self, input_iter: I) -> Result<&mut Self, super::IntersectError>
+    where
+        I: Iterator<Item = geo::Line<T>>,
+    {
+        // The sort key of every end point is computed once into a flat array, the array is
+        // then sorted and end points at identical positions are merged into one event before
+        // the events are moved into the queue in bulk.
+        let first_index = self.lines.len();
+        self.lines.reserve(input_iter.size_hint().0);
+        let mut end_points = Vec::<EndPointKey<T>>::with_capacity(2 * input_iter.size_hint().0);
+        for aline in input_iter {
+            let mut aline = self.transform_line(aline);
             if !(aline.start.x.is_finite()
                 && aline.start.y.is_finite()
                 && aline.end.x.is_finite()
                 && aline.end.y.is_finite())
             {
+                self.lines.truncate(first_index);
+                self.reversed.truncate(first_index);
                 return Err(super::IntersectError::InvalidData(
                     "Can't check for intersections on non-finite data".to_string(),
                 ));
@@ -632,129 +1166,392 @@ where
 
             // Re-arrange so that:
             // SiteEvent.pos.start < SiteEvent.pos.end (primary ordering: pos.y, secondary: pos.x)
-            let aline =
-                if (SiteEventKey { pos: aline.start }).lt(&(SiteEventKey { pos: aline.end })) {
-                    geo::Line {
-                        start: aline.start,
-                        end: aline.end,
-                    }
-                } else {
-                    geo::Line {
-                        start: aline.end,
-                        end: aline.start,
-                    }
-                };
-
-            self.lines.push(aline);
+            let reversed = !self.key(aline.start).lt(&self.key(aline.end));
+            if reversed {
+                std::mem::swap(&mut aline.start, &mut aline.end);
+            };
 
-            let key_start = SiteEventKey { pos: aline.start };
-            let key_end = SiteEventKey { pos: aline.end };
+            // the index of the line, continuing after any previously added lines
+            let index = match self.lines.push(aline) {
+                Ok(index) => index,
+                Err(err) => {
+                    self.lines.truncate(first_index);
+                    self.reversed.truncate(first_index);
+                    return Err(err);
+                }
+            };
+            self.reversed.push(reversed);
 
             // start points goes into the site_event::add list
-            if let Some(mut event) = site_events.get_mut(&key_start) {
-                let mut lower = event.add.take().map_or(Vec::<usize>::new(), identity);
-                lower.push(index);
-                event.add = Some(lower);
-            } else {
-                let event = SiteEvent::<T>::with_add(&[index]);
-                let _ = site_events.insert(key_start, event);
-            }
-
+            end_points.push(EndPointKey::new(aline.start, index, false));
             // end points goes into the site_event::drop list
-            if let Some(mut event) = site_events.get_mut(&key_end) {
-                let mut upper = event.drop.take().map_or(Vec::<usize>::new(), identity);
-                upper.push(index);
-                event.drop = Some(upper);
-            } else {
-                let event = SiteEvent::<T>::with_drop(&[index]);
-                let _ = site_events.insert(key_end, event);
-            }
+            end_points.push(EndPointKey::new(aline.end, index, true));
         }
+        self.queue_end_points(end_points);
 
-        self.site_events = Some(site_events);
         #[cfg(feature = "console_trace")]
         self.debug();
         Ok(self)
     }
 
+    /// Sorts the end points, merges the end points at identical positions into one event and
+    /// moves the events into the queue
+    fn queue_end_points(&mut self, mut end_points: Vec<EndPointKey<T>>) {
+        if self.lazy_setup {
+            // one event per end point, the queue merges the events at the same position
+            // when they are popped
+            let events: Vec<(SiteEventKey<T>, SiteEvent<T>)> = end_points
+                .iter()
+                .map(|e| {
+                    let line = [e.line()];
+                    let event = if e.is_drop() {
+                        SiteEvent::<T>::with_drop(&line)
+                    } else {
+                        SiteEvent::<T>::with_add(&line)
+                    };
+                    (self.key(geo::Coordinate { x: e.x, y: e.y }), event)
+                })
+                .collect();
+            self.site_events.as_mut().unwrap().extend(events);
+            return;
+        }
+        end_points.sort_unstable_by(|a, b| {
+            a.y.partial_cmp(&b.y)
+                .unwrap_or(cmp::Ordering::Equal)
+                .then_with(|| a.x.partial_cmp(&b.x).unwrap_or(cmp::Ordering::Equal))
+        });
+
+        let mut events = Vec::<(SiteEventKey<T>, SiteEvent<T>)>::new();
+        let mut run_start = 0;
+        while run_start < end_points.len() {
+            let first = &end_points[run_start];
+            let mut run_end = run_start + 1;
+            while run_end < end_points.len()
+                && end_points[run_end].x == first.x
+                && end_points[run_end].y == first.y
+            {
+                run_end += 1;
+            }
+            let run = &end_points[run_start..run_end];
+            let add: Vec<usize> = run
+                .iter()
+                .filter(|e| !e.is_drop())
+                .map(|e| e.line())
+                .collect();
+            let drop: Vec<usize> = run
+                .iter()
+                .filter(|e| e.is_drop())
+                .map(|e| e.line())
+                .collect();
+            let mut event = SiteEvent::<T>::with_add(&add);
+            event.merge(SiteEvent::<T>::with_drop(&drop));
+            events.push((
+                self.key(geo::Coordinate {
+                    x: first.x,
+                    y: first.y,
+                }),
+                event,
+            ));
+            run_start = run_end;
+        }
+        self.site_events.as_mut().unwrap().extend(events);
+    }
+
+    /// Discards the results, and the state, of an earlier run and re-queues the lines already
+    /// added, so that they can be computed again without being added again. The stop at
+    /// first intersection and end point options can be changed in between runs, the
+    /// tolerance with [`AlgorithmData::reset_with_tolerance()`].
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [10., 10., 10., 0.]]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// assert_eq!(ad.with_ref_lines(lines.iter())?.compute()?.len(), 1);
+    /// let rv = ad
+    ///     .reset()?
+    ///     .with_ignore_end_point_intersections(true)?
+    ///     .compute()?;
+    /// assert_eq!(rv.len(), 0);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn reset(&mut self) -> Result<&mut Self, super::IntersectError> {
+        self.sweepline_pos = geo::Coordinate {
+            x: -T::max_value(),
+            y: -T::max_value(),
+        };
+        self.site_events = Some(EventQueue::default());
+        self.active_lines = Some(ahash::AHashSet::default());
+        self.status = Some(SweepStatus::default());
+        self.result = Some(rb_tree::RBMap::new());
+        self.pair_cache.clear();
+        self.pair_cache_hits = 0;
+        self.intersection_calls = 0;
+        self.handled_events = 0;
+        self.reported_intersections = 0;
+        if let Some(diagnostics) = self.diagnostics.as_mut() {
+            diagnostics.clear_history();
+        }
+        if let Some(interner) = self.interner.as_ref() {
+            self.interner = Some(CoordinateInterner::new(interner.radius())?);
+        }
+
+        let mut end_points = Vec::<EndPointKey<T>>::with_capacity(2 * self.lines.len());
+        for index in 0..self.lines.len() {
+            let index = index as u32;
+            end_points.push(EndPointKey::new(
+                self.lines.start(index as usize),
+                index,
+                false,
+            ));
+            end_points.push(EndPointKey::new(
+                self.lines.end(index as usize),
+                index,
+                true,
+            ));
+        }
+        self.queue_end_points(end_points);
+        Ok(self)
+    }
+
+    /// Like [`AlgorithmData::reset()`] but also changes the tolerance. The lines already added
+    /// are rounded to the precision model of the new tolerance, note that the lines can't be
+    /// restored to a finer precision than they already have.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use intersect2d::Tolerance;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [10.001, -5., 10.001, 5.]]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// assert_eq!(ad.with_ref_lines(lines.iter())?.compute()?.len(), 0);
+    /// let rv = ad.reset_with_tolerance(Tolerance::new(0.01, 4))?.compute()?;
+    /// assert_eq!(rv.len(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn reset_with_tolerance(
+        &mut self,
+        tolerance: Tolerance<T>,
+    ) -> Result<&mut Self, super::IntersectError> {
+        tolerance.validate()?;
+        self.tolerance = tolerance;
+        let lines = self.lines.to_lines();
+        self.lines = SegmentStore::default();
+        self.lines.reserve(lines.len());
+        for (index, aline) in lines.into_iter().enumerate() {
+            let mut aline = self.tolerance.precision.make_precise_l(aline);
+            if !self.key(aline.start).lt(&self.key(aline.end)) {
+                std::mem::swap(&mut aline.start, &mut aline.end);
+                self.reversed[index] = !self.reversed[index];
+            };
+            let _ = self.lines.push(aline)?;
+        }
+        self.reset()
+    }
+
+    /// Add data to the input lines.
+    /// Sort the end point according to the order of SiteEventKey.
+    /// Populate the event queue
+    pub fn with_ref_lines<'a, I>(
+        &mut self,
+        input_iter: I,
+    ) -> Result<&mut Self, super::IntersectError>
+    where
+        T: 'a,
+        I: Iterator<Item = &'a geo::Line<T>>,
+    {
+        if !self.payloads.is_empty() {
+            return Err(super::IntersectError::InvalidParameter(
+                "Lines without payloads can't be added after lines with payloads".to_string(),
+            ));
+        }
+        self.add_lines(input_iter.copied())
+    }
+
+    /// Creates an event key using the tolerance, and precision model, of this structure
+    #[inline(always)]
+    fn key(&self, pos: geo::Coordinate<T>) -> SiteEventKey<T> {
+        SiteEventKey::with_tolerance(self.tolerance.precision.make_precise_c(pos), self.tolerance)
+    }
+
+    /// Add input lines, each with an attached payload.
+    /// The payloads of the intersecting lines can be retrieved with
+    /// [`AlgorithmData::compute_with_payloads()`] or [`AlgorithmData::get_payload()`].
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    /// let mut ad = AlgorithmData::<f64, &str>::default();
+    /// let rv = ad
+    ///     .with_payload_lines(lines.into_iter().zip(vec!["road", "river"]))?
+    ///     .compute_with_payloads()?;
+    /// assert_eq!(rv.len(), 1);
+    /// assert_eq!(rv[0].1, vec![&"road", &"river"]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_payload_lines<I>(
+        &mut self,
+        input_iter: I,
+    ) -> Result<&mut Self, super::IntersectError>
+    where
+        I: Iterator<Item = (geo::Line<T>, P)>,
+    {
+        if self.lines.len() != self.payloads.len() {
+            return Err(super::IntersectError::InvalidParameter(
+                "Lines with payloads can't be added after lines without payloads".to_string(),
+            ));
+        }
+        let mut payloads = Vec::<P>::new();
+        let lines = input_iter.map(|(a_line, payload)| {
+            payloads.push(payload);
+            a_line
+        });
+        let _ = self.add_lines(lines)?;
+        self.payloads.append(&mut payloads);
+        Ok(self)
+    }
+
     ///
     /// Add a new intersection event to the event queue
     ///
     fn add_intersection_event(
         &self,
-        site_events: &mut rb_tree::RBMap<SiteEventKey<T>, SiteEvent<T>>,
+        site_events: &mut EventQueue<T>,
         position: &SiteEventKey<T>,
         intersecting_lines: &[usize],
     ) {
-        if let Some(event) = site_events.get_mut(position) {
-            // there were already events at this point
-            let mut intersections_added = 0;
-            for new_intersection in intersecting_lines.iter() {
-                // only add this line as an intersection if the intersection lies
-                // at the interior of the line (no end point)
-                let i_line = self.lines[*new_intersection];
-                if ulps_eq_c(&position.pos, &i_line.start) || ulps_eq_c(&position.pos, &i_line.end)
-                {
-                    continue;
-                }
-
-                if let Some(prev_intersections) = &mut event.intersection {
-                    prev_intersections.push(*new_intersection);
-                } else {
-                    let new_vec = vec![*new_intersection];
-                    event.intersection = Some(new_vec);
-                }
-                intersections_added += 1;
-            }
-            if intersections_added > 0 {
-                let mut intersections = event.intersection.take().unwrap();
-                intersections.sort_unstable();
-                intersections.dedup();
-                event.intersection = Some(intersections);
-            }
-        } else {
-            // this is the first event at this point
-            let event = SiteEvent::with_intersection(intersecting_lines);
-            let _ = site_events.insert(*position, event);
-        }
+        // Any other events at this point are merged when the event is popped
+        site_events.push(*position, SiteEvent::with_intersection(intersecting_lines));
     }
 
     /// handles input event, returns true when done
     /// If interactive is set, the method will handle only one event for each call
-    #[allow(clippy::type_complexity)]
-    pub fn compute<'a>(
+    pub fn compute(&mut self) -> Result<Intersections<T>, super::IntersectError> {
+        // this could only happen if first run interactive, but just in case..
+        if self.found_first_intersection() {
+            return self.take_results();
+        }
+
+        if self.f64_upcast {
+            let rv = self.compute_upcast();
+            return rv.map_err(|e| self.diagnose(e));
+        }
+
+        self.sweep_to_end(&mut ())
+    }
+
+    /// Runs the algorithm to completion, just like [`AlgorithmData::compute()`], and calls
+    /// `handler` on every segment start, segment end and intersection, see
+    /// [`crate::handler`]. The f64 upcast is not supported.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use intersect2d::handler::{SweepContext, SweepEventHandler};
+    /// /// Records the right neighbour of every segment when it starts
+    /// #[derive(Default)]
+    /// struct Neighbours(Vec<(usize, Option<usize>)>);
+    ///
+    /// impl SweepEventHandler<f64> for Neighbours {
+    ///     fn on_segment_start(&mut self, line: usize, context: &SweepContext<'_, f64>) {
+    ///         self.0.push((line, context.right_neighbour(line)));
+    ///     }
+    /// }
+    ///
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 0., 10.], [5., 1., 5., 9.]]);
+    /// let mut neighbours = Neighbours::default();
+    /// let rv = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute_with_handler(&mut neighbours)?;
+    /// assert_eq!(rv.len(), 0);
+    /// assert_eq!(neighbours.0, [(0, None), (1, None)]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn compute_with_handler<H>(
         &mut self,
-    ) -> Result<
-        Box<dyn ExactSizeIterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a>,
-        super::IntersectError,
-    >
+        handler: &mut H,
+    ) -> Result<Intersections<T>, super::IntersectError>
     where
-        T: 'a,
+        H: SweepEventHandler<T> + ?Sized,
     {
-        // this could only happen if first run interactive, but just in case..
-        if self.stop_at_first_intersection && self.result.as_ref().map_or(false, |x| !x.is_empty())
-        {
+        if self.f64_upcast {
+            return Err(super::IntersectError::InvalidParameter(
+                "compute_with_handler() can't be combined with the f64 upcast".to_string(),
+            ));
+        }
+        if self.found_first_intersection() {
             return self.take_results();
         }
+        self.sweep_to_end(handler)
+    }
 
-        // make the borrow checker happy by breaking the link between self and all the
-        // containers and their iterators.
-        let mut active_lines = self.active_lines.take().unwrap();
-        let mut site_events = self.site_events.take().unwrap();
-        let mut result = self.result.take().unwrap();
-        let mut neighbour_priority = self.neighbour_priority.take().unwrap();
-        let mut connected_priority = self.connected_priority.take().unwrap();
+    /// Runs the algorithm to completion for the side effects of `handler` alone: the
+    /// intersections are handed to the handler and dropped instead of being collected, so the
+    /// memory used doesn't grow with the number of intersections. See [`crate::aggregate`] for
+    /// handlers accumulating statistics.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use intersect2d::aggregate::IntersectionCounter;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 10.],
+    ///     [0., 10., 10., 0.],
+    ///     [0., 1., 10., 1.],
+    /// ]);
+    /// let mut counter = IntersectionCounter::default();
+    /// AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .aggregate(&mut counter)?;
+    /// assert_eq!(counter.points(), 3);
+    /// assert_eq!(counter.line_counts(), [2, 2, 2]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn aggregate<H>(&mut self, handler: &mut H) -> Result<(), super::IntersectError>
+    where
+        H: SweepEventHandler<T> + ?Sized,
+    {
+        self.keep_results = false;
+        let rv = self.compute_with_handler(handler);
+        self.keep_results = true;
+        rv.map(|_| ())
+    }
+
+    /// Returns the lines in the direction they were added
+    fn added_lines(&self) -> Vec<geo::Line<T>> {
+        (0..self.lines.len())
+            .map(|i| {
+                let line = self.lines.get(i);
+                if self.reversed[i] {
+                    geo::Line::new(line.end, line.start)
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    /// Returns true if the sweep should stop: `stop_at_first_intersection` is set and an
+    /// intersection was reported. The results may not be kept, see
+    /// [`AlgorithmData::aggregate()`].
+    fn found_first_intersection(&self) -> bool {
+        self.stop_at_first_intersection && self.reported_intersections > 0
+    }
+
+    /// Handles all the remaining events and takes the results
+    fn sweep_to_end<H>(
+        &mut self,
+        handler: &mut H,
+    ) -> Result<Intersections<T>, super::IntersectError>
+    where
+        H: SweepEventHandler<T> + ?Sized,
+    {
+        let (mut active_lines, mut status, mut site_events, mut result) = self.take_containers()?;
 
         loop {
+            if self.found_first_intersection() {
+                break;
+            }
             if let Some((key, event)) = site_events.pop_pair() {
                 self.handle_event(
                     &key,
-                    &event,
+                    event,
                     &mut active_lines,
-                    &mut neighbour_priority,
-                    &mut connected_priority,
+                    &mut status,
                     &mut site_events,
                     &mut result,
+                    handler,
                 );
             } else {
                 self.sweepline_pos = geo::Coordinate {
@@ -765,30 +1562,312 @@ where
             }
         }
 
-        // put the borrowed containers back
-        self.site_events = Some(site_events);
-        self.active_lines = Some(active_lines);
-        self.result = Some(result);
-        self.neighbour_priority = Some(neighbour_priority);
-        self.connected_priority = Some(connected_priority);
+        self.restore_containers((active_lines, status, site_events, result));
         self.take_results()
     }
 
+    /// Runs the algorithm like [`AlgorithmData::compute()`] and collects the results into an
+    /// owned [`IntersectionList`].
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 10.],
+    ///     [0., 10., 10., 0.],
+    ///     [0., 1., 10., 1.],
+    /// ]);
+    /// let rv = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute_collected()?;
+    /// assert_eq!(rv.len(), 3);
+    /// assert_eq!(rv[2], (geo::Coordinate { x: 5., y: 5. }, vec![0, 1]));
+    /// assert_eq!(rv[..2].len(), 2);
+    /// for (position, lines) in &rv {
+    ///     assert_eq!(lines.len(), 2);
+    /// }
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn compute_collected(&mut self) -> Result<IntersectionList<T>, super::IntersectError> {
+        Ok(self.compute()?.collect())
+    }
+
+    /// Handles at most `budget` events, and returns the results once every event is handled.
+    /// `Poll::Pending` means that there is work left, call the method again to continue.
+    /// The results are the same as those of [`AlgorithmData::compute()`], but the f64 upcast
+    /// is not supported.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use std::task::Poll;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let _ = ad.with_ref_lines(lines.iter())?;
+    /// let mut calls = 1;
+    /// let rv = loop {
+    ///     if let Poll::Ready(rv) = ad.poll_compute(2)? {
+    ///         break rv;
+    ///     }
+    ///     calls += 1;
+    /// };
+    /// assert_eq!(rv.len(), 1);
+    /// assert!(calls > 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn poll_compute(
+        &mut self,
+        budget: usize,
+    ) -> Result<Poll<Intersections<T>>, super::IntersectError> {
+        if budget == 0 {
+            return Err(super::IntersectError::InvalidParameter(
+                "The budget must be at least one event".to_string(),
+            ));
+        }
+        if self.f64_upcast {
+            return Err(super::IntersectError::InvalidParameter(
+                "poll_compute() can't be combined with the f64 upcast".to_string(),
+            ));
+        }
+        let (mut active_lines, mut status, mut site_events, mut result) = self.take_containers()?;
+
+        let mut algorithm_is_done = false;
+        for _ in 0..budget {
+            if self.found_first_intersection() {
+                algorithm_is_done = true;
+                break;
+            }
+            if let Some((key, event)) = site_events.pop_pair() {
+                self.handle_event(
+                    &key,
+                    event,
+                    &mut active_lines,
+                    &mut status,
+                    &mut site_events,
+                    &mut result,
+                    &mut (),
+                );
+            } else {
+                self.sweepline_pos = geo::Coordinate {
+                    x: T::max_value(),
+                    y: T::max_value(),
+                };
+                algorithm_is_done = true;
+                break;
+            }
+        }
+
+        self.restore_containers((active_lines, status, site_events, result));
+        if algorithm_is_done {
+            Ok(Poll::Ready(self.take_results()?))
+        } else {
+            Ok(Poll::Pending)
+        }
+    }
+
+    /// Runs the algorithm to completion inside an async task, yielding to the executor after
+    /// every `events_per_yield` events so that a long sweep doesn't block an executor thread.
+    /// See [`AlgorithmData::poll_compute()`].
+    pub async fn compute_async(
+        &mut self,
+        events_per_yield: usize,
+    ) -> Result<Intersections<T>, super::IntersectError> {
+        loop {
+            if let Poll::Ready(rv) = self.poll_compute(events_per_yield)? {
+                return Ok(rv);
+            }
+            YieldNow(false).await;
+        }
+    }
+
+    /// Moves the prepared instance to a worker thread and runs the sweep there, see
+    /// [`background`](crate::background). The progress is the part of the y range of the
+    /// lines the sweep-line has passed.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let _ = ad.with_ref_lines(lines.iter())?;
+    /// let handle = ad.compute_background()?;
+    /// let rv = handle.join()?;
+    /// assert_eq!(rv.len(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn compute_background(mut self) -> Result<BackgroundCompute<T>, super::IntersectError>
+    where
+        T: Send + 'static,
+        P: Send + 'static,
+    {
+        BackgroundCompute::spawn(move |progress| {
+            if self.f64_upcast {
+                return self.compute();
+            }
+            let (mut min_y, mut max_y) = (T::infinity(), T::neg_infinity());
+            for i in 0..self.lines.len() {
+                min_y = min_y.min(self.lines.start_y(i));
+                max_y = max_y.max(self.lines.end_y(i));
+            }
+            let range = (max_y - min_y).to_f64().unwrap();
+            loop {
+                if progress.is_cancelled() {
+                    return Err(super::IntersectError::Cancelled(
+                        "The background computation was cancelled".to_string(),
+                    ));
+                }
+                if let Poll::Ready(rv) = self.poll_compute(BACKGROUND_EVENTS_PER_CHECK)? {
+                    return Ok(rv);
+                }
+                if range > 0.0 {
+                    progress.set((self.sweepline_pos.y - min_y).to_f64().unwrap() / range);
+                }
+            }
+        })
+    }
+
+    /// Runs the algorithm like [`AlgorithmData::compute()`] and also returns the lines split at
+    /// the intersection points, see [`node_lines()`]. The pieces follow the direction of the
+    /// re-arranged lines, see [`AlgorithmData::get_lines()`].
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 0.],
+    ///     [2., -5., 2., 5.],
+    ///     [8., -5., 8., 5.],
+    /// ]);
+    /// let (rv, pieces) = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute_noded()?;
+    /// assert_eq!(rv.len(), 2);
+    /// // three pieces for the horizontal line, two for each of the other lines
+    /// assert_eq!(pieces.len(), 7);
+    /// assert_eq!(pieces[1].line, geo::Line::from([(2., 0.), (8., 0.)]));
+    /// assert_eq!(pieces[1].parent, 0);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn compute_noded(
+        &mut self,
+    ) -> Result<(Intersections<T>, Vec<NodedSegment<T>>), super::IntersectError> {
+        let results = self.compute()?;
+        let pieces = node_lines(&self.lines.to_lines(), results.as_slice(), &self.tolerance);
+        Ok((results, pieces))
+    }
+
+    /// Runs the algorithm in f64 and rounds the results back to `T`
+    fn compute_upcast(&mut self) -> Result<Intersections<T>, super::IntersectError> {
+        let to_f64 = |c: geo::Coordinate<T>| geo::Coordinate {
+            x: c.x.to_f64().unwrap(),
+            y: c.y.to_f64().unwrap(),
+        };
+        let mut upcast = AlgorithmData::<f64>::default();
+        let _ = upcast
+            .with_tolerance(self.tolerance.to_f64())?
+            .with_double_double(self.double_double)?
+            .with_filtered_predicates(self.filtered_predicates)?
+            .with_ignore_end_point_intersections(self.ignore_end_point_intersections)?
+            .with_directed_end_points(self.directed_end_points)?
+            .with_stop_at_first_intersection(self.stop_at_first_intersection)?;
+        if let Some(interner) = self.interner.as_ref() {
+            let _ = upcast.with_snap_radius(interner.radius().to_f64().unwrap())?;
+        }
+        let results = upcast
+            .with_lines(
+                self.added_lines()
+                    .into_iter()
+                    .map(|line| geo::Line::new(to_f64(line.start), to_f64(line.end))),
+            )?
+            .compute()?;
+
+        let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::with_capacity(results.len());
+        for (position, mut lines) in results {
+            let position = self.tolerance.precision.make_precise_c(geo::Coordinate {
+                x: T::from(position.x).unwrap(),
+                y: T::from(position.y).unwrap(),
+            });
+            match rv.last_mut() {
+                // points that are distinct in f64 may round to the same point
+                Some(last) if self.tolerance.ulps_eq_c(&last.0, &position) => {
+                    last.1.append(&mut lines);
+                    last.1.sort_unstable();
+                    last.1.dedup();
+                }
+                _ => rv.push((position, lines)),
+            }
+        }
+        self.sweepline_pos = geo::Coordinate {
+            x: T::max_value(),
+            y: T::max_value(),
+        };
+        Ok(rv.into())
+    }
+
+    /// Runs the algorithm to completion, just like [`AlgorithmData::compute()`], but returns
+    /// references to the payloads of the intersecting lines instead of the line indices.
+    #[allow(clippy::type_complexity)]
+    pub fn compute_with_payloads(
+        &mut self,
+    ) -> Result<Vec<(geo::Coordinate<T>, Vec<&P>)>, super::IntersectError> {
+        if self.payloads.len() != self.lines.len() {
+            return Err(super::IntersectError::InvalidParameter(
+                "The lines were not added with payloads".to_string(),
+            ));
+        }
+        let results: Vec<_> = self.compute()?.collect();
+        let payloads = &self.payloads;
+        Ok(results
+            .into_iter()
+            .map(|(pos, lines)| (pos, lines.into_iter().map(|i| &payloads[i]).collect()))
+            .collect())
+    }
+
+    /// Runs the algorithm to completion and returns the results as a geometry collection with
+    /// a parallel vector of provenance records, see
+    /// [`collection::into_geometry_collection()`](crate::collection::into_geometry_collection).
+    #[allow(clippy::type_complexity)]
+    pub fn into_geometry_collection(
+        &mut self,
+    ) -> Result<(geo::GeometryCollection<T>, Vec<Provenance>), super::IntersectError> {
+        let results = self.compute()?;
+        Ok(into_geometry_collection(&self.lines.to_lines(), results))
+    }
+
+    /// Checks `results` (as returned by [`AlgorithmData::compute()`]) against the lines and
+    /// the settings of this struct, see [`verify_results()`](crate::verify::verify_results)
+    /// and [`verify_directed_results()`](crate::verify::verify_directed_results).
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let results: Vec<_> = ad.with_ref_lines(lines.iter())?.compute()?.collect();
+    /// assert!(ad.verify_results(&results, 1000).is_empty());
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn verify_results(
+        &self,
+        results: &[(geo::Coordinate<T>, Vec<usize>)],
+        max_brute_force_lines: usize,
+    ) -> Vec<crate::verify::Discrepancy<T>> {
+        if self.directed_end_points {
+            return crate::verify::verify_directed_results(
+                &self.added_lines(),
+                results,
+                &self.tolerance,
+                max_brute_force_lines,
+            );
+        }
+        crate::verify::verify_results(
+            &self.lines.to_lines(),
+            results,
+            self.ignore_end_point_intersections,
+            &self.tolerance,
+            max_brute_force_lines,
+        )
+    }
+
     /// handles input event, returns true when done
     /// You will have call take_results() if the method returns true
     pub fn compute_iterative(&mut self) -> Result<bool, super::IntersectError> {
-        if self.stop_at_first_intersection && self.result.as_ref().map_or(false, |x| !x.is_empty())
-        {
+        if self.found_first_intersection() {
             return Ok(true);
         }
 
-        // make the borrow checker happy by breaking the link between self and all the
-        // containers and their iterators.
-        let mut active_lines = self.active_lines.take().unwrap();
-        let mut site_events = self.site_events.take().unwrap();
-        let mut result = self.result.take().unwrap();
-        let mut neighbour_priority = self.neighbour_priority.take().unwrap();
-        let mut connected_priority = self.connected_priority.take().unwrap();
+        let (mut active_lines, mut status, mut site_events, mut result) = self.take_containers()?;
 
         // return value
         let algorithm_is_done: bool;
@@ -796,12 +1875,12 @@ where
         algorithm_is_done = if let Some((key, event)) = site_events.pop_pair() {
             self.handle_event(
                 &key,
-                &event,
+                event,
                 &mut active_lines,
-                &mut neighbour_priority,
-                &mut connected_priority,
+                &mut status,
                 &mut site_events,
                 &mut result,
+                &mut (),
             );
             false
         } else {
@@ -813,31 +1892,178 @@ where
             true
         };
 
-        // put the borrowed containers back
-        self.site_events = Some(site_events);
-        self.active_lines = Some(active_lines);
-        self.result = Some(result);
-        self.neighbour_priority = Some(neighbour_priority);
-        self.connected_priority = Some(connected_priority);
+        self.restore_containers((active_lines, status, site_events, result));
         Ok(algorithm_is_done)
     }
 
+    /// Returns a copy of the sweep-line state, see [`SweepSnapshot`].
+    /// Only available with the `snapshot` feature.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [10., 0., 0., 10.]]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let _ = ad.with_ref_lines(lines.iter())?;
+    /// let snapshot = ad.snapshot()?;
+    /// assert_eq!(snapshot.event_index, 0);
+    /// // the start and the end points of both lines
+    /// assert_eq!(snapshot.pending.len(), 4);
+    /// assert!(snapshot.status.is_empty());
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    #[cfg(feature = "snapshot")]
+    pub fn snapshot(&self) -> Result<SweepSnapshot<T>, super::IntersectError> {
+        let (site_events, status, result) = match (&self.site_events, &self.status, &self.result) {
+            (Some(site_events), Some(status), Some(result)) => (site_events, status, result),
+            _ => {
+                return Err(super::IntersectError::ResultsAlreadyTaken(
+                    "Results already taken from structure".to_string(),
+                ))
+            }
+        };
+        let mut events: Vec<(&SiteEventKey<T>, &SiteEvent<T>)> = site_events.iter().collect();
+        events.sort_by(|a, b| a.0.partial_cmp(b.0).unwrap_or(cmp::Ordering::Equal));
+        let mut pending = Vec::<PendingEvent<T>>::new();
+        for (key, event) in events.into_iter() {
+            let merge_with_last = pending
+                .last()
+                .map_or(false, |last: &PendingEvent<T>| last.position == key.pos);
+            if !merge_with_last {
+                pending.push(PendingEvent {
+                    position: key.pos,
+                    add: Vec::new(),
+                    drop: Vec::new(),
+                    intersection: Vec::new(),
+                });
+            }
+            let last = pending.last_mut().unwrap();
+            last.add.extend(event.add.iter().flatten());
+            last.drop.extend(event.drop.iter().flatten());
+            last.intersection
+                .extend(event.intersection.iter().flatten());
+        }
+        for p in pending.iter_mut() {
+            for lines in [&mut p.add, &mut p.drop, &mut p.intersection] {
+                lines.sort_unstable();
+                lines.dedup();
+            }
+        }
+
+        let mut ordered = Vec::new();
+        let mut current = status.first_where(|_| true);
+        while let Some(line) = current {
+            ordered.push(line);
+            current = status.successor(line);
+        }
+        Ok(SweepSnapshot {
+            event_index: self.handled_events,
+            sweepline_pos: self.sweepline_pos,
+            pending,
+            status: ordered,
+            intersections: result.iter().map(|(k, v)| (k.pos, v.clone())).collect(),
+        })
+    }
+
+    /// Handles events until `event_index` events are handled, or until there are no more
+    /// events, and returns a copy of the sweep-line state, see
+    /// [`AlgorithmData::snapshot()`]. The sweep can't go back, an `event_index` lower than
+    /// [`AlgorithmData::get_handled_events()`] returns `InvalidParameter`, call
+    /// [`AlgorithmData::reset()`] to start over.
+    /// Only available with the `snapshot` feature.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [10., 0., 0., 10.]]);
+    /// let mut ad = AlgorithmData::<f64>::default();
+    /// let _ = ad.with_ref_lines(lines.iter())?;
+    /// // both lines started, the intersection is now pending
+    /// let snapshot = ad.snapshot_at(2)?;
+    /// assert_eq!(snapshot.status, vec![0, 1]);
+    /// assert_eq!(snapshot.pending[0].position, geo::Coordinate { x: 5., y: 5. });
+    /// assert_eq!(snapshot.pending[0].intersection, vec![0, 1]);
+    /// let snapshot = ad.snapshot_at(100)?;
+    /// assert!(snapshot.is_done());
+    /// assert_eq!(snapshot.intersections.len(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    #[cfg(feature = "snapshot")]
+    pub fn snapshot_at(
+        &mut self,
+        event_index: usize,
+    ) -> Result<SweepSnapshot<T>, super::IntersectError> {
+        if event_index < self.handled_events {
+            return Err(super::IntersectError::InvalidParameter(format!(
+                "Event {} is already handled, {} events are",
+                event_index, self.handled_events
+            )));
+        }
+        while self.handled_events < event_index {
+            if self
+                .site_events
+                .as_ref()
+                .map_or(false, |site_events| site_events.is_empty())
+            {
+                break;
+            }
+            let _ = self.compute_iterative()?;
+        }
+        self.snapshot()
+    }
+
+    /// Returns true if the end points of the event are exactly two lines, in the directions
+    /// they were added, with the end of one line at the start of the other
+    fn is_connection(&self, event: &SiteEvent<T>) -> bool {
+        // the end points at the event that are the end of the line as it was added
+        let mut heads = 0;
+        let mut tails = 0;
+        for (lines, dropped) in [(&event.drop, true), (&event.add, false)] {
+            for l in lines.iter().flatten() {
+                if self.reversed[*l] != dropped {
+                    heads += 1;
+                } else {
+                    tails += 1;
+                }
+            }
+        }
+        heads == 1 && tails == 1
+    }
+
     #[inline(always)]
     #[allow(clippy::too_many_arguments)]
-    fn handle_event(
+    fn handle_event<H>(
         &mut self,
         key: &SiteEventKey<T>,
-        event: &SiteEvent<T>,
+        mut event: SiteEvent<T>,
         active_lines: &mut ahash::AHashSet<usize>,
-        neighbour_priority: &mut MinMax<T>,
-        connected_priority: &mut MinMaxSlope<T>,
-        site_events: &mut rb_tree::RBMap<SiteEventKey<T>, SiteEvent<T>>,
+        status: &mut SweepStatus,
+        site_events: &mut EventQueue<T>,
         result: &mut rb_tree::RBMap<SiteEventKey<T>, Vec<usize>>,
-    ) {
+        handler: &mut H,
+    ) where
+        H: SweepEventHandler<T> + ?Sized,
+    {
         self.sweepline_pos = key.pos;
-
+        self.handled_events += 1;
+        self.normalize_intersections(&mut event);
+        // the active lines at the event point, in left to right order
+        let at_event = self.lines_at_sweepline_pos(status);
+        self.add_passing_lines(&mut event, &at_event);
+        let at_event = Self::extend_over_intersections(status, at_event, &event);
+
+        if let Some(diagnostics) = self.diagnostics.as_mut() {
+            diagnostics.record(
+                key.pos,
+                event.drop.as_deref().unwrap_or_default(),
+                event.add.as_deref().unwrap_or_default(),
+                event.intersection.as_deref().unwrap_or_default(),
+            );
+        }
         let removed_active_lines = event.drop.iter().flatten().count();
-        let added_active_lines = event.add.iter().flatten().count();
+        // a zero length line is both added and dropped, it only counts once
+        let added_active_lines = event
+            .add
+            .iter()
+            .flatten()
+            .filter(|l| !event.drop.iter().flatten().any(|d| d == *l))
+            .count();
         let intersections_found = event.intersection.iter().flatten().count();
 
         #[cfg(feature = "console_trace")]
@@ -868,194 +2094,341 @@ where
             active_lines.iter().collect::<Vec<&usize>>()
         );
 
+        // the position the intersection at this event point is reported at, if any
+        let mut reported = None;
+
         // Handle points converging at this point:
         // If sum of number of items in 'add' + 'drop' > 1 they must intersect at this point
-        if self.ignore_end_point_intersections {
+        let report = if self.directed_end_points {
+            // only a single line flowing into another line is a connection
+            intersections_found > 0
+                || (removed_active_lines + added_active_lines > 1 && !self.is_connection(&event))
+        } else if self.ignore_end_point_intersections {
             // only report end point intersections if they collide with
             // a calculated intersection point
-            if intersections_found > 0 {
-                if removed_active_lines > 0 {
-                    self.report_intersections_to_result(
-                        result,
-                        &self.sweepline_pos.clone(),
-                        event.drop.iter().flatten(),
-                    );
-                }
-                if added_active_lines > 0 {
-                    self.report_intersections_to_result(
-                        result,
-                        &self.sweepline_pos.clone(),
-                        event.add.iter().flatten(),
-                    );
-                }
-                self.report_intersections_to_result(
-                    result,
-                    &self.sweepline_pos.clone(),
-                    event.intersection.iter().flatten(),
-                );
-            }
-        } else if removed_active_lines + added_active_lines + intersections_found > 1 {
+            intersections_found > 0
+        } else {
             // report *all* intersections, including the end-to-end intersections
+            removed_active_lines + added_active_lines + intersections_found > 1
+        };
+        if report {
             if removed_active_lines > 0 {
-                self.report_intersections_to_result(
+                reported = Some(self.report_intersections_to_result(
                     result,
                     &self.sweepline_pos.clone(),
                     event.drop.iter().flatten(),
-                );
+                ));
             }
             if added_active_lines > 0 {
-                self.report_intersections_to_result(
+                reported = Some(self.report_intersections_to_result(
                     result,
                     &self.sweepline_pos.clone(),
                     event.add.iter().flatten(),
-                );
+                ));
             }
             if intersections_found > 0 {
-                self.report_intersections_to_result(
+                reported = Some(self.report_intersections_to_result(
                     result,
                     &self.sweepline_pos.clone(),
                     event.intersection.iter().flatten(),
-                );
+                ));
             }
         }
 
-        // remove 'drop' lines from the active lines
+        // find the neighbours of the event point before the status is changed
+        let pos = self.sweepline_pos;
+        let (left_neighbour, right_neighbour) = if at_event.is_empty() {
+            let left = status.last_where(|l| {
+                let x = self.x_at(l, &pos);
+                x < pos.x && !self.tolerance.ulps_eq(x, pos.x)
+            });
+            let right = if let Some(left) = left {
+                status.successor(left)
+            } else {
+                status.first_where(|_| true)
+            };
+            (left, right)
+        } else {
+            (
+                status.predecessor(at_event[0]),
+                status.successor(*at_event.last().unwrap()),
+            )
+        };
+
+        // Remove the 'drop' lines and the lines intersecting at this point, the intersecting
+        // lines are then re-inserted in their order just above the event point.
+        let mut inserted = Vec::<usize>::new();
+        for line_index in event.drop.iter().flatten() {
+            if active_lines.contains(line_index) {
+                handler.on_segment_end(*line_index, &SweepContext::new(pos, status, &self.lines));
+            }
+        }
         for line_index in event.drop.iter().flatten() {
             let _ = active_lines.remove(line_index);
+            let _ = status.remove(*line_index);
         }
-
-        //
-        // calculate the sweep-line status for this point (aka the 'leftright' struct)
-        //
-        neighbour_priority.clear();
-
-        'active_lines: for line_index in active_lines.iter() {
-            for i in event.intersection.iter().flatten() {
-                // lines intersecting at this point can never be left/right candidates
-                if i == line_index {
-                    continue 'active_lines;
-                }
+        for line_index in event.intersection.iter().flatten() {
+            if active_lines.contains(line_index) && status.remove(*line_index) {
+                inserted.push(*line_index);
             }
-
-            //print!("(sweepline_intersection id={:?}", line_index);
-            if let Some((intersection_x, intersection_slope)) =
-                sweepline_intersection(self.sweepline_pos, self.lines.get(*line_index).unwrap())
-            {
-                //println!(" @{}^{})", intersection_x, intersection_slope);
-                neighbour_priority.update(
-                    self.sweepline_pos.x,
-                    intersection_x,
-                    intersection_slope,
-                    *line_index,
-                );
+        }
+        let mut started = Vec::<usize>::new();
+        for line_index in event.add.iter().flatten() {
+            if event.drop.iter().flatten().any(|l| l == line_index) {
+                // a zero length line starts and ends at this event, it never becomes active
+                continue;
+            }
+            if active_lines.insert(*line_index) {
+                inserted.push(*line_index);
+                started.push(*line_index);
             }
         }
+        // All the inserted lines pass through the event point, so they are ordered by their
+        // direction alone. (Their x coordinates at the event point may differ by more than
+        // the tolerance, as intersection points are rounded.)
+        inserted.sort_unstable_by(|a, b| self.compare_direction(*a, *b));
+        for (i, line_index) in inserted.iter().enumerate() {
+            let before = &inserted[..i];
+            status.insert(*line_index, |other| {
+                if before.contains(&other) {
+                    false
+                } else {
+                    self.compare_above(*line_index, other, &pos) == cmp::Ordering::Less
+                }
+            });
+        }
 
-        // add the newly found lines
-        for l in event.add.iter().flatten() {
-            let _ = active_lines.insert(*l);
+        for line_index in started {
+            handler.on_segment_start(line_index, &SweepContext::new(pos, status, &self.lines));
+        }
+        if let Some(position) = reported {
+            self.reported_intersections += 1;
+            let key = self.key(position);
+            if let Some(lines) = result.get(&key) {
+                handler.on_intersection(
+                    position,
+                    lines,
+                    &SweepContext::new(pos, status, &self.lines),
+                );
+            }
+            if !self.keep_results {
+                let _ = result.remove(&key);
+            }
         }
 
-        //println!("Mid active lines: {:?}", active_lines);
+        #[cfg(feature = "console_trace")]
+        println!(
+            "left neighbour {:?}, right neighbour {:?}, inserted {:?}",
+            left_neighbour, right_neighbour, inserted
+        );
 
-        if intersections_found + added_active_lines == 0 {
-            #[cfg(feature = "console_trace")]
-            println!(
-                "neighbours left: {:?}",
-                neighbour_priority.slope.candidates_left
-            );
-            #[cfg(feature = "console_trace")]
-            println!(
-                "neighbours right: {:?}",
-                neighbour_priority.slope.candidates_right
-            );
-            // this event didn't spawn off new events, check neighbours
-            if !neighbour_priority.slope.candidates_left.is_empty()
-                && !neighbour_priority.slope.candidates_right.is_empty()
-            {
-                self.find_new_events(
-                    &neighbour_priority.slope.candidates_left,
-                    &neighbour_priority.slope.candidates_right,
-                    site_events,
-                );
+        // check the new neighbours for intersections
+        if let (Some(leftmost), Some(rightmost)) = (inserted.first(), inserted.last()) {
+            if let Some(left) = status.predecessor(*leftmost) {
+                self.find_new_events(&[left], &[*leftmost], site_events);
+            }
+            if let Some(right) = status.successor(*rightmost) {
+                self.find_new_events(&[right], &[*rightmost], site_events);
             }
+        } else if let (Some(left), Some(right)) = (left_neighbour, right_neighbour) {
+            self.find_new_events(&[left], &[right], site_events);
+        }
+        #[cfg(feature = "console_trace")]
+        println!();
+    }
+
+    /// Returns the x coordinate of `line` at the height of `pos`.
+    /// Horizontal lines return `pos.x` clamped to the extent of the line.
+    #[inline(always)]
+    fn x_at(&self, line: usize, pos: &geo::Coordinate<T>) -> T {
+        let lines = &self.lines;
+        let (start_y, end_y) = (lines.start_y(line), lines.end_y(line));
+        if self.tolerance.ulps_eq(start_y, end_y) {
+            // the end points are ordered, so start.x <= end.x
+            pos.x.max(lines.start_x(line)).min(lines.end_x(line))
+        } else if pos.y == start_y {
+            lines.start_x(line)
+        } else if pos.y == end_y {
+            lines.end_x(line)
+        } else if self.double_double {
+            double::x_at(lines.start(line), lines.end(line), pos.y)
         } else {
-            connected_priority.clear();
-            for l in event.add.iter().flatten() {
-                connected_priority.update_both(*l, &self.lines);
+            let start_x = lines.start_x(line);
+            start_x + (pos.y - start_y) * (lines.end_x(line) - start_x) / (end_y - start_y)
+        }
+    }
+
+    /// Compares two active lines just above the sweep-line position `pos`: first by the x
+    /// coordinate at `pos`, then by the direction of the lines (dx/dy). Horizontal lines go last.
+    /// With `filtered_predicates` the x coordinates of lines that aren't horizontal are
+    /// compared by [`compare_x_at()`](crate::predicates::compare_x_at).
+    fn compare_above(&self, a: usize, b: usize, pos: &geo::Coordinate<T>) -> cmp::Ordering {
+        let (xa, xb) = (self.x_at(a, pos), self.x_at(b, pos));
+        if !self.tolerance.ulps_eq(xa, xb) {
+            if self.filtered_predicates && !self.is_horizontal(a) && !self.is_horizontal(b) {
+                let line = |l: usize| geo::Line::new(self.lines.start(l), self.lines.end(l));
+                match predicates::compare_x_at(&line(a), &line(b), pos.y) {
+                    Some(cmp::Ordering::Equal) => return self.compare_direction(a, b),
+                    Some(order) => return order,
+                    None => (),
+                }
             }
-            for l in event.intersection.iter().flatten() {
-                connected_priority.update_both(*l, &self.lines);
+            return xa.partial_cmp(&xb).unwrap_or(cmp::Ordering::Equal);
+        }
+        self.compare_direction(a, b)
+    }
+
+    /// Returns true if the line is horizontal within the tolerance
+    #[inline(always)]
+    fn is_horizontal(&self, line: usize) -> bool {
+        self.tolerance
+            .ulps_eq(self.lines.start_y(line), self.lines.end_y(line))
+    }
+
+    /// Compares two lines by their direction (dx/dy), horizontal lines go last.
+    fn compare_direction(&self, a: usize, b: usize) -> cmp::Ordering {
+        let direction = |l: usize| {
+            let lines = &self.lines;
+            let dy = lines.end_y(l) - lines.start_y(l);
+            if self.tolerance.ulps_eq(lines.start_y(l), lines.end_y(l)) {
+                T::infinity()
+            } else {
+                (lines.end_x(l) - lines.start_x(l)) / dy
             }
-            #[cfg(feature = "console_trace")]
-            println!(
-                "left connected_priority candidates {:?}",
-                connected_priority.candidates_left
-            );
-            #[cfg(feature = "console_trace")]
-            println!(
-                "right connected_priority candidates {:?}",
-                connected_priority.candidates_right
-            );
+        };
+        let (da, db) = (direction(a), direction(b));
+        if approx::ulps_eq!(da, db) {
+            a.cmp(&b)
+        } else {
+            da.partial_cmp(&db).unwrap_or(cmp::Ordering::Equal)
+        }
+    }
 
-            if !neighbour_priority.slope.candidates_left.is_empty() {
-                #[cfg(feature = "console_trace")]
-                println!(
-                    "left neighbour_priority candidates {:?}",
-                    neighbour_priority.slope.candidates_left
-                );
+    /// Returns the active lines crossing the sweep-line at the sweep-line position, in left to
+    /// right order.
+    fn lines_at_sweepline_pos(&self, status: &SweepStatus) -> Vec<usize> {
+        let pos = self.sweepline_pos;
+        let mut rv = Vec::<usize>::new();
+        let mut current = status.first_where(|l| {
+            let x = self.x_at(l, &pos);
+            x > pos.x || self.tolerance.ulps_eq(x, pos.x)
+        });
+        while let Some(l) = current {
+            if !self.tolerance.ulps_eq(self.x_at(l, &pos), pos.x) {
+                break;
+            }
+            rv.push(l);
+            current = status.successor(l);
+        }
+        rv
+    }
 
-                self.find_new_events(
-                    &neighbour_priority.slope.candidates_left,
-                    &connected_priority.candidates_left,
-                    site_events,
-                );
+    /// Intersection points are rounded, so a line of an intersection event can miss the event
+    /// point by more than the tolerance (e.g. when the line is almost parallel to the
+    /// sweep-line). Those lines are still at the event, so the run of lines at the event point
+    /// is extended over the neighbouring intersection lines.
+    fn extend_over_intersections(
+        status: &SweepStatus,
+        mut at_event: Vec<usize>,
+        event: &SiteEvent<T>,
+    ) -> Vec<usize> {
+        let intersections = match event.intersection.as_ref() {
+            Some(intersections) => intersections,
+            None => return at_event,
+        };
+        if at_event.is_empty() {
+            match intersections.iter().find(|l| status.contains(**l)) {
+                Some(l) => at_event.push(*l),
+                None => return at_event,
             }
-            if !neighbour_priority.slope.candidates_right.is_empty() {
-                #[cfg(feature = "console_trace")]
-                println!(
-                    "right neighbour_priority candidates {:?}",
-                    neighbour_priority.slope.candidates_right
-                );
+        }
+        while let Some(l) = status.predecessor(at_event[0]) {
+            if !intersections.contains(&l) || at_event.contains(&l) {
+                break;
+            }
+            at_event.insert(0, l);
+        }
+        while let Some(l) = status.successor(*at_event.last().unwrap()) {
+            if !intersections.contains(&l) || at_event.contains(&l) {
+                break;
+            }
+            at_event.push(l);
+        }
+        at_event
+    }
 
-                self.find_new_events(
-                    &neighbour_priority.slope.candidates_right,
-                    &connected_priority.candidates_right,
-                    site_events,
-                );
+    /// The intersection lists of merged events may contain duplicates, and lines that have
+    /// an end point at the event point (those lines are in the 'add' or 'drop' lists)
+    fn normalize_intersections(&self, event: &mut SiteEvent<T>) {
+        let pos = self.sweepline_pos;
+        if let Some(mut intersections) = event.intersection.take() {
+            intersections.retain(|l| {
+                !(self.tolerance.ulps_eq_c(&self.lines.start(*l), &pos)
+                    || self.tolerance.ulps_eq_c(&self.lines.end(*l), &pos))
+            });
+            intersections.sort_unstable();
+            intersections.dedup();
+            if !intersections.is_empty() {
+                event.intersection = Some(intersections);
             }
         }
-        #[cfg(any(test, example))]
-        println!("Post active lines: {:?}", active_lines);
-        #[cfg(feature = "console_trace")]
-        println!();
+    }
+
+    /// Active lines passing through the event point with their interior intersect at the
+    /// event point, even if that intersection was never added as an event of its own.
+    /// (E.g. when an end point of one line touches the interior of another line)
+    fn add_passing_lines(&self, event: &mut SiteEvent<T>, at_event: &[usize]) {
+        let pos = self.sweepline_pos;
+        let mut passing: Vec<usize> = at_event
+            .iter()
+            .filter(|l| {
+                !(self.tolerance.ulps_eq_c(&self.lines.start(**l), &pos)
+                    || self.tolerance.ulps_eq_c(&self.lines.end(**l), &pos))
+            })
+            .copied()
+            .collect();
+        if !passing.is_empty() {
+            let mut intersections = event.intersection.take().unwrap_or_default();
+            intersections.append(&mut passing);
+            intersections.sort_unstable();
+            intersections.dedup();
+            event.intersection = Some(intersections);
+        }
     }
 
     fn find_new_events(
         &mut self,
         left: &[usize],
         right: &[usize],
-        site_events: &mut rb_tree::RBMap<SiteEventKey<T>, SiteEvent<T>>,
+        site_events: &mut EventQueue<T>,
     ) {
         for left_i in left.iter() {
             for right_i in right.iter() {
-                let left_l = &self.lines[*left_i];
-                let right_l = &self.lines[*right_i];
-                if ulps_eq_c(&left_l.end, &right_l.end) {
+                let left_l = &self.lines.get(*left_i);
+                let right_l = &self.lines.get(*right_i);
+                if self.tolerance.ulps_eq_c(&left_l.end, &right_l.end) {
                     // if endpoints are equal they will already be in the event queue
                     continue;
                 }
+                let pair = (*left_i.min(right_i), *left_i.max(right_i));
+                if self.pair_cache.contains_key(&pair) {
+                    // Two lines can only intersect once, so any intersection is already queued.
+                    self.pair_cache_hits += 1;
+                    continue;
+                }
                 #[cfg(feature = "console_trace")]
                 print!("testing intersection between {} and {}: ", left_i, right_i);
                 self.intersection_calls += 1;
-                if let Some(intersection_p) = intersect(left_l, right_l) {
-                    let intersection_p = intersection_p.single();
+                let intersection_p = if self.double_double {
+                    double::intersect_double_double(left_l, right_l, &self.tolerance)
+                } else {
+                    intersect_with_tolerance(left_l, right_l, &self.tolerance)
+                }
+                .map(|i| i.single());
+                let _ = self.pair_cache.insert(pair, intersection_p);
+                if let Some(intersection_p) = intersection_p {
                     // don't allow intersection 'behind' or 'at' current sweep-line position
-                    if intersection_p.y >= self.sweepline_pos.y
-                        && !(intersection_p.y == self.sweepline_pos.y
-                            && intersection_p.x < self.sweepline_pos.x)
-                        && !ulps_eq_c(&intersection_p, &self.sweepline_pos)
-                    {
+                    if self.key(intersection_p).gt(&self.key(self.sweepline_pos)) {
                         #[cfg(feature = "console_trace")]
                         println!(
                             "Lines {:?} and {:?} intersects at {:?}",
@@ -1064,9 +2437,7 @@ where
 
                         self.add_intersection_event(
                             site_events,
-                            &SiteEventKey {
-                                pos: intersection_p,
-                            },
+                            &self.key(intersection_p),
                             &[*left_i, *right_i],
                         )
                     }
@@ -1078,15 +2449,23 @@ where
         }
     }
 
+    /// Adds the lines to the result at `pos`, returns the position the lines were stored at
     fn report_intersections_to_result<'a, I>(
         &mut self,
         result: &mut rb_tree::RBMap<SiteEventKey<T>, Vec<usize>>,
         pos: &geo::Coordinate<T>,
         intersecting_lines: I,
-    ) where
+    ) -> geo::Coordinate<T>
+    where
         I: Iterator<Item = &'a usize>,
     {
-        let key = SiteEventKey { pos: *pos };
+        let pos = if let Some(interner) = self.interner.as_mut() {
+            let id = interner.intern(*pos);
+            interner.get(id)
+        } else {
+            *pos
+        };
+        let key = self.key(pos);
 
         let value = if let Some(value) = result.get_mut(&key) {
             value
@@ -1101,6 +2480,8 @@ where
             println!("Reported an intersection {:?} for line #{}", pos, line);
         }
         value.sort_unstable();
+        value.dedup();
+        pos
     }
 
     #[cfg(feature = "console_trace")]
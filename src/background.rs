@@ -0,0 +1,164 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Running the sweep on a worker thread.
+//!
+//! [`AlgorithmData::compute_background()`](crate::algorithm::AlgorithmData::compute_background)
+//! moves a prepared instance to a new thread and returns a [`BackgroundCompute`] handle. The
+//! handle reports the progress of the sweep, can cancel it, and returns the results when
+//! joined. This keeps e.g. the UI thread of an application responsive during large analyses.
+
+use crate::list::Intersections;
+use crate::IntersectError;
+use num_traits::Float;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+/// The state shared between the handle and the worker thread
+#[derive(Default)]
+pub(crate) struct Progress {
+    // the progress as the bits of a f64
+    fraction: AtomicU64,
+    cancelled: AtomicBool,
+    done: AtomicBool,
+}
+
+impl Progress {
+    /// Sets the progress, clamped to 0.0..=1.0
+    pub(crate) fn set(&self, fraction: f64) {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        self.fraction.store(fraction.to_bits(), Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// A handle to a sweep running on a worker thread, see the module documentation.
+/// Dropping the handle cancels the sweep.
+pub struct BackgroundCompute<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    progress: Arc<Progress>,
+    worker: Option<thread::JoinHandle<Result<Intersections<T>, IntersectError>>>,
+}
+
+impl<T> BackgroundCompute<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Send + 'static,
+    T::Epsilon: Copy,
+{
+    /// Runs `work` on a new thread, `work` is expected to update the progress and to check
+    /// for cancellation now and then.
+    pub(crate) fn spawn<F>(work: F) -> Result<Self, IntersectError>
+    where
+        F: FnOnce(&Progress) -> Result<Intersections<T>, IntersectError> + Send + 'static,
+    {
+        let progress = Arc::new(Progress::default());
+        let shared = Arc::clone(&progress);
+        let worker = thread::Builder::new()
+            .name("intersect2d".to_string())
+            .spawn(move || {
+                let rv = work(&shared);
+                if rv.is_ok() {
+                    shared.set(1.0);
+                }
+                shared.done.store(true, Ordering::Release);
+                rv
+            })
+            .map_err(|err| IntersectError::InternalError(err.to_string()))?;
+        Ok(Self {
+            progress,
+            worker: Some(worker),
+        })
+    }
+
+    /// Returns true when the worker thread is finished, [`BackgroundCompute::join()`] will
+    /// then return without blocking.
+    pub fn is_done(&self) -> bool {
+        self.progress.done.load(Ordering::Acquire)
+    }
+
+    /// Returns the approximate progress of the sweep, from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        f64::from_bits(self.progress.fraction.load(Ordering::Relaxed))
+    }
+
+    /// Asks the worker thread to stop, [`BackgroundCompute::join()`] will then return
+    /// [`IntersectError::Cancelled`] unless the sweep already finished.
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Waits for the worker thread to finish and returns the results
+    pub fn join(mut self) -> Result<Intersections<T>, IntersectError> {
+        match self.worker.take().unwrap().join() {
+            Ok(rv) => rv,
+            Err(_) => Err(IntersectError::InternalError(
+                "The worker thread panicked".to_string(),
+            )),
+        }
+    }
+}
+
+impl<T> Drop for BackgroundCompute<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn drop(&mut self) {
+        if self.worker.is_some() {
+            self.progress.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+}
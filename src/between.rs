@@ -0,0 +1,545 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Intersections between two sets of lines.
+//!
+//! [`intersections_between_sets()`] only reports the points where a line of one set meets a
+//! line of the other set. Before the sweep runs, the sets are compared by their overall
+//! bounding boxes, and by their x extents within a number of horizontal slabs. Lines that
+//! can't reach the other set are never handed to the sweep, so comparing a small query layer
+//! against a huge base layer only touches the part of the base layer near the query.
+//! [`intersections_between()`] does the same for two `geo::LineString`s and locates each
+//! point on both line strings by segment index and measure.
+//! [`boundaries_intersect()`] is a broad-phase predicate for two polygons built on the same
+//! filter, it stops at the first shared boundary point.
+
+use crate::algorithm::{AlgorithmData, SiteEventKey};
+use crate::vector::norm;
+use crate::{ulps_eq_c, IntersectError};
+use num_traits::Float;
+use std::cmp;
+
+/// A point where lines of the two sets meet.
+/// `a` and `b` are the indices of the involved lines in the first and in the second set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    pub a: Vec<usize>,
+    pub b: Vec<usize>,
+}
+
+/// Where a [`LineStringIntersection`] lies on one of the line strings.
+/// `segment` is the index of the segment, i.e. the segment from vertex `segment` to vertex
+/// `segment + 1`, and `measure` is the distance from the first vertex along the line string.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineStringLocation<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub segment: usize,
+    pub measure: T,
+}
+
+/// A point where two line strings meet, see [`intersections_between()`].
+/// `a` and `b` are the locations of the point on the first and on the second line string,
+/// a point at a vertex is reported for both of the segments sharing that vertex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineStringIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    pub a: Vec<LineStringLocation<T>>,
+    pub b: Vec<LineStringLocation<T>>,
+}
+
+/// The x extent of a set of lines inside each of a number of horizontal slabs
+struct SlabExtents<T> {
+    y_min: T,
+    slab_height: T,
+    slabs: usize,
+    // (min x, max x) of each slab, None if no line passes through the slab
+    extents: Vec<Option<(T, T)>>,
+}
+
+impl<T> SlabExtents<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn new(region: &geo::Rect<T>, slabs: usize) -> Self {
+        let height = region.max().y - region.min().y;
+        Self {
+            y_min: region.min().y,
+            slab_height: height / T::from(slabs).unwrap(),
+            slabs,
+            extents: vec![None; slabs],
+        }
+    }
+
+    fn slab_of(&self, y: T) -> usize {
+        if self.slab_height <= T::zero() {
+            return 0;
+        }
+        let slab = ((y - self.y_min) / self.slab_height).floor();
+        slab.max(T::zero())
+            .to_usize()
+            .unwrap_or(0)
+            .min(self.slabs - 1)
+    }
+
+    /// Calls `f(slab, min x, max x)` with the x extent of the part of `line` inside each
+    /// slab it passes through
+    fn for_each_slab<F>(&self, line: &geo::Line<T>, margin: T, mut f: F)
+    where
+        F: FnMut(usize, T, T),
+    {
+        let (low, high) = if line.start.y <= line.end.y {
+            (line.start, line.end)
+        } else {
+            (line.end, line.start)
+        };
+        let (first, last) = (self.slab_of(low.y), self.slab_of(high.y));
+        if first == last || high.y == low.y {
+            for slab in first..=last {
+                f(slab, low.x.min(high.x) - margin, low.x.max(high.x) + margin);
+            }
+            return;
+        }
+        let x_at = |y: T| low.x + (y - low.y) * (high.x - low.x) / (high.y - low.y);
+        for slab in first..=last {
+            let slab_low = self.y_min + self.slab_height * T::from(slab).unwrap();
+            let y0 = low.y.max(slab_low);
+            let y1 = high.y.min(slab_low + self.slab_height);
+            let (x0, x1) = (x_at(y0), x_at(y1));
+            f(slab, x0.min(x1) - margin, x0.max(x1) + margin);
+        }
+    }
+
+    fn add(&mut self, line: &geo::Line<T>, margin: T) {
+        let mut extents = std::mem::take(&mut self.extents);
+        self.for_each_slab(line, margin, |slab, x0, x1| {
+            extents[slab] = Some(match extents[slab] {
+                Some((e0, e1)) => (e0.min(x0), e1.max(x1)),
+                None => (x0, x1),
+            });
+        });
+        self.extents = extents;
+    }
+
+    fn reaches(&self, line: &geo::Line<T>, margin: T) -> bool {
+        let mut rv = false;
+        self.for_each_slab(line, margin, |slab, x0, x1| {
+            if let Some((e0, e1)) = self.extents[slab] {
+                rv |= x0 <= e1 && x1 >= e0;
+            }
+        });
+        rv
+    }
+}
+
+fn bounding_rect<T>(lines: &[geo::Line<T>]) -> Option<geo::Rect<T>>
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut iter = lines.iter();
+    let first = iter.next()?;
+    let (mut min, mut max) = (first.start, first.start);
+    for p in std::iter::once(&first.end).chain(iter.flat_map(|l| [&l.start, &l.end])) {
+        min = geo::Coordinate {
+            x: min.x.min(p.x),
+            y: min.y.min(p.y),
+        };
+        max = geo::Coordinate {
+            x: max.x.max(p.x),
+            y: max.y.max(p.y),
+        };
+    }
+    Some(geo::Rect::new(min, max))
+}
+
+fn touches_rect<T>(line: &geo::Line<T>, rect: &geo::Rect<T>) -> bool
+where
+    T: Float + geo::CoordFloat,
+{
+    line.start.x.max(line.end.x) >= rect.min().x
+        && line.start.x.min(line.end.x) <= rect.max().x
+        && line.start.y.max(line.end.y) >= rect.min().y
+        && line.start.y.min(line.end.y) <= rect.max().y
+}
+
+/// Returns the indices of the lines of `a`, and of `b`, that may reach the other set.
+/// Lines outside these lists can't intersect any line of the other set.
+/// ```
+/// # use intersect2d::between::candidate_lines;
+/// let a = intersect2d::to_lines::<_, f64>(&[[0., 0., 1., 1.]]);
+/// let b = intersect2d::to_lines::<_, f64>(&[[0., 1., 1., 0.], [5., 5., 6., 6.], [0., 9., 9., 9.]]);
+/// assert_eq!(candidate_lines(&a, &b)?, (vec![0], vec![0]));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn candidate_lines<T>(
+    a: &[geo::Line<T>],
+    b: &[geo::Line<T>],
+) -> Result<(Vec<usize>, Vec<usize>), IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    for a_line in a.iter().chain(b.iter()) {
+        if !(a_line.start.x.is_finite()
+            && a_line.start.y.is_finite()
+            && a_line.end.x.is_finite()
+            && a_line.end.y.is_finite())
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't check for intersections on non-finite data".to_string(),
+            ));
+        }
+    }
+    let (bbox_a, bbox_b) = match (bounding_rect(a), bounding_rect(b)) {
+        (Some(bbox_a), Some(bbox_b)) => (bbox_a, bbox_b),
+        _ => return Ok((Vec::new(), Vec::new())),
+    };
+    // use a margin a bit larger than the rounding errors of the coordinates
+    let scale = [bbox_a.min(), bbox_a.max(), bbox_b.min(), bbox_b.max()]
+        .iter()
+        .fold(T::one(), |s, p| s.max(p.x.abs()).max(p.y.abs()));
+    let margin = scale * T::epsilon() * T::from(16).unwrap();
+    let low = geo::Coordinate {
+        x: bbox_a.min().x.max(bbox_b.min().x) - margin,
+        y: bbox_a.min().y.max(bbox_b.min().y) - margin,
+    };
+    let high = geo::Coordinate {
+        x: bbox_a.max().x.min(bbox_b.max().x) + margin,
+        y: bbox_a.max().y.min(bbox_b.max().y) + margin,
+    };
+    if low.x > high.x || low.y > high.y {
+        // the bounding boxes of the sets don't overlap
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let region = geo::Rect::new(low, high);
+
+    let in_region = |lines: &[geo::Line<T>]| -> Vec<usize> {
+        (0..lines.len())
+            .filter(|i| touches_rect(&lines[*i], &region))
+            .collect()
+    };
+    let (in_a, in_b) = (in_region(a), in_region(b));
+    let slabs = ((in_a.len() + in_b.len()) as f64).sqrt().ceil() as usize;
+    let slabs = slabs.clamp(1, 256);
+
+    let filter = |from: &[geo::Line<T>], from_i: &[usize], to: &[geo::Line<T>], to_i: &[usize]| {
+        let mut extents = SlabExtents::new(&region, slabs);
+        for i in to_i.iter() {
+            extents.add(&to[*i], margin);
+        }
+        from_i
+            .iter()
+            .copied()
+            .filter(|i| extents.reaches(&from[*i], margin))
+            .collect::<Vec<usize>>()
+    };
+    let candidates_b = filter(b, &in_b, a, &in_a);
+    let candidates_a = filter(a, &in_a, b, &candidates_b);
+    Ok((candidates_a, candidates_b))
+}
+
+/// Returns the points where a line of `a` meets a line of `b`, intersections within a set are
+/// not reported. The lines are first reduced with [`candidate_lines()`].
+/// If `ignore_end_point_intersections` is set, points where the involved lines only meet at
+/// end points are not reported.
+/// ```
+/// # use intersect2d::between::intersections_between_sets;
+/// let query = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.]]);
+/// let base = intersect2d::to_lines::<_, f64>(&[
+///     [0., 10., 10., 0.],
+///     [0., 5., 10., 5.],
+///     [100., 100., 200., 200.],
+/// ]);
+/// let rv = intersections_between_sets(&query, &base, false)?;
+/// assert_eq!(rv.len(), 1);
+/// assert_eq!(rv[0].position, geo::Coordinate { x: 5., y: 5. });
+/// assert_eq!(rv[0].a, vec![0]);
+/// assert_eq!(rv[0].b, vec![0, 1]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn intersections_between_sets<T>(
+    a: &[geo::Line<T>],
+    b: &[geo::Line<T>],
+    ignore_end_point_intersections: bool,
+) -> Result<Vec<SetIntersection<T>>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let (candidates_a, candidates_b) = candidate_lines(a, b)?;
+    if candidates_a.is_empty() || candidates_b.is_empty() {
+        return Ok(Vec::new());
+    }
+    // the payload is (set, index in set)
+    let lines: Vec<(geo::Line<T>, (usize, usize))> = candidates_a
+        .iter()
+        .map(|i| (a[*i], (0, *i)))
+        .chain(candidates_b.iter().map(|i| (b[*i], (1, *i))))
+        .collect();
+    let mut ad = AlgorithmData::<T, (usize, usize)>::default();
+    let _ = ad
+        .with_ignore_end_point_intersections(ignore_end_point_intersections)?
+        .with_payload_lines(lines.iter().copied())?;
+    let mut rv = Vec::<SetIntersection<T>>::new();
+    for (position, involved) in ad.compute()? {
+        let mut intersection = SetIntersection {
+            position,
+            a: Vec::new(),
+            b: Vec::new(),
+        };
+        for l in involved.into_iter() {
+            match lines[l].1 {
+                (0, i) => intersection.a.push(i),
+                (_, i) => intersection.b.push(i),
+            }
+        }
+        if !intersection.a.is_empty() && !intersection.b.is_empty() {
+            intersection.a.sort_unstable();
+            intersection.b.sort_unstable();
+            rv.push(intersection);
+        }
+    }
+    Ok(rv)
+}
+
+/// The non zero length segments of `line_string` as (segment, index of the segment, measure
+/// at the start of the segment)
+fn measured_segments<T>(line_string: &geo::LineString<T>) -> Vec<(geo::Line<T>, usize, T)>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut rv = Vec::with_capacity(line_string.0.len().saturating_sub(1));
+    let mut measure = T::zero();
+    for (index, segment) in line_string.0.windows(2).enumerate() {
+        let segment = geo::Line::new(segment[0], segment[1]);
+        if ulps_eq_c(&segment.start, &segment.end) {
+            continue;
+        }
+        rv.push((segment, index, measure));
+        measure = measure + norm(&(segment.end - segment.start));
+    }
+    rv
+}
+
+/// Returns the points where `ls_a` meets `ls_b`, sorted in sweep order. The self
+/// intersections of the line strings are not reported. Both line strings go into a
+/// single sweep (see [`intersections_between_sets()`]), and each point is located on both
+/// of them by segment index and measure, see [`LineStringLocation`].
+/// Zero length segments are ignored, but they still count when indexing the segments.
+/// ```
+/// # use intersect2d::between::{intersections_between, LineStringLocation};
+/// let a = geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]);
+/// let b = geo::LineString::from(vec![(5., -5.), (5., 5.), (15., 5.)]);
+/// let rv = intersections_between(&a, &b)?;
+/// assert_eq!(rv.len(), 2);
+/// assert_eq!(rv[0].position, geo::Coordinate { x: 5., y: 0. });
+/// assert_eq!(rv[0].a, vec![LineStringLocation { segment: 0, measure: 5. }]);
+/// assert_eq!(rv[0].b, vec![LineStringLocation { segment: 0, measure: 5. }]);
+/// assert_eq!(rv[1].position, geo::Coordinate { x: 10., y: 5. });
+/// assert_eq!(rv[1].a, vec![LineStringLocation { segment: 1, measure: 15. }]);
+/// assert_eq!(rv[1].b, vec![LineStringLocation { segment: 1, measure: 15. }]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn intersections_between<T>(
+    ls_a: &geo::LineString<T>,
+    ls_b: &geo::LineString<T>,
+) -> Result<Vec<LineStringIntersection<T>>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let segments_a = measured_segments(ls_a);
+    let segments_b = measured_segments(ls_b);
+    let lines_a: Vec<geo::Line<T>> = segments_a.iter().map(|s| s.0).collect();
+    let lines_b: Vec<geo::Line<T>> = segments_b.iter().map(|s| s.0).collect();
+    let locate = |segments: &[(geo::Line<T>, usize, T)],
+                  indices: Vec<usize>,
+                  position: &geo::Coordinate<T>| {
+        indices
+            .into_iter()
+            .map(|i| {
+                let (line, segment, measure) = segments[i];
+                LineStringLocation {
+                    segment,
+                    measure: measure + norm(&(*position - line.start)),
+                }
+            })
+            .collect()
+    };
+    Ok(intersections_between_sets(&lines_a, &lines_b, false)?
+        .into_iter()
+        .map(|i| LineStringIntersection {
+            position: i.position,
+            a: locate(&segments_a, i.a, &i.position),
+            b: locate(&segments_b, i.b, &i.position),
+        })
+        .collect())
+}
+
+/// Returns true if the boundaries of two polygons (exterior and interior rings) share at least
+/// one point, touching counts as intersecting. A polygon completely inside the other one, or
+/// inside one of its holes, doesn't intersect its boundary.
+/// The rings are reduced with [`candidate_lines()`] first, and the search stops at the first
+/// point found. Small candidate sets are tested pair by pair, larger ones with the sweep-line.
+/// ```
+/// # use intersect2d::between::boundaries_intersect;
+/// let square = geo::Polygon::new(
+///     geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+///     vec![],
+/// );
+/// let inner = geo::Polygon::new(
+///     geo::LineString::from(vec![(2., 2.), (8., 2.), (8., 8.), (2., 2.)]),
+///     vec![],
+/// );
+/// let crossing = geo::Polygon::new(
+///     geo::LineString::from(vec![(5., 5.), (15., 5.), (15., 15.), (5., 5.)]),
+///     vec![],
+/// );
+/// assert!(!boundaries_intersect(&square, &inner)?);
+/// assert!(boundaries_intersect(&square, &crossing)?);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn boundaries_intersect<T>(
+    a: &geo::Polygon<T>,
+    b: &geo::Polygon<T>,
+) -> Result<bool, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let rings = |p: &geo::Polygon<T>| -> Vec<geo::Line<T>> {
+        std::iter::once(p.exterior())
+            .chain(p.interiors().iter())
+            .flat_map(|r| r.lines())
+            .collect()
+    };
+    let (a, b) = (rings(a), rings(b));
+    let (candidates_a, candidates_b) = candidate_lines(&a, &b)?;
+    if candidates_a.is_empty() || candidates_b.is_empty() {
+        return Ok(false);
+    }
+    if candidates_a.len() * candidates_b.len() <= 1024 {
+        return Ok(candidates_a.iter().any(|i| {
+            candidates_b
+                .iter()
+                .any(|j| crate::intersect(&a[*i], &b[*j]).is_some())
+        }));
+    }
+
+    // Points where only end points of lines from both sets meet are ignored by the sweep
+    // below, so look for shared vertices first.
+    let mut vertices: Vec<(SiteEventKey<T>, usize)> = candidates_a
+        .iter()
+        .flat_map(|i| [a[*i].start, a[*i].end])
+        .map(|p| (SiteEventKey::new(p.x, p.y), 0))
+        .chain(
+            candidates_b
+                .iter()
+                .flat_map(|i| [b[*i].start, b[*i].end])
+                .map(|p| (SiteEventKey::new(p.x, p.y), 1)),
+        )
+        .collect();
+    vertices.sort_unstable_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(cmp::Ordering::Equal));
+    let mut group_start = 0;
+    while group_start < vertices.len() {
+        let position = vertices[group_start].0.pos;
+        let mut group_end = group_start + 1;
+        while group_end < vertices.len() && ulps_eq_c(&vertices[group_end].0.pos, &position) {
+            group_end += 1;
+        }
+        let group = &vertices[group_start..group_end];
+        if group.iter().any(|v| v.1 != group[0].1) {
+            return Ok(true);
+        }
+        group_start = group_end;
+    }
+
+    // Within a valid polygon the rings only meet at end points, so the sweep rarely reports
+    // anything but points shared with the other polygon.
+    let lines: Vec<geo::Line<T>> = candidates_a
+        .iter()
+        .map(|i| a[*i])
+        .chain(candidates_b.iter().map(|i| b[*i]))
+        .collect();
+    let mut ad = AlgorithmData::<T>::default();
+    let _ = ad
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?;
+    let mut seen = 0;
+    loop {
+        let done = ad.compute_iterative()?;
+        let results = ad.get_results().as_ref().unwrap();
+        if results.len() != seen {
+            seen = results.len();
+            if results.iter().any(|(_, involved)| {
+                involved.iter().any(|l| *l < candidates_a.len())
+                    && involved.iter().any(|l| *l >= candidates_a.len())
+            }) {
+                return Ok(true);
+            }
+        }
+        if done {
+            return Ok(false);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Open path vs closed ring semantics of `geo::LineString` input.
+//!
+//! By default a line string is considered a ring if it has more than two points and the first
+//! point is ulps-equal to the last, see [`Closedness::Infer`]. A ring whose closure point is
+//! only almost identical to its first point then depends on the tolerance of that single
+//! comparison. [`Closedness::Open`] and [`Closedness::Closed`] state the intent explicitly.
+
+use crate::ulps_eq_c;
+use num_traits::Float;
+
+/// How the first and last point of a `geo::LineString` are interpreted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Closedness {
+    /// The line string is a ring if it has more than two points and the first and the last
+    /// point are ulps-equal.
+    #[default]
+    Infer,
+    /// The line string is an open path, the first and the last point are always end points.
+    Open,
+    /// The line string is a closed ring. If the last point is ulps-equal to the first point
+    /// it is snapped to the first point, otherwise a closing segment from the last point to
+    /// the first point is added.
+    Closed,
+}
+
+impl Closedness {
+    /// Returns true if `line_string` is a ring under these semantics.
+    /// ```
+    /// # use intersect2d::Closedness;
+    /// let almost = geo::LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1e-3)]);
+    /// assert!(!Closedness::Infer.is_closed(&almost));
+    /// assert!(Closedness::Closed.is_closed(&almost));
+    /// ```
+    pub fn is_closed<T>(self, line_string: &geo::LineString<T>) -> bool
+    where
+        T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+        T::Epsilon: Copy,
+    {
+        let points = &line_string.0;
+        match self {
+            Closedness::Open => false,
+            Closedness::Closed => points.len() > 2,
+            Closedness::Infer => {
+                points.len() > 2 && ulps_eq_c(points.first().unwrap(), points.last().unwrap())
+            }
+        }
+    }
+
+    /// Returns the segments of `line_string` under these semantics.
+    /// A closed ring always ends at exactly the point it started at.
+    /// ```
+    /// # use intersect2d::Closedness;
+    /// let open = geo::LineString::from(vec![(0., 0.), (1., 0.), (1., 1.)]);
+    /// assert_eq!(Closedness::Open.segments(&open).len(), 2);
+    /// let ring = Closedness::Closed.segments(&open);
+    /// assert_eq!(ring.len(), 3);
+    /// assert_eq!(ring[2].end, geo::Coordinate { x: 0., y: 0. });
+    /// ```
+    pub fn segments<T>(self, line_string: &geo::LineString<T>) -> Vec<geo::Line<T>>
+    where
+        T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+        T::Epsilon: Copy,
+    {
+        let points = &line_string.0;
+        let mut rv: Vec<geo::Line<T>> = points
+            .windows(2)
+            .map(|w| geo::Line::new(w[0], w[1]))
+            .collect();
+        if self == Closedness::Closed && points.len() > 2 {
+            let (first, last) = (points[0], points[points.len() - 1]);
+            if ulps_eq_c(&last, &first) {
+                rv.last_mut().unwrap().end = first;
+            } else {
+                rv.push(geo::Line::new(last, first));
+            }
+        }
+        rv
+    }
+}
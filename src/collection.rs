@@ -0,0 +1,140 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Conversion of intersection results into a `geo::GeometryCollection`.
+//!
+//! Each intersection point becomes a `geo::Point`, and each collinear overlap between two
+//! lines becomes a `geo::Line`. A parallel vector of [`Provenance`] records keeps track of the
+//! lines involved in every geometry, ready to be written as feature properties by e.g. a
+//! GeoJSON writer.
+
+use super::ulps_eq_c;
+use crate::topology::shared_sub_segment;
+use num_traits::Float;
+
+/// Where a geometry of [`into_geometry_collection()`] came from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    /// The indices of the involved lines
+    pub lines: Vec<usize>,
+    /// One flag for each involved line.
+    /// For points: true if the point is an end point of the line.
+    /// For overlaps: true if the overlap covers the entire line.
+    pub end_point: Vec<bool>,
+}
+
+/// Converts intersection results into a geometry collection of points (one per intersection)
+/// followed by lines (one per overlapping pair of lines), and a parallel vector of
+/// [`Provenance`].
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., 0., 15., 0.], [7., -5., 7., 5.]]);
+/// let (collection, provenance) = AlgorithmData::<f64>::default()
+///     .with_ignore_end_point_intersections(false)?
+///     .with_ref_lines(lines.iter())?
+///     .into_geometry_collection()?;
+/// // the two overlap end points, the crossing and the overlap itself
+/// assert_eq!(collection.0.len(), 4);
+/// assert_eq!(
+///     collection.0[3],
+///     geo::Geometry::Line([(5., 0.), (10., 0.)].into())
+/// );
+/// assert_eq!(provenance[3].lines, vec![0, 1]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn into_geometry_collection<T, I>(
+    lines: &[geo::Line<T>],
+    results: I,
+) -> (geo::GeometryCollection<T>, Vec<Provenance>)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    I: IntoIterator<Item = (geo::Coordinate<T>, Vec<usize>)>,
+{
+    let mut geometries = Vec::<geo::Geometry<T>>::new();
+    let mut provenance = Vec::<Provenance>::new();
+    let mut overlaps = Vec::<(geo::Line<T>, Provenance)>::new();
+    let mut tested = ahash::AHashSet::<(usize, usize)>::default();
+
+    for (position, involved) in results {
+        let end_point = involved
+            .iter()
+            .map(|l| ulps_eq_c(&lines[*l].start, &position) || ulps_eq_c(&lines[*l].end, &position))
+            .collect();
+        for (i, l1) in involved.iter().enumerate() {
+            for l2 in involved.iter().skip(i + 1) {
+                let (l1, l2) = (*l1.min(l2), *l1.max(l2));
+                if !tested.insert((l1, l2)) {
+                    continue;
+                }
+                if let Some(overlap) = shared_sub_segment(&lines[l1], &lines[l2]) {
+                    let covers = |l: &geo::Line<T>| {
+                        (ulps_eq_c(&overlap.start, &l.start) && ulps_eq_c(&overlap.end, &l.end))
+                            || (ulps_eq_c(&overlap.start, &l.end)
+                                && ulps_eq_c(&overlap.end, &l.start))
+                    };
+                    let end_point = vec![covers(&lines[l1]), covers(&lines[l2])];
+                    overlaps.push((
+                        overlap,
+                        Provenance {
+                            lines: vec![l1, l2],
+                            end_point,
+                        },
+                    ));
+                }
+            }
+        }
+        geometries.push(geo::Geometry::Point(geo::Point(position)));
+        provenance.push(Provenance {
+            lines: involved,
+            end_point,
+        });
+    }
+    for (overlap, p) in overlaps.into_iter() {
+        geometries.push(geo::Geometry::Line(overlap));
+        provenance.push(p);
+    }
+    (geo::GeometryCollection(geometries), provenance)
+}
@@ -0,0 +1,456 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Grouping of collinear, overlapping, segments.
+//!
+//! Inputs with many collinear overlapping segments (e.g. traced raster art) are expensive to
+//! resolve pair by pair. [`collinear_bundles()`] first groups the segments by their supporting
+//! line, sorting them by direction and by offset, and then resolves the overlaps of each group
+//! by sorting the segments along the line. That makes the pass O(m log m) for m segments, no
+//! matter how many segments are stacked on top of each other.
+//! [`collinear_clusters()`] reports the whole supporting line groups instead, including
+//! segments that don't overlap, e.g. redundant construction lines of a CAD export.
+//!
+//! For a single pair, [`overlap_interval()`] returns the shared sub-segment together with its
+//! parametric extent on both segments.
+
+use crate::vector::dot;
+use crate::{intersect_with_tolerance, IntersectError, Intersection, Tolerance};
+use num_traits::Float;
+use std::cmp;
+
+/// A maximal set of collinear segments connected by overlaps.
+/// Every segment of the bundle shares a part of positive length with at least one other
+/// segment of the bundle. Segments only touching at an end point are not bundled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollinearBundle<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The indices of the segments in the bundle, sorted
+    pub lines: Vec<usize>,
+    /// The part of the supporting line covered by the bundle. The end points are end points
+    /// of the input segments.
+    pub extent: geo::Line<T>,
+}
+
+/// A maximal set of segments on the same supporting line, see [`collinear_clusters()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollinearCluster<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The indices of the segments in the cluster, sorted
+    pub lines: Vec<usize>,
+    /// The part of the supporting line spanned by the cluster. The end points are end points
+    /// of the input segments.
+    pub extent: geo::Line<T>,
+    /// The bundles of overlapping segments within the cluster, sorted by their first segment
+    pub bundles: Vec<CollinearBundle<T>>,
+}
+
+/// A segment and its direction angle in the range `[0, PI]`
+struct Directed<T> {
+    line: usize,
+    angle: T,
+}
+
+fn cmp_float<T: Float>(a: T, b: T) -> cmp::Ordering {
+    a.partial_cmp(&b).unwrap_or(cmp::Ordering::Equal)
+}
+
+/// Finds the bundles of collinear overlapping segments.
+/// Two segments are collinear if their directions, and their distances to the origin, are
+/// within the tolerance of each other. The distance tolerance is `tolerance.epsilon`, widened
+/// to the rounding error expected at the magnitude of the coordinates. The angle tolerance is
+/// the distance tolerance divided by the length of the longest segment.
+/// The bundles are sorted by their first segment, zero length segments are ignored.
+/// ```
+/// # use intersect2d::collinear::collinear_bundles;
+/// # use intersect2d::Tolerance;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 4., 4.],
+///     [8., 8., 2., 2.],
+///     [9., 9., 10., 10.],
+///     [0., 1., 10., 1.],
+///     [20., 1., 5., 1.],
+/// ]);
+/// let bundles = collinear_bundles(&lines, &Tolerance::default())?;
+/// assert_eq!(bundles.len(), 2);
+/// assert_eq!(bundles[0].lines, vec![0, 1]);
+/// assert_eq!(bundles[1].lines, vec![3, 4]);
+/// assert_eq!(bundles[1].extent, intersect2d::to_lines::<_, f64>(&[[0., 1., 20., 1.]])[0]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn collinear_bundles<T>(
+    lines: &[geo::Line<T>],
+    tolerance: &Tolerance<T>,
+) -> Result<Vec<CollinearBundle<T>>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let (groups, distance_eps) = supporting_line_groups(lines, tolerance)?;
+    let mut rv = Vec::<CollinearBundle<T>>::new();
+    for group in groups.iter() {
+        let _ = resolve_group(lines, group, distance_eps, &mut rv);
+    }
+    rv.sort_unstable_by_key(|b| b.lines[0]);
+    Ok(rv)
+}
+
+/// Finds the clusters of segments lying on the same supporting line, overlapping or not.
+/// Collinearity is decided the same way as in [`collinear_bundles()`], each cluster holds at
+/// least two segments. The bundles of a cluster describe its overlap structure, segments of
+/// the cluster that aren't part of any bundle don't overlap any other segment of the cluster.
+/// The clusters are sorted by their first segment, zero length segments are ignored.
+/// ```
+/// # use intersect2d::collinear::collinear_clusters;
+/// # use intersect2d::Tolerance;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 4., 4.],
+///     [8., 8., 2., 2.],
+///     [9., 9., 10., 10.],
+///     [0., 1., 10., 1.],
+/// ]);
+/// let clusters = collinear_clusters(&lines, &Tolerance::default())?;
+/// assert_eq!(clusters.len(), 1);
+/// assert_eq!(clusters[0].lines, vec![0, 1, 2]);
+/// assert_eq!(clusters[0].extent, intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.]])[0]);
+/// assert_eq!(clusters[0].bundles.len(), 1);
+/// assert_eq!(clusters[0].bundles[0].lines, vec![0, 1]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn collinear_clusters<T>(
+    lines: &[geo::Line<T>],
+    tolerance: &Tolerance<T>,
+) -> Result<Vec<CollinearCluster<T>>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let (groups, distance_eps) = supporting_line_groups(lines, tolerance)?;
+    let mut rv = Vec::<CollinearCluster<T>>::with_capacity(groups.len());
+    for group in groups.iter() {
+        let mut bundles = Vec::<CollinearBundle<T>>::new();
+        let (start, end) = resolve_group(lines, group, distance_eps, &mut bundles);
+        bundles.sort_unstable_by_key(|b| b.lines[0]);
+        let mut cluster: Vec<usize> = group.members.iter().map(|m| m.1).collect();
+        cluster.sort_unstable();
+        rv.push(CollinearCluster {
+            lines: cluster,
+            extent: geo::Line::new(start, end),
+            bundles,
+        });
+    }
+    rv.sort_unstable_by_key(|c| c.lines[0]);
+    Ok(rv)
+}
+
+/// Segments on the same supporting line, `(offset, line index)`, and the direction of the line
+struct Group<T> {
+    direction: (T, T),
+    members: Vec<(T, usize)>,
+}
+
+/// Groups the segments by their supporting line, only groups of two or more segments are
+/// returned. Also returns the distance tolerance used.
+fn supporting_line_groups<T>(
+    lines: &[geo::Line<T>],
+    tolerance: &Tolerance<T>,
+) -> Result<(Vec<Group<T>>, T), IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut scale = T::zero();
+    let mut max_length = T::zero();
+    for a_line in lines.iter() {
+        if !a_line.start.x.is_finite()
+            || !a_line.start.y.is_finite()
+            || !a_line.end.x.is_finite()
+            || !a_line.end.y.is_finite()
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't bundle non-finite data".to_string(),
+            ));
+        }
+        scale = scale
+            .max(a_line.start.x.abs())
+            .max(a_line.start.y.abs())
+            .max(a_line.end.x.abs())
+            .max(a_line.end.y.abs());
+        let d = a_line.end - a_line.start;
+        max_length = max_length.max(d.x.hypot(d.y));
+    }
+    if max_length == T::zero() {
+        return Ok((Vec::new(), T::zero()));
+    }
+    let ulps = T::from(tolerance.max_ulps).unwrap() * T::epsilon();
+    let distance_eps = tolerance.epsilon.max(ulps * scale);
+    let angle_eps = (distance_eps / max_length).max(ulps);
+    let pi = T::from(std::f64::consts::PI).unwrap();
+
+    let mut directed: Vec<Directed<T>> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.start != l.end)
+        .map(|(line, l)| {
+            let d = l.end - l.start;
+            let angle = d.y.atan2(d.x);
+            let angle = if angle < T::zero() { angle + pi } else { angle };
+            Directed { line, angle }
+        })
+        .collect();
+    directed.sort_unstable_by(|a, b| cmp_float(a.angle, b.angle).then(a.line.cmp(&b.line)));
+
+    // chain the segments into clusters of (almost) parallel segments
+    let mut clusters = Vec::<Vec<Directed<T>>>::new();
+    for d in directed.into_iter() {
+        match clusters.last_mut() {
+            Some(cluster) if d.angle - cluster.last().unwrap().angle <= angle_eps => {
+                cluster.push(d)
+            }
+            _ => clusters.push(vec![d]),
+        }
+    }
+    // the angles wrap around at PI
+    if clusters.len() > 1 {
+        let first_angle = clusters[0][0].angle;
+        let last_angle = clusters.last().unwrap().last().unwrap().angle;
+        if first_angle + pi - last_angle <= angle_eps {
+            let mut last = clusters.pop().unwrap();
+            for d in last.iter_mut() {
+                d.angle = d.angle - pi;
+            }
+            last.append(&mut clusters[0]);
+            clusters[0] = last;
+        }
+    }
+
+    let mut rv = Vec::<Group<T>>::new();
+    for cluster in clusters.into_iter().filter(|c| c.len() > 1) {
+        let reference = cluster[0].angle;
+        let (dir_x, dir_y) = (reference.cos(), reference.sin());
+        let offset = |p: geo::Coordinate<T>| dir_x * p.y - dir_y * p.x;
+
+        // split the cluster into groups on the same supporting line
+        let mut by_offset: Vec<(T, usize)> = cluster
+            .iter()
+            .map(|d| {
+                let l = &lines[d.line];
+                (
+                    (offset(l.start) + offset(l.end)) / (T::one() + T::one()),
+                    d.line,
+                )
+            })
+            .collect();
+        by_offset.sort_unstable_by(|a, b| cmp_float(a.0, b.0).then(a.1.cmp(&b.1)));
+        let mut group_start = 0;
+        while group_start < by_offset.len() {
+            let mut group_end = group_start + 1;
+            while group_end < by_offset.len()
+                && by_offset[group_end].0 - by_offset[group_end - 1].0 <= distance_eps
+            {
+                group_end += 1;
+            }
+            if group_end - group_start > 1 {
+                rv.push(Group {
+                    direction: (dir_x, dir_y),
+                    members: by_offset[group_start..group_end].to_vec(),
+                });
+            }
+            group_start = group_end;
+        }
+    }
+    Ok((rv, distance_eps))
+}
+
+/// Sorts the segments of one supporting line along the line and collects the runs of
+/// overlapping segments into bundles. Returns the end points of the whole group.
+fn resolve_group<T>(
+    lines: &[geo::Line<T>],
+    group: &Group<T>,
+    distance_eps: T,
+    rv: &mut Vec<CollinearBundle<T>>,
+) -> (geo::Coordinate<T>, geo::Coordinate<T>)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let (dir_x, dir_y) = group.direction;
+    let along = |p: geo::Coordinate<T>| dir_x * p.x + dir_y * p.y;
+    // (start along the line, end along the line, start point, end point, line index)
+    let mut intervals: Vec<_> = group
+        .members
+        .iter()
+        .map(|(_, index)| {
+            let l = &lines[*index];
+            let (t0, t1) = (along(l.start), along(l.end));
+            if t0 <= t1 {
+                (t0, t1, l.start, l.end, *index)
+            } else {
+                (t1, t0, l.end, l.start, *index)
+            }
+        })
+        .collect();
+    intervals.sort_unstable_by(|a, b| cmp_float(a.0, b.0).then(a.4.cmp(&b.4)));
+
+    let group_start = intervals[0].2;
+    let mut group_end = intervals[0].3;
+    let mut group_max = intervals[0].1;
+    let mut run = vec![intervals[0].4];
+    let (mut run_start, mut run_end, mut run_max) =
+        (intervals[0].2, intervals[0].3, intervals[0].1);
+    let mut flush = |run: &mut Vec<usize>, start, end| {
+        if run.len() > 1 {
+            run.sort_unstable();
+            rv.push(CollinearBundle {
+                lines: std::mem::take(run),
+                extent: geo::Line::new(start, end),
+            });
+        }
+    };
+    for interval in intervals.iter().skip(1) {
+        if interval.0 < run_max - distance_eps {
+            run.push(interval.4);
+        } else {
+            flush(&mut run, run_start, run_end);
+            run = vec![interval.4];
+            run_start = interval.2;
+            run_max = T::neg_infinity();
+        }
+        if interval.1 > run_max {
+            run_max = interval.1;
+            run_end = interval.3;
+        }
+        if interval.1 > group_max {
+            group_max = interval.1;
+            group_end = interval.3;
+        }
+    }
+    flush(&mut run, run_start, run_end);
+    (group_start, group_end)
+}
+
+/// Returns the shared part of two collinear, overlapping, segments together with the
+/// parameter ranges of the shared part along `one` and along `other`,
+/// using the default tolerance. See [`overlap_interval_with_tolerance()`].
+/// ```
+/// # use intersect2d::collinear::overlap_interval;
+/// let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let other: geo::Line<f64> = [(12., 0.), (4., 0.)].into();
+/// let (shared, on_one, on_other) = overlap_interval(&one, &other).unwrap();
+/// assert_eq!(shared, [(4., 0.), (10., 0.)].into());
+/// assert_eq!(on_one, (0.4, 1.0));
+/// assert_eq!(on_other, (1.0, 0.25));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn overlap_interval<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+) -> Option<(geo::Line<T>, (T, T), (T, T))>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    overlap_interval_with_tolerance(one, other, &Tolerance::default())
+}
+
+/// Returns the shared part of two collinear, overlapping, segments together with the
+/// parameter ranges of the shared part along `one` and along `other`.
+/// The shared part has the direction of `one` and its end points are end points of the input
+/// segments, so the range on `one` is increasing while the range on `other` decreases if the
+/// segments point in opposite directions.
+/// Returns None if [`intersect_with_tolerance()`] doesn't report an overlap, or if the shared
+/// part has no length.
+#[allow(clippy::type_complexity)]
+pub fn overlap_interval_with_tolerance<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    tolerance: &Tolerance<T>,
+) -> Option<(geo::Line<T>, (T, T), (T, T))>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if !matches!(
+        intersect_with_tolerance(one, other, tolerance),
+        Some(Intersection::OverLap(_))
+    ) {
+        return None;
+    }
+    let parameter = |line: &geo::Line<T>, p: &geo::Coordinate<T>| {
+        let r = line.end - line.start;
+        dot(&(*p - line.start), &r) / dot(&r, &r)
+    };
+    let (t0, t1) = (parameter(one, &other.start), parameter(one, &other.end));
+    // (parameter on one, point, parameter on other)
+    let (other_low, other_high) = if t0 <= t1 {
+        ((t0, other.start, T::zero()), (t1, other.end, T::one()))
+    } else {
+        ((t1, other.end, T::one()), (t0, other.start, T::zero()))
+    };
+    let low = if other_low.0 > T::zero() {
+        other_low
+    } else {
+        (T::zero(), one.start, parameter(other, &one.start))
+    };
+    let high = if other_high.0 < T::one() {
+        other_high
+    } else {
+        (T::one(), one.end, parameter(other, &one.end))
+    };
+    if low.0 >= high.0 || tolerance.ulps_eq_c(&low.1, &high.1) {
+        return None;
+    }
+    Some((
+        geo::Line::new(low.1, high.1),
+        (low.0, high.0),
+        (low.2, high.2),
+    ))
+}
@@ -0,0 +1,215 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Conversions of plain coordinate data into line segments.
+//!
+//! [`ToLine`] is implemented for the common ways of writing down a segment: `[x0, y0, x1, y1]`
+//! arrays and 4-tuples, pairs of `[x, y]` arrays or `(x, y)` tuples, and pairs of
+//! `geo::Coordinate`s. Any iterator of such items can be collected into a [`Lines`]
+//! collection, or (without panicking on values that can't be represented as `T`) turned into
+//! a `Vec<geo::Line<T>>` with [`CollectLines::try_collect_lines()`].
+//! ```
+//! # use intersect2d::convert::{CollectLines, Lines};
+//! let lines: Lines<f64> = vec![(0, 0, 1, 1), (0, 1, 1, 0)].into_iter().collect();
+//! assert_eq!(lines.len(), 2);
+//! let lines: Vec<geo::Line<f32>> = vec![((0., 0.), (1., 1.))]
+//!     .into_iter()
+//!     .try_collect_lines()?;
+//! assert_eq!(lines[0].end, geo::Coordinate { x: 1., y: 1. });
+//! # Ok::<(), intersect2d::IntersectError>(())
+//! ```
+
+use super::{try_to_line, IntersectError};
+use num_traits::Float;
+
+/// Conversion of one item into a line segment
+pub trait ToLine<T>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    /// Returns `InvalidData` if a value can't be represented as `T`
+    fn to_line(self) -> Result<geo::Line<T>, IntersectError>;
+}
+
+impl<T> ToLine<T> for geo::Line<T>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    fn to_line(self) -> Result<geo::Line<T>, IntersectError> {
+        Ok(self)
+    }
+}
+
+impl<T> ToLine<T> for (geo::Coordinate<T>, geo::Coordinate<T>)
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    fn to_line(self) -> Result<geo::Line<T>, IntersectError> {
+        Ok(geo::Line::new(self.0, self.1))
+    }
+}
+
+impl<U, T> ToLine<T> for [U; 4]
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    fn to_line(self) -> Result<geo::Line<T>, IntersectError> {
+        try_to_line(self[0], self[1], self[2], self[3])
+    }
+}
+
+impl<U, T> ToLine<T> for (U, U, U, U)
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    fn to_line(self) -> Result<geo::Line<T>, IntersectError> {
+        try_to_line(self.0, self.1, self.2, self.3)
+    }
+}
+
+impl<U, T> ToLine<T> for [[U; 2]; 2]
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    fn to_line(self) -> Result<geo::Line<T>, IntersectError> {
+        try_to_line(self[0][0], self[0][1], self[1][0], self[1][1])
+    }
+}
+
+impl<U, T> ToLine<T> for ((U, U), (U, U))
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    fn to_line(self) -> Result<geo::Line<T>, IntersectError> {
+        try_to_line((self.0).0, (self.0).1, (self.1).0, (self.1).1)
+    }
+}
+
+/// A `Vec<geo::Line<T>>` that can be collected from any iterator of [`ToLine`] items.
+/// # Panics
+/// Collecting panics if a value can't be represented as `T`, see
+/// [`CollectLines::try_collect_lines()`] for a fallible alternative.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Lines<T>(pub Vec<geo::Line<T>>)
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy;
+
+impl<T> Lines<T>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    pub fn into_inner(self) -> Vec<geo::Line<T>> {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for Lines<T>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    type Target = Vec<geo::Line<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<Lines<T>> for Vec<geo::Line<T>>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    fn from(lines: Lines<T>) -> Self {
+        lines.0
+    }
+}
+
+impl<T, L> std::iter::FromIterator<L> for Lines<T>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+    L: ToLine<T>,
+{
+    fn from_iter<I: IntoIterator<Item = L>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|l| l.to_line().unwrap()).collect())
+    }
+}
+
+/// Fallible collection of [`ToLine`] items, implemented for every iterator
+pub trait CollectLines<T, L>
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+    L: ToLine<T>,
+{
+    /// Converts every item, returns `InvalidData` if a value can't be represented as `T`
+    fn try_collect_lines(self) -> Result<Vec<geo::Line<T>>, IntersectError>;
+}
+
+impl<T, L, I> CollectLines<T, L> for I
+where
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+    L: ToLine<T>,
+    I: Iterator<Item = L>,
+{
+    fn try_collect_lines(self) -> Result<Vec<geo::Line<T>>, IntersectError> {
+        self.map(|l| l.to_line()).collect()
+    }
+}
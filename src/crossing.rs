@@ -0,0 +1,320 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Properties of the crossings found by the intersection searches.
+//!
+//! The sweep only reports where lines meet, [`crossing_angle()`] and [`min_crossing_angle()`]
+//! tell genuine crossings apart from near-tangential ones, and [`crossing_direction()`] tells
+//! which way one segment crosses another (as needed by winding computations).
+//! [`winding_contribution()`] turns that direction into the signed contribution of a segment
+//! to the winding number along a query segment (a ray or a scanline), and
+//! [`winding_crossings()`] lists the contributions of a set of lines along a query segment.
+
+use crate::predicates::{point_side_of_line_with_tolerance, Orientation};
+use crate::vector::{cross_z, dot};
+use crate::{intersect_with_tolerance, Tolerance};
+use num_traits::Float;
+use std::cmp;
+
+/// Returns the angle between the supporting lines of two segments, in radians in the range
+/// `[0, PI/2]`. The angle of a zero length segment is undefined, zero is returned.
+/// ```
+/// # use intersect2d::crossing::crossing_angle;
+/// let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let other: geo::Line<f64> = [(5., -5.), (0., 0.)].into();
+/// assert!(approx::ulps_eq!(crossing_angle(&one, &other), std::f64::consts::FRAC_PI_4));
+/// ```
+pub fn crossing_angle<T>(one: &geo::Line<T>, other: &geo::Line<T>) -> T
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let r = one.end - one.start;
+    let s = other.end - other.start;
+    // atan2 of |r × s| and |r · s| is accurate for small angles too, unlike acos
+    let angle = cross_z(&r, &s).abs().atan2(dot(&r, &s).abs());
+    if angle.is_nan() {
+        T::zero()
+    } else {
+        angle
+    }
+}
+
+/// Returns the smallest angle between any two of the `involved` lines, as reported by the
+/// intersection searches for a single position. Returns None for less than two lines.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::crossing::min_crossing_angle;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [10., 0., 0., 10.],
+///     [0., 5., 10., 5.],
+///     [0., 4.9, 10., 5.05],
+/// ]);
+/// let results = AlgorithmData::<f64>::default()
+///     .with_ignore_end_point_intersections(true)?
+///     .with_ref_lines(lines.iter())?
+///     .compute()?;
+/// // remove the near-tangential crossing of the last two lines
+/// let one_degree = 1.0_f64.to_radians();
+/// let (crossings, tangential): (Vec<_>, Vec<_>) = results
+///     .partition(|(_, involved)| min_crossing_angle(&lines, involved).unwrap() >= one_degree);
+/// assert_eq!(crossings.len(), 3);
+/// assert_eq!(tangential[0].1, vec![2, 3]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn min_crossing_angle<T>(lines: &[geo::Line<T>], involved: &[usize]) -> Option<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut rv: Option<T> = None;
+    for (i, l1) in involved.iter().enumerate() {
+        for l2 in involved.iter().skip(i + 1) {
+            let angle = crossing_angle(&lines[*l1], &lines[*l2]);
+            rv = Some(rv.map_or(angle, |a| a.min(angle)));
+        }
+    }
+    rv
+}
+
+/// The direction in which a segment crosses another, seen along the direction of the crossed
+/// segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CrossingDirection {
+    /// The crossing segment starts to the left of the crossed segment and ends to the right
+    LeftToRight,
+    /// The crossing segment starts to the right of the crossed segment and ends to the left
+    RightToLeft,
+}
+
+/// Returns the direction in which `other` crosses `one`, using the default tolerance.
+/// See [`crossing_direction_with_tolerance()`].
+/// ```
+/// # use intersect2d::crossing::{crossing_direction, CrossingDirection};
+/// let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let other: geo::Line<f64> = [(5., 5.), (5., -5.)].into();
+/// assert_eq!(crossing_direction(&one, &other), Some(CrossingDirection::LeftToRight));
+/// assert_eq!(crossing_direction(&other, &one), Some(CrossingDirection::RightToLeft));
+/// // touching is not crossing
+/// let other: geo::Line<f64> = [(5., 5.), (5., 0.)].into();
+/// assert_eq!(crossing_direction(&one, &other), None);
+/// ```
+pub fn crossing_direction<T>(one: &geo::Line<T>, other: &geo::Line<T>) -> Option<CrossingDirection>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    crossing_direction_with_tolerance(one, other, &Tolerance::default())
+}
+
+/// Returns the direction in which `other` crosses `one`, or None if the segments don't cross
+/// properly: the end points of each segment must be strictly on opposite sides of the other
+/// segment. End points within the tolerance distance of the other segment are touching it,
+/// the decisions are made by
+/// [`point_side_of_line_with_tolerance()`](crate::predicates::point_side_of_line_with_tolerance).
+pub fn crossing_direction_with_tolerance<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    tolerance: &Tolerance<T>,
+) -> Option<CrossingDirection>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let side = |line: &geo::Line<T>, point: &geo::Coordinate<T>| {
+        point_side_of_line_with_tolerance(line, point, tolerance)
+    };
+    let one_start = side(other, &one.start);
+    let one_end = side(other, &one.end);
+    if one_start == Orientation::Collinear
+        || one_end == Orientation::Collinear
+        || one_start == one_end
+    {
+        return None;
+    }
+    match (side(one, &other.start), side(one, &other.end)) {
+        (Orientation::CounterClockwise, Orientation::Clockwise) => {
+            Some(CrossingDirection::LeftToRight)
+        }
+        (Orientation::Clockwise, Orientation::CounterClockwise) => {
+            Some(CrossingDirection::RightToLeft)
+        }
+        _ => None,
+    }
+}
+
+/// A line crossing a query segment, see [`winding_crossings()`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindingCrossing<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Where the line crosses the query segment
+    pub position: geo::Coordinate<T>,
+    /// The index of the crossing line
+    pub line: usize,
+    /// The winding contribution of the crossing, +1 or -1
+    pub contribution: i32,
+}
+
+/// Returns the winding contribution of `segment` to the query segment `query` (a ray or a
+/// scanline), using the default tolerance. See [`winding_contribution_with_tolerance()`].
+/// ```
+/// # use intersect2d::crossing::winding_contribution;
+/// let ray: geo::Line<f64> = [(0., 0.), (100., 0.)].into();
+/// // the edges of a counter clockwise square around the ray origin
+/// let right: geo::Line<f64> = [(5., -5.), (5., 5.)].into();
+/// let left: geo::Line<f64> = [(-5., 5.), (-5., -5.)].into();
+/// assert_eq!(winding_contribution(&ray, &right), 1);
+/// assert_eq!(winding_contribution(&ray, &left), 0);
+/// assert_eq!(winding_contribution(&ray, &[(5., 5.), (5., -5.)].into()), -1);
+/// ```
+pub fn winding_contribution<T>(query: &geo::Line<T>, segment: &geo::Line<T>) -> i32
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    winding_contribution_with_tolerance(query, segment, &Tolerance::default())
+}
+
+/// Returns the winding contribution of `segment` to the query segment `query`: +1 if the
+/// segment crosses the query from its right to its left side (seen along the direction of
+/// `query`), -1 if it crosses from left to right, and 0 if it doesn't cross.
+/// For a query ray pointing in +x direction, segments pointing upwards count +1, so a counter
+/// clockwise ring around the ray origin winds +1 around it.
+///
+/// End points of `segment` on the supporting line of `query` are counted as being on its
+/// left side (a half-open rule), so a polyline passing through the query at a vertex is
+/// counted exactly once, and a polyline only touching the query from the left isn't counted.
+/// A crossing at an end point of `query` is counted, collinear segments never are.
+pub fn winding_contribution_with_tolerance<T>(
+    query: &geo::Line<T>,
+    segment: &geo::Line<T>,
+    tolerance: &Tolerance<T>,
+) -> i32
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let side = |line: &geo::Line<T>, point: &geo::Coordinate<T>| {
+        point_side_of_line_with_tolerance(line, point, tolerance)
+    };
+    // the end points of segment, collinear is left
+    let right_of_query = |point: &geo::Coordinate<T>| side(query, point) == Orientation::Clockwise;
+    let (start_right, end_right) = (right_of_query(&segment.start), right_of_query(&segment.end));
+    if start_right == end_right {
+        return 0;
+    }
+    // the crossing must be within the query
+    let (query_start, query_end) = (side(segment, &query.start), side(segment, &query.end));
+    if query_start == query_end {
+        return 0;
+    }
+    if start_right {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Returns the lines crossing the query segment `query` and their winding contributions
+/// (see [`winding_contribution_with_tolerance()`]), sorted by their distance from the start
+/// of the query. The sum of the contributions is the winding number of `query.start` if
+/// `query` is long enough to leave the lines behind.
+/// ```
+/// # use intersect2d::crossing::winding_crossings;
+/// # use intersect2d::Tolerance;
+/// let square = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 0.],
+///     [10., 0., 10., 10.],
+///     [10., 10., 0., 10.],
+///     [0., 10., 0., 0.],
+/// ]);
+/// let ray: geo::Line<f64> = [(-5., 5.), (100., 5.)].into();
+/// let rv = winding_crossings(&ray, &square, &Tolerance::default());
+/// assert_eq!(rv.len(), 2);
+/// assert_eq!((rv[0].line, rv[0].contribution), (3, -1));
+/// assert_eq!((rv[1].line, rv[1].contribution), (1, 1));
+/// assert_eq!(rv.iter().map(|c| c.contribution).sum::<i32>(), 0);
+/// ```
+pub fn winding_crossings<T>(
+    query: &geo::Line<T>,
+    lines: &[geo::Line<T>],
+    tolerance: &Tolerance<T>,
+) -> Vec<WindingCrossing<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let direction = query.end - query.start;
+    let mut rv: Vec<(T, WindingCrossing<T>)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(line, segment)| {
+            let contribution = winding_contribution_with_tolerance(query, segment, tolerance);
+            if contribution == 0 {
+                return None;
+            }
+            let position = intersect_with_tolerance(query, segment, tolerance)?.single();
+            let along = dot(&(position - query.start), &direction);
+            Some((
+                along,
+                WindingCrossing {
+                    position,
+                    line,
+                    contribution,
+                },
+            ))
+        })
+        .collect();
+    rv.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap_or(cmp::Ordering::Equal)
+            .then(a.1.line.cmp(&b.1.line))
+    });
+    rv.into_iter().map(|(_, c)| c).collect()
+}
@@ -0,0 +1,278 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! CSV import and export of segments and results, enabled by the `csv` feature.
+//!
+//! Segments are stored as one `x1,y1,x2,y2` row per segment, intersections as one
+//! `x,y,lines` row per intersection point, with the indices of the involved lines separated
+//! by `;`. The files start with a header row, and the readers skip a header row if there is
+//! one. Empty rows are ignored.
+
+use crate::{try_to_line, IntersectError};
+use num_traits::Float;
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+
+fn io_error(err: std::io::Error) -> IntersectError {
+    IntersectError::IoError(err.to_string())
+}
+
+/// Splits the rows of `input` into fields, skipping empty rows and a leading header row.
+/// Calls `row` with the 1-based row number and the fields of each row.
+fn for_each_row<R, F>(input: R, mut row: F) -> Result<(), IntersectError>
+where
+    R: BufRead,
+    F: FnMut(usize, &[&str]) -> Result<(), IntersectError>,
+{
+    for (index, text) in input.lines().enumerate() {
+        let text = text.map_err(io_error)?;
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = text.split(',').map(|f| f.trim()).collect();
+        // a header row is a first row that doesn't start with a number
+        if index == 0 && fields[0].parse::<f64>().is_err() {
+            continue;
+        }
+        row(index + 1, &fields)?;
+    }
+    Ok(())
+}
+
+fn parse_field(row: usize, field: &str) -> Result<f64, IntersectError> {
+    field.parse::<f64>().map_err(|_| {
+        IntersectError::InvalidData(format!("Row {}: '{}' is not a number", row, field))
+    })
+}
+
+/// Reads segments from `x1,y1,x2,y2` rows.
+/// ```
+/// # use intersect2d::csv::read_lines_csv;
+/// let text = "x1,y1,x2,y2\n0,0,10,10\n0,10,10,0\n";
+/// let lines = read_lines_csv::<_, f64>(text.as_bytes())?;
+/// assert_eq!(lines, intersect2d::to_lines(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn read_lines_csv<R, T>(input: R) -> Result<Vec<geo::Line<T>>, IntersectError>
+where
+    R: BufRead,
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut rv = Vec::<geo::Line<T>>::new();
+    for_each_row(input, |row, fields| {
+        if fields.len() != 4 {
+            return Err(IntersectError::InvalidData(format!(
+                "Row {}: expected 4 values, found {}",
+                row,
+                fields.len()
+            )));
+        }
+        let mut values = [0.0; 4];
+        for (value, field) in values.iter_mut().zip(fields.iter()) {
+            *value = parse_field(row, field)?;
+        }
+        rv.push(try_to_line(values[0], values[1], values[2], values[3])?);
+        Ok(())
+    })?;
+    Ok(rv)
+}
+
+/// Writes segments as `x1,y1,x2,y2` rows, with a header row.
+/// ```
+/// # use intersect2d::csv::write_lines_csv;
+/// let mut buffer = Vec::<u8>::new();
+/// write_lines_csv(&mut buffer, &intersect2d::to_lines::<_, f64>(&[[0., 0.5, 10., 10.]]))?;
+/// assert_eq!(String::from_utf8(buffer).unwrap(), "x1,y1,x2,y2\n0,0.5,10,10\n");
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn write_lines_csv<W, T>(mut out: W, lines: &[geo::Line<T>]) -> Result<(), IntersectError>
+where
+    W: Write,
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Display,
+    T::Epsilon: Copy,
+{
+    writeln!(out, "x1,y1,x2,y2").map_err(io_error)?;
+    for l in lines.iter() {
+        writeln!(out, "{},{},{},{}", l.start.x, l.start.y, l.end.x, l.end.y).map_err(io_error)?;
+    }
+    out.flush().map_err(io_error)
+}
+
+/// Reads intersections from `x,y,lines` rows.
+/// ```
+/// # use intersect2d::csv::read_results_csv;
+/// let text = "x,y,lines\n5,5,0;1\n";
+/// let results = read_results_csv::<_, f64>(text.as_bytes())?;
+/// assert_eq!(results, vec![(geo::Coordinate { x: 5., y: 5. }, vec![0, 1])]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn read_results_csv<R, T>(
+    input: R,
+) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError>
+where
+    R: BufRead,
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::new();
+    for_each_row(input, |row, fields| {
+        if fields.len() != 3 {
+            return Err(IntersectError::InvalidData(format!(
+                "Row {}: expected 3 values, found {}",
+                row,
+                fields.len()
+            )));
+        }
+        let (x, y) = (parse_field(row, fields[0])?, parse_field(row, fields[1])?);
+        let position = try_to_line(x, y, x, y)?.start;
+        let lines = fields[2]
+            .split(';')
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                l.parse::<usize>().map_err(|_| {
+                    IntersectError::InvalidData(format!("Row {}: '{}' is not a line index", row, l))
+                })
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+        rv.push((position, lines));
+        Ok(())
+    })?;
+    Ok(rv)
+}
+
+/// Writes intersections as `x,y,lines` rows, one at a time. The header row is written by
+/// [`CsvResultWriter::new()`]. With the `writer` feature this is also an
+/// [`IntersectionSink`](crate::writer::IntersectionSink).
+pub struct CsvResultWriter<W: Write> {
+    out: W,
+    written: usize,
+}
+
+impl<W: Write> CsvResultWriter<W> {
+    pub fn new(mut out: W) -> Result<Self, IntersectError> {
+        writeln!(out, "x,y,lines").map_err(io_error)?;
+        Ok(Self { out, written: 0 })
+    }
+
+    /// Writes one intersection point and the indices of the involved lines
+    pub fn write_row<T>(
+        &mut self,
+        position: geo::Coordinate<T>,
+        lines: &[usize],
+    ) -> Result<(), IntersectError>
+    where
+        T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Display,
+        T::Epsilon: Copy,
+    {
+        let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        writeln!(
+            self.out,
+            "{},{},{}",
+            position.x,
+            position.y,
+            lines.join(";")
+        )
+        .map_err(io_error)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Returns the number of rows written so far, not counting the header
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    pub fn flush(&mut self) -> Result<(), IntersectError> {
+        self.out.flush().map_err(io_error)
+    }
+}
+
+#[cfg(feature = "writer")]
+impl<T, W> crate::writer::IntersectionSink<T> for CsvResultWriter<W>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Display,
+    T::Epsilon: Copy,
+    W: Write,
+{
+    fn write(
+        &mut self,
+        position: geo::Coordinate<T>,
+        lines: &[usize],
+    ) -> Result<(), IntersectError> {
+        self.write_row(position, lines)
+    }
+
+    fn finish(&mut self) -> Result<(), IntersectError> {
+        self.flush()
+    }
+}
+
+/// Writes intersections as `x,y,lines` rows, with a header row.
+/// ```
+/// # use intersect2d::csv::write_results_csv;
+/// let mut buffer = Vec::<u8>::new();
+/// write_results_csv(&mut buffer, &[(geo::Coordinate { x: 5.0_f64, y: 5.5 }, vec![0, 1])])?;
+/// assert_eq!(String::from_utf8(buffer).unwrap(), "x,y,lines\n5,5.5,0;1\n");
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn write_results_csv<W, T>(
+    out: W,
+    results: &[(geo::Coordinate<T>, Vec<usize>)],
+) -> Result<(), IntersectError>
+where
+    W: Write,
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Display,
+    T::Epsilon: Copy,
+{
+    let mut writer = CsvResultWriter::new(out)?;
+    for (position, lines) in results.iter() {
+        writer.write_row(*position, lines)?;
+    }
+    writer.flush()
+}
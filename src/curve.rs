@@ -0,0 +1,978 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Intersections of line segments with curves, and of curves with each other.
+//!
+//! The curves are given analytically, so the intersection points are found exactly (up to
+//! rounding) instead of on a polygonal approximation of the curve. Every intersection with a
+//! segment is reported as a [`CurveIntersection`], with the parameter of the point along the
+//! segment and along the curve. Intersections between two curves are reported as
+//! [`CurvePairIntersection`]s.
+//! * [`Ellipse`]: axis aligned or rotated ellipses, see [`segment_ellipse_intersections()`]
+//! * [`QuadraticBezier`]: quadratic Bézier curves (e.g. TrueType outlines), see
+//!   [`segment_quadratic_bezier_intersections()`] and [`quadratic_bezier_intersections()`]
+//! * [`CircularArc`]: circular arcs (e.g. G-code toolpaths), see
+//!   [`segment_arc_intersections()`] and [`arc_intersections()`]
+
+use crate::vector::{cross_z, dot, lerp, perp};
+use crate::{IntersectError, Tolerance};
+use num_traits::Float;
+use std::cmp;
+
+/// A point where a segment meets a curve
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CurveIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    /// The position along the segment, 0 at the start point and 1 at the end point
+    pub segment_parameter: T,
+    /// The position along the curve, see the documentation of the curve
+    pub curve_parameter: T,
+}
+
+/// A point where two curves meet
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CurvePairIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    /// The position along the first curve
+    pub parameter_a: T,
+    /// The position along the second curve
+    pub parameter_b: T,
+}
+
+/// An ellipse with the semi-axes `radius_x` and `radius_y`, rotated counter-clockwise by
+/// `rotation` radians around its center.
+/// The curve parameter of a point is the eccentric anomaly: the angle, in radians in the
+/// range `[0, 2π)`, for which [`Ellipse::point_at()`] returns the point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipse<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    center: geo::Coordinate<T>,
+    radius_x: T,
+    radius_y: T,
+    rotation: T,
+}
+
+impl<T> Ellipse<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Creates an axis aligned ellipse
+    pub fn new(
+        center: geo::Coordinate<T>,
+        radius_x: T,
+        radius_y: T,
+    ) -> Result<Self, IntersectError> {
+        Self::rotated(center, radius_x, radius_y, T::zero())
+    }
+
+    /// Creates an ellipse rotated counter-clockwise by `rotation` radians
+    pub fn rotated(
+        center: geo::Coordinate<T>,
+        radius_x: T,
+        radius_y: T,
+        rotation: T,
+    ) -> Result<Self, IntersectError> {
+        if !(center.x.is_finite() && center.y.is_finite() && rotation.is_finite()) {
+            return Err(IntersectError::InvalidData(
+                "The ellipse must be finite".to_string(),
+            ));
+        }
+        if !(radius_x.is_finite()
+            && radius_y.is_finite()
+            && radius_x > T::zero()
+            && radius_y > T::zero())
+        {
+            return Err(IntersectError::InvalidParameter(
+                "The radii of an ellipse must be positive and finite".to_string(),
+            ));
+        }
+        Ok(Self {
+            center,
+            radius_x,
+            radius_y,
+            rotation,
+        })
+    }
+
+    pub fn center(&self) -> geo::Coordinate<T> {
+        self.center
+    }
+
+    pub fn radius_x(&self) -> T {
+        self.radius_x
+    }
+
+    pub fn radius_y(&self) -> T {
+        self.radius_y
+    }
+
+    pub fn rotation(&self) -> T {
+        self.rotation
+    }
+
+    /// Returns the point of the ellipse at the parameter `angle`
+    pub fn point_at(&self, angle: T) -> geo::Coordinate<T> {
+        let (x, y) = (self.radius_x * angle.cos(), self.radius_y * angle.sin());
+        let (sin, cos) = self.rotation.sin_cos();
+        geo::Coordinate {
+            x: self.center.x + x * cos - y * sin,
+            y: self.center.y + x * sin + y * cos,
+        }
+    }
+
+    /// Maps a point into the frame where the ellipse is the unit circle
+    fn in_unit_frame(&self, p: &geo::Coordinate<T>) -> geo::Coordinate<T> {
+        let (x, y) = (p.x - self.center.x, p.y - self.center.y);
+        let (sin, cos) = self.rotation.sin_cos();
+        geo::Coordinate {
+            x: (x * cos + y * sin) / self.radius_x,
+            y: (y * cos - x * sin) / self.radius_y,
+        }
+    }
+}
+
+/// Normalizes an angle into `[0, 2π)`
+fn normalize_angle<T: Float>(angle: T) -> T {
+    let two_pi = T::from(std::f64::consts::TAU).unwrap();
+    let angle = angle % two_pi;
+    if angle < T::zero() {
+        angle + two_pi
+    } else {
+        angle
+    }
+}
+
+/// Finds the parameters `t` in [0,1] where `p0 + t*d` lies on the unit circle.
+/// A line tangent to the circle returns one parameter, also when rounding made it miss the
+/// circle by less than the tolerance.
+fn unit_circle_parameters<T>(
+    p0: &geo::Coordinate<T>,
+    d: &geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> Vec<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let two = T::one() + T::one();
+    let a = dot(d, d);
+    if a <= T::zero() {
+        return Vec::new();
+    }
+    let b = two * dot(p0, d);
+    let c = dot(p0, p0) - T::one();
+    let discriminant = b * b - two * two * a * c;
+    let closest = -b / (two * a);
+    let closest_norm = {
+        let q = geo::Coordinate {
+            x: p0.x + closest * d.x,
+            y: p0.y + closest * d.y,
+        };
+        q.x.hypot(q.y)
+    };
+    let mut rv = if tolerance.ulps_eq(closest_norm, T::one()) {
+        vec![closest]
+    } else if discriminant <= T::zero() {
+        Vec::new()
+    } else {
+        // the numerically stable form of the quadratic formula
+        let q = -(b + b.signum() * discriminant.sqrt()) / two;
+        let (t0, t1) = if q == T::zero() {
+            let t = (-c / a).sqrt();
+            (-t, t)
+        } else {
+            (q / a, c / q)
+        };
+        if t0 <= t1 {
+            vec![t0, t1]
+        } else {
+            vec![t1, t0]
+        }
+    };
+    // accept parameters rounded just outside of the segment
+    let slack = tolerance.epsilon / a.sqrt();
+    rv.retain(|t| *t >= -slack && *t <= T::one() + slack);
+    for t in rv.iter_mut() {
+        *t = t.max(T::zero()).min(T::one());
+    }
+    rv
+}
+
+/// Returns the (up to two) points where the segment meets the ellipse, ordered along the
+/// segment. A segment tangent to the ellipse gives one point.
+/// ```
+/// # use intersect2d::curve::{segment_ellipse_intersections, Ellipse};
+/// # use intersect2d::Tolerance;
+/// let ellipse = Ellipse::<f64>::new(geo::Coordinate { x: 0., y: 0. }, 4., 2.)?;
+/// let line = geo::Line::from([(-10., 0.), (10., 0.)]);
+/// let rv = segment_ellipse_intersections(&line, &ellipse, &Tolerance::default());
+/// assert_eq!(rv.len(), 2);
+/// assert!((rv[0].position.x + 4.).abs() < 1e-12);
+/// assert!((rv[1].position.x - 4.).abs() < 1e-12);
+/// assert!((rv[0].curve_parameter - std::f64::consts::PI).abs() < 1e-12);
+/// assert!((rv[1].segment_parameter - 0.7).abs() < 1e-12);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn segment_ellipse_intersections<T>(
+    line: &geo::Line<T>,
+    ellipse: &Ellipse<T>,
+    tolerance: &Tolerance<T>,
+) -> Vec<CurveIntersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let p0 = ellipse.in_unit_frame(&line.start);
+    let p1 = ellipse.in_unit_frame(&line.end);
+    let d = p1 - p0;
+    let mut rv: Vec<CurveIntersection<T>> = unit_circle_parameters(&p0, &d, tolerance)
+        .into_iter()
+        .map(|t| {
+            let q = lerp(&p0, &p1, t);
+            CurveIntersection {
+                position: lerp(&line.start, &line.end, t),
+                segment_parameter: t,
+                curve_parameter: normalize_angle(q.y.atan2(q.x)),
+            }
+        })
+        .collect();
+    rv.sort_by(|a, b| {
+        a.segment_parameter
+            .partial_cmp(&b.segment_parameter)
+            .unwrap_or(cmp::Ordering::Equal)
+    });
+    rv.dedup_by(|a, b| tolerance.ulps_eq_c(&a.position, &b.position));
+    rv
+}
+
+/// A quadratic Bézier curve from `start` to `end`, pulled towards `control`.
+/// The curve parameter of a point is the Bézier parameter `t` in `[0, 1]`, see
+/// [`QuadraticBezier::point_at()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuadraticBezier<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    start: geo::Coordinate<T>,
+    control: geo::Coordinate<T>,
+    end: geo::Coordinate<T>,
+}
+
+impl<T> QuadraticBezier<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub fn new(
+        start: geo::Coordinate<T>,
+        control: geo::Coordinate<T>,
+        end: geo::Coordinate<T>,
+    ) -> Result<Self, IntersectError> {
+        if ![start, control, end]
+            .iter()
+            .all(|c| c.x.is_finite() && c.y.is_finite())
+        {
+            return Err(IntersectError::InvalidData(
+                "The control points of a curve must be finite".to_string(),
+            ));
+        }
+        Ok(Self {
+            start,
+            control,
+            end,
+        })
+    }
+
+    pub fn start(&self) -> geo::Coordinate<T> {
+        self.start
+    }
+
+    pub fn control(&self) -> geo::Coordinate<T> {
+        self.control
+    }
+
+    pub fn end(&self) -> geo::Coordinate<T> {
+        self.end
+    }
+
+    /// Returns the point of the curve at the parameter `t`
+    pub fn point_at(&self, t: T) -> geo::Coordinate<T> {
+        lerp(
+            &lerp(&self.start, &self.control, t),
+            &lerp(&self.control, &self.end, t),
+            t,
+        )
+    }
+
+    /// Returns the derivative of the curve at the parameter `t`
+    fn derivative_at(&self, t: T) -> geo::Coordinate<T> {
+        let two = T::one() + T::one();
+        let d = lerp(&(self.control - self.start), &(self.end - self.control), t);
+        geo::Coordinate {
+            x: d.x * two,
+            y: d.y * two,
+        }
+    }
+
+    /// Splits the curve in two halves (de Casteljau)
+    fn halves(&self) -> (Self, Self) {
+        let half = T::one() / (T::one() + T::one());
+        let a = lerp(&self.start, &self.control, half);
+        let b = lerp(&self.control, &self.end, half);
+        let middle = lerp(&a, &b, half);
+        (
+            Self {
+                start: self.start,
+                control: a,
+                end: middle,
+            },
+            Self {
+                start: middle,
+                control: b,
+                end: self.end,
+            },
+        )
+    }
+
+    /// The bounding box of the control points, it contains the curve
+    fn hull_bounds(&self) -> (geo::Coordinate<T>, geo::Coordinate<T>) {
+        let points = [self.start, self.control, self.end];
+        let mut min = points[0];
+        let mut max = points[0];
+        for p in points.iter().skip(1) {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        }
+        (min, max)
+    }
+
+    /// The distance of the control point from the chord, an upper bound of how far the curve
+    /// deviates from the chord
+    fn flatness(&self) -> T {
+        let chord = self.end - self.start;
+        let length = chord.x.hypot(chord.y);
+        let offset = self.control - self.start;
+        if length > T::zero() {
+            cross_z(&chord, &offset).abs() / length
+        } else {
+            offset.x.hypot(offset.y)
+        }
+    }
+}
+
+/// Returns the real roots of `a*t² + b*t + c`, a (nearly) double root is returned once.
+/// `a` may be zero.
+fn quadratic_roots<T: Float>(a: T, b: T, c: T) -> Vec<T> {
+    let two = T::one() + T::one();
+    let four = two * two;
+    let mut discriminant = b * b - four * a * c;
+    if discriminant < T::zero()
+        && -discriminant <= T::epsilon() * four * two * (b * b + (four * a * c).abs())
+    {
+        discriminant = T::zero();
+    }
+    if discriminant < T::zero() {
+        return Vec::new();
+    }
+    if a == T::zero() {
+        return if b == T::zero() {
+            Vec::new()
+        } else {
+            vec![-c / b]
+        };
+    }
+    if discriminant == T::zero() {
+        return vec![-b / (two * a)];
+    }
+    // the numerically stable form of the quadratic formula
+    let q = -(b + b.signum() * discriminant.sqrt()) / two;
+    if q == T::zero() {
+        return vec![T::zero()];
+    }
+    vec![q / a, c / q]
+}
+
+/// Returns the points where the segment meets the curve, ordered along the segment.
+/// ```
+/// # use intersect2d::curve::{segment_quadratic_bezier_intersections, QuadraticBezier};
+/// # use intersect2d::Tolerance;
+/// // the parabola y = x², from (-2,4) to (2,4)
+/// let curve = QuadraticBezier::<f64>::new(
+///     geo::Coordinate { x: -2., y: 4. },
+///     geo::Coordinate { x: 0., y: -4. },
+///     geo::Coordinate { x: 2., y: 4. },
+/// )?;
+/// let line = geo::Line::from([(-5., 1.), (5., 1.)]);
+/// let rv = segment_quadratic_bezier_intersections(&line, &curve, &Tolerance::default());
+/// assert_eq!(rv.len(), 2);
+/// assert!((rv[0].position.x + 1.).abs() < 1e-12);
+/// assert!((rv[0].curve_parameter - 0.25).abs() < 1e-12);
+/// assert!((rv[1].segment_parameter - 0.6).abs() < 1e-12);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn segment_quadratic_bezier_intersections<T>(
+    line: &geo::Line<T>,
+    curve: &QuadraticBezier<T>,
+    tolerance: &Tolerance<T>,
+) -> Vec<CurveIntersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let two = T::one() + T::one();
+    let d = line.end - line.start;
+    let length_sq = dot(&d, &d);
+    if length_sq <= T::zero() {
+        return Vec::new();
+    }
+    // the signed distance (times the length of the segment) of the curve from the line of
+    // the segment, as a polynomial in t
+    let n = perp(&d);
+    let a = dot(
+        &n,
+        &(curve.start - curve.control - curve.control + curve.end),
+    );
+    let b = two * dot(&n, &(curve.control - curve.start));
+    let c = dot(&n, &(curve.start - line.start));
+
+    let segment_slack = tolerance.epsilon / length_sq.sqrt();
+    let mut rv = Vec::<CurveIntersection<T>>::new();
+    for t in quadratic_roots(a, b, c).into_iter() {
+        if !(t >= -T::epsilon() && t <= T::one() + T::epsilon()) {
+            continue;
+        }
+        let t = t.max(T::zero()).min(T::one());
+        let position = curve.point_at(t);
+        let s = dot(&(position - line.start), &d) / length_sq;
+        if s >= -segment_slack && s <= T::one() + segment_slack {
+            rv.push(CurveIntersection {
+                position,
+                segment_parameter: s.max(T::zero()).min(T::one()),
+                curve_parameter: t,
+            });
+        }
+    }
+    rv.sort_by(|a, b| {
+        a.segment_parameter
+            .partial_cmp(&b.segment_parameter)
+            .unwrap_or(cmp::Ordering::Equal)
+    });
+    rv.dedup_by(|a, b| tolerance.ulps_eq_c(&a.position, &b.position));
+    rv
+}
+
+/// A piece of a curve, and the parameter range it covers of the original curve
+type Piece<T> = (QuadraticBezier<T>, T, T);
+
+/// Recursively subdivides the two pieces until they are flat, then intersects their chords.
+/// The candidates are refined later.
+fn subdivide<T>(a: Piece<T>, b: Piece<T>, flat: T, depth: usize, rv: &mut Vec<(T, T)>)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let ((a_min, a_max), (b_min, b_max)) = (a.0.hull_bounds(), b.0.hull_bounds());
+    if a_max.x < b_min.x - flat
+        || b_max.x < a_min.x - flat
+        || a_max.y < b_min.y - flat
+        || b_max.y < a_min.y - flat
+    {
+        return;
+    }
+    let half = T::one() / (T::one() + T::one());
+    let (a_flat, b_flat) = (a.0.flatness() <= flat, b.0.flatness() <= flat);
+    if (a_flat && b_flat) || depth == 0 {
+        // intersect the chords
+        let (r, s) = (a.0.end - a.0.start, b.0.end - b.0.start);
+        let denominator = cross_z(&r, &s);
+        if denominator == T::zero() {
+            return;
+        }
+        let qp = b.0.start - a.0.start;
+        let u = cross_z(&qp, &s) / denominator;
+        let v = cross_z(&qp, &r) / denominator;
+        let slack = T::epsilon().sqrt();
+        if u >= -slack && u <= T::one() + slack && v >= -slack && v <= T::one() + slack {
+            rv.push((a.1 + (a.2 - a.1) * u, b.1 + (b.2 - b.1) * v));
+        }
+        return;
+    }
+    let split = |p: Piece<T>, is_flat: bool| -> Vec<Piece<T>> {
+        if is_flat {
+            vec![p]
+        } else {
+            let (first, second) = p.0.halves();
+            let middle = p.1 + (p.2 - p.1) * half;
+            vec![(first, p.1, middle), (second, middle, p.2)]
+        }
+    };
+    for a in split(a, a_flat).into_iter() {
+        for b in split(b, b_flat).iter() {
+            subdivide(a, *b, flat, depth - 1, rv);
+        }
+    }
+}
+
+/// Returns the points where two quadratic Bézier curves meet, ordered along the first curve.
+/// The curves are subdivided until the pieces are flat, the crossings of the flat pieces are
+/// then refined with Newton's method. Overlapping curves, where the curves coincide along a
+/// stretch, are not reported as such.
+/// ```
+/// # use intersect2d::curve::{quadratic_bezier_intersections, QuadraticBezier};
+/// # use intersect2d::Tolerance;
+/// let c = |x: f64, y: f64| geo::Coordinate { x, y };
+/// let a = QuadraticBezier::new(c(0., 0.), c(5., 10.), c(10., 0.))?;
+/// let b = QuadraticBezier::new(c(0., 4.), c(5., -6.), c(10., 4.))?;
+/// let rv = quadratic_bezier_intersections(&a, &b, &Tolerance::default());
+/// assert_eq!(rv.len(), 2);
+/// // by symmetry the curves cross at y = 2
+/// assert!((rv[0].position.y - 2.).abs() < 1e-9);
+/// assert!((rv[0].parameter_a - rv[0].parameter_b).abs() < 1e-9);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn quadratic_bezier_intersections<T>(
+    a: &QuadraticBezier<T>,
+    b: &QuadraticBezier<T>,
+    tolerance: &Tolerance<T>,
+) -> Vec<CurvePairIntersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let ((a_min, a_max), (b_min, b_max)) = (a.hull_bounds(), b.hull_bounds());
+    let scale = (a_max.x - a_min.x)
+        .max(a_max.y - a_min.y)
+        .max(b_max.x - b_min.x)
+        .max(b_max.y - b_min.y)
+        .max(T::min_positive_value());
+    let flat = scale * T::epsilon().sqrt().sqrt();
+    let accept = (scale * T::epsilon().sqrt()).max(tolerance.epsilon);
+
+    let mut candidates = Vec::<(T, T)>::new();
+    // Shared end points are common (consecutive curves of an outline), and ill-conditioned
+    // when the curves join smoothly, so they are tested directly.
+    for (pa, u) in [(a.start, T::zero()), (a.end, T::one())].iter() {
+        for (pb, v) in [(b.start, T::zero()), (b.end, T::one())].iter() {
+            if tolerance.ulps_eq_c(pa, pb) {
+                candidates.push((*u, *v));
+            }
+        }
+    }
+    subdivide(
+        (*a, T::zero(), T::one()),
+        (*b, T::zero(), T::one()),
+        flat,
+        32,
+        &mut candidates,
+    );
+
+    let mut rv = Vec::<CurvePairIntersection<T>>::new();
+    for (mut u, mut v) in candidates.into_iter() {
+        // Newton's method on a(u) - b(v) = 0
+        for _ in 0..8 {
+            let f = a.point_at(u) - b.point_at(v);
+            let (da, db) = (a.derivative_at(u), b.derivative_at(v));
+            let determinant = cross_z(&db, &da);
+            if determinant == T::zero() {
+                break;
+            }
+            let du = cross_z(&db, &f) / determinant;
+            let dv = cross_z(&da, &f) / determinant;
+            u = (u - du).max(T::zero()).min(T::one());
+            v = (v - dv).max(T::zero()).min(T::one());
+            if du.abs() <= T::epsilon() && dv.abs() <= T::epsilon() {
+                break;
+            }
+        }
+        let (pa, pb) = (a.point_at(u), b.point_at(v));
+        if (pa.x - pb.x).hypot(pa.y - pb.y) <= accept {
+            rv.push(CurvePairIntersection {
+                position: pa,
+                parameter_a: u,
+                parameter_b: v,
+            });
+        }
+    }
+    rv.sort_by(|a, b| {
+        a.parameter_a
+            .partial_cmp(&b.parameter_a)
+            .unwrap_or(cmp::Ordering::Equal)
+    });
+    // neighbouring pieces may find the same crossing
+    let same = T::epsilon().sqrt();
+    rv.dedup_by(|a, b| {
+        tolerance.ulps_eq_c(&a.position, &b.position)
+            || ((a.parameter_a - b.parameter_a).abs() <= same
+                && (a.parameter_b - b.parameter_b).abs() <= same)
+    });
+    rv
+}
+
+/// A circular arc starting at the angle `start_angle` (radians, counter-clockwise from the
+/// x axis) and sweeping `sweep_angle` radians, counter-clockwise if the sweep is positive.
+/// The curve parameter of a point is the part of the sweep from the start to the point, from
+/// 0 at the start to 1 at the end of the arc, see [`CircularArc::point_at()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CircularArc<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    center: geo::Coordinate<T>,
+    radius: T,
+    start_angle: T,
+    sweep_angle: T,
+}
+
+impl<T> CircularArc<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Creates an arc, the sweep must be non-zero and at most a full turn
+    pub fn new(
+        center: geo::Coordinate<T>,
+        radius: T,
+        start_angle: T,
+        sweep_angle: T,
+    ) -> Result<Self, IntersectError> {
+        if !(center.x.is_finite()
+            && center.y.is_finite()
+            && start_angle.is_finite()
+            && sweep_angle.is_finite())
+        {
+            return Err(IntersectError::InvalidData(
+                "The arc must be finite".to_string(),
+            ));
+        }
+        if !(radius.is_finite() && radius > T::zero()) {
+            return Err(IntersectError::InvalidParameter(
+                "The radius of an arc must be positive and finite".to_string(),
+            ));
+        }
+        let two_pi = T::from(std::f64::consts::TAU).unwrap();
+        if sweep_angle == T::zero() || sweep_angle.abs() > two_pi {
+            return Err(IntersectError::InvalidParameter(
+                "The sweep of an arc must be non-zero and at most a full turn".to_string(),
+            ));
+        }
+        Ok(Self {
+            center,
+            radius,
+            start_angle,
+            sweep_angle,
+        })
+    }
+
+    pub fn center(&self) -> geo::Coordinate<T> {
+        self.center
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    pub fn start_angle(&self) -> T {
+        self.start_angle
+    }
+
+    pub fn sweep_angle(&self) -> T {
+        self.sweep_angle
+    }
+
+    /// Returns the point of the arc at the parameter `t`
+    pub fn point_at(&self, t: T) -> geo::Coordinate<T> {
+        self.point_at_angle(self.start_angle + self.sweep_angle * t)
+    }
+
+    fn point_at_angle(&self, angle: T) -> geo::Coordinate<T> {
+        let (sin, cos) = angle.sin_cos();
+        geo::Coordinate {
+            x: self.center.x + self.radius * cos,
+            y: self.center.y + self.radius * sin,
+        }
+    }
+
+    /// The counter-clockwise start angle, normalized into `[0, 2π)`, and the length of the
+    /// sweep
+    fn counter_clockwise(&self) -> (T, T) {
+        if self.sweep_angle >= T::zero() {
+            (normalize_angle(self.start_angle), self.sweep_angle)
+        } else {
+            (
+                normalize_angle(self.start_angle + self.sweep_angle),
+                -self.sweep_angle,
+            )
+        }
+    }
+
+    /// The angular slack corresponding to the distance tolerance
+    fn angle_slack(&self, tolerance: &Tolerance<T>) -> T {
+        let four = T::one() + T::one() + T::one() + T::one();
+        (tolerance.epsilon / self.radius).max(T::epsilon() * four)
+    }
+
+    /// Returns the parameter of the point of the circle at `angle`, None if the point isn't
+    /// on the arc
+    fn parameter_of(&self, angle: T, tolerance: &Tolerance<T>) -> Option<T> {
+        let two_pi = T::from(std::f64::consts::TAU).unwrap();
+        let sweep = self.sweep_angle.abs();
+        let relative = if self.sweep_angle >= T::zero() {
+            normalize_angle(angle - self.start_angle)
+        } else {
+            normalize_angle(self.start_angle - angle)
+        };
+        let slack = self.angle_slack(tolerance);
+        if relative <= sweep {
+            Some(relative / sweep)
+        } else if relative - sweep <= slack {
+            Some(T::one())
+        } else if two_pi - relative <= slack {
+            Some(T::zero())
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the points where the segment meets the arc, ordered along the segment.
+/// ```
+/// # use intersect2d::curve::{segment_arc_intersections, CircularArc};
+/// # use intersect2d::Tolerance;
+/// // the upper half of the unit circle
+/// let arc = CircularArc::<f64>::new(geo::Coordinate { x: 0., y: 0. }, 1., 0., std::f64::consts::PI)?;
+/// let line = geo::Line::from([(0., -2.), (0., 2.)]);
+/// let rv = segment_arc_intersections(&line, &arc, &Tolerance::default());
+/// assert_eq!(rv.len(), 1);
+/// assert!((rv[0].position.y - 1.).abs() < 1e-12);
+/// assert!((rv[0].curve_parameter - 0.5).abs() < 1e-12);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn segment_arc_intersections<T>(
+    line: &geo::Line<T>,
+    arc: &CircularArc<T>,
+    tolerance: &Tolerance<T>,
+) -> Vec<CurveIntersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let circle = Ellipse {
+        center: arc.center,
+        radius_x: arc.radius,
+        radius_y: arc.radius,
+        rotation: T::zero(),
+    };
+    segment_ellipse_intersections(line, &circle, tolerance)
+        .into_iter()
+        .filter_map(|i| {
+            arc.parameter_of(i.curve_parameter, tolerance)
+                .map(|t| CurveIntersection {
+                    curve_parameter: t,
+                    ..i
+                })
+        })
+        .collect()
+}
+
+/// An intersection between two circular arcs, see [`arc_intersections()`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArcIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The arcs meet at a point
+    Point(CurvePairIntersection<T>),
+    /// The arcs lie on the same circle and share this (counter-clockwise) arc
+    Overlap(CircularArc<T>),
+}
+
+/// Returns the intersections of two circular arcs: zero, one (the arcs touch) or two points,
+/// or the shared parts of two arcs on the same circle. Two circles are the same if their
+/// centers and their radii are equal according to `tolerance`.
+/// ```
+/// # use intersect2d::curve::{arc_intersections, ArcIntersection, CircularArc};
+/// # use intersect2d::Tolerance;
+/// let pi = std::f64::consts::PI;
+/// let a = CircularArc::<f64>::new(geo::Coordinate { x: 0., y: 0. }, 1., 0., pi)?;
+/// let b = CircularArc::<f64>::new(geo::Coordinate { x: 1., y: 0. }, 1., 0., pi)?;
+/// let rv = arc_intersections(&a, &b, &Tolerance::default());
+/// assert_eq!(rv.len(), 1);
+/// if let ArcIntersection::Point(p) = rv[0] {
+///     assert!((p.position.x - 0.5).abs() < 1e-12);
+///     assert!((p.parameter_a - 1. / 3.).abs() < 1e-12);
+/// } else {
+///     unreachable!()
+/// }
+/// // a quarter of the circle is shared
+/// let c = CircularArc::<f64>::new(geo::Coordinate { x: 0., y: 0. }, 1., pi / 2., pi)?;
+/// let rv = arc_intersections(&a, &c, &Tolerance::default());
+/// assert!(matches!(rv[0], ArcIntersection::Overlap(o) if (o.sweep_angle() - pi / 2.).abs() < 1e-12));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn arc_intersections<T>(
+    a: &CircularArc<T>,
+    b: &CircularArc<T>,
+    tolerance: &Tolerance<T>,
+) -> Vec<ArcIntersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut rv = Vec::<ArcIntersection<T>>::new();
+    let point = |angle_a: T, angle_b: T, rv: &mut Vec<ArcIntersection<T>>| {
+        if let (Some(parameter_a), Some(parameter_b)) = (
+            a.parameter_of(angle_a, tolerance),
+            b.parameter_of(angle_b, tolerance),
+        ) {
+            rv.push(ArcIntersection::Point(CurvePairIntersection {
+                position: a.point_at_angle(angle_a),
+                parameter_a,
+                parameter_b,
+            }));
+        }
+    };
+    let same_center = tolerance.ulps_eq_c(&a.center, &b.center);
+    if same_center && tolerance.ulps_eq(a.radius, b.radius) {
+        // two arcs of the same circle, intersect the angular intervals
+        let two_pi = T::from(std::f64::consts::TAU).unwrap();
+        let (start_a, length_a) = a.counter_clockwise();
+        let (start_b, length_b) = b.counter_clockwise();
+        let offset = normalize_angle(start_b - start_a);
+        let slack = a.angle_slack(tolerance).max(b.angle_slack(tolerance));
+        let mut pieces = Vec::<(T, T)>::new();
+        for from in [offset, offset - two_pi].iter() {
+            let (low, high) = (from.max(T::zero()), (*from + length_b).min(length_a));
+            if high >= low - slack {
+                pieces.push((low, high.max(low)));
+            }
+        }
+        let overlaps: Vec<(T, T)> = pieces
+            .iter()
+            .filter(|(low, high)| *high - *low > slack)
+            .copied()
+            .collect();
+        for (low, high) in pieces.into_iter() {
+            if high - low > slack {
+                rv.push(ArcIntersection::Overlap(CircularArc {
+                    center: a.center,
+                    radius: a.radius,
+                    start_angle: start_a + low,
+                    sweep_angle: high - low,
+                }));
+            } else if !overlaps.iter().any(|(l, h)| {
+                (low - *l).abs() <= slack
+                    || (low - *h).abs() <= slack
+                    || (two_pi - (low - *l).abs()).abs() <= slack
+                    || (two_pi - (low - *h).abs()).abs() <= slack
+            }) {
+                point(start_a + low, start_a + low, &mut rv);
+            }
+        }
+        return rv;
+    }
+    if same_center {
+        return rv;
+    }
+
+    // two different circles
+    let two = T::one() + T::one();
+    let delta = b.center - a.center;
+    let distance = delta.x.hypot(delta.y);
+    let (ra, rb) = (a.radius, b.radius);
+    let touching =
+        tolerance.ulps_eq(distance, ra + rb) || tolerance.ulps_eq(distance, (ra - rb).abs());
+    if !touching && (distance > ra + rb || distance < (ra - rb).abs()) {
+        return rv;
+    }
+    // the distance from the center of a to the chord through the intersection points, and
+    // half the length of the chord
+    let along = (distance * distance + ra * ra - rb * rb) / (two * distance);
+    let half_chord_sq = ra * ra - along * along;
+    let direction = delta.y.atan2(delta.x);
+    if touching || half_chord_sq <= T::zero() {
+        let angle_a = if along >= T::zero() {
+            direction
+        } else {
+            direction + T::from(std::f64::consts::PI).unwrap()
+        };
+        let p = a.point_at_angle(angle_a);
+        point(angle_a, (p.y - b.center.y).atan2(p.x - b.center.x), &mut rv);
+        return rv;
+    }
+    let half_angle = (along / ra).max(-T::one()).min(T::one()).acos();
+    for angle_a in [direction - half_angle, direction + half_angle].iter() {
+        let p = a.point_at_angle(*angle_a);
+        point(
+            *angle_a,
+            (p.y - b.center.y).atan2(p.x - b.center.x),
+            &mut rv,
+        );
+    }
+    rv.sort_by(|x, y| match (x, y) {
+        (ArcIntersection::Point(x), ArcIntersection::Point(y)) => x
+            .parameter_a
+            .partial_cmp(&y.parameter_a)
+            .unwrap_or(cmp::Ordering::Equal),
+        _ => cmp::Ordering::Equal,
+    });
+    rv
+}
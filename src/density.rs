@@ -0,0 +1,192 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Intersection density grids.
+//!
+//! A [`DensityGrid`] bins intersection points into a regular grid of cells and counts the
+//! points of each cell, e.g. to render a heatmap of where the conflicts of a large drawing
+//! concentrate. The counts are stored row by row, starting at the row with the lowest y, so
+//! they can be copied straight into an image or matrix type.
+
+use super::IntersectError;
+use crate::tiles::cell_index;
+use num_traits::Float;
+
+/// Per-cell counts of the intersection points within `bounds`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DensityGrid<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    bounds: geo::Rect<T>,
+    columns: usize,
+    rows: usize,
+    counts: Vec<usize>,
+    outside: usize,
+}
+
+impl<T> DensityGrid<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Creates an empty grid of `columns` x `rows` cells covering `bounds`
+    pub fn new(bounds: geo::Rect<T>, columns: usize, rows: usize) -> Result<Self, IntersectError> {
+        if columns == 0 || rows == 0 {
+            return Err(IntersectError::InvalidParameter(
+                "A density grid needs at least one column and one row".to_string(),
+            ));
+        }
+        let (min, max) = (bounds.min(), bounds.max());
+        if !(min.x.is_finite() && min.y.is_finite() && max.x.is_finite() && max.y.is_finite()) {
+            return Err(IntersectError::InvalidData(
+                "The density grid bounds must be finite".to_string(),
+            ));
+        }
+        Ok(Self {
+            bounds,
+            columns,
+            rows,
+            counts: vec![0; columns * rows],
+            outside: 0,
+        })
+    }
+
+    /// Creates a grid covering `bounds` and bins all the intersection points of `results`
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use intersect2d::density::DensityGrid;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 1., 10., 1.],
+    ///     [1., 0., 1., 10.],
+    ///     [2., 0., 2., 10.],
+    ///     [9., 0., 9., 10.],
+    /// ]);
+    /// let results = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute()?;
+    /// let bounds = geo::Rect::new((0., 0.), (10., 10.));
+    /// let grid = DensityGrid::from_results(bounds, 2, 2, results)?;
+    /// assert_eq!(grid.counts(), &[2, 1, 0, 0]);
+    /// assert_eq!(grid.count(0, 0), 2);
+    /// assert_eq!(grid.max_count(), 2);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn from_results<I>(
+        bounds: geo::Rect<T>,
+        columns: usize,
+        rows: usize,
+        results: I,
+    ) -> Result<Self, IntersectError>
+    where
+        I: IntoIterator<Item = (geo::Coordinate<T>, Vec<usize>)>,
+    {
+        let mut rv = Self::new(bounds, columns, rows)?;
+        rv.extend(results.into_iter().map(|(point, _)| point));
+        Ok(rv)
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the cell containing `point`, or None if the point is outside the bounds.
+    /// Points on the upper or right border of the grid belong to the last row or column.
+    pub fn cell_of(&self, point: &geo::Coordinate<T>) -> Option<(usize, usize)> {
+        let (min, max) = (self.bounds.min(), self.bounds.max());
+        if !(point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y) {
+            return None;
+        }
+        Some((
+            cell_index(point.x, min.x, max.x, self.columns),
+            cell_index(point.y, min.y, max.y, self.rows),
+        ))
+    }
+
+    /// Adds one point to the grid
+    pub fn add(&mut self, point: &geo::Coordinate<T>) {
+        match self.cell_of(point) {
+            Some((column, row)) => self.counts[row * self.columns + column] += 1,
+            None => self.outside += 1,
+        }
+    }
+
+    /// Returns the number of points in a cell
+    pub fn count(&self, column: usize, row: usize) -> usize {
+        self.counts[row * self.columns + column]
+    }
+
+    /// Returns the counts of all the cells, row by row
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Returns the highest count of any cell
+    pub fn max_count(&self) -> usize {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Returns the number of points that were added outside the bounds of the grid
+    pub fn outside(&self) -> usize {
+        self.outside
+    }
+}
+
+impl<T> Extend<geo::Coordinate<T>> for DensityGrid<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn extend<I: IntoIterator<Item = geo::Coordinate<T>>>(&mut self, iter: I) {
+        for point in iter {
+            self.add(&point);
+        }
+    }
+}
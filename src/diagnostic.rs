@@ -0,0 +1,235 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Structured diagnostic dumps of failed computations.
+//!
+//! With a sink set by
+//! [`AlgorithmData::with_diagnostic_sink()`](crate::algorithm::AlgorithmData::with_diagnostic_sink)
+//! the sweep keeps the last [`HISTORY_LENGTH`] handled events. A computation failing with
+//! [`IntersectError::InternalError`](crate::IntersectError::InternalError) then writes one
+//! JSON document to the sink before the error is returned, ready to be attached to a bug
+//! report. The same document is returned by
+//! [`AlgorithmData::diagnostic_dump()`](crate::algorithm::AlgorithmData::diagnostic_dump):
+//! ```json
+//! {
+//!   "version": 1,
+//!   "error": "...",
+//!   "options": {"stop_at_first_intersection": false, ...},
+//!   "sweepline_pos": [x, y],
+//!   "segments": [[x1, y1, x2, y2], ...],
+//!   "event_history": [{"position": [x, y], "drop": [..], "add": [..], "intersection": [..]}, ...]
+//! }
+//! ```
+//! The segments are the stored lines, transformed and oriented the way the sweep sees them.
+//! Non-finite numbers are written as `null`.
+
+use num_traits::Float;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::Write;
+
+/// The number of handled events kept for the dump
+pub const HISTORY_LENGTH: usize = 64;
+
+/// The version of the dump format
+pub const DUMP_VERSION: u32 = 1;
+
+/// An event handled by the sweep, as recorded in the history
+struct HandledEvent<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    position: geo::Coordinate<T>,
+    drop: Vec<usize>,
+    add: Vec<usize>,
+    intersection: Vec<usize>,
+}
+
+/// The diagnostic sink of an
+/// [`AlgorithmData`](crate::algorithm::AlgorithmData) and the event history
+pub(crate) struct Diagnostics<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    sink: Box<dyn Write + Send>,
+    history: VecDeque<HandledEvent<T>>,
+}
+
+impl<T> Diagnostics<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub(crate) fn new(sink: Box<dyn Write + Send>) -> Self {
+        Self {
+            sink,
+            history: VecDeque::with_capacity(HISTORY_LENGTH),
+        }
+    }
+
+    /// Adds an event to the history, forgetting the oldest event if the history is full
+    pub(crate) fn record(
+        &mut self,
+        position: geo::Coordinate<T>,
+        drop: &[usize],
+        add: &[usize],
+        intersection: &[usize],
+    ) {
+        if self.history.len() == HISTORY_LENGTH {
+            let _ = self.history.pop_front();
+        }
+        self.history.push_back(HandledEvent {
+            position,
+            drop: drop.to_vec(),
+            add: add.to_vec(),
+            intersection: intersection.to_vec(),
+        });
+    }
+
+    pub(crate) fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Writes `dump` to the sink. Failing to write is ignored, the error that caused the
+    /// dump is the one to report.
+    pub(crate) fn write(&mut self, dump: &str) {
+        let _ = self
+            .sink
+            .write_all(dump.as_bytes())
+            .and_then(|_| self.sink.flush());
+    }
+}
+
+/// Formats a number, JSON has no representation of NaN or infinity
+pub(crate) fn json_number<T: Float>(value: T) -> String {
+    match value.to_f64() {
+        Some(v) if v.is_finite() => format!("{:?}", v),
+        _ => "null".to_string(),
+    }
+}
+
+/// Formats a string with the JSON escapes
+pub(crate) fn json_string(value: &str) -> String {
+    let mut rv = String::with_capacity(value.len() + 2);
+    rv.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => rv.push_str("\\\""),
+            '\\' => rv.push_str("\\\\"),
+            '\n' => rv.push_str("\\n"),
+            '\r' => rv.push_str("\\r"),
+            '\t' => rv.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(rv, "\\u{:04x}", c as u32);
+            }
+            c => rv.push(c),
+        }
+    }
+    rv.push('"');
+    rv
+}
+
+fn json_coordinate<T: geo::CoordFloat>(c: &geo::Coordinate<T>) -> String {
+    format!("[{},{}]", json_number(c.x), json_number(c.y))
+}
+
+fn json_indices(indices: &[usize]) -> String {
+    let items: Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Builds the dump document, `options` are pairs of option names and JSON values
+pub(crate) fn format_dump<T>(
+    error: &str,
+    options: &[(&str, String)],
+    sweepline_pos: &geo::Coordinate<T>,
+    segments: &[geo::Line<T>],
+    diagnostics: Option<&Diagnostics<T>>,
+) -> String
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let options: Vec<String> = options
+        .iter()
+        .map(|(name, value)| format!("{}:{}", json_string(name), value))
+        .collect();
+    let segments: Vec<String> = segments
+        .iter()
+        .map(|l| {
+            format!(
+                "[{},{},{},{}]",
+                json_number(l.start.x),
+                json_number(l.start.y),
+                json_number(l.end.x),
+                json_number(l.end.y)
+            )
+        })
+        .collect();
+    let history: Vec<String> = diagnostics
+        .iter()
+        .flat_map(|d| d.history.iter())
+        .map(|e| {
+            format!(
+                "{{\"position\":{},\"drop\":{},\"add\":{},\"intersection\":{}}}",
+                json_coordinate(&e.position),
+                json_indices(&e.drop),
+                json_indices(&e.add),
+                json_indices(&e.intersection)
+            )
+        })
+        .collect();
+    format!(
+        "{{\"version\":{},\"error\":{},\"options\":{{{}}},\"sweepline_pos\":{},\"segments\":[{}],\"event_history\":[{}]}}\n",
+        DUMP_VERSION,
+        json_string(error),
+        options.join(","),
+        json_coordinate(sweepline_pos),
+        segments.join(","),
+        history.join(",")
+    )
+}
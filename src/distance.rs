@@ -0,0 +1,316 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Distance between line segments.
+//!
+//! Clearance checks are usually done right next to the intersection tests, so
+//! [`segment_segment_closest_with_tolerance()`] uses the same [`Tolerance`] conventions as
+//! [`intersect_with_tolerance()`]: segments that intersect according to the tolerance are at
+//! distance zero, and segments with virtually identical end points are treated as points.
+//! [`project_point_onto_segment()`] snaps a point to the nearest point of a segment.
+//! [`classify_touch()`] combines the intersection test and the distance into one of the three
+//! cases a snapping pass tells apart, see [`TouchClassification`].
+
+use crate::predicates::{orient2d_with_tolerance, Orientation};
+use crate::vector::{dot, scale_to_coordinate};
+use crate::{intersect_with_tolerance, IntersectError, Intersection, Tolerance};
+use num_traits::Float;
+
+/// Returns the parameter `t` (in `0..=1`) of the point on `line` closest to `point`.
+/// A zero length line always returns 0.
+fn closest_t<T>(line: &geo::Line<T>, point: &geo::Coordinate<T>) -> T
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let r = line.end - line.start;
+    let r_dot_r = dot(&r, &r);
+    if r_dot_r <= T::zero() {
+        return T::zero();
+    }
+    let t = dot(&(*point - line.start), &r) / r_dot_r;
+    t.max(T::zero()).min(T::one())
+}
+
+/// Returns the point on `line` nearest to `point`, and its parameter `t` (in `0..=1`) along
+/// the line. This is [`project_point_onto_segment_with_tolerance()`] with the default
+/// tolerance.
+/// ```
+/// # use intersect2d::distance::project_point_onto_segment;
+/// let line: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let (position, t) = project_point_onto_segment(&line, &geo::Coordinate { x: 2.5, y: 3. });
+/// assert_eq!(position, geo::Coordinate { x: 2.5, y: 0. });
+/// assert_eq!(t, 0.25);
+/// let (position, t) = project_point_onto_segment(&line, &geo::Coordinate { x: -5., y: 3. });
+/// assert_eq!((position, t), (line.start, 0.0));
+/// ```
+pub fn project_point_onto_segment<T>(
+    line: &geo::Line<T>,
+    point: &geo::Coordinate<T>,
+) -> (geo::Coordinate<T>, T)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    project_point_onto_segment_with_tolerance(line, point, &Tolerance::default())
+}
+
+/// Returns the point on `line` nearest to `point`, and its parameter `t` (in `0..=1`) along
+/// the line, using the conventions of
+/// [`intersect_line_point_with_tolerance()`](crate::intersect_line_point_with_tolerance):
+/// the point is rounded to the precision model first, and a point virtually identical to an
+/// end point projects onto that end point exactly. A zero length line projects everything
+/// onto its start.
+pub fn project_point_onto_segment_with_tolerance<T>(
+    line: &geo::Line<T>,
+    point: &geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> (geo::Coordinate<T>, T)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let point = tolerance.precision.make_precise_c(*point);
+    if tolerance.ulps_eq_c(&line.start, &point) {
+        return (line.start, T::zero());
+    }
+    if tolerance.ulps_eq_c(&line.end, &point) {
+        return (line.end, T::one());
+    }
+    let t = closest_t(line, &point);
+    if t == T::zero() {
+        (line.start, t)
+    } else if t == T::one() {
+        (line.end, t)
+    } else {
+        (
+            tolerance.precision.make_precise_c(scale_to_coordinate(
+                &line.start,
+                &(line.end - line.start),
+                t,
+            )),
+            t,
+        )
+    }
+}
+
+/// Returns the closest points of two segments as
+/// `(distance, point_on_one, point_on_two, t_one, t_two)`, where `point_on_one` is
+/// `one.start + t_one * (one.end - one.start)` (and the same for `other`).
+/// This is [`segment_segment_closest_with_tolerance()`] with the default tolerance.
+/// ```
+/// # use intersect2d::distance::segment_segment_closest;
+/// let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let other: geo::Line<f64> = [(5., 2.), (15., 7.)].into();
+/// let (distance, on_one, on_two, t_one, t_two) = segment_segment_closest(&one, &other);
+/// assert_eq!(distance, 2.0);
+/// assert_eq!(on_one, geo::Coordinate { x: 5., y: 0. });
+/// assert_eq!(on_two, geo::Coordinate { x: 5., y: 2. });
+/// assert_eq!((t_one, t_two), (0.5, 0.0));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn segment_segment_closest<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+) -> (T, geo::Coordinate<T>, geo::Coordinate<T>, T, T)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    segment_segment_closest_with_tolerance(one, other, &Tolerance::default())
+}
+
+/// Returns the closest points of two segments as
+/// `(distance, point_on_one, point_on_two, t_one, t_two)`, using a custom tolerance.
+/// If the segments intersect according to [`intersect_with_tolerance()`] the distance is zero
+/// and both points are the intersection point (the start of the shared part for overlaps).
+/// Otherwise the closest pair always involves an end point, ties are resolved in the order
+/// `one.start`, `one.end`, `other.start`, `other.end`.
+#[allow(clippy::type_complexity)]
+pub fn segment_segment_closest_with_tolerance<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    tolerance: &Tolerance<T>,
+) -> (T, geo::Coordinate<T>, geo::Coordinate<T>, T, T)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if let Some(intersection) = intersect_with_tolerance(one, other, tolerance) {
+        let position = match intersection {
+            Intersection::Intersection(position) => position,
+            Intersection::OverLap(overlap) => overlap.start,
+        };
+        return (
+            T::zero(),
+            position,
+            position,
+            closest_t(one, &position),
+            closest_t(other, &position),
+        );
+    }
+
+    let r = one.end - one.start;
+    let s = other.end - other.start;
+    let candidates = [
+        (T::zero(), closest_t(other, &one.start)),
+        (T::one(), closest_t(other, &one.end)),
+        (closest_t(one, &other.start), T::zero()),
+        (closest_t(one, &other.end), T::one()),
+    ];
+    let mut rv: Option<(T, geo::Coordinate<T>, geo::Coordinate<T>, T, T)> = None;
+    for (t_one, t_two) in candidates.iter() {
+        // use the end points as they are, not as start + 1.0 * (end - start)
+        let on_one = if *t_one == T::one() {
+            one.end
+        } else {
+            scale_to_coordinate(&one.start, &r, *t_one)
+        };
+        let on_two = if *t_two == T::one() {
+            other.end
+        } else {
+            scale_to_coordinate(&other.start, &s, *t_two)
+        };
+        let d = on_two - on_one;
+        let distance = dot(&d, &d).sqrt();
+        if rv.map_or(true, |best| distance < best.0) {
+            rv = Some((distance, on_one, on_two, *t_one, *t_two));
+        }
+    }
+    rv.unwrap()
+}
+
+/// How close two segments are, see [`classify_touch()`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchClassification<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The segments are farther apart than the requested distance
+    Disjoint,
+    /// The segments are within the requested distance, but they don't cross. The value is the
+    /// distance between them, zero if they touch or overlap.
+    TouchingWithin(T),
+    /// The segments cross at a single point in the interior of both
+    ProperlyIntersecting(geo::Coordinate<T>),
+}
+
+/// Classifies the segments as disjoint, touching within `distance`, or properly
+/// intersecting, using the default tolerance. See [`classify_touch_with_tolerance()`].
+/// ```
+/// # use intersect2d::distance::{classify_touch, TouchClassification};
+/// let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let crossing: geo::Line<f64> = [(5., -5.), (5., 5.)].into();
+/// let t_junction: geo::Line<f64> = [(5., 0.), (5., 5.)].into();
+/// let near: geo::Line<f64> = [(5., 0.5), (5., 5.)].into();
+/// assert_eq!(
+///     classify_touch(&one, &crossing, 1.)?,
+///     TouchClassification::ProperlyIntersecting(geo::Coordinate { x: 5., y: 0. })
+/// );
+/// assert_eq!(classify_touch(&one, &t_junction, 1.)?, TouchClassification::TouchingWithin(0.));
+/// assert_eq!(classify_touch(&one, &near, 1.)?, TouchClassification::TouchingWithin(0.5));
+/// assert_eq!(classify_touch(&one, &near, 0.1)?, TouchClassification::Disjoint);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn classify_touch<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    distance: T,
+) -> Result<TouchClassification<T>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    classify_touch_with_tolerance(one, other, distance, &Tolerance::default())
+}
+
+/// Classifies the segments as disjoint, touching within `distance`, or properly
+/// intersecting, using a custom tolerance.
+/// The segments properly intersect if each one has its end points strictly on opposite sides
+/// of the other one, according to [`orient2d_with_tolerance()`]. Every other pair intersecting
+/// according to [`intersect_with_tolerance()`] (an end point on the other segment, a shared
+/// end point or a collinear overlap) touches at distance zero. The remaining pairs touch if
+/// [`segment_segment_closest_with_tolerance()`] is at most `distance`.
+/// Returns an `InvalidParameter` error if `distance` is negative or not finite.
+pub fn classify_touch_with_tolerance<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    distance: T,
+    tolerance: &Tolerance<T>,
+) -> Result<TouchClassification<T>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if !distance.is_finite() || distance < T::zero() {
+        return Err(IntersectError::InvalidParameter(
+            "The touch distance must be a finite, positive value".to_string(),
+        ));
+    }
+    let opposite = |line: &geo::Line<T>, a: &geo::Coordinate<T>, b: &geo::Coordinate<T>| {
+        matches!(
+            (
+                orient2d_with_tolerance(&line.start, &line.end, a, tolerance),
+                orient2d_with_tolerance(&line.start, &line.end, b, tolerance),
+            ),
+            (Orientation::Clockwise, Orientation::CounterClockwise)
+                | (Orientation::CounterClockwise, Orientation::Clockwise)
+        )
+    };
+    if opposite(one, &other.start, &other.end) && opposite(other, &one.start, &one.end) {
+        if let Some(Intersection::Intersection(position)) =
+            intersect_with_tolerance(one, other, tolerance)
+        {
+            return Ok(TouchClassification::ProperlyIntersecting(position));
+        }
+    }
+    let (gap, _, _, _, _) = segment_segment_closest_with_tolerance(one, other, tolerance);
+    Ok(if gap <= distance {
+        TouchClassification::TouchingWithin(gap)
+    } else {
+        TouchClassification::Disjoint
+    })
+}
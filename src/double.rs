@@ -0,0 +1,265 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Double-double arithmetic, a middle ground between plain floating point and the exact
+//! arithmetic of [`exact`](crate::exact).
+//!
+//! A [`DoubleDouble`] represents a value as the unevaluated sum of two floats, giving roughly
+//! twice the precision of `T` at a small constant cost. [`intersect_double_double()`]
+//! computes intersection points with it, and the sweep-line can use it for the intersection
+//! points and the ordering of the lines, see
+//! [`AlgorithmData::with_double_double()`](crate::algorithm::AlgorithmData::with_double_double).
+
+use crate::expansion::{two_product, two_sum};
+use crate::{intersect_with_tolerance, Intersection, Tolerance};
+use num_traits::Float;
+use std::ops;
+
+/// A value represented as the unevaluated sum `hi + lo`, where `lo` is smaller than half an
+/// ulp of `hi`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoubleDouble<T: Float> {
+    hi: T,
+    lo: T,
+}
+
+/// Returns (a + b, the rounding error of a + b), assuming |a| >= |b|
+#[inline(always)]
+fn fast_two_sum<T: Float>(a: T, b: T) -> (T, T) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+impl<T: Float> DoubleDouble<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            hi: value,
+            lo: T::zero(),
+        }
+    }
+
+    /// Returns the exact sum of `a` and `b`
+    /// ```
+    /// # use intersect2d::double::DoubleDouble;
+    /// let sum = DoubleDouble::from_sum(1.0, 1e-20);
+    /// assert_eq!(sum.hi(), 1.0);
+    /// assert_eq!(sum.lo(), 1e-20);
+    /// ```
+    pub fn from_sum(a: T, b: T) -> Self {
+        let (hi, lo) = two_sum(a, b);
+        Self { hi, lo }
+    }
+
+    /// Returns the exact product of `a` and `b`
+    pub fn from_product(a: T, b: T) -> Self {
+        let (hi, lo) = two_product(a, b);
+        Self { hi, lo }
+    }
+
+    /// The leading component
+    pub fn hi(&self) -> T {
+        self.hi
+    }
+
+    /// The trailing component
+    pub fn lo(&self) -> T {
+        self.lo
+    }
+
+    /// Returns the value rounded to `T`
+    pub fn value(&self) -> T {
+        self.hi + self.lo
+    }
+
+    fn renormalize(hi: T, lo: T) -> Self {
+        let (hi, lo) = fast_two_sum(hi, lo);
+        if hi.is_finite() {
+            Self { hi, lo }
+        } else {
+            Self::new(hi)
+        }
+    }
+}
+
+impl<T: Float> ops::Add for DoubleDouble<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (s, e) = two_sum(self.hi, other.hi);
+        let (t, f) = two_sum(self.lo, other.lo);
+        let (s, e) = fast_two_sum(s, e + t);
+        Self::renormalize(s, e + f)
+    }
+}
+
+impl<T: Float> ops::Neg for DoubleDouble<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            hi: -self.hi,
+            lo: -self.lo,
+        }
+    }
+}
+
+impl<T: Float> ops::Sub for DoubleDouble<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<T: Float> ops::Mul for DoubleDouble<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (p, e) = two_product(self.hi, other.hi);
+        Self::renormalize(p, e + (self.hi * other.lo + self.lo * other.hi))
+    }
+}
+
+impl<T: Float> ops::Div for DoubleDouble<T> {
+    type Output = Self;
+
+    /// Long division, three rounds of `T` precision quotients
+    /// ```
+    /// # use intersect2d::double::DoubleDouble;
+    /// let third = DoubleDouble::new(1.0_f64) / DoubleDouble::new(3.0);
+    /// assert_eq!(third.value(), 1.0 / 3.0);
+    /// assert!(third.lo() != 0.0);
+    /// assert_eq!((third * DoubleDouble::new(3.0)).value(), 1.0);
+    /// ```
+    fn div(self, other: Self) -> Self {
+        let q1 = self.hi / other.hi;
+        if !q1.is_finite() {
+            return Self::new(q1);
+        }
+        let r = self - other * Self::new(q1);
+        let q2 = r.hi / other.hi;
+        let r = r - other * Self::new(q2);
+        let q3 = r.hi / other.hi;
+        let (q1, q2) = fast_two_sum(q1, q2);
+        Self { hi: q1, lo: q2 } + Self::new(q3)
+    }
+}
+
+impl<T: Float> PartialOrd for DoubleDouble<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.hi.partial_cmp(&other.hi) {
+            Some(std::cmp::Ordering::Equal) => self.lo.partial_cmp(&other.lo),
+            ordering => ordering,
+        }
+    }
+}
+
+/// Returns the x coordinate of the (non horizontal) line at height `y`, computed in double-double
+#[inline(always)]
+pub(crate) fn x_at<T>(start: geo::Coordinate<T>, end: geo::Coordinate<T>, y: T) -> T
+where
+    T: Float + geo::CoordFloat,
+{
+    let dx = DoubleDouble::from_sum(end.x, -start.x);
+    let dy = DoubleDouble::from_sum(end.y, -start.y);
+    let offset = DoubleDouble::from_sum(y, -start.y);
+    (DoubleDouble::new(start.x) + offset * dx / dy).value()
+}
+
+/// Get any intersection point between lines, just like
+/// [`intersect_with_tolerance()`](crate::intersect_with_tolerance), but with the intersection
+/// point of crossing lines computed in double-double arithmetic.
+/// ```
+/// # use intersect2d::double::intersect_double_double;
+/// # use intersect2d::exact::intersect_exact;
+/// # use intersect2d::{Intersection, Tolerance};
+/// let one: geo::Line<f64> = [(0.1, 0.3), (7.7, 3.1)].into();
+/// let other: geo::Line<f64> = [(0.2, 5.9), (6.3, 0.7)].into();
+/// let rv = intersect_double_double(&one, &other, &Tolerance::default());
+/// match (rv, intersect_exact(&one, &other).intersection) {
+///     (Some(Intersection::Intersection(a)), Some(Intersection::Intersection(b))) => {
+///         assert_eq!(a, b)
+///     }
+///     _ => panic!(),
+/// }
+/// ```
+pub fn intersect_double_double<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    tolerance: &Tolerance<T>,
+) -> Option<Intersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    match intersect_with_tolerance(one, other, tolerance)? {
+        Intersection::Intersection(point) => {
+            let diff = |a: T, b: T| DoubleDouble::from_sum(a, -b);
+            let (rx, ry) = (diff(one.end.x, one.start.x), diff(one.end.y, one.start.y));
+            let (sx, sy) = (
+                diff(other.end.x, other.start.x),
+                diff(other.end.y, other.start.y),
+            );
+            let (qx, qy) = (
+                diff(other.start.x, one.start.x),
+                diff(other.start.y, one.start.y),
+            );
+            let r_cross_s = rx * sy - ry * sx;
+            if r_cross_s.hi() == T::zero() {
+                return Some(Intersection::Intersection(point));
+            }
+            let t = (qx * sy - qy * sx) / r_cross_s;
+            let x = (DoubleDouble::new(one.start.x) + rx * t).value();
+            let y = (DoubleDouble::new(one.start.y) + ry * t).value();
+            if !x.is_finite() || !y.is_finite() {
+                return Some(Intersection::Intersection(point));
+            }
+            Some(Intersection::Intersection(
+                tolerance.precision.make_precise_c(geo::Coordinate { x, y }),
+            ))
+        }
+        overlap => Some(overlap),
+    }
+}
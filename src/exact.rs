@@ -0,0 +1,268 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Exact intersection of a single pair of lines, for auditing the results of the floating
+//! point [`intersect()`](crate::intersect).
+//!
+//! [`intersect_exact()`] decides if, and how, two segments intersect using exact expansion
+//! arithmetic, and rounds the intersection point correctly (to nearest) only at the very end.
+//! The computation is exact as long as the products of coordinates neither overflow nor
+//! underflow.
+
+use crate::expansion::{divide, Expansion};
+use crate::{intersect, ulps_eq_c, Intersection};
+use num_traits::Float;
+use std::cmp;
+
+/// The result of [`intersect_exact()`]
+#[derive(Debug)]
+pub struct ExactIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The exact intersection, with the point correctly rounded. An overlap is the shared
+    /// part of the segments, its end points are end points of the input segments.
+    pub intersection: Option<Intersection<T>>,
+    /// True if [`intersect()`](crate::intersect) disagrees: it finds an intersection where
+    /// there is none (or the other way around), confuses a point with an overlap, or returns
+    /// a point that isn't virtually identical to the correctly rounded one.
+    pub disagrees: bool,
+}
+
+/// Computes the intersection of two segments using exact arithmetic.
+/// ```
+/// # use intersect2d::exact::intersect_exact;
+/// # use intersect2d::Intersection;
+/// let one: geo::Line<f64> = [(0., 0.), (3., 3.)].into();
+/// let other: geo::Line<f64> = [(0., 1.), (3., 0.)].into();
+/// let rv = intersect_exact(&one, &other);
+/// match rv.intersection {
+///     // 3/4 rounded to nearest
+///     Some(Intersection::Intersection(p)) => assert_eq!(p, geo::Coordinate { x: 0.75, y: 0.75 }),
+///     _ => panic!(),
+/// }
+/// assert!(!rv.disagrees);
+/// ```
+pub fn intersect_exact<T>(one: &geo::Line<T>, other: &geo::Line<T>) -> ExactIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let intersection = exact(one, other);
+    let disagrees = match (&intersection, intersect(one, other)) {
+        (None, None) => false,
+        (Some(Intersection::Intersection(a)), Some(Intersection::Intersection(b))) => {
+            !ulps_eq_c(a, &b)
+        }
+        (Some(Intersection::OverLap(a)), Some(Intersection::OverLap(b))) => {
+            // intersect() reports the projection of `other` onto `one`, compare the
+            // covered parts of `one`
+            !covers_same(one, a, &b)
+        }
+        (Some(Intersection::OverLap(a)), Some(Intersection::Intersection(b))) => {
+            // a zero length, or end-to-end, contact
+            !(ulps_eq_c(&a.start, &a.end) && ulps_eq_c(&a.start, &b))
+        }
+        _ => true,
+    };
+    ExactIntersection {
+        intersection,
+        disagrees,
+    }
+}
+
+/// Tests if the part of `one` covered by `overlap` matches the (unclipped) `projection`
+fn covers_same<T>(one: &geo::Line<T>, overlap: &geo::Line<T>, projection: &geo::Line<T>) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let r = one.end - one.start;
+    let r_dot_r = r.x * r.x + r.y * r.y;
+    let t = |p: geo::Coordinate<T>| {
+        let d = p - one.start;
+        (d.x * r.x + d.y * r.y) / r_dot_r
+    };
+    let clip = |v: T| v.max(T::zero()).min(T::one());
+    let (p0, p1) = (clip(t(projection.start)), clip(t(projection.end)));
+    let (o0, o1) = (t(overlap.start), t(overlap.end));
+    approx::ulps_eq!(p0.min(p1), o0.min(o1)) && approx::ulps_eq!(p0.max(p1), o0.max(o1))
+}
+
+struct ExactCoordinate<T> {
+    x: Expansion<T>,
+    y: Expansion<T>,
+}
+
+impl<T: Float> ExactCoordinate<T> {
+    fn new(c: &geo::Coordinate<T>) -> Self
+    where
+        T: geo::CoordFloat,
+    {
+        Self {
+            x: Expansion::new(c.x),
+            y: Expansion::new(c.y),
+        }
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.sub(&other.x),
+            y: self.y.sub(&other.y),
+        }
+    }
+
+    fn cross(&self, other: &Self) -> Expansion<T> {
+        self.x.mul(&other.y).sub(&self.y.mul(&other.x))
+    }
+
+    fn dot(&self, other: &Self) -> Expansion<T> {
+        self.x.mul(&other.x).add(&self.y.mul(&other.y))
+    }
+}
+
+fn exact<T>(one: &geo::Line<T>, other: &geo::Line<T>) -> Option<Intersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let p = ExactCoordinate::new(&one.start);
+    let q = ExactCoordinate::new(&other.start);
+    let r = ExactCoordinate::new(&one.end).sub(&p);
+    let s = ExactCoordinate::new(&other.end).sub(&q);
+    let q_minus_p = q.sub(&p);
+    let r_cross_s = r.cross(&s);
+
+    if r_cross_s.signum() == cmp::Ordering::Equal {
+        // parallel, collinear, or at least one of the segments is a point
+        let is_zero = |c: &ExactCoordinate<T>| {
+            c.x.signum() == cmp::Ordering::Equal && c.y.signum() == cmp::Ordering::Equal
+        };
+        let (base, direction) = if !is_zero(&r) { (&p, &r) } else { (&q, &s) };
+        if is_zero(direction) {
+            // two points
+            return if one.start == other.start {
+                Some(Intersection::Intersection(one.start))
+            } else {
+                None
+            };
+        }
+        let on_line = |c: &geo::Coordinate<T>| {
+            ExactCoordinate::new(c).sub(base).cross(direction).signum() == cmp::Ordering::Equal
+        };
+        if ![one.start, one.end, other.start, other.end]
+            .iter()
+            .all(on_line)
+        {
+            return None;
+        }
+        return collinear_overlap(one, other);
+    }
+
+    // t and u are the parameters of the intersection point along one and other,
+    // both are fractions with the denominator r × s
+    let t_numerator = q_minus_p.cross(&s);
+    let u_numerator = q_minus_p.cross(&r);
+    let (t_numerator, u_numerator, denominator) = if r_cross_s.signum() == cmp::Ordering::Less {
+        (t_numerator.neg(), u_numerator.neg(), r_cross_s.neg())
+    } else {
+        (t_numerator, u_numerator, r_cross_s)
+    };
+    let in_range = |numerator: &Expansion<T>| {
+        numerator.signum() != cmp::Ordering::Less
+            && denominator.sub(numerator).signum() != cmp::Ordering::Less
+    };
+    if !(in_range(&t_numerator) && in_range(&u_numerator)) {
+        return None;
+    }
+    // p + t * r = (p * denominator + t_numerator * r) / denominator
+    let x = p.x.mul(&denominator).add(&t_numerator.mul(&r.x));
+    let y = p.y.mul(&denominator).add(&t_numerator.mul(&r.y));
+    Some(Intersection::Intersection(geo::Coordinate {
+        x: divide(&x, &denominator)?,
+        y: divide(&y, &denominator)?,
+    }))
+}
+
+/// The shared part of two collinear segments (either may be a point)
+fn collinear_overlap<T>(one: &geo::Line<T>, other: &geo::Line<T>) -> Option<Intersection<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    // order the end points along a direction of the common supporting line
+    let direction = if one.start != one.end {
+        ExactCoordinate::new(&one.end).sub(&ExactCoordinate::new(&one.start))
+    } else {
+        ExactCoordinate::new(&other.end).sub(&ExactCoordinate::new(&other.start))
+    };
+    let along = |c: &geo::Coordinate<T>| ExactCoordinate::new(c).dot(&direction);
+    let compare = |a: &geo::Coordinate<T>, b: &geo::Coordinate<T>| along(a).sub(&along(b)).signum();
+    let sorted = |l: &geo::Line<T>| {
+        if compare(&l.start, &l.end) == cmp::Ordering::Greater {
+            (l.end, l.start)
+        } else {
+            (l.start, l.end)
+        }
+    };
+    let (one_low, one_high) = sorted(one);
+    let (other_low, other_high) = sorted(other);
+    let low = if compare(&one_low, &other_low) == cmp::Ordering::Less {
+        other_low
+    } else {
+        one_low
+    };
+    let high = if compare(&one_high, &other_high) == cmp::Ordering::Greater {
+        other_high
+    } else {
+        one_high
+    };
+    match compare(&low, &high) {
+        cmp::Ordering::Greater => None,
+        cmp::Ordering::Equal => Some(Intersection::Intersection(low)),
+        cmp::Ordering::Less => Some(Intersection::OverLap(geo::Line::new(low, high))),
+    }
+}
@@ -0,0 +1,209 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Exact floating point expansion arithmetic.
+//!
+//! An expansion represents a number exactly as the sum of non-overlapping floating point
+//! components, sorted by increasing magnitude (Shewchuk, "Adaptive Precision Floating-Point
+//! Arithmetic and Fast Robust Geometric Predicates"). Sums, differences and products of
+//! expansions are exact, as long as no component overflows or underflows.
+
+use num_traits::Float;
+use std::cmp;
+
+/// Returns (a + b, the rounding error of a + b)
+#[inline(always)]
+pub(crate) fn two_sum<T: Float>(a: T, b: T) -> (T, T) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Returns (a * b, the rounding error of a * b)
+#[inline(always)]
+pub(crate) fn two_product<T: Float>(a: T, b: T) -> (T, T) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Expansion<T>(Vec<T>);
+
+impl<T: Float> Expansion<T> {
+    pub(crate) fn new(value: T) -> Self {
+        if value == T::zero() {
+            Self(Vec::new())
+        } else {
+            Self(vec![value])
+        }
+    }
+
+    /// Adds a single value to the expansion
+    fn grow(&self, value: T) -> Self {
+        let mut rv = Vec::with_capacity(self.0.len() + 1);
+        let mut q = value;
+        for component in self.0.iter() {
+            let (sum, error) = two_sum(q, *component);
+            if error != T::zero() {
+                rv.push(error);
+            }
+            q = sum;
+        }
+        if q != T::zero() {
+            rv.push(q);
+        }
+        Self(rv)
+    }
+
+    pub(crate) fn add(&self, other: &Self) -> Self {
+        other.0.iter().fold(self.clone(), |acc, c| acc.grow(*c))
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        Self(self.0.iter().map(|c| -*c).collect())
+    }
+
+    pub(crate) fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    fn scale(&self, value: T) -> Self {
+        let mut rv = Self(Vec::new());
+        for component in self.0.iter() {
+            let (product, error) = two_product(*component, value);
+            rv = rv.grow(error).grow(product);
+        }
+        rv
+    }
+
+    pub(crate) fn mul(&self, other: &Self) -> Self {
+        other
+            .0
+            .iter()
+            .fold(Self(Vec::new()), |acc, c| acc.add(&self.scale(*c)))
+    }
+
+    /// The sign of the exact value
+    pub(crate) fn signum(&self) -> cmp::Ordering {
+        // the largest component decides the sign
+        match self.0.last() {
+            Some(c) if *c > T::zero() => cmp::Ordering::Greater,
+            Some(_) => cmp::Ordering::Less,
+            None => cmp::Ordering::Equal,
+        }
+    }
+
+    /// A floating point approximation of the exact value
+    pub(crate) fn approximate(&self) -> T {
+        self.0.iter().fold(T::zero(), |acc, c| acc + *c)
+    }
+}
+
+/// Returns the floating point value after `value`, towards positive infinity (for `up`) or
+/// negative infinity. Subnormal values are not handled.
+pub(crate) fn next_after<T: Float>(value: T, up: bool) -> T {
+    if value == T::zero() {
+        let tiny = T::min_positive_value();
+        return if up { tiny } else { -tiny };
+    }
+    let (mantissa, exponent, sign) = value.integer_decode();
+    let two = T::one() + T::one();
+    let ulp = two.powi(exponent as i32);
+    // moving towards zero from the first value of a binade uses the ulp of the binade below
+    let towards_zero = (sign > 0) != up;
+    let at_binade_start = mantissa.is_power_of_two();
+    let step = if towards_zero && at_binade_start {
+        ulp / two
+    } else {
+        ulp
+    };
+    if up {
+        value + step
+    } else {
+        value - step
+    }
+}
+
+/// Returns `numerator / denominator` correctly rounded (to nearest, ties to even).
+/// Returns None if the denominator is zero.
+pub(crate) fn divide<T: Float>(numerator: &Expansion<T>, denominator: &Expansion<T>) -> Option<T> {
+    let (numerator, denominator) = match denominator.signum() {
+        cmp::Ordering::Equal => return None,
+        cmp::Ordering::Less => (numerator.neg(), denominator.neg()),
+        cmp::Ordering::Greater => (numerator.clone(), denominator.clone()),
+    };
+    let two = T::one() + T::one();
+    let mut q = numerator.approximate() / denominator.approximate();
+    if !q.is_finite() {
+        return Some(q);
+    }
+    // the sign of numerator - (q + offset) * denominator, with the sum done exactly
+    let residual = |q: T, offset: T| {
+        numerator
+            .sub(&Expansion::new(q).grow(offset).mul(&denominator))
+            .signum()
+    };
+    let is_even = |q: T| q.integer_decode().0 & 1 == 0;
+    // a handful of steps is enough, the approximation is off by a few ulps at most
+    for _ in 0..64 {
+        let up = next_after(q, true);
+        let down = next_after(q, false);
+        match residual(q, (up - q) / two) {
+            cmp::Ordering::Greater => {
+                q = up;
+                continue;
+            }
+            cmp::Ordering::Equal if !is_even(q) => return Some(up),
+            _ => (),
+        }
+        match residual(q, (down - q) / two) {
+            cmp::Ordering::Less => q = down,
+            cmp::Ordering::Equal if !is_even(q) => return Some(down),
+            _ => return Some(q),
+        }
+    }
+    Some(q)
+}
@@ -0,0 +1,341 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Classification of the faces of an arrangement by fill rules.
+//!
+//! [`classify_faces()`] nodes a set of directed segments, traces the faces of the resulting
+//! planar graph and computes the winding number of every bounded face with respect to the
+//! input segments. Crossing a segment from its right to its left side increases the winding
+//! number by one, the unbounded face has winding number zero.
+//! A [`FillRule`] then decides which faces are filled, [`filled_faces()`] returns just those.
+
+use crate::algorithm::AlgorithmData;
+use crate::list::cluster_points;
+use crate::nesting::ring_contains;
+use crate::noding::node_lines;
+use crate::{IntersectError, Tolerance};
+use num_traits::Float;
+use std::cmp;
+use std::collections::VecDeque;
+
+/// Decides which faces of an arrangement are filled, given their winding numbers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// Faces with an odd winding number are filled
+    #[default]
+    EvenOdd,
+    /// Faces with a winding number other than zero are filled
+    NonZero,
+}
+
+impl FillRule {
+    /// Returns true if a face with winding number `winding` is filled
+    /// ```
+    /// # use intersect2d::fill::FillRule;
+    /// assert!(FillRule::EvenOdd.is_filled(-1));
+    /// assert!(!FillRule::EvenOdd.is_filled(2));
+    /// assert!(FillRule::NonZero.is_filled(2));
+    /// assert!(!FillRule::NonZero.is_filled(0));
+    /// ```
+    pub fn is_filled(self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// A bounded face of an arrangement.
+/// The exterior ring of `polygon` is counter clockwise, the interior rings are the clockwise
+/// outlines of the parts of the arrangement inside the face that aren't connected to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Face<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub polygon: geo::Polygon<T>,
+    /// The winding number of the face with respect to the input segments
+    pub winding: i32,
+}
+
+impl<T> Face<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns true if `rule` fills the face
+    pub fn is_filled(&self, rule: FillRule) -> bool {
+        rule.is_filled(self.winding)
+    }
+}
+
+/// twice the signed area of a closed vertex cycle, positive if counter clockwise
+fn signed_area<T>(cycle: &[geo::Coordinate<T>]) -> T
+where
+    T: Float + geo::CoordFloat,
+{
+    let n = cycle.len();
+    (0..n).fold(T::zero(), |sum, i| {
+        let (a, b) = (cycle[i], cycle[(i + 1) % n]);
+        sum + a.x * b.y - b.x * a.y
+    })
+}
+
+fn find(parent: &mut [usize], mut v: usize) -> usize {
+    while parent[v] != v {
+        parent[v] = parent[parent[v]];
+        v = parent[v];
+    }
+    v
+}
+
+/// Returns the bounded faces of the arrangement of the directed segments `lines`, with their
+/// winding numbers. The faces are reported in no particular order.
+/// Parts of the input that don't bound a face (e.g. dangling segments) don't contribute.
+/// ```
+/// # use intersect2d::fill::{classify_faces, FillRule};
+/// // two overlapping counter clockwise squares
+/// let square = |x: f64, y: f64| [
+///     [x, y, x + 10., y],
+///     [x + 10., y, x + 10., y + 10.],
+///     [x + 10., y + 10., x, y + 10.],
+///     [x, y + 10., x, y],
+/// ];
+/// let mut lines = intersect2d::to_lines::<_, f64>(&square(0., 0.));
+/// lines.extend(intersect2d::to_lines::<_, f64>(&square(5., 5.)));
+/// let faces = classify_faces(&lines)?;
+/// let mut windings: Vec<_> = faces.iter().map(|f| f.winding).collect();
+/// windings.sort_unstable();
+/// assert_eq!(windings, vec![1, 1, 2]);
+/// assert_eq!(faces.iter().filter(|f| f.is_filled(FillRule::EvenOdd)).count(), 2);
+/// assert_eq!(faces.iter().filter(|f| f.is_filled(FillRule::NonZero)).count(), 3);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn classify_faces<T>(lines: &[geo::Line<T>]) -> Result<Vec<Face<T>>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+    let results = AlgorithmData::<T>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?
+        .compute()?;
+    // node the input lines themselves, the direction of the pieces matters
+    let pieces = node_lines(lines, results.as_slice(), &Tolerance::default());
+    let ends: Vec<geo::Coordinate<T>> = pieces
+        .iter()
+        .flat_map(|p| [p.line.start, p.line.end])
+        .collect();
+    let (ids, vertices) = cluster_points(&ends, &Tolerance::default());
+
+    // The undirected edges (low vertex, high vertex) and the change of the winding number
+    // when crossing the edge from the right to the left side of low -> high
+    let mut deltas = ahash::AHashMap::<(usize, usize), i32>::default();
+    for piece in 0..pieces.len() {
+        let (a, b) = (ids[2 * piece], ids[2 * piece + 1]);
+        if a != b {
+            *deltas.entry((a.min(b), a.max(b))).or_insert(0) += if a < b { 1 } else { -1 };
+        }
+    }
+    let mut edges: Vec<((usize, usize), i32)> = deltas.into_iter().collect();
+    edges.sort_unstable();
+
+    // half edge 2k is low -> high of edge k, 2k + 1 is high -> low
+    let origin = |h: usize| {
+        let (low, high) = edges[h / 2].0;
+        if h.is_multiple_of(2) {
+            low
+        } else {
+            high
+        }
+    };
+    let mut outgoing = vec![Vec::<usize>::new(); vertices.len()];
+    for h in 0..2 * edges.len() {
+        outgoing[origin(h)].push(h);
+    }
+    let mut slot = vec![0; 2 * edges.len()];
+    for (v, out) in outgoing.iter_mut().enumerate() {
+        // counter clockwise around the vertex
+        let angle = |h: &usize| {
+            let d = vertices[origin(*h ^ 1)] - vertices[v];
+            d.y.atan2(d.x)
+        };
+        out.sort_by(|a, b| {
+            angle(a)
+                .partial_cmp(&angle(b))
+                .unwrap_or(cmp::Ordering::Equal)
+        });
+        for (i, h) in out.iter().enumerate() {
+            slot[*h] = i;
+        }
+    }
+    // the next half edge with the same face on its left side
+    let next = |h: usize| {
+        let out = &outgoing[origin(h ^ 1)];
+        out[(slot[h ^ 1] + out.len() - 1) % out.len()]
+    };
+
+    let mut cycle_of = vec![usize::MAX; 2 * edges.len()];
+    let mut cycles = Vec::<Vec<usize>>::new();
+    for first in 0..2 * edges.len() {
+        if cycle_of[first] != usize::MAX {
+            continue;
+        }
+        let mut cycle = Vec::new();
+        let mut h = first;
+        while cycle_of[h] == usize::MAX {
+            cycle_of[h] = cycles.len();
+            cycle.push(origin(h));
+            h = next(h);
+        }
+        cycles.push(cycle);
+    }
+    let coordinates = |cycle: &[usize]| -> Vec<geo::Coordinate<T>> {
+        cycle.iter().map(|v| vertices[*v]).collect()
+    };
+    let ring = |cycle: &[usize]| -> geo::LineString<T> {
+        let mut c = coordinates(cycle);
+        c.push(c[0]);
+        geo::LineString(c)
+    };
+    let areas: Vec<T> = cycles
+        .iter()
+        .map(|c| signed_area(&coordinates(c)))
+        .collect();
+
+    // the connected components of the graph
+    let mut parent: Vec<usize> = (0..vertices.len()).collect();
+    for ((a, b), _) in edges.iter() {
+        let (a, b) = (find(&mut parent, *a), find(&mut parent, *b));
+        parent[a] = b;
+    }
+    let component: Vec<usize> = cycles.iter().map(|c| find(&mut parent, c[0])).collect();
+
+    // Counter clockwise cycles are the outlines of bounded faces, the others are the outer
+    // outlines of a component, they belong to the smallest face of another component around
+    // them, or to the unbounded face
+    let unbounded = cycles.len();
+    let bounded: Vec<usize> = (0..cycles.len())
+        .filter(|c| areas[*c] > T::zero())
+        .collect();
+    let mut face_of = vec![unbounded; cycles.len()];
+    let mut rings: Vec<geo::LineString<T>> = vec![geo::LineString(Vec::new()); cycles.len()];
+    for c in bounded.iter() {
+        face_of[*c] = *c;
+        rings[*c] = ring(&cycles[*c]);
+    }
+    for c in (0..cycles.len()).filter(|c| areas[*c] <= T::zero()) {
+        let point = vertices[cycles[c][0]];
+        face_of[c] = bounded
+            .iter()
+            .filter(|b| component[**b] != component[c] && ring_contains(&rings[**b], &point))
+            .min_by(|a, b| {
+                areas[**a]
+                    .partial_cmp(&areas[**b])
+                    .unwrap_or(cmp::Ordering::Equal)
+            })
+            .copied()
+            .unwrap_or(unbounded);
+    }
+
+    // Walk from the unbounded face to its neighbours across the edges
+    let mut neighbours = vec![Vec::<(usize, i32)>::new(); cycles.len() + 1];
+    for (k, (_, delta)) in edges.iter().enumerate() {
+        let (left, right) = (face_of[cycle_of[2 * k]], face_of[cycle_of[2 * k + 1]]);
+        if left != right {
+            neighbours[right].push((left, *delta));
+            neighbours[left].push((right, -*delta));
+        }
+    }
+    let mut winding = vec![None; cycles.len() + 1];
+    winding[unbounded] = Some(0);
+    let mut queue = VecDeque::from(vec![unbounded]);
+    while let Some(face) = queue.pop_front() {
+        let w = winding[face].unwrap();
+        for (other, delta) in neighbours[face].iter() {
+            if winding[*other].is_none() {
+                winding[*other] = Some(w + delta);
+                queue.push_back(*other);
+            }
+        }
+    }
+
+    let mut rv = Vec::<Face<T>>::with_capacity(bounded.len());
+    for c in bounded.into_iter() {
+        let winding = winding[c].ok_or_else(|| {
+            IntersectError::InternalError(format!("Face {} is not connected to the others", c))
+        })?;
+        let interiors = (0..cycles.len())
+            .filter(|h| face_of[*h] == c && *h != c && areas[*h] < T::zero())
+            .map(|h| ring(&cycles[h]))
+            .collect();
+        rv.push(Face {
+            polygon: geo::Polygon::new(rings[c].clone(), interiors),
+            winding,
+        });
+    }
+    Ok(rv)
+}
+
+/// Returns the faces of the arrangement of the directed segments `lines` that are filled
+/// according to `rule`, see [`classify_faces()`]
+pub fn filled_faces<T>(
+    lines: &[geo::Line<T>],
+    rule: FillRule,
+) -> Result<Vec<geo::Polygon<T>>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    Ok(classify_faces(lines)?
+        .into_iter()
+        .filter(|f| f.is_filled(rule))
+        .map(|f| f.polygon)
+        .collect())
+}
@@ -0,0 +1,278 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Fuzzing helpers, enabled by the `fuzz` feature.
+//!
+//! [`FuzzInput::decode()`] turns arbitrary bytes (as handed out by cargo-fuzz) into a set of
+//! segments and options, and [`shrink()`] reduces a failing input to a minimal set of
+//! segments with simple coordinates. A fuzz target in a user repository then only needs a
+//! couple of lines:
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| {
+//!     let input = intersect2d::fuzz::FuzzInput::<f64>::decode(data);
+//!     let _ = input.run();
+//! });
+//! ```
+//!
+//! The byte format is compact: the first byte holds the options, every following group of
+//! 8 bytes is one segment made of four little endian `i16` coordinates (x1, y1, x2, y2). The
+//! coordinates are scaled by 1/4 (bit 1 of the option byte clear) or by 1/4096 (bit 1 set),
+//! the coarse grid produces lots of shared end points, overlaps and crossings at grid points.
+//! Trailing bytes that don't make up a whole segment are ignored.
+
+use crate::algorithm::AlgorithmData;
+use crate::IntersectError;
+use num_traits::Float;
+
+const IGNORE_END_POINTS: u8 = 1;
+const FINE_GRID: u8 = 2;
+
+/// A set of segments and options decoded from bytes
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuzzInput<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub lines: Vec<geo::Line<T>>,
+    pub ignore_end_point_intersections: bool,
+    /// true if the coordinates are on the 1/4096 grid, false for the 1/4 grid
+    pub fine_grid: bool,
+}
+
+impl<T> FuzzInput<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn scale(fine_grid: bool) -> T {
+        T::from(if fine_grid { 4096.0 } else { 4.0 }).unwrap()
+    }
+
+    /// Decodes any sequence of bytes into an input, see the module documentation for the
+    /// format. An empty slice decodes to an empty set of segments.
+    pub fn decode(bytes: &[u8]) -> Self {
+        let options = bytes.first().copied().unwrap_or(0);
+        let fine_grid = options & FINE_GRID != 0;
+        let scale = Self::scale(fine_grid);
+        let value = |b: &[u8]| T::from(i16::from_le_bytes([b[0], b[1]])).unwrap() / scale;
+        let lines = bytes
+            .get(1..)
+            .unwrap_or(&[])
+            .chunks_exact(8)
+            .map(|c| {
+                geo::Line::new(
+                    geo::Coordinate {
+                        x: value(&c[0..2]),
+                        y: value(&c[2..4]),
+                    },
+                    geo::Coordinate {
+                        x: value(&c[4..6]),
+                        y: value(&c[6..8]),
+                    },
+                )
+            })
+            .collect();
+        Self {
+            lines,
+            ignore_end_point_intersections: options & IGNORE_END_POINTS != 0,
+            fine_grid,
+        }
+    }
+
+    /// Encodes the input into bytes, `decode(encode(input)) == input`.
+    /// Returns an error if a coordinate isn't on the grid of the input.
+    /// ```
+    /// # use intersect2d::fuzz::FuzzInput;
+    /// let input = FuzzInput::<f64>::decode(&[1, 4, 0, 0, 0, 8, 0, 252, 255]);
+    /// assert_eq!(input.lines, intersect2d::to_lines(&[[1., 0., 2., -1.]]));
+    /// assert!(input.ignore_end_point_intersections);
+    /// assert_eq!(input.encode()?, vec![1, 4, 0, 0, 0, 8, 0, 252, 255]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn encode(&self) -> Result<Vec<u8>, IntersectError> {
+        let mut rv = Vec::with_capacity(1 + 8 * self.lines.len());
+        let mut options = 0;
+        if self.ignore_end_point_intersections {
+            options |= IGNORE_END_POINTS;
+        }
+        if self.fine_grid {
+            options |= FINE_GRID;
+        }
+        rv.push(options);
+        let scale = Self::scale(self.fine_grid);
+        for a_line in self.lines.iter() {
+            for v in [a_line.start.x, a_line.start.y, a_line.end.x, a_line.end.y].iter() {
+                let scaled = *v * scale;
+                match scaled.to_i16() {
+                    Some(i) if T::from(i).unwrap() == scaled => {
+                        rv.extend_from_slice(&i.to_le_bytes())
+                    }
+                    _ => {
+                        return Err(IntersectError::InvalidData(format!(
+                            "The coordinate {:?} is not on the fuzz input grid",
+                            v
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(rv)
+    }
+
+    /// Runs the sweep-line on the input
+    #[allow(clippy::type_complexity)]
+    pub fn run(&self) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError>
+    where
+        T: num_traits::ToPrimitive,
+    {
+        Ok(AlgorithmData::<T>::default()
+            .with_ignore_end_point_intersections(self.ignore_end_point_intersections)?
+            .with_ref_lines(self.lines.iter())?
+            .compute()?
+            .collect())
+    }
+}
+
+/// Reduces a failing input to a (locally) minimal one. `fails` must return true for
+/// `input`, and is called repeatedly with smaller candidates. The returned input still fails.
+///
+/// First segments are removed, in shrinking chunks (delta debugging), then each remaining
+/// coordinate is simplified: rounded to an integer, and moved towards zero, as long as the
+/// input keeps failing.
+/// ```
+/// # use intersect2d::fuzz::{shrink, FuzzInput};
+/// let input = FuzzInput {
+///     lines: intersect2d::to_lines::<_, f64>(&[
+///         [0., 0., 10., 10.],
+///         [3., 3., 7., 4.],
+///         [0.25, 10., 10., 0.75],
+///         [20., 20., 30., 20.],
+///     ]),
+///     ignore_end_point_intersections: true,
+///     fine_grid: false,
+/// };
+/// // "fails" whenever there's a crossing
+/// let minimal = shrink(&input, |i| !i.run().unwrap().is_empty());
+/// assert_eq!(minimal.lines.len(), 2);
+/// ```
+pub fn shrink<T, F>(input: &FuzzInput<T>, mut fails: F) -> FuzzInput<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    F: FnMut(&FuzzInput<T>) -> bool,
+{
+    let mut current = input.clone();
+
+    // remove chunks of segments, halving the chunk size when nothing can be removed
+    let mut chunk = (current.lines.len() / 2).max(1);
+    while !current.lines.is_empty() {
+        let mut removed = false;
+        let mut start = 0;
+        while start < current.lines.len() {
+            let end = (start + chunk).min(current.lines.len());
+            let mut candidate = current.clone();
+            let _ = candidate.lines.drain(start..end);
+            if fails(&candidate) {
+                current = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed {
+            if chunk == 1 {
+                break;
+            }
+            chunk /= 2;
+        }
+    }
+
+    // simplify the coordinates
+    let two = T::one() + T::one();
+    for l in 0..current.lines.len() {
+        for c in 0..4 {
+            loop {
+                let value = coordinate(&current.lines[l], c);
+                let simpler = [value.trunc(), (value / two).trunc(), T::zero()];
+                let mut changed = false;
+                for s in simpler.iter() {
+                    if *s == value {
+                        continue;
+                    }
+                    let mut candidate = current.clone();
+                    set_coordinate(&mut candidate.lines[l], c, *s);
+                    if fails(&candidate) {
+                        current = candidate;
+                        changed = true;
+                        break;
+                    }
+                }
+                if !changed {
+                    break;
+                }
+            }
+        }
+    }
+    current
+}
+
+fn coordinate<T: Float + geo::CoordFloat>(line: &geo::Line<T>, index: usize) -> T {
+    match index {
+        0 => line.start.x,
+        1 => line.start.y,
+        2 => line.end.x,
+        _ => line.end.y,
+    }
+}
+
+fn set_coordinate<T: Float + geo::CoordFloat>(line: &mut geo::Line<T>, index: usize, value: T) {
+    match index {
+        0 => line.start.x = value,
+        1 => line.start.y = value,
+        2 => line.end.x = value,
+        _ => line.end.y = value,
+    }
+}
@@ -0,0 +1,251 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Seedable generators of the classic hard cases for line segment intersection.
+//!
+//! Every generator is deterministic: the same `size` and `seed` always produce the same
+//! segments, on every platform and in every version of the crate, so the cases can be shared
+//! between robustness test suites.
+//! * [`concurrent()`]: all segments passing through a single point
+//! * [`lattice()`]: a grid of horizontal and vertical pieces sharing their end points
+//! * [`collinear_stack()`]: overlapping segments on a single supporting line
+//! * [`near_parallel_fan()`]: long segments with almost identical directions
+
+use num_traits::Float;
+
+/// A small, portable, pseudo random generator (splitmix64)
+struct Random(u64);
+
+impl Random {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// returns a value in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// returns a value in `[low, high)`
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+}
+
+fn line<T: Float + geo::CoordFloat>(x1: f64, y1: f64, x2: f64, y2: f64) -> geo::Line<T> {
+    geo::Line::new(
+        geo::Coordinate {
+            x: T::from(x1).unwrap(),
+            y: T::from(y1).unwrap(),
+        },
+        geo::Coordinate {
+            x: T::from(x2).unwrap(),
+            y: T::from(y2).unwrap(),
+        },
+    )
+}
+
+/// Returns `size` segments in random directions, all passing exactly through the same point.
+/// The common point is an end point of some of the segments and lies in the interior of the
+/// others. The directions are distinct small integer vectors, so every end point is exactly
+/// representable and no two segments are collinear. At most 256 segments are returned.
+/// ```
+/// # use intersect2d::generators::concurrent;
+/// let lines = concurrent::<f64>(10, 42);
+/// assert_eq!(lines.len(), 10);
+/// assert_eq!(lines, concurrent::<f64>(10, 42));
+/// ```
+pub fn concurrent<T>(size: usize, seed: u64) -> Vec<geo::Line<T>>
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut random = Random(seed);
+    let mut small =
+        |low: i64, high: i64| low + (random.next_u64() % (high - low + 1) as u64) as i64;
+    let (cx, cy) = (
+        small(-100, 100) as f64 * 0.25,
+        small(-100, 100) as f64 * 0.25,
+    );
+    let gcd = |mut a: i64, mut b: i64| {
+        while b != 0 {
+            let t = a % b;
+            a = b;
+            b = t;
+        }
+        a.abs()
+    };
+    let mut directions = Vec::<(i64, i64)>::new();
+    let mut rv = Vec::<geo::Line<T>>::new();
+    for i in 0..size.min(256) {
+        let (dx, dy) = loop {
+            let (dx, dy) = (small(-16, 16), small(-16, 16));
+            // primitive vectors, each direction used only once
+            if (dx != 0 || dy != 0)
+                && gcd(dx, dy) == 1
+                && !directions.contains(&(dx, dy))
+                && !directions.contains(&(-dx, -dy))
+            {
+                directions.push((dx, dy));
+                break (dx as f64, dy as f64);
+            }
+        };
+        let before = if i % 4 == 0 { 0.0 } else { small(1, 8) as f64 };
+        let after = small(1, 8) as f64;
+        rv.push(line(
+            cx - dx * before,
+            cy - dy * before,
+            cx + dx * after,
+            cy + dy * after,
+        ));
+    }
+    rv
+}
+
+/// Returns a `size` × `size` lattice of unit cells, made of `2 * size * (size + 1)`
+/// horizontal and vertical pieces meeting at their end points. The seed decides the order
+/// and the direction of the pieces.
+/// ```
+/// # use intersect2d::generators::lattice;
+/// assert_eq!(lattice::<f64>(3, 1).len(), 24);
+/// ```
+pub fn lattice<T>(size: usize, seed: u64) -> Vec<geo::Line<T>>
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut random = Random(seed);
+    let mut rv = Vec::with_capacity(2 * size * (size + 1));
+    for i in 0..=size {
+        for j in 0..size {
+            let (i, j) = (i as f64, j as f64);
+            rv.push([i, j, i, j + 1.]);
+            rv.push([j, i, j + 1., i]);
+        }
+    }
+    // Fisher-Yates shuffle, and random directions
+    for i in (1..rv.len()).rev() {
+        let j = (random.next_u64() % (i as u64 + 1)) as usize;
+        rv.swap(i, j);
+    }
+    rv.into_iter()
+        .map(|c| {
+            if random.next_u64() & 1 == 0 {
+                line(c[0], c[1], c[2], c[3])
+            } else {
+                line(c[2], c[3], c[0], c[1])
+            }
+        })
+        .collect()
+}
+
+/// Returns `size` overlapping segments on a single supporting line in a random direction.
+/// Every segment overlaps the middle of the stack.
+/// ```
+/// # use intersect2d::generators::collinear_stack;
+/// # use intersect2d::collinear::collinear_bundles;
+/// let lines = collinear_stack::<f64>(8, 7);
+/// let bundles = collinear_bundles(&lines, &intersect2d::Tolerance::default())?;
+/// assert_eq!(bundles.len(), 1);
+/// assert_eq!(bundles[0].lines.len(), 8);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn collinear_stack<T>(size: usize, seed: u64) -> Vec<geo::Line<T>>
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut random = Random(seed);
+    // integer directions keep the end points exactly on the supporting line
+    let (dx, dy) = loop {
+        let (dx, dy) = (
+            (random.next_u64() % 9) as f64 - 4.,
+            (random.next_u64() % 9) as f64 - 4.,
+        );
+        if dx != 0.0 || dy != 0.0 {
+            break (dx, dy);
+        }
+    };
+    let (ox, oy) = (
+        (random.next_u64() % 21) as f64 - 10.,
+        (random.next_u64() % 21) as f64 - 10.,
+    );
+    (0..size)
+        .map(|_| {
+            let t0 = -((random.next_u64() % 16) as f64) - 1.;
+            let t1 = (random.next_u64() % 16) as f64 + 1.;
+            let (t0, t1) = if random.next_u64() & 1 == 0 {
+                (t0, t1)
+            } else {
+                (t1, t0)
+            };
+            line(ox + dx * t0, oy + dy * t0, ox + dx * t1, oy + dy * t1)
+        })
+        .collect()
+}
+
+/// Returns `size` long segments, starting spread out along a short base line and ending with
+/// directions that differ by tiny angles, so that most pairs are nearly parallel and cross
+/// at shallow angles.
+/// ```
+/// # use intersect2d::generators::near_parallel_fan;
+/// let lines = near_parallel_fan::<f64>(16, 3);
+/// assert_eq!(lines.len(), 16);
+/// ```
+pub fn near_parallel_fan<T>(size: usize, seed: u64) -> Vec<geo::Line<T>>
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut random = Random(seed);
+    (0..size)
+        .map(|_| {
+            let y0 = random.range(-1e-6, 1e-6);
+            let slope = random.range(-1e-9, 1e-9);
+            let length = random.range(500., 1000.);
+            line(0., y0, length, y0 + slope * length)
+        })
+        .collect()
+}
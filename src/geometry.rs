@@ -0,0 +1,360 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Intersections between any two `geo::Geometry` values.
+//!
+//! Every geometry is decomposed into points and line segments with [`decompose()`], each piece
+//! remembering its [`GeometryLocation`]. The segments of the two geometries are intersected
+//! with [`intersections_between_sets()`](crate::between::intersections_between_sets), the
+//! points are tested against the pieces of the other geometry one by one.
+//! [`GeometryIntersections`] makes this available as a method on `geo::Geometry`.
+
+use crate::between::intersections_between_sets;
+use crate::order::sweep_order;
+use crate::{intersect_line_point, ulps_eq_c, IntersectError};
+use num_traits::Float;
+
+/// Where a point or a segment of a decomposed geometry comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GeometryLocation {
+    /// The index of the simple geometry (point, line, line string, polygon, rect or triangle).
+    /// Members of multi geometries and geometry collections are counted depth first, a
+    /// geometry that isn't a collection is member 0.
+    pub member: usize,
+    /// The ring of a polygon, 0 is the exterior ring, 1.. the interior rings.
+    /// Always 0 for other geometries.
+    pub ring: usize,
+    /// The index of the segment in the line string or ring, `None` for points
+    pub segment: Option<usize>,
+}
+
+/// The points and line segments of a geometry, see [`decompose()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decomposition<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub points: Vec<(geo::Coordinate<T>, GeometryLocation)>,
+    pub lines: Vec<(geo::Line<T>, GeometryLocation)>,
+}
+
+/// A point where the two geometries meet.
+/// `a` and `b` are the sorted locations of the involved pieces of the first and of the
+/// second geometry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeometryIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    pub a: Vec<GeometryLocation>,
+    pub b: Vec<GeometryLocation>,
+}
+
+/// Splits a geometry into points and line segments.
+/// Zero length segments are skipped, rects and triangles are decomposed like the polygons
+/// they describe.
+/// ```
+/// # use intersect2d::geometry::{decompose, GeometryLocation};
+/// let square = geo::Geometry::Rect(geo::Rect::new((0., 0.), (10., 10.)));
+/// let d = decompose(&square);
+/// assert!(d.points.is_empty());
+/// assert_eq!(d.lines.len(), 4);
+/// assert_eq!(d.lines[3].1, GeometryLocation { member: 0, ring: 0, segment: Some(3) });
+/// ```
+pub fn decompose<T>(geometry: &geo::Geometry<T>) -> Decomposition<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut rv = Decomposition {
+        points: Vec::new(),
+        lines: Vec::new(),
+    };
+    let mut member = 0_usize;
+    decompose_(geometry, &mut member, &mut rv);
+    rv
+}
+
+fn push_ring<T>(
+    points: &[geo::Coordinate<T>],
+    member: usize,
+    ring: usize,
+    rv: &mut Decomposition<T>,
+) where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    for (segment, w) in points.windows(2).enumerate() {
+        if !ulps_eq_c(&w[0], &w[1]) {
+            rv.lines.push((
+                geo::Line::new(w[0], w[1]),
+                GeometryLocation {
+                    member,
+                    ring,
+                    segment: Some(segment),
+                },
+            ));
+        }
+    }
+}
+
+fn push_polygon<T>(polygon: &geo::Polygon<T>, member: usize, rv: &mut Decomposition<T>)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    for (ring, a_ring) in std::iter::once(polygon.exterior())
+        .chain(polygon.interiors().iter())
+        .enumerate()
+    {
+        push_ring(&a_ring.0, member, ring, rv);
+    }
+}
+
+fn decompose_<T>(geometry: &geo::Geometry<T>, member: &mut usize, rv: &mut Decomposition<T>)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    match geometry {
+        geo::Geometry::Point(p) => {
+            rv.points.push((
+                p.0,
+                GeometryLocation {
+                    member: *member,
+                    ring: 0,
+                    segment: None,
+                },
+            ));
+            *member += 1;
+        }
+        geo::Geometry::Line(l) => {
+            push_ring(&[l.start, l.end], *member, 0, rv);
+            *member += 1;
+        }
+        geo::Geometry::LineString(ls) => {
+            push_ring(&ls.0, *member, 0, rv);
+            *member += 1;
+        }
+        geo::Geometry::Polygon(p) => {
+            push_polygon(p, *member, rv);
+            *member += 1;
+        }
+        geo::Geometry::Rect(r) => {
+            push_polygon(&r.to_polygon(), *member, rv);
+            *member += 1;
+        }
+        geo::Geometry::Triangle(t) => {
+            push_polygon(&t.to_polygon(), *member, rv);
+            *member += 1;
+        }
+        geo::Geometry::MultiPoint(mp) => {
+            for p in mp.0.iter() {
+                decompose_(&geo::Geometry::Point(*p), member, rv);
+            }
+        }
+        geo::Geometry::MultiLineString(mls) => {
+            for ls in mls.0.iter() {
+                push_ring(&ls.0, *member, 0, rv);
+                *member += 1;
+            }
+        }
+        geo::Geometry::MultiPolygon(mp) => {
+            for p in mp.0.iter() {
+                push_polygon(p, *member, rv);
+                *member += 1;
+            }
+        }
+        geo::Geometry::GeometryCollection(gc) => {
+            for g in gc.0.iter() {
+                decompose_(g, member, rv);
+            }
+        }
+    }
+}
+
+/// Returns the points where the geometries `a` and `b` meet, sorted in sweep-line order.
+/// Touching counts as meeting, intersections within one of the geometries are not reported.
+/// ```
+/// # use intersect2d::geometry::{geometry_intersections, GeometryLocation};
+/// let square = geo::Geometry::Rect(geo::Rect::new((0., 0.), (10., 10.)));
+/// let path = geo::Geometry::LineString(vec![(5., 5.), (15., 5.)].into());
+/// let rv = geometry_intersections(&square, &path)?;
+/// assert_eq!(rv.len(), 1);
+/// assert_eq!(rv[0].position, geo::Coordinate { x: 10., y: 5. });
+/// assert_eq!(rv[0].a, vec![GeometryLocation { member: 0, ring: 0, segment: Some(0) }]);
+/// assert_eq!(rv[0].b, vec![GeometryLocation { member: 0, ring: 0, segment: Some(0) }]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn geometry_intersections<T>(
+    a: &geo::Geometry<T>,
+    b: &geo::Geometry<T>,
+) -> Result<Vec<GeometryIntersection<T>>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let (a, b) = (decompose(a), decompose(b));
+    if a.points
+        .iter()
+        .chain(b.points.iter())
+        .any(|(p, _)| !(p.x.is_finite() && p.y.is_finite()))
+    {
+        return Err(IntersectError::InvalidData(
+            "Can't check for intersections on non-finite data".to_string(),
+        ));
+    }
+
+    let lines_a: Vec<geo::Line<T>> = a.lines.iter().map(|l| l.0).collect();
+    let lines_b: Vec<geo::Line<T>> = b.lines.iter().map(|l| l.0).collect();
+    let mut hits: Vec<GeometryIntersection<T>> =
+        intersections_between_sets(&lines_a, &lines_b, false)?
+            .into_iter()
+            .map(|i| GeometryIntersection {
+                position: i.position,
+                a: i.a.iter().map(|l| a.lines[*l].1).collect(),
+                b: i.b.iter().map(|l| b.lines[*l].1).collect(),
+            })
+            .collect();
+
+    // points against everything in the other geometry
+    let touches = |p: &geo::Coordinate<T>, other: &Decomposition<T>| -> Vec<GeometryLocation> {
+        other
+            .points
+            .iter()
+            .filter(|(q, _)| ulps_eq_c(p, q))
+            .map(|(_, location)| *location)
+            .chain(
+                other
+                    .lines
+                    .iter()
+                    .filter(|(l, _)| intersect_line_point(l, p).is_some())
+                    .map(|(_, location)| *location),
+            )
+            .collect()
+    };
+    for (p, location) in a.points.iter() {
+        let other = touches(p, &b);
+        if !other.is_empty() {
+            hits.push(GeometryIntersection {
+                position: *p,
+                a: vec![*location],
+                b: other,
+            });
+        }
+    }
+    for (p, location) in b.points.iter() {
+        // point to point hits were found above
+        let other: Vec<_> = touches(p, &a)
+            .into_iter()
+            .filter(|l| l.segment.is_some())
+            .collect();
+        if !other.is_empty() {
+            hits.push(GeometryIntersection {
+                position: *p,
+                a: other,
+                b: vec![*location],
+            });
+        }
+    }
+
+    // merge the hits at the same position
+    hits.sort_by(|x, y| sweep_order(&x.position, &y.position));
+    let mut rv = Vec::<GeometryIntersection<T>>::with_capacity(hits.len());
+    for hit in hits.into_iter() {
+        match rv.last_mut() {
+            Some(last) if ulps_eq_c(&last.position, &hit.position) => {
+                last.a.extend(hit.a);
+                last.b.extend(hit.b);
+            }
+            _ => rv.push(hit),
+        }
+    }
+    for i in rv.iter_mut() {
+        i.a.sort_unstable();
+        i.a.dedup();
+        i.b.sort_unstable();
+        i.b.dedup();
+    }
+    Ok(rv)
+}
+
+/// Intersections between `geo::Geometry` values, see [`geometry_intersections()`]
+pub trait GeometryIntersections<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns the points where `self` meets `other`, sorted in sweep-line order.
+    /// ```
+    /// # use intersect2d::geometry::GeometryIntersections;
+    /// let a = geo::Geometry::Line(geo::Line::from([(0., 0.), (10., 10.)]));
+    /// let b = geo::Geometry::MultiPoint(vec![(5., 5.), (5., 6.)].into());
+    /// let rv = a.intersections_with(&b)?;
+    /// assert_eq!(rv.len(), 1);
+    /// assert_eq!(rv[0].b[0].member, 0);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    fn intersections_with(
+        &self,
+        other: &geo::Geometry<T>,
+    ) -> Result<Vec<GeometryIntersection<T>>, IntersectError>;
+}
+
+impl<T> GeometryIntersections<T> for geo::Geometry<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn intersections_with(
+        &self,
+        other: &geo::Geometry<T>,
+    ) -> Result<Vec<GeometryIntersection<T>>, IntersectError> {
+        geometry_intersections(self, other)
+    }
+}
@@ -0,0 +1,163 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Hooks into the sweep-line events.
+//!
+//! [`AlgorithmData::compute_with_handler()`](crate::algorithm::AlgorithmData::compute_with_handler)
+//! runs the regular sweep and calls a [`SweepEventHandler`] when a segment enters or leaves
+//! the sweep-line status and when an intersection point is reported. The handler gets a
+//! [`SweepContext`] with read access to the status, i.e. the active segments ordered left to
+//! right along the sweep-line, so overlays, area accumulation or clipping can be built on
+//! top of the sweep without changing it.
+//!
+//! At every event point the segments ending there are reported first, while they are still
+//! in the status, then the segments starting there and finally the intersection point, once
+//! the status is updated.
+
+use crate::segments::SegmentStore;
+use crate::status::SweepStatus;
+use num_traits::Float;
+
+/// Read access to the sweep-line state during a [`SweepEventHandler`] call
+pub struct SweepContext<'a, T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    position: geo::Coordinate<T>,
+    status: &'a SweepStatus,
+    lines: &'a SegmentStore<T>,
+}
+
+impl<'a, T> SweepContext<'a, T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub(crate) fn new(
+        position: geo::Coordinate<T>,
+        status: &'a SweepStatus,
+        lines: &'a SegmentStore<T>,
+    ) -> Self {
+        Self {
+            position,
+            status,
+            lines,
+        }
+    }
+
+    /// The position of the event being handled
+    pub fn position(&self) -> geo::Coordinate<T> {
+        self.position
+    }
+
+    /// Returns the line at `index`, as stored by the sweep: the start point comes first in
+    /// sweep-line order
+    pub fn line(&self, index: usize) -> Option<geo::Line<T>> {
+        if index < self.lines.len() {
+            Some(self.lines.get(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if `line` is in the sweep-line status
+    pub fn is_active(&self, line: usize) -> bool {
+        self.status.contains(line)
+    }
+
+    /// Returns the active line to the left of `line`, if `line` is active and has one
+    pub fn left_neighbour(&self, line: usize) -> Option<usize> {
+        self.status.predecessor(line)
+    }
+
+    /// Returns the active line to the right of `line`, if `line` is active and has one
+    pub fn right_neighbour(&self, line: usize) -> Option<usize> {
+        self.status.successor(line)
+    }
+
+    /// Returns the active lines, ordered left to right along the sweep-line
+    pub fn active_lines(&self) -> Vec<usize> {
+        let mut rv = Vec::new();
+        let mut current = self.status.first_where(|_| true);
+        while let Some(line) = current {
+            rv.push(line);
+            current = self.status.successor(line);
+        }
+        rv
+    }
+}
+
+/// Callbacks of the sweep events, all methods default to doing nothing.
+/// The line indices are the positions of the lines in the input.
+pub trait SweepEventHandler<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Called when `line` has been inserted into the sweep-line status
+    fn on_segment_start(&mut self, _line: usize, _context: &SweepContext<'_, T>) {}
+
+    /// Called when `line` is about to be removed from the sweep-line status
+    fn on_segment_end(&mut self, _line: usize, _context: &SweepContext<'_, T>) {}
+
+    /// Called when an intersection between `lines` is reported at `position`. `lines` are all
+    /// the lines reported at that point so far.
+    fn on_intersection(
+        &mut self,
+        _position: geo::Coordinate<T>,
+        _lines: &[usize],
+        _context: &SweepContext<'_, T>,
+    ) {
+    }
+}
+
+/// The handler used by the plain computations, it does nothing
+impl<T> SweepEventHandler<T> for ()
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+}
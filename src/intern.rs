@@ -0,0 +1,212 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Tolerance aware interning of coordinates.
+//!
+//! Intersection points computed from different pairs of lines rarely have the exact same
+//! bits, even when they describe the same location. [`CoordinateInterner`] hands out one
+//! canonical coordinate for every location, so repeated hits at (nearly) the same place can
+//! share one result entry. The interner is a hash grid with cells the size of the snap
+//! radius, so a lookup only has to examine the 3x3 cells around the coordinate.
+
+use crate::IntersectError;
+use num_traits::Float;
+
+/// Maps coordinates within `radius` of an already interned coordinate to that coordinate.
+/// The first coordinate seen at a location becomes the canonical one.
+/// ```
+/// # use intersect2d::intern::CoordinateInterner;
+/// let mut interner = CoordinateInterner::<f64>::new(1e-6)?;
+/// let a = interner.intern(geo::Coordinate { x: 1.0, y: 2.0 });
+/// let b = interner.intern(geo::Coordinate { x: 1.0000001, y: 1.9999999 });
+/// let c = interner.intern(geo::Coordinate { x: 1.1, y: 2.0 });
+/// assert_eq!(a, b);
+/// assert_ne!(a, c);
+/// assert_eq!(interner.get(b), geo::Coordinate { x: 1.0, y: 2.0 });
+/// assert_eq!(interner.len(), 2);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct CoordinateInterner<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    radius: T,
+    // indices into `coordinates`, bucketed by grid cell
+    cells: ahash::AHashMap<(i64, i64), Vec<usize>>,
+    coordinates: Vec<geo::Coordinate<T>>,
+}
+
+impl<T> CoordinateInterner<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Creates an interner merging coordinates within `radius` (Euclidean distance) of
+    /// each other. The radius must be positive and finite.
+    pub fn new(radius: T) -> Result<Self, IntersectError> {
+        if !radius.is_finite() || radius <= T::zero() {
+            return Err(IntersectError::InvalidParameter(
+                "The snap radius must be positive and finite".to_string(),
+            ));
+        }
+        Ok(Self {
+            radius,
+            cells: ahash::AHashMap::default(),
+            coordinates: Vec::new(),
+        })
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    /// Returns the number of distinct coordinates interned so far
+    pub fn len(&self) -> usize {
+        self.coordinates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coordinates.is_empty()
+    }
+
+    /// Returns the canonical coordinate of `id`
+    pub fn get(&self, id: usize) -> geo::Coordinate<T> {
+        self.coordinates[id]
+    }
+
+    /// The canonical coordinates, indexed by id
+    pub fn coordinates(&self) -> &[geo::Coordinate<T>] {
+        &self.coordinates
+    }
+
+    /// Returns the id of the canonical coordinate within `radius` of `coordinate`, or
+    /// interns `coordinate` as a new canonical coordinate.
+    /// If several canonical coordinates are within range, the closest one is used.
+    pub fn intern(&mut self, coordinate: geo::Coordinate<T>) -> usize {
+        if let Some(id) = self.find(coordinate) {
+            return id;
+        }
+        let id = self.coordinates.len();
+        self.coordinates.push(coordinate);
+        let cell = self.cell_of(coordinate);
+        self.cells.entry(cell).or_default().push(id);
+        id
+    }
+
+    /// Returns the id of the closest canonical coordinate within `radius` of `coordinate`
+    pub fn find(&self, coordinate: geo::Coordinate<T>) -> Option<usize> {
+        let (cx, cy) = self.cell_of(coordinate);
+        let radius_sq = self.radius * self.radius;
+        let mut best: Option<(T, usize)> = None;
+        for x in cx.saturating_sub(1)..=cx.saturating_add(1) {
+            for y in cy.saturating_sub(1)..=cy.saturating_add(1) {
+                if let Some(ids) = self.cells.get(&(x, y)) {
+                    for id in ids.iter() {
+                        let d = self.coordinates[*id] - coordinate;
+                        let distance_sq = d.x * d.x + d.y * d.y;
+                        if distance_sq <= radius_sq && best.map_or(true, |(b, _)| distance_sq < b) {
+                            best = Some((distance_sq, *id));
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|b| b.1)
+    }
+
+    // Coordinates too far out to be represented by an i64 cell index are saturated into
+    // the outermost cells. That only makes those cells crowded, the distance test is exact.
+    fn cell_of(&self, coordinate: geo::Coordinate<T>) -> (i64, i64) {
+        let cell = |v: T| {
+            let v = (v / self.radius).floor();
+            v.to_i64()
+                .unwrap_or(if v < T::zero() { i64::MIN } else { i64::MAX })
+        };
+        (cell(coordinate.x), cell(coordinate.y))
+    }
+}
+
+/// Merges result points within `radius` of each other into one entry, the involved line
+/// lists are joined (sorted, without duplicates).
+/// The entries keep the order of their canonical (first seen) points.
+/// ```
+/// # use intersect2d::intern::intern_results;
+/// let results = vec![
+///     (geo::Coordinate { x: 1.0, y: 1.0 }, vec![0, 1]),
+///     (geo::Coordinate { x: 1.0, y: 1.0000001 }, vec![1, 2]),
+///     (geo::Coordinate { x: 5.0, y: 1.0 }, vec![3, 4]),
+/// ];
+/// let merged = intern_results(results.into_iter(), 1e-6)?;
+/// assert_eq!(merged.len(), 2);
+/// assert_eq!(merged[0], (geo::Coordinate { x: 1.0, y: 1.0 }, vec![0, 1, 2]));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn intern_results<T, I>(
+    results: I,
+    radius: T,
+) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    I: Iterator<Item = (geo::Coordinate<T>, Vec<usize>)>,
+{
+    let mut interner = CoordinateInterner::new(radius)?;
+    let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::new();
+    for (position, lines) in results {
+        let id = interner.intern(position);
+        if id == rv.len() {
+            rv.push((position, Vec::new()));
+        }
+        rv[id].1.extend(lines);
+    }
+    for entry in rv.iter_mut() {
+        entry.1.sort_unstable();
+        entry.1.dedup();
+    }
+    Ok(rv)
+}
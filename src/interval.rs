@@ -0,0 +1,188 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Interval arithmetic for floating point filters.
+//!
+//! An [`Interval`] is a pair of bounds guaranteed to contain the exact result of the
+//! computation: every operation is done in plain floating point and the bounds are rounded
+//! outwards by one ulp. If the resulting interval doesn't contain zero the sign of the exact
+//! result is known, otherwise an exact computation is needed. The filtered predicates in
+//! [`predicates`](crate::predicates) are built on this.
+
+use crate::expansion::next_after;
+use num_traits::Float;
+use std::{cmp, ops};
+
+/// A closed interval `[lo, hi]` containing an exact, unknown, value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval<T: Float> {
+    lo: T,
+    hi: T,
+}
+
+/// Widens the bounds by one ulp, the error of a rounded to nearest operation is at most half
+/// an ulp
+#[inline(always)]
+fn widen<T: Float>(lo: T, hi: T) -> Interval<T> {
+    let down = |v: T| {
+        if v.is_finite() {
+            next_after(v, false)
+        } else {
+            v
+        }
+    };
+    let up = |v: T| {
+        if v.is_finite() {
+            next_after(v, true)
+        } else {
+            v
+        }
+    };
+    Interval {
+        lo: down(lo),
+        hi: up(hi),
+    }
+}
+
+impl<T: Float> Interval<T> {
+    /// The interval containing only `value`
+    pub fn new(value: T) -> Self {
+        Self {
+            lo: value,
+            hi: value,
+        }
+    }
+
+    /// The lower bound
+    pub fn lo(&self) -> T {
+        self.lo
+    }
+
+    /// The upper bound
+    pub fn hi(&self) -> T {
+        self.hi
+    }
+
+    /// Returns the sign of every value in the interval, or None if the interval contains
+    /// zero (or is NaN) and the sign is uncertain.
+    /// ```
+    /// # use intersect2d::interval::Interval;
+    /// # use std::cmp::Ordering;
+    /// let a = Interval::new(0.1_f64) * Interval::new(3.0);
+    /// assert_eq!((a - Interval::new(0.2)).sign(), Some(Ordering::Greater));
+    /// // 0.1 * 3.0 is not exactly 0.3, but the interval can't tell
+    /// assert_eq!((a - Interval::new(0.3)).sign(), None);
+    /// assert_eq!((Interval::new(0.3) - Interval::new(0.3)).sign(), Some(Ordering::Equal));
+    /// ```
+    pub fn sign(&self) -> Option<cmp::Ordering> {
+        if self.lo > T::zero() {
+            Some(cmp::Ordering::Greater)
+        } else if self.hi < T::zero() {
+            Some(cmp::Ordering::Less)
+        } else if self.lo == T::zero() && self.hi == T::zero() {
+            Some(cmp::Ordering::Equal)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Float> ops::Add for Interval<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        if self.lo == self.hi && other.lo == other.hi {
+            let (sum, error) = crate::expansion::two_sum(self.lo, other.lo);
+            if error == T::zero() {
+                return Self::new(sum);
+            }
+        }
+        widen(self.lo + other.lo, self.hi + other.hi)
+    }
+}
+
+impl<T: Float> ops::Neg for Interval<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl<T: Float> ops::Sub for Interval<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<T: Float> ops::Mul for Interval<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        if self.lo == self.hi && other.lo == other.hi {
+            let (product, error) = crate::expansion::two_product(self.lo, other.lo);
+            if error == T::zero() {
+                return Self::new(product);
+            }
+        }
+        let candidates = [
+            self.lo * other.lo,
+            self.lo * other.hi,
+            self.hi * other.lo,
+            self.hi * other.hi,
+        ];
+        if candidates.iter().any(|c| c.is_nan()) {
+            return Self::new(T::nan());
+        }
+        let lo = candidates.iter().fold(T::infinity(), |a, b| a.min(*b));
+        let hi = candidates.iter().fold(T::neg_infinity(), |a, b| a.max(*b));
+        widen(lo, hi)
+    }
+}
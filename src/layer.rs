@@ -0,0 +1,250 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+//! Intersections of line string layers.
+//!
+//! The self intersection traits on `Vec<geo::LineString<T>>` number the segments of all the
+//! line strings in order, as one flat collection. [`LayerIntersections`] runs the same single
+//! sweep over the whole layer but reports every involved segment as a
+//! `(line string index, segment index)` pair, segment `i` of a line string going from vertex
+//! `i` to vertex `i + 1`. [`LayerIntersections::layer_intersections_with()`] does the same for
+//! the points where two layers meet.
+
+use crate::between::intersections_between_sets;
+use crate::{layer_lines, IntersectError, SelfIntersectingExclusive, Tolerance};
+use num_traits::Float;
+
+/// A point where segments of a layer meet, see [`LayerIntersections`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerIntersection<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    /// The involved segments as (line string index, segment index), sorted
+    pub segments: Vec<(usize, usize)>,
+}
+
+/// A point where the segments of two layers meet, see
+/// [`LayerIntersections::layer_intersections_with()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerCrossing<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    /// The involved segments of the first layer as (line string index, segment index), sorted
+    pub a: Vec<(usize, usize)>,
+    /// The involved segments of the second layer as (line string index, segment index), sorted
+    pub b: Vec<(usize, usize)>,
+}
+
+/// The index of the first flat segment of every line string, and the total segment count
+fn segment_offsets<T>(layer: &[geo::LineString<T>]) -> Vec<usize>
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut rv = Vec::with_capacity(layer.len() + 1);
+    let mut offset = 0;
+    rv.push(offset);
+    for line_string in layer.iter() {
+        offset += line_string.0.len().saturating_sub(1);
+        rv.push(offset);
+    }
+    rv
+}
+
+/// Maps the flat segment `index` to (line string index, segment index)
+fn locate(offsets: &[usize], index: usize) -> Option<(usize, usize)> {
+    if index >= *offsets.last()? {
+        return None;
+    }
+    // the last line string starting at or before index, empty line strings start at the
+    // same offset as the next one
+    let feature = offsets.partition_point(|o| *o <= index) - 1;
+    Some((feature, index - offsets[feature]))
+}
+
+/// Maps the flat segment indices to sorted (line string index, segment index) pairs
+fn locate_all(offsets: &[usize], indices: &[usize]) -> Vec<(usize, usize)> {
+    let mut rv: Vec<(usize, usize)> = indices.iter().filter_map(|i| locate(offsets, *i)).collect();
+    rv.sort_unstable();
+    rv
+}
+
+/// Intersection queries on a layer of line strings, reporting the segments as
+/// (line string index, segment index) pairs
+pub trait LayerIntersections<T>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns the (line string index, segment index) of the flat segment `index`, as used by
+    /// the self intersection traits of the layer. Returns None if the layer has less segments.
+    /// ```
+    /// # use intersect2d::layer::LayerIntersections;
+    /// let layer = vec![
+    ///     geo::LineString::<f64>::from(vec![(0., 0.), (1., 0.), (2., 0.)]),
+    ///     geo::LineString::from(vec![(0., 1.), (1., 1.)]),
+    /// ];
+    /// assert_eq!(layer.segment_location(1), Some((0, 1)));
+    /// assert_eq!(layer.segment_location(2), Some((1, 0)));
+    /// assert_eq!(layer.segment_location(3), None);
+    /// ```
+    fn segment_location(&self, index: usize) -> Option<(usize, usize)>;
+
+    /// Returns the intersections of the layer, like
+    /// [`SelfIntersectingExclusive::self_intersections()`], with the segments as
+    /// (line string index, segment index) pairs.
+    /// ```
+    /// # use intersect2d::layer::{LayerIntersection, LayerIntersections};
+    /// let layer = vec![
+    ///     geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]),
+    ///     geo::LineString::from(vec![(5., 10.), (5., -10.)]),
+    /// ];
+    /// assert_eq!(
+    ///     layer.layer_self_intersections()?,
+    ///     vec![LayerIntersection {
+    ///         position: geo::Coordinate { x: 5., y: 0. },
+    ///         segments: vec![(0, 0), (1, 0)]
+    ///     }]
+    /// );
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    fn layer_self_intersections(&self) -> Result<Vec<LayerIntersection<T>>, IntersectError>;
+
+    /// Same as [`LayerIntersections::layer_self_intersections()`] but using a custom tolerance.
+    fn layer_self_intersections_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<Vec<LayerIntersection<T>>, IntersectError>;
+
+    /// Returns the points where the segments of this layer meet the segments of `other`,
+    /// end points included, in one sweep over both layers (see
+    /// [`intersections_between_sets()`]). The intersections within each layer are not
+    /// reported.
+    /// ```
+    /// # use intersect2d::layer::LayerIntersections;
+    /// let roads = vec![
+    ///     geo::LineString::from(vec![(0., 0.), (10., 0.)]),
+    ///     geo::LineString::from(vec![(10., 0.), (10., 10.), (0., 10.)]),
+    /// ];
+    /// let rivers = vec![geo::LineString::from(vec![(5., -5.), (5., 15.)])];
+    /// let rv = roads.layer_intersections_with(&rivers)?;
+    /// assert_eq!(rv.len(), 2);
+    /// assert_eq!(rv[0].a, vec![(0, 0)]);
+    /// assert_eq!(rv[1].position, geo::Coordinate { x: 5., y: 10. });
+    /// assert_eq!(rv[1].a, vec![(1, 1)]);
+    /// assert_eq!(rv[1].b, vec![(0, 0)]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    fn layer_intersections_with(
+        &self,
+        other: &[geo::LineString<T>],
+    ) -> Result<Vec<LayerCrossing<T>>, IntersectError>;
+}
+
+impl<T> LayerIntersections<T> for Vec<geo::LineString<T>>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn segment_location(&self, index: usize) -> Option<(usize, usize)> {
+        locate(&segment_offsets(self), index)
+    }
+
+    fn layer_self_intersections(&self) -> Result<Vec<LayerIntersection<T>>, IntersectError> {
+        let offsets = segment_offsets(self);
+        Ok(self
+            .self_intersections()?
+            .map(|(position, involved)| LayerIntersection {
+                position,
+                segments: locate_all(&offsets, &involved),
+            })
+            .collect())
+    }
+
+    fn layer_self_intersections_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<Vec<LayerIntersection<T>>, IntersectError> {
+        let offsets = segment_offsets(self);
+        Ok(self
+            .self_intersections_with_tolerance(tolerance)?
+            .map(|(position, involved)| LayerIntersection {
+                position,
+                segments: locate_all(&offsets, &involved),
+            })
+            .collect())
+    }
+
+    fn layer_intersections_with(
+        &self,
+        other: &[geo::LineString<T>],
+    ) -> Result<Vec<LayerCrossing<T>>, IntersectError> {
+        let (offsets_a, offsets_b) = (segment_offsets(self), segment_offsets(other));
+        Ok(
+            intersections_between_sets(&layer_lines(self), &layer_lines(other), false)?
+                .into_iter()
+                .map(|i| LayerCrossing {
+                    position: i.position,
+                    a: locate_all(&offsets_a, &i.a),
+                    b: locate_all(&offsets_b, &i.b),
+                })
+                .collect(),
+        )
+    }
+}
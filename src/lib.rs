@@ -55,7 +55,78 @@ use geo::algorithm::intersects::Intersects;
 use num_traits::{Float, Zero};
 use thiserror::Error;
 
+pub mod aggregate;
 pub mod algorithm;
+pub mod background;
+pub mod between;
+pub mod closedness;
+pub mod collection;
+pub mod collinear;
+pub mod convert;
+pub mod crossing;
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod curve;
+pub mod density;
+pub mod diagnostic;
+pub mod distance;
+pub mod double;
+pub mod exact;
+mod expansion;
+pub mod fill;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod generators;
+pub mod geometry;
+pub mod handler;
+pub mod intern;
+pub mod interval;
+pub mod layer;
+pub mod list;
+pub mod location;
+pub mod loops;
+pub mod monotone;
+pub mod motion;
+pub mod multiplicity;
+pub mod nearest;
+pub mod nesting;
+pub mod noding;
+pub mod oracle;
+pub mod order;
+pub mod path;
+pub mod precision;
+pub mod predicates;
+pub mod prelude;
+pub mod preprocess;
+#[cfg(feature = "image")]
+pub mod raster;
+pub mod regression;
+pub mod relate;
+pub mod report;
+mod segments;
+pub mod self_overlap;
+pub mod simplify;
+pub mod small;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod spherical;
+pub mod split;
+mod status;
+pub mod streaming;
+pub mod tiles;
+pub mod tolerance;
+pub mod topology;
+pub mod vector;
+pub mod verify;
+pub mod visibility;
+#[cfg(feature = "writer")]
+pub mod writer;
+
+pub use closedness::Closedness;
+use list::Intersections;
+pub use tolerance::{EqualityMode, Tolerance};
+pub use vector::scale_to_coordinate;
+use vector::{cross_z, div, dot};
 
 #[derive(Error, Debug)]
 pub enum IntersectError {
@@ -67,9 +138,36 @@ pub enum IntersectError {
     InvalidSearchParameter(String),
     #[error("Results already taken from the algorithm data struct")]
     ResultsAlreadyTaken(String),
+    #[error("Invalid parameter value")]
+    InvalidParameter(String),
+    #[error("I/O error")]
+    IoError(String),
+    #[error("The computation was cancelled")]
+    Cancelled(String),
+}
+
+/// The format version written by the `serde` implementations of
+/// [`IntersectionList`](list::IntersectionList) and [`SegmentIndex`](nearest::SegmentIndex).
+/// Data written with another version is rejected when deserialized.
+#[cfg(feature = "serde")]
+pub const SERIALIZATION_VERSION: u32 = 1;
+
+/// Fails the deserialization if `version` isn't [`SERIALIZATION_VERSION`]
+#[cfg(feature = "serde")]
+pub(crate) fn check_serialization_version<E: serde::de::Error>(version: u32) -> Result<(), E> {
+    if version == SERIALIZATION_VERSION {
+        Ok(())
+    } else {
+        Err(E::custom(format!(
+            "unsupported format version {}, expected {}",
+            version, SERIALIZATION_VERSION
+        )))
+    }
 }
 
 /// Utility function converting an array slice into a vec of Line
+/// # Panics
+/// Panics if a value can't be represented as `T`, see [`try_to_lines()`]
 #[allow(dead_code)]
 pub fn to_lines<U, T>(points: &[[U; 4]]) -> Vec<geo::Line<T>>
 where
@@ -77,20 +175,150 @@ where
     T: Float + approx::UlpsEq + geo::CoordFloat,
     T::Epsilon: Copy,
 {
-    let mut rv = Vec::with_capacity(points.len());
-    for p in points.iter() {
-        rv.push(geo::Line::<T>::new(
-            geo::Coordinate {
-                x: T::from(p[0]).unwrap(),
-                y: T::from(p[1]).unwrap(),
-            },
-            geo::Coordinate {
-                x: T::from(p[2]).unwrap(),
-                y: T::from(p[3]).unwrap(),
-            },
+    try_to_lines(points).unwrap()
+}
+
+/// Converts one value, failing if it can't be represented as `T` or if a finite value
+/// overflows `T`
+fn try_convert<U, T>(value: U) -> Result<T, IntersectError>
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float,
+{
+    match T::from(value) {
+        Some(v) if v.is_finite() || !value.to_f64().map_or(false, |f| f.is_finite()) => Ok(v),
+        _ => Err(IntersectError::InvalidData(
+            "Could not convert a coordinate value".to_string(),
+        )),
+    }
+}
+
+/// Builds a line from two points, failing if a value can't be represented as `T`
+pub(crate) fn try_to_line<U, T>(x0: U, y0: U, x1: U, y1: U) -> Result<geo::Line<T>, IntersectError>
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    Ok(geo::Line::<T>::new(
+        geo::Coordinate {
+            x: try_convert(x0)?,
+            y: try_convert(y0)?,
+        },
+        geo::Coordinate {
+            x: try_convert(x1)?,
+            y: try_convert(y1)?,
+        },
+    ))
+}
+
+/// Converts an array slice of `[x0, y0, x1, y1]` into a vec of Line.
+/// Returns `InvalidData` if a value can't be represented as `T`, or if a finite value
+/// overflows `T`.
+/// ```
+/// # use intersect2d::{try_to_lines, IntersectError};
+/// let lines = try_to_lines::<_, f32>(&[[0_u64, 0, 1, 1]])?;
+/// assert_eq!(lines[0].end, geo::Coordinate { x: 1., y: 1. });
+/// assert!(matches!(
+///     try_to_lines::<_, f32>(&[[0., 0., 1., 1e300]]),
+///     Err(IntersectError::InvalidData(_))
+/// ));
+/// # Ok::<(), IntersectError>(())
+/// ```
+pub fn try_to_lines<U, T>(points: &[[U; 4]]) -> Result<Vec<geo::Line<T>>, IntersectError>
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    points
+        .iter()
+        .map(|p| try_to_line(p[0], p[1], p[2], p[3]))
+        .collect()
+}
+
+/// Converts a slice of `[x, y]` points into a vec of Line, every two consecutive points form
+/// one line. Returns `InvalidData` if the number of points is odd, or if a value can't be
+/// represented as `T`.
+/// ```
+/// # use intersect2d::try_to_lines_from_points;
+/// let lines = try_to_lines_from_points::<_, f64>(&[[0, 0], [1, 1], [0, 1], [1, 0]])?;
+/// assert_eq!(lines, intersect2d::to_lines(&[[0, 0, 1, 1], [0, 1, 1, 0]]));
+/// assert!(try_to_lines_from_points::<_, f64>(&[[0, 0]]).is_err());
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn try_to_lines_from_points<U, T>(
+    points: &[[U; 2]],
+) -> Result<Vec<geo::Line<T>>, IntersectError>
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    if points.len() & 1 == 1 {
+        return Err(IntersectError::InvalidData(
+            "Lines need an even number of points".to_string(),
+        ));
+    }
+    points
+        .chunks_exact(2)
+        .map(|p| try_to_line(p[0][0], p[0][1], p[1][0], p[1][1]))
+        .collect()
+}
+
+/// Converts a slice of `(x, y)` tuples into a vec of Line, every two consecutive points form
+/// one line. Returns `InvalidData` if the number of points is odd, or if a value can't be
+/// represented as `T`.
+/// ```
+/// # use intersect2d::try_to_lines_from_tuples;
+/// let lines = try_to_lines_from_tuples::<_, f64>(&[(0, 0), (1, 1), (0, 1), (1, 0)])?;
+/// assert_eq!(lines, intersect2d::to_lines(&[[0, 0, 1, 1], [0, 1, 1, 0]]));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn try_to_lines_from_tuples<U, T>(
+    points: &[(U, U)],
+) -> Result<Vec<geo::Line<T>>, IntersectError>
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    if points.len() & 1 == 1 {
+        return Err(IntersectError::InvalidData(
+            "Lines need an even number of points".to_string(),
+        ));
+    }
+    points
+        .chunks_exact(2)
+        .map(|p| try_to_line(p[0].0, p[0].1, p[1].0, p[1].1))
+        .collect()
+}
+
+/// Converts a flat slice of coordinates, `x0, y0, x1, y1` for every line, into a vec of Line.
+/// Returns `InvalidData` if the length isn't a multiple of four, or if a value can't be
+/// represented as `T`.
+/// ```
+/// # use intersect2d::try_to_lines_from_flat;
+/// let lines = try_to_lines_from_flat::<_, f64>(&[0, 0, 1, 1, 0, 1, 1, 0])?;
+/// assert_eq!(lines, intersect2d::to_lines(&[[0, 0, 1, 1], [0, 1, 1, 0]]));
+/// assert!(try_to_lines_from_flat::<_, f64>(&[0, 0, 1]).is_err());
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn try_to_lines_from_flat<U, T>(coordinates: &[U]) -> Result<Vec<geo::Line<T>>, IntersectError>
+where
+    U: num_traits::ToPrimitive + Copy,
+    T: Float + approx::UlpsEq + geo::CoordFloat,
+    T::Epsilon: Copy,
+{
+    if coordinates.len() & 3 != 0 {
+        return Err(IntersectError::InvalidData(
+            "Lines need four coordinates each".to_string(),
         ));
     }
-    rv
+    coordinates
+        .chunks_exact(4)
+        .map(|c| try_to_line(c[0], c[1], c[2], c[3]))
+        .collect()
 }
 
 /// Get any intersection point between line segment and point.
@@ -103,11 +331,25 @@ where
     T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
     T::Epsilon: Copy,
 {
+    intersect_line_point_with_tolerance(line, point, &Tolerance::default())
+}
+
+/// Get any intersection point between line segment and point, using a custom tolerance.
+pub fn intersect_line_point_with_tolerance<T>(
+    line: &geo::Line<T>,
+    point: &geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> Option<Intersection<T>>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let point = &tolerance.precision.make_precise_c(*point);
     // take care of end point equality
-    if approx::ulps_eq!(&line.start.x, &point.x) && approx::ulps_eq!(&line.start.y, &point.y) {
+    if tolerance.ulps_eq_c(&line.start, point) {
         return Some(Intersection::Intersection(*point));
     }
-    if approx::ulps_eq!(&line.end.x, &point.x) && approx::ulps_eq!(&line.end.y, &point.y) {
+    if tolerance.ulps_eq_c(&line.end, point) {
         return Some(Intersection::Intersection(*point));
     }
 
@@ -124,12 +366,81 @@ where
 
     #[cfg(feature = "console_trace")]
     println!("ab={:?}, ap={:?}, pb={:?}, ap+pb={:?}", ab, ap, pb, ap + pb);
-    if approx::ulps_eq!(&ab, &(ap + pb)) {
+    if tolerance.ulps_eq(ab, ap + pb) {
         return Some(Intersection::Intersection(*point));
     }
     None
 }
 
+/// The relation between a point and a line segment, see [`line_point_relation()`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinePointRelation<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The parametric position of the point projected onto the supporting line of the
+    /// segment: 0 at the start, 1 at the end. It is not clamped to the segment.
+    pub t: T,
+    /// The signed perpendicular distance from the supporting line of the segment, positive
+    /// to the left of the segment direction.
+    pub offset: T,
+    /// True if [`intersect_line_point_with_tolerance()`] considers the point to be on the
+    /// segment
+    pub on_line: bool,
+}
+
+/// Returns the parametric position and the signed offset of a point relative to a line
+/// segment, using the default tolerance.
+/// ```
+/// # use intersect2d::line_point_relation;
+/// let line: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let relation = line_point_relation(&line, &geo::Coordinate { x: 2.5, y: -1. });
+/// assert_eq!(relation.t, 0.25);
+/// assert_eq!(relation.offset, -1.0);
+/// assert!(!relation.on_line);
+/// ```
+pub fn line_point_relation<T>(
+    line: &geo::Line<T>,
+    point: &geo::Coordinate<T>,
+) -> LinePointRelation<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    line_point_relation_with_tolerance(line, point, &Tolerance::default())
+}
+
+/// Returns the parametric position and the signed offset of a point relative to a line
+/// segment, using a custom tolerance for the `on_line` decision.
+/// A zero length segment has `t` = 0 and the (unsigned) distance to the point as offset.
+pub fn line_point_relation_with_tolerance<T>(
+    line: &geo::Line<T>,
+    point: &geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> LinePointRelation<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let on_line = intersect_line_point_with_tolerance(line, point, tolerance).is_some();
+    let r = line.end - line.start;
+    let d = *point - line.start;
+    let r_dot_r = dot(&r, &r);
+    if r_dot_r <= T::zero() {
+        return LinePointRelation {
+            t: T::zero(),
+            offset: dot(&d, &d).sqrt(),
+            on_line,
+        };
+    }
+    LinePointRelation {
+        t: dot(&d, &r) / r_dot_r,
+        offset: cross_z(&r, &d) / r_dot_r.sqrt(),
+        on_line,
+    }
+}
+
 #[allow(dead_code)]
 pub enum Intersection<T>
 where
@@ -169,43 +480,134 @@ where
     }
 }
 
+/// Formats a point intersection as `(x, y)` and an overlap as `(x0, y0)-(x1, y1)`, the
+/// formatting options (e.g. the precision) are applied to every coordinate.
+/// ```
+/// # use intersect2d::Intersection;
+/// let point = Intersection::Intersection(geo::Coordinate { x: 1.0_f64, y: 2.5 });
+/// assert_eq!(point.to_string(), "(1, 2.5)");
+/// let overlap = Intersection::OverLap(geo::Line::<f64>::new((0., 0.), (1., 0.)));
+/// assert_eq!(format!("{:.1}", overlap), "(0.0, 0.0)-(1.0, 0.0)");
+/// ```
+impl<T> fmt::Display for Intersection<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + fmt::Display,
+    T::Epsilon: Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coordinate = |f: &mut fmt::Formatter<'_>, c: &geo::Coordinate<T>| {
+            f.write_str("(")?;
+            fmt::Display::fmt(&c.x, f)?;
+            f.write_str(", ")?;
+            fmt::Display::fmt(&c.y, f)?;
+            f.write_str(")")
+        };
+        match self {
+            Self::Intersection(a) => coordinate(f, a),
+            Self::OverLap(a) => {
+                coordinate(f, &a.start)?;
+                f.write_str("-")?;
+                coordinate(f, &a.end)
+            }
+        }
+    }
+}
+
 /// Get any intersection point between lines.
 /// Note that this function always detects endpoint-to-endpoint intersections.
 /// Most of this is from <https://stackoverflow.com/a/565282>
-#[allow(clippy::many_single_char_names)]
 pub fn intersect<T>(one: &geo::Line<T>, other: &geo::Line<T>) -> Option<Intersection<T>>
 where
     T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
     T::Epsilon: Copy,
 {
+    intersect_with_tolerance(one, other, &Tolerance::default())
+}
+
+/// Get any intersection point between lines, using an explicit absolute epsilon and ulps
+/// tolerance instead of the defaults. This is a shortcut for [`intersect_with_tolerance()`]
+/// with [`Tolerance::new(abs_eps, max_ulps)`](Tolerance::new) and the thresholds below.
+/// The tolerance is also what decides if the lines are parallel, or collinear: `abs_eps` is
+/// measured as a distance, so two nearly parallel segments are treated as parallel when the
+/// shorter one turns away from the longer one by less than `abs_eps` over its length.
+/// `abs_eps` is used as both the parallel and the collinear threshold of the tolerance, a
+/// tolerance without thresholds, like the default one used by [`intersect()`], tests the
+/// cross products with `approx::ulps_eq!()` instead.
+/// ```
+/// # use intersect2d::{intersect, intersect_with, Intersection};
+/// // two long, nearly parallel, segments crossing at a far away point
+/// let line1: geo::Line<f64> = [(0., 0.), (1000., 0.)].into();
+/// let line2: geo::Line<f64> = [(0., 1e-9), (1000., -1e-9)].into();
+/// assert!(matches!(intersect(&line1, &line2), Some(Intersection::Intersection(_))));
+/// assert!(matches!(intersect_with(&line1, &line2, 1e-6, 4), Some(Intersection::OverLap(_))));
+/// ```
+pub fn intersect_with<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    abs_eps: T,
+    max_ulps: u32,
+) -> Option<Intersection<T>>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let tolerance = Tolerance {
+        parallel: Some(abs_eps),
+        collinear: Some(abs_eps),
+        ..Tolerance::new(abs_eps, max_ulps)
+    };
+    intersect_with_tolerance(one, other, &tolerance)
+}
+
+/// Get any intersection point between lines, using a custom tolerance when comparing points.
+/// The returned points are rounded to the precision model of the tolerance.
+/// ```
+/// # use intersect2d::{intersect, intersect_with_tolerance, Tolerance};
+/// let line1: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+/// let line2: geo::Line<f64> = [(10.001, -5.), (10.001, 5.)].into();
+/// assert!(intersect(&line1, &line2).is_none());
+/// let tolerance = Tolerance::new(0.01, 4);
+/// assert!(intersect_with_tolerance(&line1, &line2, &tolerance).is_some());
+/// ```
+#[allow(clippy::many_single_char_names)]
+pub fn intersect_with_tolerance<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+    tolerance: &Tolerance<T>,
+) -> Option<Intersection<T>>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let eps = tolerance.epsilon;
     #[allow(clippy::suspicious_operation_groupings)]
     {
         // AABB tests
-        if one.end.x > other.end.x
-            && one.end.x > other.start.x
-            && one.start.x > other.end.x
-            && one.start.x > other.start.x
+        if one.end.x - eps > other.end.x
+            && one.end.x - eps > other.start.x
+            && one.start.x - eps > other.end.x
+            && one.start.x - eps > other.start.x
         {
             return None;
         }
-        if one.end.x < other.end.x
-            && one.end.x < other.start.x
-            && one.start.x < other.end.x
-            && one.start.x < other.start.x
+        if one.end.x + eps < other.end.x
+            && one.end.x + eps < other.start.x
+            && one.start.x + eps < other.end.x
+            && one.start.x + eps < other.start.x
         {
             return None;
         }
-        if one.end.y > other.end.y
-            && one.end.y > other.start.y
-            && one.start.y > other.end.y
-            && one.start.y > other.start.y
+        if one.end.y - eps > other.end.y
+            && one.end.y - eps > other.start.y
+            && one.start.y - eps > other.end.y
+            && one.start.y - eps > other.start.y
         {
             return None;
         }
-        if one.end.y < other.end.y
-            && one.end.y < other.start.y
-            && one.start.y < other.end.y
-            && one.start.y < other.start.y
+        if one.end.y + eps < other.end.y
+            && one.end.y + eps < other.start.y
+            && one.start.y + eps < other.end.y
+            && one.start.y + eps < other.start.y
         {
             return None;
         }
@@ -219,33 +621,46 @@ where
     let q_minus_p = q - p;
     let q_minus_p_cross_r = cross_z(&q_minus_p, &r);
 
+    // Without thresholds, the parallel and collinear decisions test the cross products with
+    // `approx::ulps_eq!()`. With thresholds they are measured as distances: the lines are
+    // parallel if the shorter line turns away from the direction of the longer line by less
+    // than the threshold over its length, and collinear if the start of `other` is within the
+    // threshold of the supporting line of `one`.
+    let r_length = dot(&r, &r).sqrt();
+    let is_parallel = tolerance.is_parallel(r_cross_s, r_length.max(dot(&s, &s).sqrt()));
+
     // If r × s = 0 then the two lines are parallel
-    if approx::ulps_eq!(&r_cross_s, &T::zero()) {
+    if is_parallel {
         // one (or both) of the lines may be a point
-        let one_is_a_point = ulps_eq_c(&one.start, &one.end);
-        let other_is_a_point = ulps_eq_c(&other.start, &other.end);
+        let one_is_a_point = tolerance.ulps_eq_c(&one.start, &one.end);
+        let other_is_a_point = tolerance.ulps_eq_c(&other.start, &other.end);
         if one_is_a_point || other_is_a_point {
-            if one_is_a_point && other_is_a_point && ulps_eq_c(&one.start, &other.start) {
-                return Some(Intersection::Intersection(one.start));
+            if one_is_a_point && other_is_a_point && tolerance.ulps_eq_c(&one.start, &other.start) {
+                return Some(Intersection::Intersection(
+                    tolerance.precision.make_precise_c(one.start),
+                ));
             }
             return if one_is_a_point {
-                intersect_line_point(other, &one.start)
+                intersect_line_point_with_tolerance(other, &one.start, tolerance)
             } else {
-                intersect_line_point(one, &other.start)
+                intersect_line_point_with_tolerance(one, &other.start, tolerance)
             };
         }
 
+        let is_collinear = tolerance.is_collinear(q_minus_p_cross_r, r_length);
         // If r × s = 0 and (q − p) × r = 0, then the two lines are collinear.
-        if approx::ulps_eq!(&q_minus_p_cross_r, &T::zero()) {
+        if is_collinear {
             let r_dot_r = dot(&r, &r);
             let r_div_r_dot_r = div(&r, r_dot_r);
             let s_dot_r = dot(&s, &r);
             let t0 = dot(&q_minus_p, &r_div_r_dot_r);
             let t1 = t0 + s_dot_r / r_dot_r;
 
-            Some(Intersection::OverLap(geo::Line::new(
-                scale_to_coordinate(&p, &r, t0),
-                scale_to_coordinate(&p, &r, t1),
+            Some(Intersection::OverLap(tolerance.precision.make_precise_l(
+                geo::Line::new(
+                    scale_to_coordinate(&p, &r, t0),
+                    scale_to_coordinate(&p, &r, t1),
+                ),
             )))
         } else {
             // If r × s = 0 and (q − p) × r ≠ 0,
@@ -256,61 +671,23 @@ where
         // the lines are not parallel
         let t = cross_z(&q_minus_p, &div(&s, r_cross_s));
         let u = cross_z(&q_minus_p, &div(&r, r_cross_s));
-        Some(Intersection::Intersection(scale_to_coordinate(&p, &r, t)))
-    }
-}
-
-#[inline(always)]
-pub fn scale_to_coordinate<T>(
-    point: &geo::Coordinate<T>,
-    vector: &geo::Coordinate<T>,
-    scale: T,
-) -> geo::Coordinate<T>
-where
-    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
-    T::Epsilon: Copy,
-{
-    geo::Coordinate {
-        x: point.x + scale * vector.x,
-        y: point.y + scale * vector.y,
-    }
-}
 
-#[inline(always)]
-/// Divides a 'vector' by 'b'. Obviously, don't feed this with 'b' == 0
-fn div<T>(a: &geo::Coordinate<T>, b: T) -> geo::Coordinate<T>
-where
-    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
-    T::Epsilon: Copy,
-{
-    geo::Coordinate {
-        x: a.x / b,
-        y: a.y / b,
+        // If r × s ≠ 0 and 0 ≤ t ≤ 1 and 0 ≤ u ≤ 1,
+        // the two line segments meet at the point p + t r = q + u s.
+        // The parameters are allowed to overshoot by the tolerance epsilon (as a distance).
+        let t_slack = eps / dot(&r, &r).sqrt();
+        let u_slack = eps / dot(&s, &s).sqrt();
+        if t < -t_slack || t > T::one() + t_slack || u < -u_slack || u > T::one() + u_slack {
+            return None;
+        }
+        Some(Intersection::Intersection(
+            tolerance
+                .precision
+                .make_precise_c(scale_to_coordinate(&p, &r, t)),
+        ))
     }
 }
 
-#[inline(always)]
-/// from https://stackoverflow.com/a/565282 :
-///  "Define the 2-dimensional vector cross product v × w to be vx wy − vy wx."
-/// This function returns the z component of v × w
-fn cross_z<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> T
-where
-    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
-    T::Epsilon: Copy,
-{
-    a.x * b.y - a.y * b.x
-}
-
-#[inline(always)]
-/// calculate the dot product of two lines
-fn dot<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> T
-where
-    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
-    T::Epsilon: Copy,
-{
-    a.x * b.x + a.y * b.y
-}
-
 /// Trait for self intersection tests where the end points are excluded
 pub trait SelfIntersectingExclusive<T>
 where
@@ -326,15 +703,20 @@ where
     fn is_self_intersecting(&self) -> Result<bool, IntersectError>;
 
     /// Returns a list of intersection points and the involved lines, if any intersections are found.
-    #[allow(clippy::type_complexity)]
-    fn self_intersections<'a>(
+    fn self_intersections(&self) -> Result<Intersections<T>, IntersectError>;
+    /// Same as [`is_self_intersecting()`](SelfIntersectingExclusive::is_self_intersecting)
+    /// but using a custom tolerance.
+    fn is_self_intersecting_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<bool, IntersectError>;
+
+    /// Same as [`self_intersections()`](SelfIntersectingExclusive::self_intersections)
+    /// but using a custom tolerance.
+    fn self_intersections_with_tolerance(
         &self,
-    ) -> Result<
-        Box<dyn ExactSizeIterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a>,
-        IntersectError,
-    >
-    where
-        T: 'a;
+        tolerance: &Tolerance<T>,
+    ) -> Result<Intersections<T>, IntersectError>;
 }
 
 /// Trait for self intersection tests where the end points are included
@@ -354,15 +736,82 @@ where
 
     /// Returns a list of intersection points and the involved lines, if any intersections are found.
     /// If the end points are identical they will be reported too.
-    #[allow(clippy::type_complexity)]
-    fn self_intersections_inclusive<'a>(
+    fn self_intersections_inclusive(&self) -> Result<Intersections<T>, IntersectError>;
+    /// Same as
+    /// [`is_self_intersecting_inclusive()`](SelfIntersectingInclusive::is_self_intersecting_inclusive)
+    /// but using a custom tolerance.
+    fn is_self_intersecting_inclusive_with_tolerance(
         &self,
-    ) -> Result<
-        Box<dyn ExactSizeIterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a>,
-        IntersectError,
-    >
-    where
-        T: 'a;
+        tolerance: &Tolerance<T>,
+    ) -> Result<bool, IntersectError>;
+
+    /// Same as
+    /// [`self_intersections_inclusive()`](SelfIntersectingInclusive::self_intersections_inclusive)
+    /// but using a custom tolerance.
+    fn self_intersections_inclusive_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<Intersections<T>, IntersectError>;
+}
+
+/// The tolerance aware implementation of the self intersection traits.
+/// Uses a brute force test for small inputs and the sweep-line algorithm for the rest.
+fn self_intersections_with_tolerance_<T>(
+    lines: &[geo::Line<T>],
+    ignore_end_point_intersections: bool,
+    stop_at_first_intersection: bool,
+    tolerance: &Tolerance<T>,
+) -> Result<Intersections<T>, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if lines.len() >= 25 {
+        // at around >25 line segments the sweep-line algorithm is faster
+        return algorithm::AlgorithmData::<T>::default()
+            .with_tolerance(*tolerance)?
+            .with_ignore_end_point_intersections(ignore_end_point_intersections)?
+            .with_stop_at_first_intersection(stop_at_first_intersection)?
+            .with_lazy_setup(stop_at_first_intersection)?
+            .with_ref_lines(lines.iter())?
+            .compute();
+    }
+    tolerance.validate()?;
+    // sanity check for each line
+    for a_line in lines.iter() {
+        if !a_line.start.x.is_finite()
+            || !a_line.start.y.is_finite()
+            || !a_line.end.x.is_finite()
+            || !a_line.end.y.is_finite()
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't check for intersections on non-finite data".to_string(),
+            ));
+        }
+    }
+    let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::new();
+    for (i, l1) in lines.iter().enumerate() {
+        for (j, l2) in lines.iter().enumerate().skip(i + 1) {
+            if ignore_end_point_intersections
+                && share_end_point(l1, l2, |a, b| tolerance.ulps_eq_c(a, b))
+            {
+                continue;
+            }
+            if let Some(intersection) = intersect_with_tolerance(l1, l2, tolerance) {
+                rv.push((intersection.single(), vec![i, j]));
+                if stop_at_first_intersection {
+                    return Ok(rv.into());
+                }
+            }
+        }
+    }
+    // Like the other brute force tests this reports intersections pair by pair
+    Ok(rv.into())
 }
 
 impl<T> SelfIntersectingInclusive<T> for Vec<geo::Line<T>>
@@ -414,6 +863,7 @@ where
             Ok(algorithm::AlgorithmData::<T>::default()
                 .with_ignore_end_point_intersections(false)?
                 .with_stop_at_first_intersection(true)?
+                .with_lazy_setup(true)?
                 .with_ref_lines(self.iter())?
                 .compute()?
                 .next()
@@ -461,16 +911,7 @@ where
     /// // and more...
     ///
     /// ```
-    #[allow(clippy::type_complexity)]
-    fn self_intersections_inclusive<'a>(
-        &self,
-    ) -> Result<
-        Box<dyn ExactSizeIterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a>,
-        IntersectError,
-    >
-    where
-        T: 'a,
-    {
+    fn self_intersections_inclusive(&self) -> Result<Intersections<T>, IntersectError> {
         if self.len() < 25 {
             // at around <25 line segments the brute force test is faster
 
@@ -497,7 +938,7 @@ where
             // This will only return intersections between two lines at a single point
             // If more than that are intersecting it will be reported once for each pair.
             // Todo: fix it!
-            Ok(Box::new(rv.into_iter()))
+            Ok(rv.into())
         } else {
             // at around >25 line segments the sweep-line algorithm is faster
             algorithm::AlgorithmData::<T>::default()
@@ -507,6 +948,34 @@ where
                 .compute()
         }
     }
+
+    fn is_self_intersecting_inclusive_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<bool, IntersectError> {
+        Ok(
+            self_intersections_with_tolerance_(self, false, true, tolerance)?
+                .next()
+                .is_some(),
+        )
+    }
+
+    /// Returns an iterator containing the found intersections, using a custom tolerance.
+    /// ```
+    /// # use intersect2d::{SelfIntersectingInclusive, Tolerance};
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [10.001, -5., 10.001, 5.]]);
+    /// assert_eq!(lines.self_intersections_inclusive()?.count(), 0);
+    /// let tolerance = Tolerance::new(0.01, 4);
+    /// assert!(lines.is_self_intersecting_inclusive_with_tolerance(&tolerance)?);
+    /// assert_eq!(lines.self_intersections_inclusive_with_tolerance(&tolerance)?.count(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    fn self_intersections_inclusive_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<Intersections<T>, IntersectError> {
+        self_intersections_with_tolerance_(self, false, false, tolerance)
+    }
 }
 
 impl<T> SelfIntersectingExclusive<T> for Vec<geo::Line<T>>
@@ -558,13 +1027,11 @@ where
                     ));
                 }
             }
+            // the same end point test as the *_with_tolerance() methods
+            let tolerance = Tolerance::<T>::default();
             for l1 in self.iter().enumerate() {
                 for l2 in self.iter().skip(l1.0 + 1) {
-                    if ulps_eq_c(&l1.1.start, &l2.start)
-                        || ulps_eq_c(&l1.1.start, &l2.end)
-                        || ulps_eq_c(&l1.1.end, &l2.start)
-                        || ulps_eq_c(&l1.1.end, &l2.end)
-                    {
+                    if share_end_point(l1.1, l2, |a, b| tolerance.ulps_eq_c(a, b)) {
                         continue;
                     }
                     if l1.1.intersects(l2) {
@@ -577,6 +1044,7 @@ where
             Ok(algorithm::AlgorithmData::<T>::default()
                 .with_ignore_end_point_intersections(true)?
                 .with_stop_at_first_intersection(true)?
+                .with_lazy_setup(true)?
                 .with_ref_lines(self.iter())?
                 .compute()?
                 .next()
@@ -618,16 +1086,7 @@ where
     /// assert_eq!(rv[1].1, vec!(0_usize, 3));
     /// assert!(ulps_eq_c(&rv[1].0, &geo::Coordinate{x: 133.33333333333333, y: 100.0}));
     /// ```
-    #[allow(clippy::type_complexity)]
-    fn self_intersections<'a>(
-        &self,
-    ) -> Result<
-        Box<dyn ExactSizeIterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a>,
-        IntersectError,
-    >
-    where
-        T: 'a,
-    {
+    fn self_intersections(&self) -> Result<Intersections<T>, IntersectError> {
         if self.len() < 25 {
             // at around <25 line segments the brute force test is faster
 
@@ -644,16 +1103,14 @@ where
                 }
             }
             let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::new();
+            // the same end point test as the *_with_tolerance() methods
+            let tolerance = Tolerance::<T>::default();
             for l1 in self.iter().enumerate() {
                 for l2 in self.iter().enumerate().skip(l1.0 + 1) {
-                    if ulps_eq_c(&l1.1.start, &l2.1.start)
-                        || ulps_eq_c(&l1.1.start, &l2.1.end)
-                        || ulps_eq_c(&l1.1.end, &l2.1.start)
-                        || ulps_eq_c(&l1.1.end, &l2.1.end)
-                    {
+                    if share_end_point(l1.1, l2.1, |a, b| tolerance.ulps_eq_c(a, b)) {
                         continue;
                     }
-                    if let Some(i) = intersect(l1.1, l2.1) {
+                    if let Some(i) = intersect_with_tolerance(l1.1, l2.1, &tolerance) {
                         rv.push((i.single(), vec![l1.0, l2.0]));
                     }
                 }
@@ -661,7 +1118,7 @@ where
             // This will only return intersections between two lines at a single point
             // If more than that are intersecting it will be reported once for each pair.
             // Todo: fix it!
-            Ok(Box::new(rv.into_iter()))
+            Ok(rv.into())
         } else {
             // at around >25 line segments the sweep-line algorithm is faster
             algorithm::AlgorithmData::<T>::default()
@@ -671,6 +1128,34 @@ where
                 .compute()
         }
     }
+
+    fn is_self_intersecting_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<bool, IntersectError> {
+        Ok(
+            self_intersections_with_tolerance_(self, true, true, tolerance)?
+                .next()
+                .is_some(),
+        )
+    }
+
+    /// Returns an iterator containing the found intersections, using a custom tolerance.
+    /// ```
+    /// # use intersect2d::{SelfIntersectingExclusive, Tolerance};
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [10.001, -5., 10.001, 5.]]);
+    /// assert!(!lines.is_self_intersecting()?);
+    /// let tolerance = Tolerance::new(0.01, 4);
+    /// assert!(lines.is_self_intersecting_with_tolerance(&tolerance)?);
+    /// assert_eq!(lines.self_intersections_with_tolerance(&tolerance)?.count(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    fn self_intersections_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<Intersections<T>, IntersectError> {
+        self_intersections_with_tolerance_(self, true, false, tolerance)
+    }
 }
 
 impl<T> SelfIntersectingExclusive<T> for geo::LineString<T>
@@ -719,13 +1204,11 @@ where
                     ));
                 }
             }
+            // the same end point test as the *_with_tolerance() methods
+            let tolerance = Tolerance::<T>::default();
             for l1 in self.lines().enumerate() {
                 for l2 in self.lines().skip(l1.0 + 1) {
-                    if ulps_eq_c(&l1.1.start, &l2.start)
-                        || ulps_eq_c(&l1.1.start, &l2.end)
-                        || ulps_eq_c(&l1.1.end, &l2.start)
-                        || ulps_eq_c(&l1.1.end, &l2.end)
-                    {
+                    if share_end_point(&l1.1, &l2, |a, b| tolerance.ulps_eq_c(a, b)) {
                         continue;
                     }
                     if l1.1.intersects(&l2) {
@@ -738,6 +1221,7 @@ where
             Ok(algorithm::AlgorithmData::<T>::default()
                 .with_ignore_end_point_intersections(true)?
                 .with_stop_at_first_intersection(true)?
+                .with_lazy_setup(true)?
                 .with_lines(self.lines())?
                 .compute()?
                 .next()
@@ -781,16 +1265,7 @@ where
     /// assert_eq!(rv[1].1, vec!(0_usize,3));
     /// assert!(ulps_eq_c(&rv[1].0, &geo::Coordinate{x: 133.33333333333334, y: 100.0}));
     /// ```
-    #[allow(clippy::type_complexity)]
-    fn self_intersections<'a>(
-        &self,
-    ) -> Result<
-        Box<dyn ExactSizeIterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a>,
-        IntersectError,
-    >
-    where
-        T: 'a,
-    {
+    fn self_intersections(&self) -> Result<Intersections<T>, IntersectError> {
         if self.0.len() < 25 {
             // at around <25 line segments the brute force test is faster
             // sanity check for each line
@@ -802,16 +1277,14 @@ where
                 }
             }
             let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::new();
+            // the same end point test as the *_with_tolerance() methods
+            let tolerance = Tolerance::<T>::default();
             for l1 in self.lines().enumerate() {
                 for l2 in self.lines().enumerate().skip(l1.0 + 1) {
-                    if ulps_eq_c(&l1.1.start, &l2.1.start)
-                        || ulps_eq_c(&l1.1.start, &l2.1.end)
-                        || ulps_eq_c(&l1.1.end, &l2.1.start)
-                        || ulps_eq_c(&l1.1.end, &l2.1.end)
-                    {
+                    if share_end_point(&l1.1, &l2.1, |a, b| tolerance.ulps_eq_c(a, b)) {
                         continue;
                     }
-                    if let Some(i) = intersect(&l1.1, &l2.1) {
+                    if let Some(i) = intersect_with_tolerance(&l1.1, &l2.1, &tolerance) {
                         rv.push((i.single(), vec![l1.0, l2.0]));
                     }
                 }
@@ -819,7 +1292,7 @@ where
             // This will only return intersections between two lines at a single point
             // If more than that are intersecting it will be reported once for each pair.
             // Todo: fix it!
-            Ok(Box::new(rv.into_iter()))
+            Ok(rv.into())
         } else {
             // at around >25 line segments the sweep-line algorithm is faster
             algorithm::AlgorithmData::<T>::default()
@@ -829,17 +1302,157 @@ where
                 .compute()
         }
     }
+
+    fn is_self_intersecting_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<bool, IntersectError> {
+        Ok(self_intersections_with_tolerance_(
+            &self.lines().collect::<Vec<_>>(),
+            true,
+            true,
+            tolerance,
+        )?
+        .next()
+        .is_some())
+    }
+
+    /// Returns an iterator containing the found intersections, using a custom tolerance.
+    /// ```
+    /// # use intersect2d::{SelfIntersectingExclusive, Tolerance};
+    /// // the last vertex almost touches the first segment
+    /// let line_string = geo::LineString::<f64>::from(vec![(0., 0.), (10., 0.), (10., 10.), (5., 0.001)]);
+    /// assert!(!line_string.is_self_intersecting()?);
+    /// let tolerance = Tolerance::new(0.01, 4);
+    /// assert!(line_string.is_self_intersecting_with_tolerance(&tolerance)?);
+    /// assert_eq!(line_string.self_intersections_with_tolerance(&tolerance)?.count(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    fn self_intersections_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<Intersections<T>, IntersectError> {
+        self_intersections_with_tolerance_(
+            &self.lines().collect::<Vec<_>>(),
+            true,
+            false,
+            tolerance,
+        )
+    }
+}
+
+/// The segments of a layer of line strings, flattened in feature order
+pub(crate) fn layer_lines<T>(layer: &[geo::LineString<T>]) -> Vec<geo::Line<T>>
+where
+    T: Float + geo::CoordFloat,
+{
+    layer
+        .iter()
+        .flat_map(|ls| ls.0.windows(2).map(|w| geo::Line::new(w[0], w[1])))
+        .collect()
+}
+
+impl<T> SelfIntersectingExclusive<T> for Vec<geo::LineString<T>>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns true if any segment of the layer intersects any other segment, of the same or of
+    /// another line string, at a point that isn't an end point of both.
+    /// ```
+    /// # use intersect2d::SelfIntersectingExclusive;
+    /// let layer = vec![
+    ///     geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]),
+    ///     geo::LineString::from(vec![(10., 10.), (20., 10.)]),
+    /// ];
+    /// assert!(!layer.is_self_intersecting()?);
+    /// let layer = vec![
+    ///     geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]),
+    ///     geo::LineString::from(vec![(5., 10.), (5., -10.)]),
+    /// ];
+    /// assert!(layer.is_self_intersecting()?);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    fn is_self_intersecting(&self) -> Result<bool, IntersectError> {
+        layer_lines(self).is_self_intersecting()
+    }
+
+    /// Returns the intersections of the layer. The line indices count the segments of all
+    /// the line strings in order, [`layer::LayerIntersections`] reports them as (line string
+    /// index, segment index) pairs instead.
+    fn self_intersections(&self) -> Result<Intersections<T>, IntersectError> {
+        layer_lines(self).self_intersections()
+    }
+
+    fn is_self_intersecting_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<bool, IntersectError> {
+        layer_lines(self).is_self_intersecting_with_tolerance(tolerance)
+    }
+
+    fn self_intersections_with_tolerance(
+        &self,
+        tolerance: &Tolerance<T>,
+    ) -> Result<Intersections<T>, IntersectError> {
+        layer_lines(self).self_intersections_with_tolerance(tolerance)
+    }
 }
 
 /// returns true if the two coordinates are virtually identical
-///
+/// (within the default epsilon and max_ulps of `T`, see [`ulps_eq_c_with()`])
 #[inline(always)]
 pub fn ulps_eq_c<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> bool
 where
     T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
     T::Epsilon: Copy,
 {
-    approx::ulps_eq!(&a.x, &b.x) && approx::ulps_eq!(&a.y, &b.y)
+    ulps_eq_c_with(a, b, T::default_epsilon(), T::default_max_ulps())
+}
+
+/// returns true if the two coordinates are virtually identical, `x` and `y` are compared
+/// with `approx::ulps_eq!(.., epsilon = epsilon, max_ulps = max_ulps)`.
+/// Values closer than the absolute `epsilon` are always equal, so a larger epsilon loosens
+/// the comparison near zero where units in the last place are tiny. Larger `max_ulps`
+/// values loosen it far from zero.
+/// ```
+/// # use intersect2d::{ulps_eq_c, ulps_eq_c_with};
+/// let a = geo::Coordinate { x: 1.0e6, y: 0.0 };
+/// let b = geo::Coordinate { x: 1.0e6 + 1.0e-9, y: 1.0e-12 };
+/// assert!(!ulps_eq_c(&a, &b));
+/// assert!(ulps_eq_c_with(&a, &b, 1.0e-9, 32));
+/// ```
+#[inline(always)]
+pub fn ulps_eq_c_with<T>(
+    a: &geo::Coordinate<T>,
+    b: &geo::Coordinate<T>,
+    epsilon: T::Epsilon,
+    max_ulps: u32,
+) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    a.x.ulps_eq(&b.x, epsilon, max_ulps) && a.y.ulps_eq(&b.y, epsilon, max_ulps)
+}
+
+/// Returns true if the two lines have an end point in common according to `eq`
+#[inline(always)]
+fn share_end_point<T, F>(l1: &geo::Line<T>, l2: &geo::Line<T>, eq: F) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    F: Fn(&geo::Coordinate<T>, &geo::Coordinate<T>) -> bool,
+{
+    eq(&l1.start, &l2.start)
+        || eq(&l1.start, &l2.end)
+        || eq(&l1.end, &l2.start)
+        || eq(&l1.end, &l2.end)
 }
 
 #[inline(always)]
@@ -0,0 +1,696 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Owned containers of intersection results.
+//!
+//! [`AlgorithmData::compute()`](crate::algorithm::AlgorithmData::compute) returns the results
+//! as an [`Intersections`] iterator, [`IntersectionList`] holds the collected results instead
+//! (as returned by
+//! [`AlgorithmData::compute_collected()`](crate::algorithm::AlgorithmData::compute_collected)).
+//! It dereferences to a slice, so indexing, slicing and all the slice methods work on it.
+//! With the `rayon` feature both containers can be turned into rayon parallel iterators.
+//! With the `serde` feature [`IntersectionList`] implements `Serialize` and `Deserialize`,
+//! tagged with [`SERIALIZATION_VERSION`](crate::SERIALIZATION_VERSION).
+//! [`IntersectionList::to_dot()`] exports the results as a Graphviz intersection graph.
+//! [`IntersectionList::unique_points()`] merges the intersection points that are identical
+//! within a [`Tolerance`] and returns the distinct locations.
+//! [`IntersectionList::by_segment()`] regroups the results per input segment, sorted along
+//! the segment, the order needed to split the segments.
+//! [`IntersectionList::end_point_intersections()`] and
+//! [`IntersectionList::interior_intersections()`] split the results of an inclusive search
+//! into the points where the involved lines only meet at their end points, and the rest.
+
+use crate::vector::dot;
+use crate::Tolerance;
+use num_traits::Float;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::fmt;
+use std::fmt::Write;
+
+/// Groups `points` that are identical within `tolerance`. Returns the group of every point
+/// and the first point, in sweep-line order, of every group. The groups are numbered by y
+/// then x.
+pub(crate) fn cluster_points<T>(
+    points: &[geo::Coordinate<T>],
+    tolerance: &Tolerance<T>,
+) -> (Vec<usize>, Vec<geo::Coordinate<T>>)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let precise: Vec<geo::Coordinate<T>> = points
+        .iter()
+        .map(|p| tolerance.precision.make_precise_c(*p))
+        .collect();
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|a, b| {
+        let (a, b) = (&precise[*a], &precise[*b]);
+        a.y.partial_cmp(&b.y)
+            .unwrap_or(cmp::Ordering::Equal)
+            .then(a.x.partial_cmp(&b.x).unwrap_or(cmp::Ordering::Equal))
+    });
+    let mut groups = vec![0; points.len()];
+    // (precise position, position) of the first point of each group
+    let mut rv = Vec::<(geo::Coordinate<T>, geo::Coordinate<T>)>::new();
+    for i in order.into_iter() {
+        let p = &precise[i];
+        // only the groups with a nearby y need to be compared
+        let group = rv
+            .iter()
+            .enumerate()
+            .rev()
+            .take_while(|(_, (q, _))| q.y >= p.y || tolerance.ulps_eq(q.y, p.y))
+            .find(|(_, (q, _))| tolerance.ulps_eq_c(q, p))
+            .map(|(group, _)| group);
+        groups[i] = group.unwrap_or_else(|| {
+            rv.push((*p, points[i]));
+            rv.len() - 1
+        });
+    }
+    (groups, rv.into_iter().map(|(_, p)| p).collect())
+}
+
+/// Returns the distinct positions of `items`, positions identical within `tolerance` are
+/// merged into the first of them in sweep-line order
+fn unique_points<T>(
+    items: &[(geo::Coordinate<T>, Vec<usize>)],
+    tolerance: &Tolerance<T>,
+) -> Vec<geo::Coordinate<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let points: Vec<geo::Coordinate<T>> = items.iter().map(|(p, _)| *p).collect();
+    cluster_points(&points, tolerance).1
+}
+
+/// An intersection point on an input segment, see [`IntersectionList::by_segment()`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SegmentPoint<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The parametric position of the point along the segment, 0 at the start and 1 at the
+    /// end
+    pub t: T,
+    pub position: geo::Coordinate<T>,
+    /// The index of the intersection in the results
+    pub result: usize,
+}
+
+/// Returns the points of `items` on each of the `lines`, sorted by their parametric position
+/// along the line. Line indices outside of `lines` are ignored.
+pub(crate) fn by_segment<T>(
+    items: &[(geo::Coordinate<T>, Vec<usize>)],
+    lines: &[geo::Line<T>],
+) -> Vec<Vec<SegmentPoint<T>>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut rv = vec![Vec::<SegmentPoint<T>>::new(); lines.len()];
+    for (result, (position, involved)) in items.iter().enumerate() {
+        for l in involved.iter().filter(|l| **l < lines.len()) {
+            let line = &lines[*l];
+            let r = line.end - line.start;
+            let r_dot_r = dot(&r, &r);
+            let t = if r_dot_r > T::zero() {
+                dot(&(*position - line.start), &r) / r_dot_r
+            } else {
+                T::zero()
+            };
+            rv[*l].push(SegmentPoint {
+                t,
+                position: *position,
+                result,
+            });
+        }
+    }
+    for points in rv.iter_mut() {
+        points.sort_by(|a, b| a.t.partial_cmp(&b.t).unwrap_or(cmp::Ordering::Equal));
+    }
+    rv
+}
+
+/// Returns true if `position` is an end point, within `tolerance`, of all the `involved` lines.
+/// Indices outside of `lines` are never end points.
+fn is_end_point_intersection<T>(
+    position: &geo::Coordinate<T>,
+    involved: &[usize],
+    lines: &[geo::Line<T>],
+    tolerance: &Tolerance<T>,
+) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    involved.iter().all(|l| {
+        lines.get(*l).map_or(false, |line| {
+            tolerance.ulps_eq_c(&line.start, position) || tolerance.ulps_eq_c(&line.end, position)
+        })
+    })
+}
+
+/// The intersection points and the indices of the lines involved in each point, in sweep-line
+/// order
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    items: Vec<(geo::Coordinate<T>, Vec<usize>)>,
+}
+
+impl<T> IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the intersection at position `index`, or None if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&(geo::Coordinate<T>, Vec<usize>)> {
+        self.items.get(index)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, (geo::Coordinate<T>, Vec<usize>)> {
+        self.items.iter()
+    }
+
+    /// Returns the intersection points without the involved lines
+    pub fn points(&self) -> impl Iterator<Item = geo::Coordinate<T>> + '_ {
+        self.items.iter().map(|(p, _)| *p)
+    }
+
+    pub fn into_inner(self) -> Vec<(geo::Coordinate<T>, Vec<usize>)> {
+        self.items
+    }
+
+    /// Returns the distinct intersection points, sorted by y then x. Points identical within
+    /// `tolerance` are merged, the first of them in sweep-line order represents the group.
+    /// ```
+    /// # use intersect2d::list::IntersectionList;
+    /// # use intersect2d::Tolerance;
+    /// let c = |x: f64, y: f64| geo::Coordinate { x, y };
+    /// let results: IntersectionList<f64> = vec![
+    ///     (c(1., 1.), vec![0, 1]),
+    ///     (c(1.001, 1.), vec![2, 3]),
+    ///     (c(5., 1.), vec![0, 2]),
+    /// ]
+    /// .into();
+    /// assert_eq!(results.unique_points(&Tolerance::default()).len(), 3);
+    /// assert_eq!(
+    ///     results.unique_points(&Tolerance::new(0.01, 4)),
+    ///     vec![c(1., 1.), c(5., 1.)]
+    /// );
+    /// ```
+    pub fn unique_points(&self, tolerance: &Tolerance<T>) -> Vec<geo::Coordinate<T>> {
+        unique_points(&self.items, tolerance)
+    }
+
+    /// Returns the intersections on each of the `lines` the results were computed for,
+    /// indexed like `lines` and sorted by the parametric position along the line. An
+    /// intersection involving several lines is listed for each of them, and an overlap for
+    /// both of its end points.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [10., 0., 0., 0.],
+    ///     [2., -1., 2., 1.],
+    ///     [7., -1., 7., 1.],
+    /// ]);
+    /// let results = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute_collected()?;
+    /// let by_segment = results.by_segment(&lines);
+    /// assert_eq!(by_segment.len(), 3);
+    /// let t: Vec<f64> = by_segment[0].iter().map(|p| p.t).collect();
+    /// assert_eq!(t, vec![0.3, 0.8]);
+    /// assert_eq!(by_segment[0][0].position, geo::Coordinate { x: 7., y: 0. });
+    /// assert_eq!(results[by_segment[0][0].result].1, vec![0, 2]);
+    /// assert_eq!(by_segment[1].len(), 1);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn by_segment(&self, lines: &[geo::Line<T>]) -> Vec<Vec<SegmentPoint<T>>> {
+        by_segment(&self.items, lines)
+    }
+
+    /// Returns the intersections where all the involved lines meet at one of their end
+    /// points (within `tolerance`), i.e. the points a search ignoring end point
+    /// intersections wouldn't report. `lines` are the lines the results were computed for.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// # use intersect2d::Tolerance;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 0.],
+    ///     [10., 0., 10., 10.],
+    ///     [5., -5., 5., 5.],
+    ///     [0., 0., 0., 10.],
+    ///     [-5., 5., 0., 5.],
+    /// ]);
+    /// let results = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute_collected()?;
+    /// let tolerance = Tolerance::default();
+    /// let end_points: Vec<_> = results.end_point_intersections(&lines, &tolerance).collect();
+    /// assert_eq!(end_points.len(), 2);
+    /// assert_eq!(end_points[0].0, geo::Coordinate { x: 0., y: 0. });
+    /// assert_eq!(end_points[1].0, geo::Coordinate { x: 10., y: 0. });
+    /// let interior: Vec<_> = results.interior_intersections(&lines, &tolerance).collect();
+    /// assert_eq!(interior.len(), 2);
+    /// // the end point of line 4 touches the interior of line 3
+    /// assert_eq!(interior[1].0, geo::Coordinate { x: 0., y: 5. });
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn end_point_intersections<'a>(
+        &'a self,
+        lines: &'a [geo::Line<T>],
+        tolerance: &'a Tolerance<T>,
+    ) -> impl Iterator<Item = &'a (geo::Coordinate<T>, Vec<usize>)> + 'a {
+        self.items
+            .iter()
+            .filter(move |(p, involved)| is_end_point_intersection(p, involved, lines, tolerance))
+    }
+
+    /// Returns the intersections where at least one of the involved lines is met in its
+    /// interior, the complement of [`IntersectionList::end_point_intersections()`].
+    pub fn interior_intersections<'a>(
+        &'a self,
+        lines: &'a [geo::Line<T>],
+        tolerance: &'a Tolerance<T>,
+    ) -> impl Iterator<Item = &'a (geo::Coordinate<T>, Vec<usize>)> + 'a {
+        self.items
+            .iter()
+            .filter(move |(p, involved)| !is_end_point_intersection(p, involved, lines, tolerance))
+    }
+}
+
+impl<T> IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + fmt::Display,
+    T::Epsilon: Copy,
+{
+    /// Returns the intersection graph as a Graphviz DOT document. The nodes are the lines
+    /// involved in any intersection, named by their index. Every pair of lines meeting at an
+    /// intersection point is connected by an edge labeled with the position of the point.
+    /// ```
+    /// # use intersect2d::algorithm::AlgorithmData;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 5., 10., 5.],
+    ///     [5., 0., 5., 10.],
+    ///     [2., 0., 2., 10.],
+    /// ]);
+    /// let results = AlgorithmData::<f64>::default()
+    ///     .with_ref_lines(lines.iter())?
+    ///     .compute_collected()?;
+    /// let expected = [
+    ///     "graph intersections {",
+    ///     "    0;",
+    ///     "    1;",
+    ///     "    2;",
+    ///     "    0 -- 2 [label=\"(2, 5)\"];",
+    ///     "    0 -- 1 [label=\"(5, 5)\"];",
+    ///     "}",
+    /// ];
+    /// assert_eq!(results.to_dot().lines().collect::<Vec<_>>(), expected);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut nodes: Vec<usize> = self
+            .items
+            .iter()
+            .flat_map(|(_, lines)| lines.iter().copied())
+            .collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+
+        let mut rv = String::from("graph intersections {\n");
+        for node in nodes {
+            let _ = writeln!(rv, "    {};", node);
+        }
+        for (position, lines) in self.items.iter() {
+            let mut lines = lines.clone();
+            lines.sort_unstable();
+            lines.dedup();
+            for (i, l1) in lines.iter().enumerate() {
+                for l2 in lines.iter().skip(i + 1) {
+                    let _ = writeln!(
+                        rv,
+                        "    {} -- {} [label=\"({}, {})\"];",
+                        l1, l2, position.x, position.y
+                    );
+                }
+            }
+        }
+        rv.push_str("}\n");
+        rv
+    }
+}
+
+impl<T> From<Vec<(geo::Coordinate<T>, Vec<usize>)>> for IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn from(items: Vec<(geo::Coordinate<T>, Vec<usize>)>) -> Self {
+        Self { items }
+    }
+}
+
+impl<T> std::iter::FromIterator<(geo::Coordinate<T>, Vec<usize>)> for IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn from_iter<I: IntoIterator<Item = (geo::Coordinate<T>, Vec<usize>)>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> std::ops::Deref for IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    type Target = [(geo::Coordinate<T>, Vec<usize>)];
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<T> IntoIterator for IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    type Item = (geo::Coordinate<T>, Vec<usize>);
+    type IntoIter = std::vec::IntoIter<(geo::Coordinate<T>, Vec<usize>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    type Item = &'a (geo::Coordinate<T>, Vec<usize>);
+    type IntoIter = std::slice::Iter<'a, (geo::Coordinate<T>, Vec<usize>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+/// The iterator over the results of a search, as returned by
+/// [`AlgorithmData::compute()`](crate::algorithm::AlgorithmData::compute) and the self
+/// intersection traits. The results are owned by the iterator, so it can be cloned to
+/// iterate them more than once, and traversed from either end.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [0., 10., 10., 0.],
+///     [0., 1., 10., 1.],
+/// ]);
+/// let results = AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .compute()?;
+/// let last = results.clone().next_back().unwrap();
+/// assert_eq!(last.0, geo::Coordinate { x: 5., y: 5. });
+/// assert_eq!(results.rev().next(), Some(last));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    inner: std::vec::IntoIter<(geo::Coordinate<T>, Vec<usize>)>,
+}
+
+impl<T> Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns the remaining results as a slice
+    pub fn as_slice(&self) -> &[(geo::Coordinate<T>, Vec<usize>)] {
+        self.inner.as_slice()
+    }
+
+    /// Returns the distinct points of the remaining results, see
+    /// [`IntersectionList::unique_points()`]
+    pub fn unique_points(&self, tolerance: &Tolerance<T>) -> Vec<geo::Coordinate<T>> {
+        unique_points(self.inner.as_slice(), tolerance)
+    }
+
+    /// Returns the remaining intersections on each of the `lines`, see
+    /// [`IntersectionList::by_segment()`]. The result indices refer to [`Intersections::as_slice()`].
+    pub fn by_segment(&self, lines: &[geo::Line<T>]) -> Vec<Vec<SegmentPoint<T>>> {
+        by_segment(self.inner.as_slice(), lines)
+    }
+
+    /// Returns the remaining end point intersections, see
+    /// [`IntersectionList::end_point_intersections()`]
+    pub fn end_point_intersections<'a>(
+        &'a self,
+        lines: &'a [geo::Line<T>],
+        tolerance: &'a Tolerance<T>,
+    ) -> impl Iterator<Item = &'a (geo::Coordinate<T>, Vec<usize>)> + 'a {
+        self.as_slice()
+            .iter()
+            .filter(move |(p, involved)| is_end_point_intersection(p, involved, lines, tolerance))
+    }
+
+    /// Returns the remaining interior intersections, see
+    /// [`IntersectionList::interior_intersections()`]
+    pub fn interior_intersections<'a>(
+        &'a self,
+        lines: &'a [geo::Line<T>],
+        tolerance: &'a Tolerance<T>,
+    ) -> impl Iterator<Item = &'a (geo::Coordinate<T>, Vec<usize>)> + 'a {
+        self.as_slice()
+            .iter()
+            .filter(move |(p, involved)| !is_end_point_intersection(p, involved, lines, tolerance))
+    }
+}
+
+impl<T> From<Vec<(geo::Coordinate<T>, Vec<usize>)>> for Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn from(items: Vec<(geo::Coordinate<T>, Vec<usize>)>) -> Self {
+        Self {
+            inner: items.into_iter(),
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<(geo::Coordinate<T>, Vec<usize>)> for Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn from_iter<I: IntoIterator<Item = (geo::Coordinate<T>, Vec<usize>)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> Iterator for Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    type Item = (geo::Coordinate<T>, Vec<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+}
+
+impl<T> std::iter::FusedIterator for Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+}
+
+#[cfg(feature = "rayon")]
+impl<T> IntoParallelIterator for IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Send,
+    T::Epsilon: Copy,
+{
+    type Iter = rayon::vec::IntoIter<(geo::Coordinate<T>, Vec<usize>)>;
+    type Item = (geo::Coordinate<T>, Vec<usize>);
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.items.into_par_iter()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T> IntoParallelIterator for &'a IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Sync,
+    T::Epsilon: Copy,
+{
+    type Iter = rayon::slice::Iter<'a, (geo::Coordinate<T>, Vec<usize>)>;
+    type Item = &'a (geo::Coordinate<T>, Vec<usize>);
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.items.par_iter()
+    }
+}
+
+/// The remaining items are moved into a vector, re-using the allocation, and split between
+/// the rayon worker threads from there.
+#[cfg(feature = "rayon")]
+impl<T> IntoParallelIterator for Intersections<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Send,
+    T::Epsilon: Copy,
+{
+    type Iter = rayon::vec::IntoIter<(geo::Coordinate<T>, Vec<usize>)>;
+    type Item = (geo::Coordinate<T>, Vec<usize>);
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.inner.collect::<Vec<_>>().into_par_iter()
+    }
+}
+
+/// The serialized form of an [`IntersectionList`], one `(x, y, lines)` tuple per point
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct IntersectionListRef<'a, T: Serialize> {
+    version: u32,
+    items: Vec<(T, T, &'a [usize])>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct IntersectionListOwned<T> {
+    version: u32,
+    items: Vec<(T, T, Vec<usize>)>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Serialize,
+    T::Epsilon: Copy,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        IntersectionListRef {
+            version: crate::SERIALIZATION_VERSION,
+            items: self
+                .items
+                .iter()
+                .map(|(c, lines)| (c.x, c.y, lines.as_slice()))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for IntersectionList<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Deserialize<'de>,
+    T::Epsilon: Copy,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let wire = IntersectionListOwned::<T>::deserialize(deserializer)?;
+        crate::check_serialization_version(wire.version)?;
+        Ok(Self {
+            items: wire
+                .items
+                .into_iter()
+                .map(|(x, y, lines)| (geo::Coordinate { x, y }, lines))
+                .collect(),
+        })
+    }
+}
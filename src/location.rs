@@ -0,0 +1,331 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Point location in an arrangement of noded segments.
+//!
+//! [`PointLocator`] splits the plane into vertical slabs at every end point and sorts the
+//! segments crossing each slab bottom to top, the persistent sweep slabs of Dobkin and Lipton.
+//! A query is two binary searches, O(log n), and finds the segment the point is on and the
+//! closest segments directly below and above it. The slabs take O(n²) memory in the worst case,
+//! and far less for typical data. The same slabs answer vertical ray queries, see
+//! [`PointLocator::crossings_below()`], the parity of such a count tells if a point is inside
+//! the faces of the arrangement. An arbitrary segment soup can be noded with
+//! [`node_lines()`](crate::noding::node_lines) first.
+//!
+//! The segments must be noded, i.e. they may only touch at their end points. The
+//! noded segments of an arrangement are left unchanged by the sweep-line
+//! ([`AlgorithmData`](crate::algorithm::AlgorithmData) with `ignore_end_point_intersections`
+//! reports nothing for them), which is what [`PointLocator::new()`] checks.
+
+use crate::algorithm::AlgorithmData;
+use crate::{ulps_eq_c, IntersectError};
+use num_traits::Float;
+use std::cmp;
+
+/// The result of a [`PointLocator::locate()`] query, the segment indices refer to the input
+/// slice
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    /// A segment the point is on, if any
+    pub on: Option<usize>,
+    /// The closest segment directly below the point (not counting `on`)
+    pub below: Option<usize>,
+    /// The closest segment directly above the point (not counting `on`)
+    pub above: Option<usize>,
+}
+
+/// A point location structure over noded segments, see the module documentation
+pub struct PointLocator<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    // the input segments, oriented left to right (bottom to top if vertical)
+    lines: Vec<geo::Line<T>>,
+    // the distinct x coordinates of the end points, slab i is between xs[i] and xs[i+1]
+    xs: Vec<T>,
+    // the non vertical segments crossing each slab, sorted bottom to top
+    slabs: Vec<Vec<usize>>,
+    // the vertical segments at each x coordinate
+    verticals: Vec<Vec<usize>>,
+}
+
+impl<T> PointLocator<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Builds the point location structure.
+    /// Returns an `InvalidData` error if the segments are not noded.
+    /// ```
+    /// # use intersect2d::location::{Location, PointLocator};
+    /// // a square split by a diagonal
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 0.],
+    ///     [10., 0., 10., 10.],
+    ///     [10., 10., 0., 10.],
+    ///     [0., 10., 0., 0.],
+    ///     [0., 0., 10., 10.],
+    /// ]);
+    /// let locator = PointLocator::new(&lines)?;
+    /// let location = locator.locate(&geo::Coordinate { x: 7., y: 3. });
+    /// assert_eq!(location, Location { on: None, below: Some(0), above: Some(4) });
+    /// let location = locator.locate(&geo::Coordinate { x: 3., y: 7. });
+    /// assert_eq!(location, Location { on: None, below: Some(4), above: Some(2) });
+    /// let location = locator.locate(&geo::Coordinate { x: 5., y: 5. });
+    /// assert_eq!(location, Location { on: Some(4), below: Some(0), above: Some(2) });
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn new(lines: &[geo::Line<T>]) -> Result<Self, IntersectError> {
+        if AlgorithmData::<T>::default()
+            .with_ignore_end_point_intersections(true)?
+            .with_stop_at_first_intersection(true)?
+            .with_ref_lines(lines.iter())?
+            .compute()?
+            .next()
+            .is_some()
+        {
+            return Err(IntersectError::InvalidData(
+                "The segments must be noded, they may only touch at their end points".to_string(),
+            ));
+        }
+        let compare = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal);
+        let lines: Vec<geo::Line<T>> = lines
+            .iter()
+            .map(|l| {
+                let left_to_right = (l.start.x, l.start.y) <= (l.end.x, l.end.y);
+                if left_to_right {
+                    *l
+                } else {
+                    geo::Line::new(l.end, l.start)
+                }
+            })
+            .collect();
+        let mut xs: Vec<T> = lines
+            .iter()
+            .flat_map(|l| vec![l.start.x, l.end.x])
+            .collect();
+        xs.sort_unstable_by(compare);
+        xs.dedup();
+        let slab_of = |x: T| xs.partition_point(|v| *v < x);
+
+        let mut slabs = vec![Vec::<usize>::new(); xs.len().saturating_sub(1)];
+        let mut verticals = vec![Vec::<usize>::new(); xs.len()];
+        for (i, a_line) in lines.iter().enumerate() {
+            let (first, last) = (slab_of(a_line.start.x), slab_of(a_line.end.x));
+            if first == last {
+                if !ulps_eq_c(&a_line.start, &a_line.end) {
+                    verticals[first].push(i);
+                }
+                continue;
+            }
+            for slab in slabs[first..last].iter_mut() {
+                slab.push(i);
+            }
+        }
+        let mut rv = Self {
+            lines,
+            xs,
+            slabs: Vec::new(),
+            verticals,
+        };
+        for (i, mut slab) in slabs.into_iter().enumerate() {
+            // the segments don't cross, so any x inside the slab gives the same order
+            let x = (rv.xs[i] + rv.xs[i + 1]) / (T::one() + T::one());
+            slab.sort_unstable_by(|a, b| compare(&rv.y_at(*a, x), &rv.y_at(*b, x)));
+            rv.slabs.push(slab);
+        }
+        Ok(rv)
+    }
+
+    /// The y coordinate of the non vertical segment `line` at `x`
+    fn y_at(&self, line: usize, x: T) -> T {
+        let l = &self.lines[line];
+        if x == l.start.x {
+            l.start.y
+        } else if x == l.end.x {
+            l.end.y
+        } else {
+            l.start.y + (x - l.start.x) * (l.end.y - l.start.y) / (l.end.x - l.start.x)
+        }
+    }
+
+    /// Returns the number of segments
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns true if there are no segments
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Finds the segment `point` is on, and the closest segments directly below and above it.
+    /// A point on an end point shared by several segments reports one of them as `on`.
+    pub fn locate(&self, point: &geo::Coordinate<T>) -> Location {
+        let mut rv = Location::default();
+        if self.xs.is_empty() || point.x < self.xs[0] || point.x > *self.xs.last().unwrap() {
+            return rv;
+        }
+        let index = self.xs.partition_point(|v| *v < point.x);
+        if index < self.xs.len() && self.xs[index] == point.x {
+            for l in self.verticals[index].iter() {
+                let a_line = &self.lines[*l];
+                if a_line.start.y <= point.y && point.y <= a_line.end.y {
+                    rv.on = Some(*l);
+                }
+            }
+        }
+        // a point on a slab boundary is located in the slab to the right, or the last slab
+        let slab = match self.slabs.len() {
+            0 => return rv,
+            len if self.xs[index] == point.x => index.min(len - 1),
+            _ => index - 1,
+        };
+        let slab = &self.slabs[slab];
+        let y = |l: usize| self.y_at(l, point.x);
+        let first_not_below = slab.partition_point(|l| {
+            let y = y(*l);
+            y < point.y && !approx::ulps_eq!(y, point.y)
+        });
+        let mut first_above = first_not_below;
+        while first_above < slab.len() && approx::ulps_eq!(y(slab[first_above]), point.y) {
+            if rv.on.is_none() {
+                rv.on = Some(slab[first_above]);
+            }
+            first_above += 1;
+        }
+        if first_not_below > 0 {
+            rv.below = Some(slab[first_not_below - 1]);
+        }
+        rv.above = slab.get(first_above).copied();
+        rv
+    }
+
+    /// The slab a vertical ray through `point` crosses, and the range of its segments the
+    /// point is on, `None` if the ray crosses no slab. A segment crosses the ray if its
+    /// left end point is at or left of `point.x`, and its right end point is right of it.
+    fn ray_slab(&self, point: &geo::Coordinate<T>) -> Option<(&[usize], usize, usize)> {
+        if self.xs.is_empty() || point.x < self.xs[0] || point.x >= *self.xs.last().unwrap() {
+            return None;
+        }
+        // a point on a slab boundary is in the slab to the right
+        let index = self.xs.partition_point(|v| *v < point.x);
+        let slab = if self.xs[index] == point.x {
+            index
+        } else {
+            index - 1
+        };
+        let slab = &self.slabs[slab];
+        let y = |l: usize| self.y_at(l, point.x);
+        let first_not_below = slab.partition_point(|l| {
+            let y = y(*l);
+            y < point.y && !approx::ulps_eq!(y, point.y)
+        });
+        let mut first_above = first_not_below;
+        while first_above < slab.len() && approx::ulps_eq!(y(slab[first_above]), point.y) {
+            first_above += 1;
+        }
+        Some((slab, first_not_below, first_above))
+    }
+
+    /// Returns the segments crossed by the vertical ray from `point` downwards, sorted from the
+    /// closest to the farthest.
+    /// Only segments with their left end point at or left of `point.x`, and their right end
+    /// point right of it, are counted. A ray through a vertex then counts exactly one of the
+    /// two segments meeting there if they continue on either side, and none or both if they
+    /// both end on the same side, so the parity of the count is right for any point not on a
+    /// segment. Vertical segments and the segments `point` is on are never counted.
+    /// ```
+    /// # use intersect2d::location::PointLocator;
+    /// // a square with a diagonal, and a triangle inside the upper half
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 0.],
+    ///     [10., 0., 10., 10.],
+    ///     [10., 10., 0., 10.],
+    ///     [0., 10., 0., 0.],
+    ///     [0., 0., 10., 10.],
+    ///     [1., 8., 4., 8.],
+    ///     [4., 8., 1., 5.],
+    ///     [1., 5., 1., 8.],
+    /// ]);
+    /// let locator = PointLocator::new(&lines)?;
+    /// let point = geo::Coordinate { x: 2., y: 7. };
+    /// assert_eq!(locator.segments_below(&point), &[6, 4, 0]);
+    /// assert_eq!(locator.crossings_below(&point), 3);
+    /// assert_eq!(locator.segments_above(&point), &[5, 2]);
+    /// assert_eq!(locator.crossings_above(&point), 2);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn segments_below(&self, point: &geo::Coordinate<T>) -> Vec<usize> {
+        self.ray_slab(point)
+            .map_or_else(Vec::new, |(slab, first_not_below, _)| {
+                slab[..first_not_below].iter().rev().copied().collect()
+            })
+    }
+
+    /// Returns the segments crossed by the vertical ray from `point` upwards, sorted from the
+    /// closest to the farthest, see [`PointLocator::segments_below()`].
+    pub fn segments_above(&self, point: &geo::Coordinate<T>) -> Vec<usize> {
+        self.ray_slab(point)
+            .map_or_else(Vec::new, |(slab, _, first_above)| {
+                slab[first_above..].to_vec()
+            })
+    }
+
+    /// Returns the number of segments crossed by the vertical ray from `point` downwards,
+    /// see [`PointLocator::segments_below()`], without building the list.
+    pub fn crossings_below(&self, point: &geo::Coordinate<T>) -> usize {
+        self.ray_slab(point)
+            .map_or(0, |(_, first_not_below, _)| first_not_below)
+    }
+
+    /// Returns the number of segments crossed by the vertical ray from `point` upwards,
+    /// see [`PointLocator::segments_below()`], without building the list.
+    pub fn crossings_above(&self, point: &geo::Coordinate<T>) -> usize {
+        self.ray_slab(point)
+            .map_or(0, |(slab, _, first_above)| slab.len() - first_above)
+    }
+}
@@ -0,0 +1,145 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+//! Loops of self intersecting rings.
+//!
+//! [`ring_loops()`] splits a self intersecting ring at its crossings, and at the vertices it
+//! passes more than once, into simple sub-loops and returns the signed area of each one. A
+//! figure eight becomes two loops of opposite orientation, while a ring crossing itself in a
+//! tiny twist gets a "pinhole" loop of negligible area that can be told apart from the
+//! significant loops.
+
+use crate::algorithm::AlgorithmData;
+use crate::list::cluster_points;
+use crate::nesting::signed_ring_area;
+use crate::noding::node_lines;
+use crate::{ulps_eq_c, Closedness, IntersectError, Tolerance};
+use num_traits::Float;
+
+/// A simple sub-loop of a ring, see [`ring_loops()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RingLoop<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The closed loop, in the direction of the input ring. It starts, and ends, at the
+    /// point where it was split off the ring.
+    pub ring: geo::LineString<T>,
+    /// The signed area of the loop, positive if the loop is counter clockwise
+    pub signed_area: T,
+}
+
+/// Splits `ring` into simple loops at the points where it crosses, or touches, itself and
+/// returns the loops in the order they are closed when walking the ring. The ring is closed
+/// (see [`Closedness::Closed`]) whether or not it repeats its first point. The signed areas of
+/// the loops add up to the signed area of the ring. Parts where the ring doubles back over
+/// itself only enclose zero area, loops with less than three vertices are dropped.
+/// ```
+/// # use intersect2d::loops::ring_loops;
+/// let figure_eight = geo::LineString::from(vec![(0., 0.), (10., 10.), (10., 0.), (0., 10.)]);
+/// let loops = ring_loops(&figure_eight)?;
+/// assert_eq!(loops.len(), 2);
+/// // the right loop is closed first, it is clockwise
+/// assert_eq!(loops[0].signed_area, -25.);
+/// assert_eq!(loops[0].ring.0[0], geo::Coordinate { x: 5., y: 5. });
+/// assert_eq!(loops[1].signed_area, 25.);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn ring_loops<T>(ring: &geo::LineString<T>) -> Result<Vec<RingLoop<T>>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let lines: Vec<geo::Line<T>> = Closedness::Closed
+        .segments(ring)
+        .into_iter()
+        .filter(|l| !ulps_eq_c(&l.start, &l.end))
+        .collect();
+    let results: Vec<(geo::Coordinate<T>, Vec<usize>)> = AlgorithmData::<T>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    let tolerance = Tolerance::default();
+    // the vertices of the ring with the split points inserted, in ring order
+    let vertices: Vec<geo::Coordinate<T>> = node_lines(&lines, &results, &tolerance)
+        .into_iter()
+        .map(|piece| piece.line.start)
+        .collect();
+    let (ids, _) = cluster_points(&vertices, &tolerance);
+
+    let mut rv = Vec::<RingLoop<T>>::new();
+    let mut push_loop = |points: Vec<geo::Coordinate<T>>| {
+        // the points of a loop are closed, a loop with three points is a spike
+        if points.len() > 3 {
+            let ring = geo::LineString::from(points);
+            let signed_area = signed_ring_area(&ring);
+            rv.push(RingLoop { ring, signed_area });
+        }
+    };
+    // the vertices of the loop being walked, and where each of their positions is on the stack
+    let mut stack = Vec::<usize>::with_capacity(vertices.len());
+    let mut on_stack = ahash::AHashMap::<usize, usize>::default();
+    for (v, id) in ids.iter().enumerate() {
+        if let Some(first) = on_stack.get(id).copied() {
+            let mut points: Vec<geo::Coordinate<T>> =
+                stack[first..].iter().map(|v| vertices[*v]).collect();
+            points.push(vertices[stack[first]]);
+            for popped in stack.drain(first + 1..) {
+                let _ = on_stack.remove(&ids[popped]);
+            }
+            push_loop(points);
+        } else {
+            let _ = on_stack.insert(*id, stack.len());
+            stack.push(v);
+        }
+    }
+    if !stack.is_empty() {
+        let mut points: Vec<geo::Coordinate<T>> = stack.iter().map(|v| vertices[*v]).collect();
+        points.push(vertices[stack[0]]);
+        push_loop(points);
+    }
+    Ok(rv)
+}
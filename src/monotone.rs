@@ -0,0 +1,184 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+//! Monotone decomposition of polylines.
+//!
+//! A polyline is x-monotone if the x coordinates of its vertices never decrease, or never
+//! increase, along the polyline. Every vertical line then meets an x-monotone piece in at most
+//! one point (or one vertical segment), which is what makes monotone chains useful for
+//! triangulation, envelope computation and for sweeps. [`monotone_pieces()`] splits a
+//! `geo::LineString` into maximal monotone pieces along either axis.
+
+use num_traits::Float;
+
+/// The coordinate a piece is monotone in, see [`monotone_pieces()`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MonotoneAxis {
+    /// The x coordinate never changes direction along the piece
+    #[default]
+    X,
+    /// The y coordinate never changes direction along the piece
+    Y,
+}
+
+impl MonotoneAxis {
+    /// The change of the coordinate along this axis from `from` to `to`
+    fn delta<T>(self, from: &geo::Coordinate<T>, to: &geo::Coordinate<T>) -> T
+    where
+        T: Float + geo::CoordFloat,
+    {
+        match self {
+            MonotoneAxis::X => to.x - from.x,
+            MonotoneAxis::Y => to.y - from.y,
+        }
+    }
+}
+
+/// A monotone part of a line string, see [`monotone_pieces()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonotonePiece<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The vertices of the piece, in the direction of the original line string
+    pub line_string: geo::LineString<T>,
+    /// The index of the first vertex of the piece in the original line string
+    pub first_vertex: usize,
+    /// The index of the last vertex of the piece in the original line string
+    pub last_vertex: usize,
+    /// True if the coordinate increases along the piece, false if it decreases.
+    /// A piece where the coordinate never changes (e.g. a vertical piece split along
+    /// [`MonotoneAxis::X`]) is reported as increasing.
+    pub increasing: bool,
+}
+
+/// Splits `line_string` into maximal pieces that are monotone along `axis`.
+/// The pieces are returned in the order of the line string and consecutive pieces share the
+/// vertex where the direction turns. The monotonicity is weak: segments that don't change the
+/// coordinate at all (and repeated vertices) never split a piece, they stay with the piece
+/// before them. A closed line string is split as if it was open at its first vertex.
+/// Returns an empty list for line strings with less than two vertices.
+/// ```
+/// # use intersect2d::monotone::{monotone_pieces, MonotoneAxis};
+/// let zigzag = geo::LineString::from(vec![(0., 0.), (2., 1.), (4., 0.), (3., 2.), (5., 3.)]);
+/// let pieces = monotone_pieces(&zigzag, MonotoneAxis::X);
+/// assert_eq!(pieces.len(), 3);
+/// assert_eq!((pieces[0].first_vertex, pieces[0].last_vertex), (0, 2));
+/// assert_eq!((pieces[1].first_vertex, pieces[1].last_vertex), (2, 3));
+/// assert!(!pieces[1].increasing);
+/// assert_eq!((pieces[2].first_vertex, pieces[2].last_vertex), (3, 4));
+/// // along y the same line string turns at every inner vertex but the last one
+/// assert_eq!(monotone_pieces(&zigzag, MonotoneAxis::Y).len(), 3);
+/// ```
+pub fn monotone_pieces<T>(
+    line_string: &geo::LineString<T>,
+    axis: MonotoneAxis,
+) -> Vec<MonotonePiece<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let vertices = &line_string.0;
+    let mut rv = Vec::<MonotonePiece<T>>::new();
+    if vertices.len() < 2 {
+        return rv;
+    }
+    let mut push_piece = |first: usize, last: usize, direction: Option<bool>| {
+        rv.push(MonotonePiece {
+            line_string: geo::LineString::from(vertices[first..=last].to_vec()),
+            first_vertex: first,
+            last_vertex: last,
+            increasing: direction.unwrap_or(true),
+        })
+    };
+    let mut first = 0;
+    // None until the first segment changing the coordinate
+    let mut direction: Option<bool> = None;
+    for (i, segment) in vertices.windows(2).enumerate() {
+        let delta = axis.delta(&segment[0], &segment[1]);
+        if delta == T::zero() {
+            continue;
+        }
+        let increasing = delta > T::zero();
+        match direction {
+            Some(d) if d != increasing => {
+                push_piece(first, i, direction);
+                first = i;
+                direction = Some(increasing);
+            }
+            _ => direction = Some(increasing),
+        }
+    }
+    push_piece(first, vertices.len() - 1, direction);
+    rv
+}
+
+/// Returns true if `line_string` is monotone along `axis`, i.e. if [`monotone_pieces()`]
+/// would not split it.
+/// ```
+/// # use intersect2d::monotone::{is_monotone, MonotoneAxis};
+/// let path = geo::LineString::from(vec![(0., 0.), (1., 5.), (1., -5.), (3., 0.)]);
+/// assert!(is_monotone(&path, MonotoneAxis::X));
+/// assert!(!is_monotone(&path, MonotoneAxis::Y));
+/// ```
+pub fn is_monotone<T>(line_string: &geo::LineString<T>, axis: MonotoneAxis) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut direction: Option<bool> = None;
+    for segment in line_string.0.windows(2) {
+        let delta = axis.delta(&segment[0], &segment[1]);
+        if delta == T::zero() {
+            continue;
+        }
+        let increasing = delta > T::zero();
+        if direction.map_or(false, |d| d != increasing) {
+            return false;
+        }
+        direction = Some(increasing);
+    }
+    true
+}
@@ -0,0 +1,188 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Continuous collision detection of moving segments.
+//!
+//! [`time_of_contact()`] is the moving counterpart of [`intersect()`](crate::intersect): both
+//! segments translate with a constant velocity during the time interval `[0, 1]`, and the
+//! earliest time the segments touch is returned together with the contact point.
+//!
+//! In the frame of one of the segments only the other one moves, and two segments that don't
+//! intersect at the start can only come into contact when an end point of one of them hits
+//! the other segment. So the query reduces to four ray vs segment tests.
+
+use crate::vector::{cross_z, dot};
+use crate::{intersect_with_tolerance, Intersection, Tolerance};
+use num_traits::Float;
+
+/// Returns the earliest `t` in [0,1] where `point + t * direction` hits the segment
+fn ray_hit<T>(
+    point: geo::Coordinate<T>,
+    direction: geo::Coordinate<T>,
+    segment: &geo::Line<T>,
+    tolerance: &Tolerance<T>,
+) -> Option<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let length_sq = dot(&direction, &direction);
+    if length_sq == T::zero() {
+        return None;
+    }
+    let e = segment.end - segment.start;
+    let w = segment.start - point;
+    let denominator = cross_z(&direction, &e);
+    // the distance of the segment start from the line of the ray
+    let offset = cross_z(&w, &direction) / length_sq.sqrt();
+    let slack = T::epsilon() * (T::one() + T::one() + T::one() + T::one());
+    if denominator.abs() <= T::epsilon() * length_sq.sqrt() * e.x.hypot(e.y) {
+        if !tolerance.ulps_eq(offset, T::zero()) {
+            return None;
+        }
+        // the ray runs along the segment, it hits the closest end point first
+        let t0 = dot(&w, &direction) / length_sq;
+        let t1 = dot(&(segment.end - point), &direction) / length_sq;
+        let (first, last) = (t0.min(t1), t0.max(t1));
+        return if last < -slack || first > T::one() + slack {
+            None
+        } else {
+            Some(first.max(T::zero()).min(T::one()))
+        };
+    }
+    let t = cross_z(&w, &e) / denominator;
+    let s = cross_z(&w, &direction) / denominator;
+    if t >= -slack && t <= T::one() + slack && s >= -slack && s <= T::one() + slack {
+        Some(t.max(T::zero()).min(T::one()))
+    } else {
+        None
+    }
+}
+
+/// Returns the earliest time in `[0, 1]` when the segment `one`, moving with `velocity_one`,
+/// touches the segment `other` moving with `velocity_other`, and the point of contact at that
+/// time. The velocities are the displacements over the whole interval. Segments already
+/// touching at time 0 return time 0.
+/// Uses the default tolerance, see [`time_of_contact_with_tolerance()`].
+/// ```
+/// # use intersect2d::motion::time_of_contact;
+/// // a vertical segment moving right towards a static one
+/// let one: geo::Line<f64> = [(0., 0.), (0., 2.)].into();
+/// let other: geo::Line<f64> = [(5., 1.), (9., 5.)].into();
+/// let (t, point) = time_of_contact(
+///     &one,
+///     geo::Coordinate { x: 10., y: 0. },
+///     &other,
+///     geo::Coordinate { x: 0., y: 0. },
+/// )
+/// .unwrap();
+/// assert_eq!(t, 0.5);
+/// assert_eq!(point, geo::Coordinate { x: 5., y: 1. });
+/// ```
+pub fn time_of_contact<T>(
+    one: &geo::Line<T>,
+    velocity_one: geo::Coordinate<T>,
+    other: &geo::Line<T>,
+    velocity_other: geo::Coordinate<T>,
+) -> Option<(T, geo::Coordinate<T>)>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    time_of_contact_with_tolerance(
+        one,
+        velocity_one,
+        other,
+        velocity_other,
+        &Tolerance::default(),
+    )
+}
+
+/// Returns the earliest time of contact of two moving segments, using an explicit tolerance.
+/// See [`time_of_contact()`].
+pub fn time_of_contact_with_tolerance<T>(
+    one: &geo::Line<T>,
+    velocity_one: geo::Coordinate<T>,
+    other: &geo::Line<T>,
+    velocity_other: geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> Option<(T, geo::Coordinate<T>)>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    match intersect_with_tolerance(one, other, tolerance) {
+        Some(Intersection::Intersection(point)) => return Some((T::zero(), point)),
+        Some(Intersection::OverLap(overlap)) => return Some((T::zero(), overlap.start)),
+        None => (),
+    }
+    // the motion of `one` relative to `other`
+    let relative = velocity_one - velocity_other;
+    let backwards = geo::Coordinate {
+        x: -relative.x,
+        y: -relative.y,
+    };
+    let mut rv: Option<(T, geo::Coordinate<T>)> = None;
+    let mut check = |hit: Option<T>, point: geo::Coordinate<T>, velocity: geo::Coordinate<T>| {
+        if let Some(t) = hit {
+            if rv.map_or(true, |(best, _)| t < best) {
+                rv = Some((
+                    t,
+                    geo::Coordinate {
+                        x: point.x + velocity.x * t,
+                        y: point.y + velocity.y * t,
+                    },
+                ));
+            }
+        }
+    };
+    for p in [one.start, one.end].iter() {
+        check(ray_hit(*p, relative, other, tolerance), *p, velocity_one);
+    }
+    for p in [other.start, other.end].iter() {
+        check(ray_hit(*p, backwards, one, tolerance), *p, velocity_other);
+    }
+    rv
+}
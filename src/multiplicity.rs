@@ -0,0 +1,146 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Multiplicity of intersection points.
+//!
+//! The multiplicity of an intersection point is the number of lines involved in it. Points
+//! where three or more lines meet are rare in clean data and often the result of over-snapping,
+//! so [`multiplicity_report()`] counts the points of every multiplicity and keeps the ones at
+//! or above a threshold in one pass over the results.
+
+use num_traits::Float;
+use std::collections::BTreeMap;
+
+/// The result of [`multiplicity_report()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiplicityReport<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The number of intersection points of each multiplicity
+    pub counts: BTreeMap<usize, usize>,
+    /// The intersection points with a multiplicity of at least the threshold, in input order
+    pub points: Vec<(geo::Coordinate<T>, Vec<usize>)>,
+}
+
+impl<T> MultiplicityReport<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns the number of intersection points with a multiplicity of at least `k`
+    pub fn count_at_least(&self, k: usize) -> usize {
+        self.counts.range(k..).map(|(_, count)| *count).sum()
+    }
+
+    /// Returns the highest multiplicity of any intersection point, 0 if there are none
+    pub fn max_multiplicity(&self) -> usize {
+        self.counts.keys().next_back().copied().unwrap_or(0)
+    }
+}
+
+/// Counts the intersection points of every multiplicity, and collects the points with a
+/// multiplicity of at least `min_multiplicity`.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::multiplicity::multiplicity_report;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [0., 10., 10., 0.],
+///     [5., 0., 5., 10.],
+///     [0., 1., 10., 1.],
+/// ]);
+/// let results = AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .compute()?;
+/// let report = multiplicity_report(results, 3);
+/// assert_eq!(report.counts.get(&2), Some(&3));
+/// assert_eq!(report.count_at_least(3), 1);
+/// assert_eq!(report.max_multiplicity(), 3);
+/// assert_eq!(report.points, vec![(geo::Coordinate { x: 5., y: 5. }, vec![0, 1, 2])]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn multiplicity_report<T, I>(results: I, min_multiplicity: usize) -> MultiplicityReport<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    I: IntoIterator<Item = (geo::Coordinate<T>, Vec<usize>)>,
+{
+    let mut rv = MultiplicityReport {
+        counts: BTreeMap::new(),
+        points: Vec::new(),
+    };
+    for (position, lines) in results {
+        *rv.counts.entry(lines.len()).or_insert(0) += 1;
+        if lines.len() >= min_multiplicity {
+            rv.points.push((position, lines));
+        }
+    }
+    rv
+}
+
+/// Returns the intersection points with a multiplicity of at least `min_multiplicity`
+/// ```
+/// # use intersect2d::multiplicity::with_multiplicity;
+/// let results = vec![
+///     (geo::Coordinate { x: 1.0, y: 1.0 }, vec![0, 1]),
+///     (geo::Coordinate { x: 2.0, y: 1.0 }, vec![0, 2, 3]),
+/// ];
+/// assert_eq!(with_multiplicity(results, 3).count(), 1);
+/// ```
+pub fn with_multiplicity<T, I>(
+    results: I,
+    min_multiplicity: usize,
+) -> impl Iterator<Item = (geo::Coordinate<T>, Vec<usize>)>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    I: IntoIterator<Item = (geo::Coordinate<T>, Vec<usize>)>,
+{
+    results
+        .into_iter()
+        .filter(move |(_, lines)| lines.len() >= min_multiplicity)
+}
@@ -0,0 +1,403 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Proximity queries against a set of segments.
+//!
+//! Snapping and interactive picking need to find the segments near a point, usually right
+//! next to the intersection tests of the same data. A [`SegmentIndex`] buckets the segments
+//! into a uniform grid of cells (the same layout as a [`TileGrid`](crate::tiles::TileGrid))
+//! and answers [`SegmentIndex::k_nearest_segments()`] and [`SegmentIndex::segments_within()`]
+//! by only visiting the cells around the query point.
+//!
+//! Building the index is the expensive part, so with the `serde` feature a [`SegmentIndex`]
+//! implements `Serialize` and `Deserialize` and can be cached between runs. The data is tagged
+//! with [`SERIALIZATION_VERSION`](crate::SERIALIZATION_VERSION) and checked for consistency
+//! when it is read back.
+
+use super::IntersectError;
+use crate::distance::project_point_onto_segment;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+
+/// A uniform grid index of segments, see the module documentation
+#[derive(Clone, Debug)]
+pub struct SegmentIndex<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    lines: Vec<geo::Line<T>>,
+    min: geo::Coordinate<T>,
+    /// the size of one cell
+    cell: geo::Coordinate<T>,
+    columns: usize,
+    rows: usize,
+    /// the indices of the lines overlapping each cell, row by row
+    cells: Vec<Vec<usize>>,
+}
+
+impl<T> SegmentIndex<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Builds the index, the lines are referred to by their position in `lines`
+    pub fn new(lines: &[geo::Line<T>]) -> Result<Self, IntersectError> {
+        if lines.iter().any(|l| {
+            !(l.start.x.is_finite()
+                && l.start.y.is_finite()
+                && l.end.x.is_finite()
+                && l.end.y.is_finite())
+        }) {
+            return Err(IntersectError::InvalidData(
+                "Can't index non-finite data".to_string(),
+            ));
+        }
+        let mut iter = lines.iter().flat_map(|l| [l.start, l.end].to_vec());
+        let first = iter.next().unwrap_or(geo::Coordinate {
+            x: T::zero(),
+            y: T::zero(),
+        });
+        let (min, max) = iter.fold((first, first), |(min, max), c| {
+            (
+                geo::Coordinate {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                geo::Coordinate {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        });
+        // about one line per cell
+        let side = ((lines.len() as f64).sqrt().ceil() as usize).max(1);
+        let (columns, rows) = (side, side);
+        let size = |low: T, high: T, cells: usize| {
+            let s = (high - low) / T::from(cells).unwrap();
+            if s > T::zero() {
+                s
+            } else {
+                T::one()
+            }
+        };
+        let mut rv = Self {
+            lines: lines.to_vec(),
+            min,
+            cell: geo::Coordinate {
+                x: size(min.x, max.x, columns),
+                y: size(min.y, max.y, rows),
+            },
+            columns,
+            rows,
+            cells: vec![Vec::new(); columns * rows],
+        };
+        for (index, l) in lines.iter().enumerate() {
+            let (c0, r0) = rv.cell_of(&geo::Coordinate {
+                x: l.start.x.min(l.end.x),
+                y: l.start.y.min(l.end.y),
+            });
+            let (c1, r1) = rv.cell_of(&geo::Coordinate {
+                x: l.start.x.max(l.end.x),
+                y: l.start.y.max(l.end.y),
+            });
+            for row in r0..=r1 {
+                for column in c0..=c1 {
+                    rv.cells[row * columns + column].push(index);
+                }
+            }
+        }
+        Ok(rv)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// The cell containing `point`, points outside the grid are clamped to the border cells
+    fn cell_of(&self, point: &geo::Coordinate<T>) -> (usize, usize) {
+        let cell = |v: T, min: T, size: T, cells: usize| {
+            ((v - min) / size)
+                .floor()
+                .max(T::zero())
+                .to_usize()
+                .unwrap_or(0)
+                .min(cells - 1)
+        };
+        (
+            cell(point.x, self.min.x, self.cell.x, self.columns),
+            cell(point.y, self.min.y, self.cell.y, self.rows),
+        )
+    }
+
+    fn distance(&self, line: usize, point: &geo::Coordinate<T>) -> T {
+        let (closest, _) = project_point_onto_segment(&self.lines[line], point);
+        let d = closest - *point;
+        d.x.hypot(d.y)
+    }
+
+    /// Returns the `k` segments nearest to `point` as `(line index, distance)`, sorted by
+    /// distance and then by index.
+    /// ```
+    /// # use intersect2d::nearest::SegmentIndex;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 0.],
+    ///     [0., 5., 10., 5.],
+    ///     [12., 0., 12., 10.],
+    /// ]);
+    /// let index = SegmentIndex::new(&lines)?;
+    /// let point = geo::Coordinate { x: 11., y: 4. };
+    /// assert_eq!(index.k_nearest_segments(&point, 2), vec![(2, 1.), (1, 2_f64.sqrt())]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn k_nearest_segments(&self, point: &geo::Coordinate<T>, k: usize) -> Vec<(usize, T)> {
+        let mut rv = Vec::<(usize, T)>::new();
+        if k == 0 || self.lines.is_empty() || !(point.x.is_finite() && point.y.is_finite()) {
+            return rv;
+        }
+        let (column, row) = (
+            self.cell_of(point).0 as isize,
+            self.cell_of(point).1 as isize,
+        );
+        let mut visited = vec![false; self.lines.len()];
+        let mut ring = 0_isize;
+        loop {
+            // visit the cells of the ring at chebyshev distance `ring` from the center cell
+            for r in (row - ring)..=(row + ring) {
+                for c in (column - ring)..=(column + ring) {
+                    if (r - row).abs() != ring && (c - column).abs() != ring {
+                        continue;
+                    }
+                    if r < 0 || c < 0 || r as usize >= self.rows || c as usize >= self.columns {
+                        continue;
+                    }
+                    for line in self.cells[r as usize * self.columns + c as usize].iter() {
+                        if !visited[*line] {
+                            visited[*line] = true;
+                            rv.push((*line, self.distance(*line, point)));
+                        }
+                    }
+                }
+            }
+            // every segment closer than `covered` has been seen
+            let axis = |v: T, min: T, size: T, center: isize, cells: usize| {
+                let low = if center - ring <= 0 {
+                    T::infinity()
+                } else {
+                    v - (min + size * T::from(center - ring).unwrap())
+                };
+                let high = if center + ring + 1 >= cells as isize {
+                    T::infinity()
+                } else {
+                    min + size * T::from(center + ring + 1).unwrap() - v
+                };
+                low.min(high)
+            };
+            let covered = axis(point.x, self.min.x, self.cell.x, column, self.columns).min(axis(
+                point.y,
+                self.min.y,
+                self.cell.y,
+                row,
+                self.rows,
+            ));
+            rv.sort_unstable_by(|a, b| {
+                a.1.partial_cmp(&b.1)
+                    .unwrap_or(cmp::Ordering::Equal)
+                    .then(a.0.cmp(&b.0))
+            });
+            let done = covered == T::infinity() || (rv.len() >= k && rv[k - 1].1 <= covered);
+            if done {
+                rv.truncate(k);
+                return rv;
+            }
+            ring += 1;
+        }
+    }
+
+    /// Returns the segments within `radius` of `point` as `(line index, distance)`, sorted by
+    /// distance and then by index.
+    /// ```
+    /// # use intersect2d::nearest::SegmentIndex;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0., 0., 10., 0.],
+    ///     [0., 5., 10., 5.],
+    ///     [12., 0., 12., 10.],
+    /// ]);
+    /// let index = SegmentIndex::new(&lines)?;
+    /// let point = geo::Coordinate { x: 5., y: 1. };
+    /// assert_eq!(index.segments_within(&point, 4.), vec![(0, 1.), (1, 4.)]);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn segments_within(&self, point: &geo::Coordinate<T>, radius: T) -> Vec<(usize, T)> {
+        let mut rv = Vec::<(usize, T)>::new();
+        if self.lines.is_empty()
+            || radius.is_nan()
+            || radius < T::zero()
+            || !(point.x.is_finite() && point.y.is_finite())
+        {
+            return rv;
+        }
+        let (c0, r0) = self.cell_of(&geo::Coordinate {
+            x: point.x - radius,
+            y: point.y - radius,
+        });
+        let (c1, r1) = self.cell_of(&geo::Coordinate {
+            x: point.x + radius,
+            y: point.y + radius,
+        });
+        let mut visited = vec![false; self.lines.len()];
+        for row in r0..=r1 {
+            for column in c0..=c1 {
+                for line in self.cells[row * self.columns + column].iter() {
+                    if !visited[*line] {
+                        visited[*line] = true;
+                        let distance = self.distance(*line, point);
+                        if distance <= radius {
+                            rv.push((*line, distance));
+                        }
+                    }
+                }
+            }
+        }
+        rv.sort_unstable_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap_or(cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+        rv
+    }
+}
+
+/// The serialized form of a [`SegmentIndex`], lines are stored as `[x1, y1, x2, y2]`
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SegmentIndexWire<T> {
+    version: u32,
+    lines: Vec<[T; 4]>,
+    min: [T; 2],
+    cell: [T; 2],
+    columns: usize,
+    rows: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for SegmentIndex<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Serialize,
+    T::Epsilon: Copy,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SegmentIndexWire {
+            version: crate::SERIALIZATION_VERSION,
+            lines: self
+                .lines
+                .iter()
+                .map(|l| [l.start.x, l.start.y, l.end.x, l.end.y])
+                .collect(),
+            min: [self.min.x, self.min.y],
+            cell: [self.cell.x, self.cell.y],
+            columns: self.columns,
+            rows: self.rows,
+            cells: self.cells.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for SegmentIndex<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + Deserialize<'de>,
+    T::Epsilon: Copy,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let wire = SegmentIndexWire::<T>::deserialize(deserializer)?;
+        crate::check_serialization_version(wire.version)?;
+        // the queries index the cells and lines directly, so reject anything inconsistent
+        let finite = wire.lines.iter().flatten().all(|v| v.is_finite())
+            && wire.min.iter().all(|v| v.is_finite())
+            && wire.cell.iter().all(|v| v.is_finite() && *v > T::zero());
+        if !finite {
+            return Err(de::Error::custom("invalid segment index coordinates"));
+        }
+        if wire.columns == 0
+            || wire.rows == 0
+            || wire.columns.checked_mul(wire.rows) != Some(wire.cells.len())
+            || wire.cells.iter().flatten().any(|i| *i >= wire.lines.len())
+        {
+            return Err(de::Error::custom("inconsistent segment index cells"));
+        }
+        Ok(Self {
+            lines: wire
+                .lines
+                .into_iter()
+                .map(|[x1, y1, x2, y2]| {
+                    geo::Line::new(
+                        geo::Coordinate { x: x1, y: y1 },
+                        geo::Coordinate { x: x2, y: y2 },
+                    )
+                })
+                .collect(),
+            min: geo::Coordinate {
+                x: wire.min[0],
+                y: wire.min[1],
+            },
+            cell: geo::Coordinate {
+                x: wire.cell[0],
+                y: wire.cell[1],
+            },
+            columns: wire.columns,
+            rows: wire.rows,
+            cells: wire.cells,
+        })
+    }
+}
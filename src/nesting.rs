@@ -0,0 +1,292 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Nesting hierarchy of closed rings.
+//!
+//! Assembling polygons from raw rings needs to know which rings lie inside which.
+//! [`ring_nesting()`] first verifies with the sweep-line that the rings don't meet each other,
+//! then tests one vertex of every ring against the other rings with a crossing count. Rings at
+//! an even depth are shells, and the rings directly inside them are their holes. Self
+//! intersections within a ring are not checked, see
+//! [`SelfIntersectingExclusive`](crate::SelfIntersectingExclusive) for that.
+//! [`assemble_polygons()`] turns the hierarchy into `geo::Polygon`s, with the shells oriented
+//! counter clockwise and the holes clockwise regardless of how the input rings were oriented.
+
+use super::{ulps_eq_c, IntersectError};
+use crate::algorithm::AlgorithmData;
+use num_traits::Float;
+
+/// The result of [`ring_nesting()`], all indices refer to the position of the ring in the input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RingNesting {
+    /// The innermost ring containing each ring, None for the outermost rings
+    pub parent: Vec<Option<usize>>,
+    /// The number of rings containing each ring
+    pub depth: Vec<usize>,
+}
+
+impl RingNesting {
+    /// Returns the rings not contained in any other ring
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.parent.len())
+            .filter(|i| self.parent[*i].is_none())
+            .collect()
+    }
+
+    /// Returns the rings directly inside `ring`
+    pub fn children(&self, ring: usize) -> Vec<usize> {
+        (0..self.parent.len())
+            .filter(|i| self.parent[*i] == Some(ring))
+            .collect()
+    }
+
+    /// Returns true if the ring is a hole, i.e. it is at an odd depth
+    pub fn is_hole(&self, ring: usize) -> bool {
+        self.depth[ring] & 1 == 1
+    }
+}
+
+/// the signed area of a closed ring, positive if it is counter clockwise
+pub(crate) fn signed_ring_area<T>(ring: &geo::LineString<T>) -> T
+where
+    T: Float + geo::CoordFloat,
+{
+    let twice = ring
+        .0
+        .windows(2)
+        .fold(T::zero(), |sum, w| sum + w[0].x * w[1].y - w[1].x * w[0].y);
+    twice / (T::one() + T::one())
+}
+
+/// the absolute area of a closed ring
+fn ring_area<T>(ring: &geo::LineString<T>) -> T
+where
+    T: Float + geo::CoordFloat,
+{
+    signed_ring_area(ring).abs()
+}
+
+/// Returns `ring`, reversed if needed to make it counter clockwise (or clockwise)
+fn oriented<T>(ring: &geo::LineString<T>, counter_clockwise: bool) -> geo::LineString<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut rv = ring.clone();
+    if (signed_ring_area(ring) > T::zero()) != counter_clockwise {
+        rv.0.reverse();
+    }
+    rv
+}
+
+/// Crossing count test of a point that is known not to be on the ring
+pub(crate) fn ring_contains<T>(ring: &geo::LineString<T>, point: &geo::Coordinate<T>) -> bool
+where
+    T: Float + geo::CoordFloat,
+{
+    let mut inside = false;
+    for w in ring.0.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Determines which rings are inside which.
+/// Every ring must be closed and have at least four vertices, and no two rings may meet,
+/// otherwise `InvalidData` is returned.
+/// ```
+/// # use intersect2d::nesting::ring_nesting;
+/// let square = |x: f64, y: f64, size: f64| {
+///     geo::LineString::from(vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size), (x, y)])
+/// };
+/// let rings = vec![square(1., 1., 8.), square(0., 0., 10.), square(2., 2., 1.), square(20., 0., 1.)];
+/// let nesting = ring_nesting(&rings)?;
+/// assert_eq!(nesting.parent, vec![Some(1), None, Some(0), None]);
+/// assert_eq!(nesting.depth, vec![1, 0, 2, 0]);
+/// assert_eq!(nesting.roots(), vec![1, 3]);
+/// assert!(nesting.is_hole(0));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn ring_nesting<T>(rings: &[geo::LineString<T>]) -> Result<RingNesting, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut lines = Vec::<geo::Line<T>>::new();
+    let mut owner = Vec::<usize>::new();
+    for (index, ring) in rings.iter().enumerate() {
+        if ring.0.len() < 4 || !ulps_eq_c(ring.0.first().unwrap(), ring.0.last().unwrap()) {
+            return Err(IntersectError::InvalidData(format!(
+                "Ring {} is not closed",
+                index
+            )));
+        }
+        for a_line in ring.lines() {
+            lines.push(a_line);
+            owner.push(index);
+        }
+    }
+    for (_, involved) in AlgorithmData::<T>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+    {
+        let first = owner[involved[0]];
+        if let Some(other) = involved.iter().map(|l| owner[*l]).find(|o| *o != first) {
+            return Err(IntersectError::InvalidData(format!(
+                "Rings {} and {} intersect",
+                first.min(other),
+                first.max(other)
+            )));
+        }
+    }
+
+    let bounds: Vec<geo::Rect<T>> = rings
+        .iter()
+        .map(|ring| {
+            let first = ring.0[0];
+            let (min, max) = ring.0.iter().fold((first, first), |(min, max), c| {
+                (
+                    geo::Coordinate {
+                        x: min.x.min(c.x),
+                        y: min.y.min(c.y),
+                    },
+                    geo::Coordinate {
+                        x: max.x.max(c.x),
+                        y: max.y.max(c.y),
+                    },
+                )
+            });
+            geo::Rect::new(min, max)
+        })
+        .collect();
+    let areas: Vec<T> = rings.iter().map(ring_area).collect();
+
+    let mut rv = RingNesting {
+        parent: vec![None; rings.len()],
+        depth: vec![0; rings.len()],
+    };
+    for (i, ring) in rings.iter().enumerate() {
+        let point = ring.0[0];
+        for (j, other) in rings.iter().enumerate() {
+            let (b, p) = (&bounds[j], &bounds[i]);
+            if i == j
+                || p.min().x < b.min().x
+                || p.min().y < b.min().y
+                || p.max().x > b.max().x
+                || p.max().y > b.max().y
+                || !ring_contains(other, &point)
+            {
+                continue;
+            }
+            rv.depth[i] += 1;
+            // the containing rings are nested, the smallest one is the innermost
+            if rv.parent[i].map_or(true, |parent| areas[j] < areas[parent]) {
+                rv.parent[i] = Some(j);
+            }
+        }
+    }
+    Ok(rv)
+}
+
+/// Assembles polygons from closed rings, see [`ring_nesting()`] for the requirements on the
+/// rings. Every ring at an even depth becomes the exterior of a polygon, oriented counter
+/// clockwise, and the rings directly inside it become its interiors, oriented clockwise.
+/// The polygons are returned in the order of their exterior rings in the input.
+/// ```
+/// # use intersect2d::nesting::assemble_polygons;
+/// let square = |x: f64, y: f64, size: f64| {
+///     geo::LineString::from(vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size), (x, y)])
+/// };
+/// // an island in a lake in an island
+/// let rings = vec![square(2., 2., 1.), square(1., 1., 8.), square(0., 0., 10.)];
+/// let polygons = assemble_polygons(&rings)?;
+/// assert_eq!(polygons.len(), 2);
+/// assert_eq!(polygons[0].exterior(), &rings[0]);
+/// assert!(polygons[0].interiors().is_empty());
+/// assert_eq!(polygons[1].exterior(), &rings[2]);
+/// // the hole is reversed to be clockwise
+/// let mut hole = rings[1].clone();
+/// hole.0.reverse();
+/// assert_eq!(polygons[1].interiors(), &[hole]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn assemble_polygons<T>(
+    rings: &[geo::LineString<T>],
+) -> Result<Vec<geo::Polygon<T>>, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let nesting = ring_nesting(rings)?;
+    Ok((0..rings.len())
+        .filter(|i| !nesting.is_hole(*i))
+        .map(|shell| {
+            geo::Polygon::new(
+                oriented(&rings[shell], true),
+                nesting
+                    .children(shell)
+                    .into_iter()
+                    .map(|hole| oriented(&rings[hole], false))
+                    .collect(),
+            )
+        })
+        .collect())
+}
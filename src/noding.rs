@@ -0,0 +1,119 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Noding: splitting the input lines at the intersection points.
+//!
+//! [`AlgorithmData::compute_noded()`](crate::algorithm::AlgorithmData::compute_noded) runs the
+//! sweep and returns, next to the intersections, every input line split into the
+//! [`NodedSegment`]s between consecutive intersection points. [`node_lines()`] does the same
+//! for results that were computed earlier.
+
+use crate::list::{by_segment, SegmentPoint};
+use crate::Tolerance;
+use num_traits::Float;
+
+/// A part of an input line between two consecutive split points, or the end points of the
+/// line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NodedSegment<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The geometry of the piece, in the direction of the parent line
+    pub line: geo::Line<T>,
+    /// The index of the input line this piece is a part of
+    pub parent: usize,
+}
+
+/// Splits every line at the intersection points (as returned by
+/// [`AlgorithmData::compute()`](crate::algorithm::AlgorithmData::compute)) it is involved in.
+/// The pieces are returned ordered by parent and then along the parent line, a line without
+/// intersections is returned as a single piece. Split points within `tolerance` of each other,
+/// or of an end point of the line, are merged.
+/// ```
+/// # use intersect2d::noding::node_lines;
+/// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., -5., 5., 5.]]);
+/// let results: Vec<_> = intersect2d::algorithm::AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .compute()?
+///     .collect();
+/// let pieces = node_lines(&lines, &results, &intersect2d::Tolerance::default());
+/// assert_eq!(pieces.len(), 4);
+/// assert_eq!(pieces[0].line, geo::Line::from([(0., 0.), (5., 0.)]));
+/// assert_eq!(pieces[3].parent, 1);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn node_lines<T>(
+    lines: &[geo::Line<T>],
+    results: &[(geo::Coordinate<T>, Vec<usize>)],
+    tolerance: &Tolerance<T>,
+) -> Vec<NodedSegment<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    // the split points of every line, sorted along the line
+    let splits = by_segment(results, lines);
+    let mut rv = Vec::<NodedSegment<T>>::with_capacity(lines.len() + 2 * results.len());
+    for (parent, (line, points)) in lines.iter().zip(splits).enumerate() {
+        let mut start = line.start;
+        for SegmentPoint { position, .. } in points.into_iter() {
+            if tolerance.ulps_eq_c(&start, &position) || tolerance.ulps_eq_c(&line.end, &position) {
+                continue;
+            }
+            rv.push(NodedSegment {
+                line: geo::Line::new(start, position),
+                parent,
+            });
+            start = position;
+        }
+        rv.push(NodedSegment {
+            line: geo::Line::new(start, line.end),
+            parent,
+        });
+    }
+    rv
+}
@@ -0,0 +1,325 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Brute force reference implementation, for differential testing of the sweep-line.
+//!
+//! [`self_intersections_bruteforce()`] tests every pair of segments, O(n²), and reports the
+//! results in the same format and order as
+//! [`AlgorithmData::compute()`](crate::algorithm::AlgorithmData::compute): one entry for each
+//! intersection point in sweep-line order with the sorted indices of all the lines passing
+//! through it. Unlike the [`SelfIntersectingExclusive`](crate::SelfIntersectingExclusive)
+//! implementations it never switches to the sweep-line, whatever the size of the input.
+
+use crate::list::Intersections;
+use crate::order::sweep_order;
+use crate::{
+    intersect_line_point_with_tolerance, intersect_with_tolerance, IntersectError, Intersection,
+    Tolerance,
+};
+use num_traits::Float;
+
+/// Finds all the intersections by testing every pair of lines, using the default tolerance.
+/// See [`self_intersections_bruteforce_with_tolerance()`].
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::oracle::self_intersections_bruteforce;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [0., 10., 10., 0.],
+///     [0., 5., 10., 5.],
+///     [10., 10., 20., 10.],
+/// ]);
+/// let sweep: Vec<_> = AlgorithmData::<f64>::default()
+///     .with_ignore_end_point_intersections(true)?
+///     .with_ref_lines(lines.iter())?
+///     .compute()?
+///     .collect();
+/// let oracle: Vec<_> = self_intersections_bruteforce(&lines, true)?.collect();
+/// assert_eq!(oracle, sweep);
+/// assert_eq!(oracle[0].1, vec![0, 1, 2]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn self_intersections_bruteforce<T>(
+    lines: &[geo::Line<T>],
+    ignore_end_point_intersections: bool,
+) -> Result<Intersections<T>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    self_intersections_bruteforce_with_tolerance(
+        lines,
+        ignore_end_point_intersections,
+        &Tolerance::default(),
+    )
+}
+
+/// Finds all the intersections by testing every pair of lines.
+/// `ignore_end_point_intersections` has the same meaning as for the sweep-line: points where
+/// the involved lines only meet at their end points are not reported.
+/// The position reported for a crossing may be computed from another pair of lines than the
+/// sweep-line uses, so it may differ in the last few bits.
+pub fn self_intersections_bruteforce_with_tolerance<T>(
+    lines: &[geo::Line<T>],
+    ignore_end_point_intersections: bool,
+    tolerance: &Tolerance<T>,
+) -> Result<Intersections<T>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    for a_line in lines.iter() {
+        if !(a_line.start.x.is_finite()
+            && a_line.start.y.is_finite()
+            && a_line.end.x.is_finite()
+            && a_line.end.y.is_finite())
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't check for intersections on non-finite data".to_string(),
+            ));
+        }
+    }
+    let mut candidates = Vec::<(geo::Coordinate<T>, Vec<usize>)>::new();
+    for_each_candidate(lines, tolerance, |position, involved| {
+        candidates.push((position, involved.to_vec()))
+    });
+    candidates.sort_by(|a, b| sweep_order(&a.0, &b.0));
+    let mut interior = vec![false; candidates.len()];
+    let mut merged_into = vec![0; candidates.len()];
+    let mut pair_groups = ahash::AHashMap::<(usize, usize), usize>::default();
+    let groups = group_candidates(
+        lines,
+        tolerance,
+        &mut candidates,
+        &mut interior,
+        &mut merged_into,
+        &mut pair_groups,
+    );
+    candidates.truncate(groups);
+
+    let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::new();
+    for (g, (position, involved)) in candidates.into_iter().enumerate() {
+        if merged_into[g] != g {
+            continue;
+        }
+        let report = if ignore_end_point_intersections {
+            interior[g] && involved.len() > 1
+        } else {
+            involved.len() > 1
+        };
+        if report {
+            rv.push((position, involved));
+        }
+    }
+    Ok(rv.into())
+}
+
+/// A set of line indices, as grouped by [`group_candidates()`]
+pub(crate) trait LineSet {
+    fn insert(&mut self, line: usize);
+    fn contains(&self, line: usize) -> bool;
+    fn union(&mut self, other: &Self);
+    /// Calls `f` with every pair of lines `(l1, l2)` of the set, `l1 < l2`
+    fn for_each_pair<F: FnMut(usize, usize)>(&self, f: F);
+}
+
+/// A sorted vec of line indices
+impl LineSet for Vec<usize> {
+    fn insert(&mut self, line: usize) {
+        if let Err(i) = self.binary_search(&line) {
+            Vec::insert(self, i, line);
+        }
+    }
+
+    fn contains(&self, line: usize) -> bool {
+        self.binary_search(&line).is_ok()
+    }
+
+    fn union(&mut self, other: &Self) {
+        self.extend(other.iter());
+        self.sort_unstable();
+        self.dedup();
+    }
+
+    fn for_each_pair<F: FnMut(usize, usize)>(&self, mut f: F) {
+        for (k, l1) in self.iter().enumerate() {
+            for l2 in self.iter().skip(k + 1) {
+                f(*l1, *l2);
+            }
+        }
+    }
+}
+
+/// The group where a pair of lines meet, as recorded by [`group_candidates()`]
+pub(crate) trait PairGroups {
+    fn get(&self, pair: (usize, usize)) -> Option<usize>;
+    fn insert(&mut self, pair: (usize, usize), group: usize);
+}
+
+impl PairGroups for ahash::AHashMap<(usize, usize), usize> {
+    fn get(&self, pair: (usize, usize)) -> Option<usize> {
+        ahash::AHashMap::get(self, &pair).copied()
+    }
+
+    fn insert(&mut self, pair: (usize, usize), group: usize) {
+        let _ = ahash::AHashMap::insert(self, pair, group);
+    }
+}
+
+/// Calls `f` with every candidate intersection point: every end point, with its line, and
+/// every crossing, with the two crossing lines.
+pub(crate) fn for_each_candidate<T, F>(lines: &[geo::Line<T>], tolerance: &Tolerance<T>, mut f: F)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    F: FnMut(geo::Coordinate<T>, &[usize]),
+{
+    for (i, a_line) in lines.iter().enumerate() {
+        f(a_line.start, &[i]);
+        f(a_line.end, &[i]);
+        for (j, other) in lines.iter().enumerate().skip(i + 1) {
+            // lines sharing an end point can't cross anywhere else, and the shared part of
+            // overlapping lines starts and ends at end points. Those are already candidates.
+            let shares_end_point = |p: &geo::Coordinate<T>| {
+                tolerance.ulps_eq_c(p, &other.start) || tolerance.ulps_eq_c(p, &other.end)
+            };
+            if shares_end_point(&a_line.start) || shares_end_point(&a_line.end) {
+                continue;
+            }
+            if let Some(Intersection::Intersection(p)) =
+                intersect_with_tolerance(a_line, other, tolerance)
+            {
+                f(p, &[i, j]);
+            }
+        }
+    }
+}
+
+/// Groups the candidate points, sorted in sweep-line order, into intersection points with
+/// all the lines passing through them. The groups are written to the start of `candidates`
+/// and the number of groups is returned. `interior[g]` tells if some line passes through the
+/// interior of its segment at group `g`. A group `g` with `merged_into[g] != g` was merged
+/// into an earlier group and must be skipped. `interior` and `merged_into` must be at least
+/// as long as `candidates`.
+pub(crate) fn group_candidates<T, S, P>(
+    lines: &[geo::Line<T>],
+    tolerance: &Tolerance<T>,
+    candidates: &mut [(geo::Coordinate<T>, S)],
+    interior: &mut [bool],
+    merged_into: &mut [usize],
+    pair_groups: &mut P,
+) -> usize
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    S: LineSet + Clone,
+    P: PairGroups,
+{
+    let mut groups = 0;
+    let mut start = 0;
+    while start < candidates.len() {
+        let position = candidates[start].0;
+        let mut involved = candidates[start].1.clone();
+        let mut end = start + 1;
+        while end < candidates.len() && tolerance.ulps_eq_c(&candidates[end].0, &position) {
+            involved.union(&candidates[end].1);
+            end += 1;
+        }
+        start = end;
+
+        // the lines passing through the point, and if they pass through the interior
+        let mut through_interior = false;
+        for (i, a_line) in lines.iter().enumerate() {
+            let at_end_point = tolerance.ulps_eq_c(&a_line.start, &position)
+                || tolerance.ulps_eq_c(&a_line.end, &position);
+            if at_end_point {
+                involved.insert(i);
+            } else if involved.contains(i)
+                || intersect_line_point_with_tolerance(a_line, &position, tolerance).is_some()
+            {
+                involved.insert(i);
+                through_interior = true;
+            }
+        }
+        // groups <= start, so no unread candidate is overwritten
+        candidates[groups] = (position, involved);
+        interior[groups] = through_interior;
+        groups += 1;
+    }
+
+    // Two lines that don't overlap meet at most once. The crossings of different pairs of
+    // lines through the same point may still round to points further apart than the
+    // tolerance, and so may a crossing and an end point touching the interior of the other
+    // line. A group containing a pair of lines that has already been seen at an earlier
+    // group (in sweep-line order) is merged into that group.
+    for g in 0..groups {
+        merged_into[g] = g;
+        let mut target = None;
+        candidates[g].1.for_each_pair(|l1, l2| {
+            if let Some(other) = pair_groups.get((l1, l2)) {
+                target = Some(merged_into[other]);
+            }
+        });
+        if let Some(target) = target {
+            merged_into[g] = target;
+            let involved = candidates[g].1.clone();
+            candidates[target].1.union(&involved);
+            interior[target] |= interior[g];
+        }
+        let owner = merged_into[g];
+        // every pair of non-overlapping lines meeting here can only meet here
+        candidates[g].1.for_each_pair(|l1, l2| {
+            if pair_groups.get((l1, l2)).is_none()
+                && !matches!(
+                    intersect_with_tolerance(&lines[l1], &lines[l2], tolerance),
+                    Some(Intersection::OverLap(_))
+                )
+            {
+                pair_groups.insert((l1, l2), owner);
+            }
+        });
+    }
+    groups
+}
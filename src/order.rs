@@ -0,0 +1,174 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! The order the sweep-line visits coordinates in.
+//!
+//! The sweep-line moves upwards: events are ordered by `y` and events at the same height by
+//! `x`, the results of [`AlgorithmData::compute()`](crate::algorithm::AlgorithmData::compute)
+//! come in this order. Values within the tolerance compare as equal, so two coordinates that
+//! the sweep merges into one event compare as equal too.
+//!
+//! [`sweep_order()`] is the comparator and [`SweepOrdered`] wraps a coordinate so that
+//! it can be sorted or used as a key of a `BTreeMap`. Note that comparing with a tolerance isn't
+//! transitive for chains of points closer than the tolerance, just like in the sweep.
+//! NaN values compare as equal to everything, the sweep rejects non-finite input anyway.
+
+use crate::algorithm::SiteEventKey;
+use crate::Tolerance;
+use num_traits::Float;
+use std::cmp;
+
+/// Compares two coordinates in sweep-line order with the default tolerance.
+/// ```
+/// # use intersect2d::order::sweep_order;
+/// # use std::cmp::Ordering;
+/// let c = |x: f64, y: f64| geo::Coordinate { x, y };
+/// assert_eq!(sweep_order(&c(5., 0.), &c(0., 1.)), Ordering::Less);
+/// assert_eq!(sweep_order(&c(1., 1.), &c(0., 1.)), Ordering::Greater);
+/// assert_eq!(sweep_order(&c(0.1 + 0.2, 1.), &c(0.3, 1.)), Ordering::Equal);
+/// ```
+#[inline(always)]
+pub fn sweep_order<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> cmp::Ordering
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    SiteEventKey::new(a.x, a.y)
+        .partial_cmp(&SiteEventKey::new(b.x, b.y))
+        .unwrap_or(cmp::Ordering::Equal)
+}
+
+/// Compares two coordinates in sweep-line order, the order of an
+/// [`AlgorithmData`](crate::algorithm::AlgorithmData) set up with
+/// [`with_tolerance()`](crate::algorithm::AlgorithmData::with_tolerance).
+/// ```
+/// # use intersect2d::order::sweep_order_with_tolerance;
+/// # use intersect2d::Tolerance;
+/// # use std::cmp::Ordering;
+/// let (a, b) = (geo::Coordinate { x: 0., y: 1.0 }, geo::Coordinate { x: 5., y: 1.001 });
+/// let tolerance = Tolerance::new(0.01, 4);
+/// // the heights are equal within the tolerance, so x decides
+/// assert_eq!(sweep_order_with_tolerance(&b, &a, &tolerance), Ordering::Greater);
+/// assert_eq!(sweep_order_with_tolerance(&a, &b, &Tolerance::default()), Ordering::Less);
+/// ```
+#[inline(always)]
+pub fn sweep_order_with_tolerance<T>(
+    a: &geo::Coordinate<T>,
+    b: &geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> cmp::Ordering
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    SiteEventKey::with_tolerance(*a, *tolerance)
+        .partial_cmp(&SiteEventKey::with_tolerance(*b, *tolerance))
+        .unwrap_or(cmp::Ordering::Equal)
+}
+
+/// A coordinate ordered by [`sweep_order()`]
+/// ```
+/// # use intersect2d::order::SweepOrdered;
+/// let mut points = vec![
+///     SweepOrdered(geo::Coordinate { x: 1., y: 2. }),
+///     SweepOrdered(geo::Coordinate { x: 3., y: 1. }),
+///     SweepOrdered(geo::Coordinate { x: 0., y: 2. }),
+/// ];
+/// points.sort();
+/// let xs: Vec<f64> = points.iter().map(|p| p.0.x).collect();
+/// assert_eq!(xs, [3., 0., 1.]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SweepOrdered<T>(pub geo::Coordinate<T>)
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy;
+
+impl<T> From<geo::Coordinate<T>> for SweepOrdered<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn from(coordinate: geo::Coordinate<T>) -> Self {
+        Self(coordinate)
+    }
+}
+
+impl<T> Ord for SweepOrdered<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        sweep_order(&self.0, &other.0)
+    }
+}
+
+impl<T> PartialOrd for SweepOrdered<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for SweepOrdered<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<T> Eq for SweepOrdered<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+}
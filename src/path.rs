@@ -0,0 +1,118 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Queries along a polyline path.
+//!
+//! [`first_intersection_along()`] walks a `geo::LineString` from its first vertex and stops
+//! at the first obstacle it meets, e.g. when simulating an agent moving along a route.
+
+use crate::collinear::overlap_interval;
+use crate::vector::norm;
+use crate::{intersect, Intersection};
+use num_traits::Float;
+
+/// Returns the first point, measured as distance along `path`, where the path meets one of the
+/// `obstacles`, as (distance, point, obstacle index). If several obstacles are met at the same
+/// point the one with the lowest index is returned. For an obstacle overlapping the path the
+/// point where the overlap begins is returned.
+/// The path is walked segment by segment, and the walk stops at the first segment meeting an
+/// obstacle.
+/// ```
+/// # use intersect2d::path::first_intersection_along;
+/// let path = geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]);
+/// let obstacles = intersect2d::to_lines::<_, f64>(&[[5., 5., 15., 5.], [8., -1., 8., 1.]]);
+/// let (distance, point, obstacle) = first_intersection_along(&path, &obstacles).unwrap();
+/// assert_eq!(obstacle, 1);
+/// assert_eq!(point, geo::Coordinate { x: 8., y: 0. });
+/// assert_eq!(distance, 8.);
+/// # let (distance, _, obstacle) = first_intersection_along(&path, &obstacles[..1]).unwrap();
+/// # assert_eq!((distance, obstacle), (15., 0));
+/// ```
+pub fn first_intersection_along<T>(
+    path: &geo::LineString<T>,
+    obstacles: &[geo::Line<T>],
+) -> Option<(T, geo::Coordinate<T>, usize)>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut travelled = T::zero();
+    for segment in path.0.windows(2) {
+        let segment = geo::Line::new(segment[0], segment[1]);
+        let length = norm(&(segment.end - segment.start));
+        if length == T::zero() {
+            continue;
+        }
+        // (distance from the start of the segment, point, obstacle)
+        let mut best: Option<(T, geo::Coordinate<T>, usize)> = None;
+        for (index, obstacle) in obstacles.iter().enumerate() {
+            let point = match intersect(&segment, obstacle) {
+                Some(Intersection::Intersection(point)) => point,
+                Some(Intersection::OverLap(overlap)) => {
+                    // the shared part has the direction of the path segment
+                    if let Some((shared, _, _)) = overlap_interval(&segment, obstacle) {
+                        shared.start
+                    } else if norm(&(overlap.start - segment.start))
+                        <= norm(&(overlap.end - segment.start))
+                    {
+                        overlap.start
+                    } else {
+                        overlap.end
+                    }
+                }
+                None => continue,
+            };
+            let distance = norm(&(point - segment.start));
+            if best.map_or(true, |b| distance < b.0) {
+                best = Some((distance, point, index));
+            }
+        }
+        if let Some((distance, point, index)) = best {
+            return Some((travelled + distance, point, index));
+        }
+        travelled = travelled + length;
+    }
+    None
+}
@@ -0,0 +1,147 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Precision models, JTS style.
+//!
+//! A [`PrecisionModel`] decides which coordinates are representable. Input coordinates and
+//! computed intersection points are rounded to the model before being compared, so data from
+//! sources with different precision produce one intersection point instead of several near
+//! duplicates. The precision model is part of the [`Tolerance`](crate::Tolerance).
+
+use super::IntersectError;
+use num_traits::Float;
+
+/// The precision model used for coordinates
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PrecisionModel<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    /// Full floating point precision, no rounding is done
+    #[default]
+    Floating,
+    /// Coordinates are rounded to the nearest multiple of the grid size
+    FixedGrid(T),
+    /// Coordinates are rounded to the nearest `integer / scale`, e.g. a scale of 1000.0 keeps
+    /// three decimals. Unlike `FixedGrid(0.001)` values with few decimals are reproduced exactly.
+    ScaledInteger(T),
+}
+
+impl<T> PrecisionModel<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    /// Returns an error if the grid size or scale isn't a finite, positive value
+    pub fn validate(&self) -> Result<(), IntersectError> {
+        match self {
+            Self::Floating => Ok(()),
+            Self::FixedGrid(v) | Self::ScaledInteger(v) => {
+                if v.is_finite() && *v > T::zero() {
+                    Ok(())
+                } else {
+                    Err(IntersectError::InvalidParameter(
+                        "The precision model grid size and scale must be finite, positive values"
+                            .to_string(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Returns the distance between two neighbouring representable values, zero for `Floating`
+    pub fn resolution(&self) -> T {
+        match self {
+            Self::Floating => T::zero(),
+            Self::FixedGrid(grid_size) => *grid_size,
+            Self::ScaledInteger(scale) => T::one() / *scale,
+        }
+    }
+
+    /// Rounds a value to the precision model
+    /// ```
+    /// # use intersect2d::precision::PrecisionModel;
+    /// assert_eq!(PrecisionModel::FixedGrid(0.5).make_precise(1.3), 1.5);
+    /// assert_eq!(PrecisionModel::ScaledInteger(100.0).make_precise(0.123), 0.12);
+    /// assert_eq!(PrecisionModel::Floating.make_precise(0.123), 0.123);
+    /// ```
+    #[inline(always)]
+    pub fn make_precise(&self, value: T) -> T {
+        match self {
+            Self::Floating => value,
+            Self::FixedGrid(grid_size) => (value / *grid_size).round() * *grid_size,
+            Self::ScaledInteger(scale) => (value * *scale).round() / *scale,
+        }
+    }
+
+    /// Rounds a coordinate to the precision model
+    #[inline(always)]
+    pub fn make_precise_c(&self, coordinate: geo::Coordinate<T>) -> geo::Coordinate<T> {
+        match self {
+            Self::Floating => coordinate,
+            _ => geo::Coordinate {
+                x: self.make_precise(coordinate.x),
+                y: self.make_precise(coordinate.y),
+            },
+        }
+    }
+
+    /// Rounds both end points of a line to the precision model
+    #[inline(always)]
+    pub fn make_precise_l(&self, line: geo::Line<T>) -> geo::Line<T> {
+        geo::Line {
+            start: self.make_precise_c(line.start),
+            end: self.make_precise_c(line.end),
+        }
+    }
+
+    /// Returns the same precision model in f64
+    pub(crate) fn to_f64(self) -> PrecisionModel<f64> {
+        match self {
+            Self::Floating => PrecisionModel::Floating,
+            Self::FixedGrid(grid_size) => PrecisionModel::FixedGrid(grid_size.to_f64().unwrap()),
+            Self::ScaledInteger(scale) => PrecisionModel::ScaledInteger(scale.to_f64().unwrap()),
+        }
+    }
+}
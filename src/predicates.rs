@@ -0,0 +1,409 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Robust orientation predicates.
+//!
+//! [`orient2d()`] and [`point_side_of_line()`] decide the orientation of three points exactly:
+//! a fast floating point filter handles the clear cases and the uncertain ones are evaluated
+//! with exact expansion arithmetic, the same arithmetic [`intersect_exact()`](crate::exact::intersect_exact)
+//! uses. The `_with_tolerance` variants report points within the tolerance distance of the
+//! line as collinear, the same decision [`intersect_with_tolerance()`](crate::intersect_with_tolerance)
+//! makes, so the two agree on what touches what.
+//!
+//! [`orient2d_interval()`] and [`compare_x_at()`] use an [`Interval`] arithmetic filter
+//! instead, only falling back to exact arithmetic when the interval contains zero.
+//!
+//! [`is_convex()`] and [`is_convex_with_tolerance()`] test rings for convexity with the same
+//! orientation predicates.
+
+use crate::expansion::Expansion;
+use crate::interval::Interval;
+use crate::vector::dot;
+use crate::Tolerance;
+use num_traits::Float;
+use std::cmp;
+
+/// The orientation of three points, or the side of a line a point is on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// The points turn left, i.e. the point is to the left of the line
+    CounterClockwise,
+    /// The points turn right, i.e. the point is to the right of the line
+    Clockwise,
+    /// The points are on a common line
+    Collinear,
+}
+
+impl Orientation {
+    fn from_ordering(ordering: cmp::Ordering) -> Self {
+        match ordering {
+            cmp::Ordering::Greater => Self::CounterClockwise,
+            cmp::Ordering::Less => Self::Clockwise,
+            cmp::Ordering::Equal => Self::Collinear,
+        }
+    }
+}
+
+/// Returns the exact sign of (q - p) × (r - p)
+fn orient2d_sign<T>(
+    p: &geo::Coordinate<T>,
+    q: &geo::Coordinate<T>,
+    r: &geo::Coordinate<T>,
+) -> cmp::Ordering
+where
+    T: Float + geo::CoordFloat,
+{
+    let left = (q.x - p.x) * (r.y - p.y);
+    let right = (q.y - p.y) * (r.x - p.x);
+    let determinant = left - right;
+    // the error bound of the floating point determinant, from Shewchuk's orient2d
+    let epsilon = T::epsilon();
+    let bound = (T::from(3.0).unwrap() + T::from(16.0).unwrap() * epsilon)
+        * epsilon
+        * (left.abs() + right.abs());
+    if determinant > bound {
+        return cmp::Ordering::Greater;
+    }
+    if -determinant > bound {
+        return cmp::Ordering::Less;
+    }
+    orient2d_exact(p, q, r)
+}
+
+/// Returns the sign of (q - p) × (r - p) computed with exact expansion arithmetic
+fn orient2d_exact<T>(
+    p: &geo::Coordinate<T>,
+    q: &geo::Coordinate<T>,
+    r: &geo::Coordinate<T>,
+) -> cmp::Ordering
+where
+    T: Float + geo::CoordFloat,
+{
+    let (px, py) = (Expansion::new(p.x), Expansion::new(p.y));
+    let qx = Expansion::new(q.x).sub(&px);
+    let qy = Expansion::new(q.y).sub(&py);
+    let rx = Expansion::new(r.x).sub(&px);
+    let ry = Expansion::new(r.y).sub(&py);
+    qx.mul(&ry).sub(&qy.mul(&rx)).signum()
+}
+
+/// Returns the orientation of the points `p`, `q` and `r`. The result is exact as long as the
+/// products of coordinate differences neither overflow nor underflow.
+/// ```
+/// # use intersect2d::predicates::{orient2d, Orientation};
+/// let p = geo::Coordinate { x: 0.5, y: 0.5 };
+/// let q = geo::Coordinate { x: 12., y: 12. };
+/// let r = geo::Coordinate { x: 24., y: 24. };
+/// assert_eq!(orient2d(&p, &q, &r), Orientation::Collinear);
+/// let r = geo::Coordinate { x: 24., y: 24.000000000000004 };
+/// assert_eq!(orient2d(&p, &q, &r), Orientation::CounterClockwise);
+/// ```
+pub fn orient2d<T>(
+    p: &geo::Coordinate<T>,
+    q: &geo::Coordinate<T>,
+    r: &geo::Coordinate<T>,
+) -> Orientation
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    Orientation::from_ordering(orient2d_sign(p, q, r))
+}
+
+/// Returns the side of the (infinite) supporting line of `line` that `point` is on, seen in
+/// the direction of the line. This is `orient2d(line.start, line.end, point)`.
+pub fn point_side_of_line<T>(line: &geo::Line<T>, point: &geo::Coordinate<T>) -> Orientation
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    orient2d(&line.start, &line.end, point)
+}
+
+/// Returns the orientation of the points `p`, `q` and `r`, treating `r` as collinear if it is
+/// within the tolerance distance of the line through `p` and `q` (or if `p` and `q` are
+/// virtually identical). Outside of the tolerance the decision is exact.
+/// ```
+/// # use intersect2d::predicates::{orient2d_with_tolerance, Orientation};
+/// # use intersect2d::Tolerance;
+/// let p = geo::Coordinate { x: 0., y: 0. };
+/// let q = geo::Coordinate { x: 10., y: 0. };
+/// let r = geo::Coordinate { x: 5., y: -0.001 };
+/// assert_eq!(orient2d_with_tolerance(&p, &q, &r, &Tolerance::default()), Orientation::Clockwise);
+/// assert_eq!(orient2d_with_tolerance(&p, &q, &r, &Tolerance::new(0.01, 4)), Orientation::Collinear);
+/// ```
+pub fn orient2d_with_tolerance<T>(
+    p: &geo::Coordinate<T>,
+    q: &geo::Coordinate<T>,
+    r: &geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> Orientation
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if tolerance.ulps_eq_c(p, q) {
+        return Orientation::Collinear;
+    }
+    let d = *q - *p;
+    let length = (d.x * d.x + d.y * d.y).sqrt();
+    let distance = ((q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x)) / length;
+    if tolerance.ulps_eq(distance, T::zero()) {
+        return Orientation::Collinear;
+    }
+    orient2d(p, q, r)
+}
+
+/// Returns the side of the supporting line of `line` that `point` is on, treating points
+/// within the tolerance distance of the line as collinear.
+pub fn point_side_of_line_with_tolerance<T>(
+    line: &geo::Line<T>,
+    point: &geo::Coordinate<T>,
+    tolerance: &Tolerance<T>,
+) -> Orientation
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    orient2d_with_tolerance(&line.start, &line.end, point, tolerance)
+}
+
+/// Returns the orientation of the points `p`, `q` and `r`, like [`orient2d()`] but filtered by
+/// interval arithmetic. The result is exact as long as the products of coordinate differences
+/// neither overflow nor underflow.
+/// ```
+/// # use intersect2d::predicates::{orient2d_interval, Orientation};
+/// let p = geo::Coordinate { x: 0.5, y: 0.5 };
+/// let q = geo::Coordinate { x: 12., y: 12. };
+/// let r = geo::Coordinate { x: 24., y: 24.000000000000004 };
+/// assert_eq!(orient2d_interval(&p, &q, &r), Orientation::CounterClockwise);
+/// ```
+pub fn orient2d_interval<T>(
+    p: &geo::Coordinate<T>,
+    q: &geo::Coordinate<T>,
+    r: &geo::Coordinate<T>,
+) -> Orientation
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let (px, py) = (Interval::new(p.x), Interval::new(p.y));
+    let qx = Interval::new(q.x) - px;
+    let qy = Interval::new(q.y) - py;
+    let rx = Interval::new(r.x) - px;
+    let ry = Interval::new(r.y) - py;
+    let sign = (qx * ry - qy * rx)
+        .sign()
+        .unwrap_or_else(|| orient2d_exact(p, q, r));
+    Orientation::from_ordering(sign)
+}
+
+/// Compares the x coordinates of the supporting lines of `a` and `b` at height `y`, the order
+/// of the lines along a horizontal sweep-line. Returns None if either line is horizontal.
+/// The comparison is filtered by interval arithmetic and exact as long as the products of
+/// coordinates neither overflow nor underflow.
+/// ```
+/// # use intersect2d::predicates::compare_x_at;
+/// # use std::cmp::Ordering;
+/// let a: geo::Line<f64> = [(0., 0.), (1., 3.)].into();
+/// let b: geo::Line<f64> = [(1., 0.), (0., 3.)].into();
+/// assert_eq!(compare_x_at(&a, &b, 1.0), Some(Ordering::Less));
+/// assert_eq!(compare_x_at(&a, &b, 1.5), Some(Ordering::Equal));
+/// assert_eq!(compare_x_at(&a, &b, 2.0), Some(Ordering::Greater));
+/// ```
+pub fn compare_x_at<T>(a: &geo::Line<T>, b: &geo::Line<T>, y: T) -> Option<cmp::Ordering>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if a.start.y == a.end.y || b.start.y == b.end.y {
+        return None;
+    }
+    // orient the lines upwards so that the denominators are positive
+    let upwards = |l: &geo::Line<T>| {
+        if l.start.y < l.end.y {
+            (l.start, l.end)
+        } else {
+            (l.end, l.start)
+        }
+    };
+    let (a0, a1) = upwards(a);
+    let (b0, b1) = upwards(b);
+    // x(y) = x0 + (y - y0) * dx / dy, so compare
+    // (x0 * dy + (y - y0) * dx) of a times dy of b with the same of b times dy of a
+    let interval = || {
+        let i = Interval::new;
+        let numerator = |p0: geo::Coordinate<T>, p1: geo::Coordinate<T>| {
+            i(p0.x) * (i(p1.y) - i(p0.y)) + (i(y) - i(p0.y)) * (i(p1.x) - i(p0.x))
+        };
+        (numerator(a0, a1) * (i(b1.y) - i(b0.y)) - numerator(b0, b1) * (i(a1.y) - i(a0.y))).sign()
+    };
+    let exact = || {
+        let e = Expansion::new;
+        let numerator = |p0: geo::Coordinate<T>, p1: geo::Coordinate<T>| {
+            e(p0.x)
+                .mul(&e(p1.y).sub(&e(p0.y)))
+                .add(&e(y).sub(&e(p0.y)).mul(&e(p1.x).sub(&e(p0.x))))
+        };
+        numerator(a0, a1)
+            .mul(&e(b1.y).sub(&e(b0.y)))
+            .sub(&numerator(b0, b1).mul(&e(a1.y).sub(&e(a0.y))))
+            .signum()
+    };
+    Some(interval().unwrap_or_else(exact))
+}
+
+/// Returns true if `ring` is a convex polygon ring, deciding every turn exactly with
+/// [`orient2d()`]. See [`is_convex_with_tolerance()`].
+/// ```
+/// # use intersect2d::predicates::is_convex;
+/// let square = geo::LineString::from(vec![(0., 0.), (1., 0.), (2., 0.), (2., 2.), (0., 2.)]);
+/// assert!(is_convex(&square));
+/// let dented = geo::LineString::from(vec![(0., 0.), (1., 0.1), (2., 0.), (2., 2.), (0., 2.)]);
+/// assert!(!is_convex(&dented));
+/// // a pentagram turns the same way at every vertex, but winds around twice
+/// let pentagram = geo::LineString::from(vec![(0., 10.), (6., -8.), (-9.5, 3.), (9.5, 3.), (-6., -8.)]);
+/// assert!(!is_convex(&pentagram));
+/// ```
+pub fn is_convex<T>(ring: &geo::LineString<T>) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    is_convex_(ring, &Tolerance::default(), orient2d)
+}
+
+/// Returns true if `ring` is a convex polygon ring, in either orientation. Turns within the
+/// tolerance distance of a straight line are collinear, see [`orient2d_with_tolerance()`].
+/// The ring may, or may not, repeat its first point at the end, and repeated vertices are
+/// ignored. Runs of collinear vertices along an edge are accepted, but a collinear vertex
+/// where the ring doubles back (a zero area spike) is not. A ring that winds around more than
+/// once, or one without any turns (all vertices on a common line), is not convex.
+/// ```
+/// # use intersect2d::predicates::{is_convex, is_convex_with_tolerance};
+/// # use intersect2d::Tolerance;
+/// let almost = geo::LineString::from(vec![(0., 0.), (1., 0.001), (2., 0.), (2., 2.), (0., 2.)]);
+/// assert!(!is_convex(&almost));
+/// assert!(is_convex_with_tolerance(&almost, &Tolerance::new(0.01, 4)));
+/// ```
+pub fn is_convex_with_tolerance<T>(ring: &geo::LineString<T>, tolerance: &Tolerance<T>) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    is_convex_(ring, tolerance, |p, q, r| {
+        orient2d_with_tolerance(p, q, r, tolerance)
+    })
+}
+
+fn is_convex_<T, F>(ring: &geo::LineString<T>, tolerance: &Tolerance<T>, orientation: F) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    F: Fn(&geo::Coordinate<T>, &geo::Coordinate<T>, &geo::Coordinate<T>) -> Orientation,
+{
+    let mut vertices = Vec::<geo::Coordinate<T>>::with_capacity(ring.0.len());
+    for point in ring.0.iter() {
+        if vertices
+            .last()
+            .map_or(true, |last| !tolerance.ulps_eq_c(last, point))
+        {
+            vertices.push(*point);
+        }
+    }
+    while vertices.len() > 1 && tolerance.ulps_eq_c(&vertices[0], vertices.last().unwrap()) {
+        let _ = vertices.pop();
+    }
+    let n = vertices.len();
+    if n < 3 {
+        return false;
+    }
+    // the vertices where the ring turns, collinear vertices only continue an edge
+    let mut corners = Vec::<geo::Coordinate<T>>::with_capacity(n);
+    let mut turn: Option<Orientation> = None;
+    for i in 0..n {
+        let (p, q, r) = (
+            &vertices[(i + n - 1) % n],
+            &vertices[i],
+            &vertices[(i + 1) % n],
+        );
+        match orientation(p, q, r) {
+            Orientation::Collinear => {
+                if dot(&(*q - *p), &(*r - *q)) < T::zero() {
+                    return false;
+                }
+            }
+            o => {
+                if turn.map_or(false, |t| t != o) {
+                    return false;
+                }
+                turn = Some(o);
+                corners.push(*q);
+            }
+        }
+    }
+    if corners.len() < 3 {
+        return false;
+    }
+    // a ring turning the same way at every corner is convex if it winds around once, then
+    // the x and the y components of the edge directions change their sign twice at most
+    let sign_changes = |component: fn(&geo::Coordinate<T>) -> T| {
+        let mut changes = 0;
+        let mut previous: Option<bool> = None;
+        for i in 0..=corners.len() {
+            let d = component(&(corners[(i + 1) % corners.len()] - corners[i % corners.len()]));
+            if d == T::zero() {
+                continue;
+            }
+            let positive = d > T::zero();
+            if previous.map_or(false, |p| p != positive) {
+                changes += 1;
+            }
+            previous = Some(positive);
+        }
+        changes
+    };
+    sign_changes(|c| c.x) <= 2 && sign_changes(|c| c.y) <= 2
+}
@@ -0,0 +1,69 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Convenience re-exports of the commonly used traits, types and functions.
+//! ```
+//! use intersect2d::prelude::*;
+//!
+//! let line_string = geo::LineString::<f64>::from(vec![(0., 0.), (10., 10.), (10., 0.), (0., 10.)]);
+//! assert!(line_string.is_self_intersecting()?);
+//! let lines = to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+//! assert!(matches!(
+//!     intersect(&lines[0], &lines[1]),
+//!     Some(Intersection::Intersection(_))
+//! ));
+//! # Ok::<(), IntersectError>(())
+//! ```
+
+pub use crate::algorithm::AlgorithmData;
+pub use crate::between::intersections_between_sets;
+pub use crate::convert::{CollectLines, ToLine};
+pub use crate::layer::LayerIntersections;
+pub use crate::list::IntersectionList;
+pub use crate::{
+    intersect, intersect_line_point, intersect_line_point_with_tolerance, intersect_with,
+    intersect_with_tolerance, to_lines, try_to_lines, EqualityMode, IntersectError, Intersection,
+    SelfIntersectingExclusive, SelfIntersectingInclusive, Tolerance,
+};
@@ -0,0 +1,278 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Preprocessing of the input geometry before it is handed to the intersection tests.
+//!
+//! Tessellated data often contains tiny slivers that only produce degenerate events in the
+//! sweep-line. [`filter_short_segments()`] removes (or merges) such segments and keeps track of
+//! where each surviving segment came from, so that results can be mapped back to the
+//! original input indices.
+
+use super::{ulps_eq_c, IntersectError};
+use crate::order::sweep_order;
+use num_traits::Float;
+
+/// How segments shorter than the threshold are handled by [`filter_short_segments()`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortSegmentMode {
+    /// The short segments are removed, the remaining segments are left untouched.
+    Drop,
+    /// The short segments are collapsed into their midpoint and every end point connected to
+    /// them is moved to that point. Connected chains of short segments collapse into the
+    /// centroid of their end points. Segments collapsing into a single point are removed.
+    Merge,
+}
+
+/// The output of [`filter_short_segments()`].
+/// `original_indices[i]` is the index of `lines[i]` in the unfiltered input.
+#[derive(Clone, Debug)]
+pub struct FilteredLines<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub lines: Vec<geo::Line<T>>,
+    pub original_indices: Vec<usize>,
+}
+
+impl<T> FilteredLines<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns the index in the unfiltered input of the filtered line at `index`
+    pub fn original_index(&self, index: usize) -> Option<usize> {
+        self.original_indices.get(index).copied()
+    }
+
+    /// Translates the line indices of an intersection result, computed on the filtered lines,
+    /// back to the indices of the unfiltered input.
+    /// ```
+    /// # use intersect2d::preprocess::{filter_short_segments, ShortSegmentMode};
+    /// # use intersect2d::SelfIntersectingInclusive;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[
+    ///     [0, 0, 0, 0],
+    ///     [0, 0, 10, 10],
+    ///     [0, 10, 10, 0],
+    /// ]);
+    /// let filtered = filter_short_segments(&lines, 0.1, ShortSegmentMode::Drop).unwrap();
+    /// let rv: Vec<_> = filtered
+    ///     .remap(filtered.lines.self_intersections_inclusive().unwrap())
+    ///     .collect();
+    /// assert_eq!(rv.len(), 1);
+    /// assert_eq!(rv[0].1, vec![1_usize, 2]);
+    /// ```
+    pub fn remap<'a, I>(
+        &'a self,
+        results: I,
+    ) -> impl Iterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a
+    where
+        I: Iterator<Item = (geo::Coordinate<T>, Vec<usize>)> + 'a,
+    {
+        results.map(move |(pos, lines)| {
+            (
+                pos,
+                lines
+                    .into_iter()
+                    .map(|i| self.original_indices[i])
+                    .collect(),
+            )
+        })
+    }
+}
+
+/// Removes, or merges, all the line segments shorter than `min_length`.
+/// ```
+/// # use intersect2d::preprocess::{filter_short_segments, ShortSegmentMode};
+/// # use intersect2d::ulps_eq_c;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 0.],
+///     [10., 0., 10., 0.001],
+///     [10., 0.001, 20., 0.],
+/// ]);
+/// let filtered = filter_short_segments(&lines, 0.01, ShortSegmentMode::Merge).unwrap();
+/// assert_eq!(filtered.original_indices, vec![0_usize, 2]);
+/// assert!(ulps_eq_c(&filtered.lines[0].end, &filtered.lines[1].start));
+/// assert!(ulps_eq_c(&filtered.lines[0].end, &geo::Coordinate { x: 10.0, y: 0.0005 }));
+/// ```
+pub fn filter_short_segments<T>(
+    lines: &[geo::Line<T>],
+    min_length: T,
+    mode: ShortSegmentMode,
+) -> Result<FilteredLines<T>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if !min_length.is_finite() || min_length < T::zero() {
+        return Err(IntersectError::InvalidParameter(
+            "The minimum segment length must be a finite, positive value".to_string(),
+        ));
+    }
+    for a_line in lines.iter() {
+        if !a_line.start.x.is_finite()
+            || !a_line.start.y.is_finite()
+            || !a_line.end.x.is_finite()
+            || !a_line.end.y.is_finite()
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't filter non-finite data".to_string(),
+            ));
+        }
+    }
+    let min_length_sq = min_length * min_length;
+    let is_short = |l: &geo::Line<T>| {
+        let d = l.end - l.start;
+        d.x * d.x + d.y * d.y < min_length_sq
+    };
+
+    match mode {
+        ShortSegmentMode::Drop => {
+            let mut rv = FilteredLines {
+                lines: Vec::with_capacity(lines.len()),
+                original_indices: Vec::with_capacity(lines.len()),
+            };
+            for (index, a_line) in lines.iter().enumerate() {
+                if !is_short(a_line) {
+                    rv.lines.push(*a_line);
+                    rv.original_indices.push(index);
+                }
+            }
+            Ok(rv)
+        }
+        ShortSegmentMode::Merge => Ok(merge_short_segments(lines, is_short)),
+    }
+}
+
+/// Collapses the short segments by joining their end points into common nodes.
+/// The end point at `endpoints[2*i]` is the start of line `i`, `endpoints[2*i+1]` the end.
+fn merge_short_segments<T, F>(lines: &[geo::Line<T>], is_short: F) -> FilteredLines<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    F: Fn(&geo::Line<T>) -> bool,
+{
+    // sort all the end points so that (virtually) identical points are adjacent
+    let mut sorted: Vec<usize> = (0..lines.len() * 2).collect();
+    let endpoint = |i: usize| {
+        let l = &lines[i / 2];
+        [l.start, l.end][i % 2]
+    };
+    sorted.sort_unstable_by(|a, b| {
+        let (a, b) = (endpoint(*a), endpoint(*b));
+        sweep_order(&a, &b)
+    });
+
+    // assign a node to each group of identical points
+    let mut node_of = vec![0_usize; sorted.len()];
+    let mut node_pos = Vec::<geo::Coordinate<T>>::new();
+    for i in sorted.into_iter() {
+        let pos = endpoint(i);
+        match node_pos.last() {
+            Some(last) if ulps_eq_c(last, &pos) => (),
+            _ => node_pos.push(pos),
+        }
+        node_of[i] = node_pos.len() - 1;
+    }
+
+    // join the nodes connected by short segments
+    let mut parent: Vec<usize> = (0..node_pos.len()).collect();
+    fn find(parent: &mut [usize], mut node: usize) -> usize {
+        while parent[node] != node {
+            parent[node] = parent[parent[node]];
+            node = parent[node];
+        }
+        node
+    }
+    for (index, a_line) in lines.iter().enumerate() {
+        if is_short(a_line) {
+            let a = find(&mut parent, node_of[index * 2]);
+            let b = find(&mut parent, node_of[index * 2 + 1]);
+            if a != b {
+                parent[b] = a;
+            }
+        }
+    }
+
+    // every joined group of nodes is represented by the centroid of its nodes
+    let mut sum = vec![
+        geo::Coordinate {
+            x: T::zero(),
+            y: T::zero()
+        };
+        node_pos.len()
+    ];
+    let mut count = vec![T::zero(); node_pos.len()];
+    for (node, pos) in node_pos.iter().enumerate() {
+        let root = find(&mut parent, node);
+        sum[root] = sum[root] + *pos;
+        count[root] = count[root] + T::one();
+    }
+
+    let mut rv = FilteredLines {
+        lines: Vec::with_capacity(lines.len()),
+        original_indices: Vec::with_capacity(lines.len()),
+    };
+    for index in 0..lines.len() {
+        let start = find(&mut parent, node_of[index * 2]);
+        let end = find(&mut parent, node_of[index * 2 + 1]);
+        if start == end {
+            // the segment collapsed into a single point
+            continue;
+        }
+        rv.lines.push(geo::Line {
+            start: geo::Coordinate {
+                x: sum[start].x / count[start],
+                y: sum[start].y / count[start],
+            },
+            end: geo::Coordinate {
+                x: sum[end].x / count[end],
+                y: sum[end].y / count[end],
+            },
+        });
+        rv.original_indices.push(index);
+    }
+    rv
+}
@@ -0,0 +1,358 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Raster debug rendering, enabled by the `image` feature.
+//!
+//! [`rasterize()`] draws segments and intersection markers into an [`RgbaImage`], which can
+//! be inspected pixel by pixel or written as a PNG file with [`RgbaImage::write_png()`]. The
+//! drawing is scaled to fit the image, keeping the aspect ratio, with the y axis pointing up.
+//! Colors and marker size are chosen by a [`RasterStyle`].
+//!
+//! The PNG encoder stores the image uncompressed, the files are large but need no extra
+//! dependencies.
+
+use crate::IntersectError;
+use num_traits::Float;
+use std::io::Write;
+
+/// A color as red, green, blue and alpha
+pub type Rgba = [u8; 4];
+
+/// The styling hooks of [`rasterize()`], all methods have defaults: black segments and red
+/// markers on a white background
+pub trait RasterStyle {
+    fn background(&self) -> Rgba {
+        [255, 255, 255, 255]
+    }
+
+    /// The color of the segment at position `index` of the input lines
+    fn segment_color(&self, _index: usize) -> Rgba {
+        [0, 0, 0, 255]
+    }
+
+    /// The color of the marker of an intersection between `lines`
+    fn marker_color(&self, _lines: &[usize]) -> Rgba {
+        [255, 0, 0, 255]
+    }
+
+    /// The radius of the intersection markers in pixels, 0 draws a single pixel
+    fn marker_radius(&self) -> u32 {
+        2
+    }
+}
+
+/// The default [`RasterStyle`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultStyle;
+
+impl RasterStyle for DefaultStyle {}
+
+/// An 8 bit per channel RGBA image, stored row by row from the top
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Creates an image filled with `color`
+    pub fn new(width: u32, height: u32, color: Rgba) -> Result<Self, IntersectError> {
+        if width == 0 || height == 0 {
+            return Err(IntersectError::InvalidParameter(format!(
+                "The image size must be positive, got {}x{}",
+                width, height
+            )));
+        }
+        let pixels = color
+            .iter()
+            .copied()
+            .cycle()
+            .take(width as usize * height as usize * 4)
+            .collect();
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the color at column `x` and row `y`, or None if it is out of bounds
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Rgba> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let mut rv = [0_u8; 4];
+        rv.copy_from_slice(&self.pixels[i..i + 4]);
+        Some(rv)
+    }
+
+    /// Sets the color at column `x` and row `y`, pixels out of bounds are ignored
+    pub fn put_pixel(&mut self, x: i64, y: i64, color: Rgba) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        self.pixels[i..i + 4].copy_from_slice(&color);
+    }
+
+    /// The raw RGBA bytes, row by row from the top
+    pub fn as_raw(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Encodes the image as an uncompressed PNG file
+    pub fn encode_png(&self) -> Vec<u8> {
+        let row_len = self.width as usize * 4;
+        // every row starts with the filter type, 0 is no filter
+        let mut scanlines = Vec::<u8>::with_capacity((row_len + 1) * self.height as usize);
+        for row in self.pixels.chunks(row_len) {
+            scanlines.push(0);
+            scanlines.extend_from_slice(row);
+        }
+
+        let mut header = Vec::<u8>::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // bit depth 8, color type 6 (RGBA), default compression, filter and interlace methods
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut rv = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        png_chunk(&mut rv, b"IHDR", &header);
+        png_chunk(&mut rv, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut rv, b"IEND", &[]);
+        rv
+    }
+
+    /// Writes the image as an uncompressed PNG file
+    pub fn write_png<W: Write>(&self, mut out: W) -> Result<(), IntersectError> {
+        out.write_all(&self.encode_png())
+            .and_then(|_| out.flush())
+            .map_err(|e| IntersectError::IoError(e.to_string()))
+    }
+
+    /// Draws a line between two pixel positions
+    fn draw_line(&mut self, from: (f64, f64), to: (f64, f64), color: Rgba) {
+        let steps = (to.0 - from.0)
+            .abs()
+            .max((to.1 - from.1).abs())
+            .ceil()
+            .max(1.0);
+        for step in 0..=steps as i64 {
+            let t = step as f64 / steps;
+            self.put_pixel(
+                (from.0 + (to.0 - from.0) * t).round() as i64,
+                (from.1 + (to.1 - from.1) * t).round() as i64,
+                color,
+            );
+        }
+    }
+
+    /// Draws a filled disc centered at a pixel position
+    fn draw_disc(&mut self, center: (f64, f64), radius: u32, color: Rgba) {
+        let (cx, cy) = (center.0.round() as i64, center.1.round() as i64);
+        let r = radius as i64;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if dx * dx + dy * dy <= r * r {
+                    self.put_pixel(cx + dx, cy + dy, color);
+                }
+            }
+        }
+    }
+}
+
+/// The CRC-32 used by PNG chunks
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for b in bytes.iter() {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+    let mut rv = Vec::<u8>::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    rv.extend_from_slice(&[0x78, 0x01]);
+    let blocks = data.len().max(1).div_ceil(MAX_BLOCK);
+    for block in 0..blocks {
+        let chunk = &data[block * MAX_BLOCK..((block + 1) * MAX_BLOCK).min(data.len())];
+        rv.push(if block + 1 == blocks { 1 } else { 0 });
+        let len = chunk.len() as u16;
+        rv.extend_from_slice(&len.to_le_bytes());
+        rv.extend_from_slice(&(!len).to_le_bytes());
+        rv.extend_from_slice(chunk);
+    }
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in data.iter() {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    rv.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    rv
+}
+
+/// Draws `lines` and the intersection points of `results` into a `width` x `height` image.
+/// The drawing is scaled to fit the image with a margin of the marker radius.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::raster::{rasterize, DefaultStyle, RasterStyle};
+/// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+/// let results = AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .compute_collected()?;
+/// let image = rasterize(&lines, &results, 64, 64, &DefaultStyle)?;
+/// assert_eq!(image.pixel(32, 32), Some(DefaultStyle.marker_color(&[0, 1])));
+/// let mut png = Vec::<u8>::new();
+/// image.write_png(&mut png)?;
+/// assert_eq!(&png[1..4], b"PNG");
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn rasterize<T, S>(
+    lines: &[geo::Line<T>],
+    results: &[(geo::Coordinate<T>, Vec<usize>)],
+    width: u32,
+    height: u32,
+    style: &S,
+) -> Result<RgbaImage, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    S: RasterStyle + ?Sized,
+{
+    let mut image = RgbaImage::new(width, height, style.background())?;
+    let to_f64 = |c: &geo::Coordinate<T>| {
+        (
+            c.x.to_f64().unwrap_or(f64::NAN),
+            c.y.to_f64().unwrap_or(f64::NAN),
+        )
+    };
+    let points: Vec<(f64, f64)> = lines
+        .iter()
+        .flat_map(|l| [to_f64(&l.start), to_f64(&l.end)])
+        .chain(results.iter().map(|(p, _)| to_f64(p)))
+        .collect();
+    if points.iter().any(|p| !(p.0.is_finite() && p.1.is_finite())) {
+        return Err(IntersectError::InvalidData(
+            "Can't rasterize non-finite data".to_string(),
+        ));
+    }
+    if points.is_empty() {
+        return Ok(image);
+    }
+    let (min, max) = points.iter().fold((points[0], points[0]), |(min, max), p| {
+        (
+            (min.0.min(p.0), min.1.min(p.1)),
+            (max.0.max(p.0), max.1.max(p.1)),
+        )
+    });
+
+    let margin = style.marker_radius() as f64;
+    let available = (
+        (width as f64 - 1.0 - 2.0 * margin).max(0.0),
+        (height as f64 - 1.0 - 2.0 * margin).max(0.0),
+    );
+    let size = (max.0 - min.0, max.1 - min.1);
+    let scale = match (size.0 > 0.0, size.1 > 0.0) {
+        (true, true) => (available.0 / size.0).min(available.1 / size.1),
+        (true, false) => available.0 / size.0,
+        (false, true) => available.1 / size.1,
+        (false, false) => 0.0,
+    };
+    // center the drawing in the image
+    let offset = (
+        margin + (available.0 - size.0 * scale) / 2.0,
+        margin + (available.1 - size.1 * scale) / 2.0,
+    );
+    let to_pixel = |p: (f64, f64)| {
+        (
+            offset.0 + (p.0 - min.0) * scale,
+            height as f64 - 1.0 - (offset.1 + (p.1 - min.1) * scale),
+        )
+    };
+
+    for (index, l) in lines.iter().enumerate() {
+        image.draw_line(
+            to_pixel(to_f64(&l.start)),
+            to_pixel(to_f64(&l.end)),
+            style.segment_color(index),
+        );
+    }
+    for (position, involved) in results.iter() {
+        image.draw_disc(
+            to_pixel(to_f64(position)),
+            style.marker_radius(),
+            style.marker_color(involved),
+        );
+    }
+    Ok(image)
+}
@@ -0,0 +1,342 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Regression case capture.
+//!
+//! A [`RegressionCase`] holds an input (segments and options) together with the result
+//! computed for it, and can be written to and read back from a small text file. Every
+//! coordinate is written with the shortest representation that parses back to the identical
+//! value, so nothing is lost on the way into a bug report. [`RegressionCase::reproduces()`]
+//! re-runs the case and compares against the recorded result.
+//!
+//! The format, one item per line:
+//! ```text
+//! intersect2d-case 1
+//! ignore_end_point_intersections true
+//! tolerance <epsilon> <max_ulps>
+//! precision floating | fixed_grid <size> | scaled_integer <scale>
+//! lines <count>
+//! <x1> <y1> <x2> <y2>
+//! ...
+//! results <count>
+//! <x> <y> <line> <line> ...
+//! ...
+//! ```
+
+use crate::algorithm::AlgorithmData;
+use crate::precision::PrecisionModel;
+use crate::{IntersectError, Tolerance};
+use num_traits::Float;
+use std::io::{BufRead, Write};
+
+const HEADER: &str = "intersect2d-case 1";
+
+/// An input for the sweep-line and its recorded result
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionCase<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub lines: Vec<geo::Line<T>>,
+    pub ignore_end_point_intersections: bool,
+    pub tolerance: Tolerance<T>,
+    /// The recorded result, in the format of
+    /// [`AlgorithmData::compute()`](crate::algorithm::AlgorithmData::compute)
+    pub results: Vec<(geo::Coordinate<T>, Vec<usize>)>,
+}
+
+fn io_error(err: std::io::Error) -> IntersectError {
+    IntersectError::IoError(err.to_string())
+}
+
+fn parse_error(line: usize, what: &str) -> IntersectError {
+    IntersectError::InvalidData(format!("Regression case line {}: {}", line, what))
+}
+
+/// Reads the text format line by line, keeping track of the line number for the errors
+struct CaseReader<R> {
+    lines: std::io::Lines<R>,
+    line_number: usize,
+}
+
+impl<R: BufRead> CaseReader<R> {
+    fn next_line(&mut self) -> Result<(usize, String), IntersectError> {
+        self.line_number += 1;
+        match self.lines.next() {
+            Some(line) => Ok((self.line_number, line.map_err(io_error)?)),
+            None => Err(IntersectError::InvalidData(
+                "Regression case: unexpected end of file".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the values of a "key values..." line
+    fn keyed(&mut self, key: &str) -> Result<(usize, Vec<String>), IntersectError> {
+        let (n, line) = self.next_line()?;
+        let mut words = line.split_whitespace();
+        if words.next() != Some(key) {
+            return Err(parse_error(n, &format!("expected '{}'", key)));
+        }
+        Ok((n, words.map(|w| w.to_string()).collect()))
+    }
+}
+
+impl<T> RegressionCase<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Runs the sweep-line on the lines and records the result
+    /// ```
+    /// # use intersect2d::regression::RegressionCase;
+    /// # use intersect2d::Tolerance;
+    /// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 1., 1.], [0., 1., 1., 0.1]]);
+    /// let case = RegressionCase::capture(lines, true, Tolerance::default())?;
+    /// let mut file = Vec::<u8>::new();
+    /// case.write(&mut file)?;
+    /// let loaded = RegressionCase::<f64>::read(file.as_slice())?;
+    /// assert_eq!(loaded, case);
+    /// assert!(loaded.reproduces()?);
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn capture(
+        lines: Vec<geo::Line<T>>,
+        ignore_end_point_intersections: bool,
+        tolerance: Tolerance<T>,
+    ) -> Result<Self, IntersectError> {
+        let mut rv = Self {
+            lines,
+            ignore_end_point_intersections,
+            tolerance,
+            results: Vec::new(),
+        };
+        rv.results = rv.rerun()?;
+        Ok(rv)
+    }
+
+    /// Runs the sweep-line on the recorded input, and returns the new result
+    #[allow(clippy::type_complexity)]
+    pub fn rerun(&self) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError> {
+        Ok(AlgorithmData::<T>::default()
+            .with_tolerance(self.tolerance)?
+            .with_ignore_end_point_intersections(self.ignore_end_point_intersections)?
+            .with_ref_lines(self.lines.iter())?
+            .compute()?
+            .collect())
+    }
+
+    /// Returns true if re-running the case produces the recorded result, bit for bit
+    pub fn reproduces(&self) -> Result<bool, IntersectError> {
+        Ok(self.rerun()? == self.results)
+    }
+
+    /// Writes the case in the text format described in the module documentation
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), IntersectError> {
+        // Debug formatting of floats is the shortest representation that round trips
+        writeln!(writer, "{}", HEADER).map_err(io_error)?;
+        writeln!(
+            writer,
+            "ignore_end_point_intersections {}",
+            self.ignore_end_point_intersections
+        )
+        .map_err(io_error)?;
+        writeln!(
+            writer,
+            "tolerance {:?} {}",
+            self.tolerance.epsilon, self.tolerance.max_ulps
+        )
+        .map_err(io_error)?;
+        match self.tolerance.precision {
+            PrecisionModel::Floating => writeln!(writer, "precision floating"),
+            PrecisionModel::FixedGrid(size) => writeln!(writer, "precision fixed_grid {:?}", size),
+            PrecisionModel::ScaledInteger(scale) => {
+                writeln!(writer, "precision scaled_integer {:?}", scale)
+            }
+        }
+        .map_err(io_error)?;
+        writeln!(writer, "lines {}", self.lines.len()).map_err(io_error)?;
+        for l in self.lines.iter() {
+            writeln!(
+                writer,
+                "{:?} {:?} {:?} {:?}",
+                l.start.x, l.start.y, l.end.x, l.end.y
+            )
+            .map_err(io_error)?;
+        }
+        writeln!(writer, "results {}", self.results.len()).map_err(io_error)?;
+        for (position, involved) in self.results.iter() {
+            write!(writer, "{:?} {:?}", position.x, position.y).map_err(io_error)?;
+            for l in involved.iter() {
+                write!(writer, " {}", l).map_err(io_error)?;
+            }
+            writeln!(writer).map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a case written by [`RegressionCase::write()`]
+    pub fn read<R: BufRead>(reader: R) -> Result<Self, IntersectError> {
+        let mut reader = CaseReader {
+            lines: reader.lines(),
+            line_number: 0,
+        };
+        let float = |n: usize, s: &str| -> Result<T, IntersectError> {
+            let value = T::from_str_radix(s, 10).map_err(|_| parse_error(n, "invalid number"))?;
+            if value.is_finite() {
+                Ok(value)
+            } else {
+                Err(parse_error(n, "non-finite number"))
+            }
+        };
+        let integer = |n: usize, s: &str| -> Result<usize, IntersectError> {
+            s.parse::<usize>()
+                .map_err(|_| parse_error(n, "invalid integer"))
+        };
+
+        let (n, header) = reader.next_line()?;
+        if header.trim() != HEADER {
+            return Err(parse_error(n, "not an intersect2d regression case"));
+        }
+        let (n, value) = reader.keyed("ignore_end_point_intersections")?;
+        let ignore_end_point_intersections = match value.as_slice() {
+            [v] if v == "true" => true,
+            [v] if v == "false" => false,
+            _ => return Err(parse_error(n, "expected true or false")),
+        };
+        let (n, value) = reader.keyed("tolerance")?;
+        if value.len() != 2 {
+            return Err(parse_error(n, "expected an epsilon and max ulps"));
+        }
+        let mut tolerance = Tolerance::new(
+            float(n, &value[0])?,
+            value[1]
+                .parse::<u32>()
+                .map_err(|_| parse_error(n, "invalid integer"))?,
+        );
+        let (n, value) = reader.keyed("precision")?;
+        let precision = match value.as_slice() {
+            [m] if m == "floating" => PrecisionModel::Floating,
+            [m, v] if m == "fixed_grid" => PrecisionModel::FixedGrid(float(n, v)?),
+            [m, v] if m == "scaled_integer" => PrecisionModel::ScaledInteger(float(n, v)?),
+            _ => return Err(parse_error(n, "unknown precision model")),
+        };
+        tolerance = tolerance.with_precision_model(precision)?;
+
+        let (n, value) = reader.keyed("lines")?;
+        let line_count = match value.as_slice() {
+            [v] => integer(n, v)?,
+            _ => return Err(parse_error(n, "expected a count")),
+        };
+        let mut case_lines = Vec::with_capacity(line_count);
+        for _ in 0..line_count {
+            let (n, line) = reader.next_line()?;
+            let values = line
+                .split_whitespace()
+                .map(|w| float(n, w))
+                .collect::<Result<Vec<T>, IntersectError>>()?;
+            if values.len() != 4 {
+                return Err(parse_error(n, "expected four coordinates"));
+            }
+            case_lines.push(geo::Line::new(
+                geo::Coordinate {
+                    x: values[0],
+                    y: values[1],
+                },
+                geo::Coordinate {
+                    x: values[2],
+                    y: values[3],
+                },
+            ));
+        }
+
+        let (n, value) = reader.keyed("results")?;
+        let result_count = match value.as_slice() {
+            [v] => integer(n, v)?,
+            _ => return Err(parse_error(n, "expected a count")),
+        };
+        let mut results = Vec::with_capacity(result_count);
+        for _ in 0..result_count {
+            let (n, line) = reader.next_line()?;
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.len() < 2 {
+                return Err(parse_error(n, "expected a position"));
+            }
+            let position = geo::Coordinate {
+                x: float(n, words[0])?,
+                y: float(n, words[1])?,
+            };
+            let mut involved = Vec::with_capacity(words.len() - 2);
+            for w in words[2..].iter() {
+                let l = integer(n, w)?;
+                if l >= line_count {
+                    return Err(parse_error(n, "line index out of range"));
+                }
+                involved.push(l);
+            }
+            results.push((position, involved));
+        }
+        Ok(Self {
+            lines: case_lines,
+            ignore_end_point_intersections,
+            tolerance,
+            results,
+        })
+    }
+
+    /// Writes the case to a file
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), IntersectError> {
+        let file = std::fs::File::create(path).map_err(io_error)?;
+        let mut writer = std::io::BufWriter::new(file);
+        self.write(&mut writer)?;
+        writer.flush().map_err(io_error)
+    }
+
+    /// Reads a case from a file
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, IntersectError> {
+        let file = std::fs::File::open(path).map_err(io_error)?;
+        Self::read(std::io::BufReader::new(file))
+    }
+}
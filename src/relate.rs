@@ -0,0 +1,406 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! DE-9IM relations between line sets.
+//!
+//! [`relate()`] computes the [`IntersectionMatrix`] between two collections of
+//! `geo::LineString`s, with the same semantics as `relate()` in JTS/GEOS. The boundary of a line
+//! set is found with the "mod-2" rule: a point is on the boundary if it is the end point of an
+//! odd number of open line strings.
+//!
+//! The intersection points are found with the sweep-line algorithm, the segments of both sets
+//! are then split at these points and each piece is classified against the other set.
+
+use super::{intersect_line_point, ulps_eq_c, Closedness, IntersectError};
+use crate::algorithm::AlgorithmData;
+use crate::order::sweep_order;
+use num_traits::Float;
+use std::{cmp, fmt};
+
+/// The dimension of an intersection, `Empty` is written as 'F' in DE-9IM notation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Dimension {
+    Empty,
+    Point,
+    Curve,
+    Surface,
+}
+
+impl Dimension {
+    /// Returns the DE-9IM character of the dimension
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::Empty => 'F',
+            Self::Point => '0',
+            Self::Curve => '1',
+            Self::Surface => '2',
+        }
+    }
+}
+
+/// The location of a point relative to a geometry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+    Interior = 0,
+    Boundary = 1,
+    Exterior = 2,
+}
+
+/// A DE-9IM intersection matrix.
+/// The rows are the locations in the first geometry, the columns the locations in the second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntersectionMatrix([[Dimension; 3]; 3]);
+
+impl IntersectionMatrix {
+    /// Returns the dimension of the intersection between location `a` of the first geometry and
+    /// location `b` of the second geometry
+    pub fn get(&self, a: Location, b: Location) -> Dimension {
+        self.0[a as usize][b as usize]
+    }
+
+    /// raise the dimension of an entry to at least `dimension`
+    fn set_at_least(&mut self, a: Location, b: Location, dimension: Dimension) {
+        let entry = &mut self.0[a as usize][b as usize];
+        *entry = cmp::max(*entry, dimension);
+    }
+
+    /// Tests the matrix against a DE-9IM pattern, e.g. "T*F**F***".
+    /// The pattern characters are 'T' (not empty), 'F' (empty), '*' (anything), '0', '1'
+    /// and '2'.
+    /// ```
+    /// # use intersect2d::relate::relate;
+    /// let a = vec![geo::LineString::from(vec![(0., 0.), (10., 0.)])];
+    /// let b = vec![geo::LineString::from(vec![(5., -5.), (5., 5.)])];
+    /// let matrix = relate(&a, &b)?;
+    /// assert_eq!(matrix.to_string(), "0F1FF0102");
+    /// assert!(matrix.matches("0********")?);
+    /// assert!(matrix.is_crosses());
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn matches(&self, pattern: &str) -> Result<bool, IntersectError> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        if pattern.len() != 9 {
+            return Err(IntersectError::InvalidParameter(
+                "A DE-9IM pattern must be 9 characters long".to_string(),
+            ));
+        }
+        let mut rv = true;
+        for (i, p) in pattern.iter().enumerate() {
+            let d = self.0[i / 3][i % 3];
+            rv &= match p.to_ascii_uppercase() {
+                '*' => true,
+                'T' => d != Dimension::Empty,
+                'F' | '0' | '1' | '2' => d.as_char() == p.to_ascii_uppercase(),
+                _ => {
+                    return Err(IntersectError::InvalidParameter(format!(
+                        "Invalid DE-9IM pattern character '{}'",
+                        p
+                    )))
+                }
+            };
+        }
+        Ok(rv)
+    }
+
+    fn is_empty(&self, a: Location, b: Location) -> bool {
+        self.get(a, b) == Dimension::Empty
+    }
+
+    /// The geometries have no point in common
+    pub fn is_disjoint(&self) -> bool {
+        use Location::{Boundary, Interior};
+        self.is_empty(Interior, Interior)
+            && self.is_empty(Interior, Boundary)
+            && self.is_empty(Boundary, Interior)
+            && self.is_empty(Boundary, Boundary)
+    }
+
+    /// The geometries have at least one point in common
+    pub fn is_intersects(&self) -> bool {
+        !self.is_disjoint()
+    }
+
+    /// The geometries only have boundary points in common
+    pub fn is_touches(&self) -> bool {
+        self.is_empty(Location::Interior, Location::Interior) && self.is_intersects()
+    }
+
+    /// The interiors of the (line) geometries intersect in points only
+    pub fn is_crosses(&self) -> bool {
+        self.get(Location::Interior, Location::Interior) == Dimension::Point
+    }
+
+    /// The interiors of the geometries share a curve, but neither geometry contains the other
+    pub fn is_overlaps(&self) -> bool {
+        use Location::{Exterior, Interior};
+        self.get(Interior, Interior) == Dimension::Curve
+            && !self.is_empty(Interior, Exterior)
+            && !self.is_empty(Exterior, Interior)
+    }
+
+    /// The first geometry lies within the second
+    pub fn is_within(&self) -> bool {
+        use Location::{Boundary, Exterior, Interior};
+        !self.is_empty(Interior, Interior)
+            && self.is_empty(Interior, Exterior)
+            && self.is_empty(Boundary, Exterior)
+    }
+
+    /// The first geometry contains the second
+    pub fn is_contains(&self) -> bool {
+        use Location::{Boundary, Exterior, Interior};
+        !self.is_empty(Interior, Interior)
+            && self.is_empty(Exterior, Interior)
+            && self.is_empty(Exterior, Boundary)
+    }
+
+    /// The geometries are topologically equal
+    pub fn is_equals(&self) -> bool {
+        self.is_within() && self.is_contains()
+    }
+}
+
+impl fmt::Display for IntersectionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.0.iter() {
+            for d in row.iter() {
+                write!(f, "{}", d.as_char())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn compare_position<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> cmp::Ordering
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    sweep_order(a, b)
+}
+
+/// Returns the boundary points, sorted in sweep-line order, of a line set using the mod-2 rule
+fn boundary<T>(line_strings: &[geo::LineString<T>]) -> Vec<geo::Coordinate<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut end_points = Vec::<geo::Coordinate<T>>::new();
+    for a_line_string in line_strings.iter() {
+        if let (Some(first), Some(last)) = (a_line_string.0.first(), a_line_string.0.last()) {
+            if a_line_string.0.len() > 1 && !Closedness::Infer.is_closed(a_line_string) {
+                end_points.push(*first);
+                end_points.push(*last);
+            }
+        }
+    }
+    end_points.sort_unstable_by(compare_position);
+    let mut rv = Vec::new();
+    let mut group_start = 0;
+    while group_start < end_points.len() {
+        let mut group_end = group_start + 1;
+        while group_end < end_points.len()
+            && ulps_eq_c(&end_points[group_end], &end_points[group_start])
+        {
+            group_end += 1;
+        }
+        if (group_end - group_start) % 2 == 1 {
+            rv.push(end_points[group_start]);
+        }
+        group_start = group_end;
+    }
+    rv
+}
+
+/// Returns the projection parameter of `p` along `line`
+fn parameter<T>(line: &geo::Line<T>, p: &geo::Coordinate<T>) -> T
+where
+    T: Float + geo::CoordFloat,
+{
+    let r = line.end - line.start;
+    let d = *p - line.start;
+    (d.x * r.x + d.y * r.y) / (r.x * r.x + r.y * r.y)
+}
+
+/// Computes the DE-9IM intersection matrix between two line sets.
+/// See [`IntersectionMatrix::matches()`] for an example.
+pub fn relate<T>(
+    a: &[geo::LineString<T>],
+    b: &[geo::LineString<T>],
+) -> Result<IntersectionMatrix, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    // the segments of both sets, the payload tells which set the segment belongs to
+    let mut lines = Vec::<(geo::Line<T>, usize)>::new();
+    for (side, line_strings) in [a, b].iter().enumerate() {
+        for a_line_string in line_strings.iter() {
+            for a_line in a_line_string.lines() {
+                if !ulps_eq_c(&a_line.start, &a_line.end) {
+                    lines.push((a_line, side));
+                }
+            }
+        }
+    }
+    let results = AlgorithmData::<T, usize>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_payload_lines(lines.iter().copied())?
+        .compute()?;
+
+    // the points where the sets meet, and with what segments they meet
+    let mut nodes = Vec::<geo::Coordinate<T>>::new();
+    let mut split_points = vec![Vec::<geo::Coordinate<T>>::new(); lines.len()];
+    let mut neighbours = vec![Vec::<usize>::new(); lines.len()];
+    for (position, involved) in results {
+        if !(involved.iter().any(|l| lines[*l].1 == 0) && involved.iter().any(|l| lines[*l].1 == 1))
+        {
+            continue;
+        }
+        nodes.push(position);
+        for l in involved.iter() {
+            split_points[*l].push(position);
+            for other in involved.iter() {
+                if lines[*other].1 != lines[*l].1 {
+                    neighbours[*l].push(*other);
+                }
+            }
+        }
+    }
+    // the nodes are already sorted in sweep-line order
+    let boundaries = [boundary(a), boundary(b)];
+    let contains = |sorted: &[geo::Coordinate<T>], p: &geo::Coordinate<T>| {
+        sorted
+            .binary_search_by(|probe| compare_position(probe, p))
+            .is_ok()
+    };
+    let locate = |side: usize, p: &geo::Coordinate<T>| {
+        if contains(&boundaries[side], p) {
+            Location::Boundary
+        } else if contains(&nodes, p) {
+            // the nodes lie on both sets
+            Location::Interior
+        } else {
+            Location::Exterior
+        }
+    };
+
+    let mut matrix = IntersectionMatrix([[Dimension::Empty; 3]; 3]);
+    matrix.0[2][2] = Dimension::Surface;
+
+    // classify the points where something happens
+    for p in nodes
+        .iter()
+        .chain(boundaries[0].iter())
+        .chain(boundaries[1].iter())
+    {
+        let (la, lb) = (locate(0, p), locate(1, p));
+        if la != Location::Exterior || lb != Location::Exterior {
+            matrix.set_at_least(la, lb, Dimension::Point);
+        }
+    }
+
+    // split the segments at the nodes and classify each piece against the other set
+    for (index, (line, side)) in lines.iter().enumerate() {
+        let mut points = std::mem::take(&mut split_points[index]);
+        points.push(line.start);
+        points.push(line.end);
+        points.sort_unstable_by(|p, q| {
+            parameter(line, p)
+                .partial_cmp(&parameter(line, q))
+                .unwrap_or(cmp::Ordering::Equal)
+        });
+        points.dedup_by(|p, q| ulps_eq_c(p, q));
+        for piece in points.windows(2) {
+            let midpoint = geo::Coordinate {
+                x: (piece[0].x + piece[1].x) / (T::one() + T::one()),
+                y: (piece[0].y + piece[1].y) / (T::one() + T::one()),
+            };
+            let on_other = neighbours[index]
+                .iter()
+                .any(|other| intersect_line_point(&lines[*other].0, &midpoint).is_some());
+            let location = if on_other {
+                Location::Interior
+            } else {
+                Location::Exterior
+            };
+            if *side == 0 {
+                matrix.set_at_least(Location::Interior, location, Dimension::Curve);
+            } else {
+                matrix.set_at_least(location, Location::Interior, Dimension::Curve);
+            }
+        }
+    }
+    Ok(matrix)
+}
+
+/// Computes the DE-9IM intersection matrix between two sets of lines, every line is treated
+/// as a line string of its own.
+pub fn relate_lines<T>(
+    a: &[geo::Line<T>],
+    b: &[geo::Line<T>],
+) -> Result<IntersectionMatrix, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let to_line_strings = |lines: &[geo::Line<T>]| -> Vec<geo::LineString<T>> {
+        lines
+            .iter()
+            .map(|l| geo::LineString(vec![l.start, l.end]))
+            .collect()
+    };
+    relate(&to_line_strings(a), &to_line_strings(b))
+}
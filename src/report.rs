@@ -0,0 +1,175 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Human readable intersection reports.
+//!
+//! [`format_report()`] turns the results of a search into a plain text table with one row per
+//! intersection point: the position, the involved lines and the [`IntersectionKind`] of the
+//! point, for CLI output and log files.
+
+use super::ulps_eq_c;
+use crate::collinear::overlap_interval;
+use num_traits::Float;
+use std::fmt;
+use std::fmt::Write;
+
+/// How the lines meet at an intersection point
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntersectionKind {
+    /// The point is in the interior of every involved line
+    Crossing,
+    /// The point is an end point of some, but not all, of the involved lines
+    Touch,
+    /// The point is an end point of every involved line
+    EndPoints,
+    /// Some of the involved lines are collinear and overlap
+    Overlap,
+}
+
+impl fmt::Display for IntersectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Crossing => "crossing",
+            Self::Touch => "touch",
+            Self::EndPoints => "end points",
+            Self::Overlap => "overlap",
+        })
+    }
+}
+
+/// Classifies an intersection point, `involved` are indices into `lines`
+pub fn intersection_kind<T>(
+    lines: &[geo::Line<T>],
+    position: &geo::Coordinate<T>,
+    involved: &[usize],
+) -> IntersectionKind
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    for (i, l1) in involved.iter().enumerate() {
+        for l2 in involved.iter().skip(i + 1) {
+            if overlap_interval(&lines[*l1], &lines[*l2]).is_some() {
+                return IntersectionKind::Overlap;
+            }
+        }
+    }
+    let end_points = involved
+        .iter()
+        .filter(|l| ulps_eq_c(&lines[**l].start, position) || ulps_eq_c(&lines[**l].end, position))
+        .count();
+    if end_points == 0 {
+        IntersectionKind::Crossing
+    } else if end_points == involved.len() {
+        IntersectionKind::EndPoints
+    } else {
+        IntersectionKind::Touch
+    }
+}
+
+/// Formats the results of a search as a table, `lines` are the input lines of the search.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::report::format_report;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [0., 10., 10., 0.],
+///     [10., 0., 10., 5.],
+/// ]);
+/// let results = AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .compute()?;
+/// let report = format_report(&lines, results);
+/// let expected = [
+///     " #  position  lines  kind",
+///     " 0  (10, 0)   1, 2   end points",
+///     " 1  (5, 5)    0, 1   crossing",
+/// ];
+/// assert_eq!(report.lines().collect::<Vec<_>>(), expected);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn format_report<T, I>(lines: &[geo::Line<T>], results: I) -> String
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq + fmt::Display,
+    T::Epsilon: Copy,
+    I: IntoIterator<Item = (geo::Coordinate<T>, Vec<usize>)>,
+{
+    let rows: Vec<[String; 4]> = results
+        .into_iter()
+        .enumerate()
+        .map(|(i, (position, involved))| {
+            let kind = intersection_kind(lines, &position, &involved);
+            [
+                i.to_string(),
+                format!("({}, {})", position.x, position.y),
+                involved
+                    .iter()
+                    .map(|l| l.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                kind.to_string(),
+            ]
+        })
+        .collect();
+    let header = ["#", "position", "lines", "kind"];
+    let mut widths = header.map(str::len);
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut rv = String::new();
+    let mut write_row = |cells: [&str; 4]| {
+        let _ = write!(rv, " {:>w$}", cells[0], w = widths[0]);
+        let _ = write!(rv, "  {:<w$}", cells[1], w = widths[1]);
+        let _ = write!(rv, "  {:<w$}", cells[2], w = widths[2]);
+        let _ = writeln!(rv, "  {}", cells[3]);
+    };
+    write_row(header);
+    for row in rows.iter() {
+        write_row([&row[0], &row[1], &row[2], &row[3]]);
+    }
+    rv
+}
@@ -0,0 +1,167 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Structure-of-arrays storage of the input line segments.
+//!
+//! The sweep mostly reads one or two coordinates of a segment at a time (e.g. the x
+//! coordinates when ordering the sweep-line status), so the end point coordinates are stored
+//! in four separate arrays instead of a `Vec<geo::Line<T>>`. Segments are converted to and
+//! from `geo::Line` only at the API boundary.
+//! Segment indices are limited to `u32`.
+
+use crate::IntersectError;
+
+#[derive(Clone, Debug)]
+pub(crate) struct SegmentStore<T> {
+    start_x: Vec<T>,
+    start_y: Vec<T>,
+    end_x: Vec<T>,
+    end_y: Vec<T>,
+}
+
+impl<T> Default for SegmentStore<T> {
+    fn default() -> Self {
+        Self {
+            start_x: Vec::new(),
+            start_y: Vec::new(),
+            end_x: Vec::new(),
+            end_y: Vec::new(),
+        }
+    }
+}
+
+impl<T> SegmentStore<T>
+where
+    T: geo::CoordNum,
+{
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.start_x.len()
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.start_x.is_empty()
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.start_x.reserve(additional);
+        self.start_y.reserve(additional);
+        self.end_x.reserve(additional);
+        self.end_y.reserve(additional);
+    }
+
+    /// Appends a segment, returns its index
+    pub(crate) fn push(&mut self, line: geo::Line<T>) -> Result<u32, IntersectError> {
+        let index = self.len();
+        if index > u32::MAX as usize {
+            return Err(IntersectError::InvalidData(format!(
+                "Can't handle more than {} lines",
+                u32::MAX as usize + 1
+            )));
+        }
+        self.start_x.push(line.start.x);
+        self.start_y.push(line.start.y);
+        self.end_x.push(line.end.x);
+        self.end_y.push(line.end.y);
+        Ok(index as u32)
+    }
+
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.start_x.truncate(len);
+        self.start_y.truncate(len);
+        self.end_x.truncate(len);
+        self.end_y.truncate(len);
+    }
+
+    #[inline(always)]
+    pub(crate) fn start(&self, index: usize) -> geo::Coordinate<T> {
+        geo::Coordinate {
+            x: self.start_x[index],
+            y: self.start_y[index],
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn end(&self, index: usize) -> geo::Coordinate<T> {
+        geo::Coordinate {
+            x: self.end_x[index],
+            y: self.end_y[index],
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn start_x(&self, index: usize) -> T {
+        self.start_x[index]
+    }
+
+    #[inline(always)]
+    pub(crate) fn start_y(&self, index: usize) -> T {
+        self.start_y[index]
+    }
+
+    #[inline(always)]
+    pub(crate) fn end_x(&self, index: usize) -> T {
+        self.end_x[index]
+    }
+
+    #[inline(always)]
+    pub(crate) fn end_y(&self, index: usize) -> T {
+        self.end_y[index]
+    }
+
+    #[inline(always)]
+    pub(crate) fn get(&self, index: usize) -> geo::Line<T> {
+        geo::Line {
+            start: self.start(index),
+            end: self.end(index),
+        }
+    }
+
+    /// Converts all the segments to `geo::Line`s
+    pub(crate) fn to_lines(&self) -> Vec<geo::Line<T>> {
+        (0..self.len()).map(|i| self.get(i)).collect()
+    }
+}
@@ -0,0 +1,176 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+//! Self overlaps of polylines.
+//!
+//! The self intersection tests of `geo::LineString` skip every pair of segments sharing an end
+//! point, so a segment doubling back over the previous one (a zero area spike) goes unnoticed,
+//! while a later part of the line string running along an earlier edge is reported as
+//! one or two plain intersection points. [`self_intersection_kinds()`] reports the crossings,
+//! the spikes and the retraced edges of a line string as separate kinds of
+//! [`SelfIntersectionKind`].
+
+use crate::algorithm::AlgorithmData;
+use crate::collinear::overlap_interval;
+use crate::{ulps_eq_c, Closedness, IntersectError};
+use num_traits::Float;
+
+/// A self intersection of a line string, see [`self_intersection_kinds()`].
+/// The segment indices refer to the segments of the line string, segment `i` goes from vertex
+/// `i` to vertex `i + 1`, and the first index of a pair is always the lower one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelfIntersectionKind<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Two segments cross, or touch, at a single point that isn't the vertex connecting them.
+    Crossing {
+        position: geo::Coordinate<T>,
+        segments: (usize, usize),
+    },
+    /// A segment doubles back over the segment right before it. `overlap` is the shared
+    /// part, in the direction of the first segment.
+    Spike {
+        overlap: geo::Line<T>,
+        segments: (usize, usize),
+    },
+    /// A segment runs along a part of an earlier, not connected, segment. `overlap` is the
+    /// shared part, in the direction of the first segment.
+    Retrace {
+        overlap: geo::Line<T>,
+        segments: (usize, usize),
+    },
+}
+
+/// Returns the self intersections of `line_string`, telling true crossings apart from
+/// collinear overlaps. Overlapping segments are reported once, as a spike if they are
+/// connected and as a retraced edge otherwise, and never as crossings. The first and the last
+/// segment of a closed line string (see [`Closedness::Infer`]) are connected.
+/// Zero length segments are ignored, the segments on either side of them are connected.
+/// The results, apart from the overlaps, are sorted by position.
+/// ```
+/// # use intersect2d::self_overlap::{self_intersection_kinds, SelfIntersectionKind};
+/// let spike = geo::LineString::from(vec![(0., 0.), (10., 0.), (6., 0.)]);
+/// assert_eq!(
+///     self_intersection_kinds(&spike)?,
+///     vec![SelfIntersectionKind::Spike {
+///         overlap: [(6., 0.), (10., 0.)].into(),
+///         segments: (0, 1)
+///     }]
+/// );
+///
+/// let crossing = geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (4., -2.)]);
+/// assert_eq!(
+///     self_intersection_kinds(&crossing)?,
+///     vec![SelfIntersectionKind::Crossing {
+///         position: geo::Coordinate { x: 5., y: 0. },
+///         segments: (0, 2)
+///     }]
+/// );
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn self_intersection_kinds<T>(
+    line_string: &geo::LineString<T>,
+) -> Result<Vec<SelfIntersectionKind<T>>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    // the non zero length segments, and their index in the line string
+    let mut lines = Vec::<geo::Line<T>>::new();
+    let mut origin = Vec::<usize>::new();
+    for (segment, vertices) in line_string.0.windows(2).enumerate() {
+        let a_line = geo::Line::new(vertices[0], vertices[1]);
+        if ulps_eq_c(&a_line.start, &a_line.end) {
+            continue;
+        }
+        lines.push(a_line);
+        origin.push(segment);
+    }
+    let closed = Closedness::Infer.is_closed(line_string);
+    // l1 < l2, index into lines
+    let connected = |l1: usize, l2: usize| {
+        l2 == l1 + 1 || (closed && l1 == 0 && l2 + 1 == lines.len() && lines.len() > 2)
+    };
+    let results = AlgorithmData::<T>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?
+        .compute()?;
+
+    let mut rv = Vec::<SelfIntersectionKind<T>>::new();
+    let mut tested_overlaps = ahash::AHashSet::<(usize, usize)>::default();
+    for (position, mut involved) in results {
+        involved.sort_unstable();
+        for (i, l1) in involved.iter().enumerate() {
+            for l2 in involved.iter().skip(i + 1) {
+                let (l1, l2) = (*l1, *l2);
+                let segments = (origin[l1], origin[l2]);
+                if let Some((overlap, _, _)) = overlap_interval(&lines[l1], &lines[l2]) {
+                    if tested_overlaps.insert((l1, l2)) {
+                        rv.push(if connected(l1, l2) {
+                            SelfIntersectionKind::Spike { overlap, segments }
+                        } else {
+                            SelfIntersectionKind::Retrace { overlap, segments }
+                        });
+                    }
+                    continue;
+                }
+                if connected(l1, l2) {
+                    // the connecting vertex
+                    let vertex = if l2 == l1 + 1 {
+                        lines[l1].end
+                    } else {
+                        lines[l1].start
+                    };
+                    if ulps_eq_c(&vertex, &position) {
+                        continue;
+                    }
+                }
+                rv.push(SelfIntersectionKind::Crossing { position, segments });
+            }
+        }
+    }
+    Ok(rv)
+}
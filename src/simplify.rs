@@ -0,0 +1,344 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Topology preserving line simplification.
+//!
+//! Plain Visvalingam-Whyatt simplification repeatedly removes the vertex spanning the
+//! smallest triangle with its neighbours, which can make a line string cross itself or
+//! another feature. [`simplify_vw_preserve_topology()`] runs the same algorithm over a whole
+//! layer at once, and rejects every removal where the shortcut segment would intersect the
+//! current (already simplified) geometry anywhere but at its own end points, or where the
+//! removed triangle contains a vertex of the layer.
+//! The area threshold has the same meaning as the `epsilon` of `geo::SimplifyVW`.
+
+use super::{intersect, ulps_eq_c, Closedness, IntersectError, Intersection};
+use crate::collinear::overlap_interval;
+use crate::vector::cross_z;
+use num_traits::Float;
+use std::cmp;
+use std::collections::BinaryHeap;
+
+/// A vertex removal candidate, ordered so that the `BinaryHeap` pops the smallest area first
+struct Candidate<T> {
+    area: T,
+    feature: usize,
+    vertex: usize,
+    /// the version of the vertex when the candidate was created, stale candidates are skipped
+    version: usize,
+}
+
+impl<T: Float> Candidate<T> {
+    fn sort_cmp(&self, other: &Self) -> cmp::Ordering {
+        self.area
+            .partial_cmp(&other.area)
+            .unwrap_or(cmp::Ordering::Equal)
+            .then(self.feature.cmp(&other.feature))
+            .then(self.vertex.cmp(&other.vertex))
+    }
+}
+
+impl<T: Float> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sort_cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<T: Float> Eq for Candidate<T> {}
+
+impl<T: Float> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Float> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        // reversed, BinaryHeap is a max-heap
+        other.sort_cmp(self)
+    }
+}
+
+/// The vertices of one feature as a doubly linked list, removed vertices are unlinked
+struct Chain<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    points: Vec<geo::Coordinate<T>>,
+    prev: Vec<usize>,
+    next: Vec<usize>,
+    alive: Vec<bool>,
+    version: Vec<usize>,
+    remaining: usize,
+    /// the minimum number of vertices, 4 for rings and 2 for open line strings
+    minimum: usize,
+}
+
+impl<T> Chain<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// the area of the triangle spanned by an interior vertex and its neighbours
+    fn area(&self, vertex: usize) -> T {
+        let (p, v, n) = (
+            self.points[self.prev[vertex]],
+            self.points[vertex],
+            self.points[self.next[vertex]],
+        );
+        cross_z(&(v - p), &(n - p)).abs() / (T::one() + T::one())
+    }
+
+    fn is_interior(&self, vertex: usize) -> bool {
+        vertex != 0 && vertex + 1 != self.points.len()
+    }
+}
+
+/// Returns true if the point lies inside, or on the border, of the triangle `(a, b, c)`
+fn in_triangle<T>(
+    point: &geo::Coordinate<T>,
+    a: &geo::Coordinate<T>,
+    b: &geo::Coordinate<T>,
+    c: &geo::Coordinate<T>,
+) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let d1 = cross_z(&(*b - *a), &(*point - *a));
+    let d2 = cross_z(&(*c - *b), &(*point - *b));
+    let d3 = cross_z(&(*a - *c), &(*point - *c));
+    let has_negative = d1 < T::zero() || d2 < T::zero() || d3 < T::zero();
+    let has_positive = d1 > T::zero() || d2 > T::zero() || d3 > T::zero();
+    if has_negative && has_positive {
+        return false;
+    }
+    // a degenerate triangle is a segment, check the bounding box too
+    point.x >= a.x.min(b.x).min(c.x)
+        && point.x <= a.x.max(b.x).max(c.x)
+        && point.y >= a.y.min(b.y).min(c.y)
+        && point.y <= a.y.max(b.y).max(c.y)
+}
+
+/// Returns true if `vertex` of `feature` can be removed: the shortcut segment replacing
+/// `(prev, vertex)` and `(vertex, next)` only meets the current geometry at its end points,
+/// and no other vertex lies within the removed triangle (i.e. no feature changes sides)
+fn is_removal_valid<T>(chains: &[Chain<T>], feature: usize, vertex: usize) -> bool
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let chain = &chains[feature];
+    let (prev, next) = (chain.prev[vertex], chain.next[vertex]);
+    let (p, v, n) = (chain.points[prev], chain.points[vertex], chain.points[next]);
+    let shortcut = geo::Line::new(p, n);
+    if ulps_eq_c(&p, &n) {
+        return false;
+    }
+    let (low_x, high_x) = (p.x.min(v.x).min(n.x), p.x.max(v.x).max(n.x));
+    let (low_y, high_y) = (p.y.min(v.y).min(n.y), p.y.max(v.y).max(n.y));
+    for (index, chain) in chains.iter().enumerate() {
+        let mut i = 0;
+        while i < chain.points.len() {
+            let point = chain.points[i];
+            if !ulps_eq_c(&point, &p)
+                && !ulps_eq_c(&point, &v)
+                && !ulps_eq_c(&point, &n)
+                && in_triangle(&point, &p, &v, &n)
+            {
+                return false;
+            }
+            if i + 1 == chain.points.len() {
+                break;
+            }
+            let j = chain.next[i];
+            if index == feature && (i == vertex || j == vertex || (i, j) == (prev, next)) {
+                i = j;
+                continue;
+            }
+            let segment = geo::Line::new(point, chain.points[j]);
+            i = j;
+            if segment.start.x.max(segment.end.x) < low_x
+                || segment.start.x.min(segment.end.x) > high_x
+                || segment.start.y.max(segment.end.y) < low_y
+                || segment.start.y.min(segment.end.y) > high_y
+            {
+                continue;
+            }
+            match intersect(&shortcut, &segment) {
+                None => (),
+                Some(Intersection::Intersection(point))
+                    if ulps_eq_c(&point, &p) || ulps_eq_c(&point, &n) => {}
+                // collinear segments only touching at an end point are fine
+                Some(Intersection::OverLap(_))
+                    if overlap_interval(&shortcut, &segment).is_none() => {}
+                Some(_) => return false,
+            }
+        }
+    }
+    true
+}
+
+/// Simplifies every line string of a layer with the Visvalingam-Whyatt algorithm, removing
+/// vertices spanning a triangle with an area smaller than `epsilon`.
+/// A removal is rejected if the new segment would intersect any other segment of the layer
+/// (or of the same line string), or if a vertex of the layer lies within the removed
+/// triangle. So no new self intersections or crossings between features are created, and no
+/// feature ends up on the other side of a line string. Intersections already present in the input are left as they are.
+/// The end points of the line strings are never removed, closed rings keep at least four
+/// vertices. Every removal is checked against the whole layer, so this is meant for layers of
+/// moderate size.
+/// ```
+/// # use intersect2d::simplify::simplify_vw_preserve_topology;
+/// let layer = vec![
+///     geo::LineString::from(vec![(0., 0.), (5., 1.), (10., 0.)]),
+///     // a small feature inside the triangle that would be cut off
+///     geo::LineString::from(vec![(5., 0.2), (5., 0.8)]),
+///     geo::LineString::from(vec![(0., 5.), (5., 6.), (10., 5.)]),
+/// ];
+/// let simplified = simplify_vw_preserve_topology(&layer, 10.)?;
+/// assert_eq!(simplified[0], layer[0]);
+/// assert_eq!(simplified[1], layer[1]);
+/// assert_eq!(simplified[2], geo::LineString::from(vec![(0., 5.), (10., 5.)]));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn simplify_vw_preserve_topology<T>(
+    line_strings: &[geo::LineString<T>],
+    epsilon: T,
+) -> Result<Vec<geo::LineString<T>>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if !(epsilon.is_finite() && epsilon >= T::zero()) {
+        return Err(IntersectError::InvalidParameter(
+            "The simplification epsilon must be finite and not negative".to_string(),
+        ));
+    }
+    let mut chains = Vec::<Chain<T>>::with_capacity(line_strings.len());
+    for a_line_string in line_strings.iter() {
+        if a_line_string
+            .0
+            .iter()
+            .any(|c| !(c.x.is_finite() && c.y.is_finite()))
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't simplify non-finite data".to_string(),
+            ));
+        }
+        let closed = Closedness::Infer.is_closed(a_line_string);
+        let points = a_line_string.0.clone();
+        let len = points.len();
+        chains.push(Chain {
+            prev: (0..len).map(|i| i.saturating_sub(1)).collect(),
+            next: (0..len).map(|i| i + 1).collect(),
+            alive: vec![true; len],
+            version: vec![0; len],
+            remaining: len,
+            minimum: if closed { 4 } else { 2 },
+            points,
+        });
+    }
+
+    let mut heap = BinaryHeap::<Candidate<T>>::new();
+    for (feature, chain) in chains.iter().enumerate() {
+        for vertex in (0..chain.points.len()).filter(|v| chain.is_interior(*v)) {
+            heap.push(Candidate {
+                area: chain.area(vertex),
+                feature,
+                vertex,
+                version: 0,
+            });
+        }
+    }
+    while let Some(candidate) = heap.pop() {
+        if candidate.area >= epsilon {
+            break;
+        }
+        let (feature, vertex) = (candidate.feature, candidate.vertex);
+        let chain = &chains[feature];
+        if !chain.alive[vertex]
+            || chain.version[vertex] != candidate.version
+            || chain.remaining <= chain.minimum
+        {
+            continue;
+        }
+        if !is_removal_valid(&chains, feature, vertex) {
+            continue;
+        }
+        let (prev, next) = (chain.prev[vertex], chain.next[vertex]);
+        let chain = &mut chains[feature];
+        chain.alive[vertex] = false;
+        chain.remaining -= 1;
+        chain.next[prev] = next;
+        chain.prev[next] = prev;
+        for neighbour in [prev, next].iter().copied() {
+            if chain.is_interior(neighbour) {
+                chain.version[neighbour] += 1;
+                heap.push(Candidate {
+                    area: chain.area(neighbour),
+                    feature,
+                    vertex: neighbour,
+                    version: chain.version[neighbour],
+                });
+            }
+        }
+    }
+
+    Ok(chains
+        .into_iter()
+        .map(|chain| {
+            geo::LineString(
+                chain
+                    .points
+                    .iter()
+                    .zip(chain.alive.iter())
+                    .filter(|(_, alive)| **alive)
+                    .map(|(p, _)| *p)
+                    .collect(),
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,270 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Allocation free intersection tests for tiny inputs.
+//!
+//! Setting up the sweep-line costs a handful of heap allocations, which dominates the run
+//! time when the input only has a few segments (e.g. per-tile calls).
+//! [`intersect_small()`] handles up to [`MAX_SMALL_LINES`] segments by testing every pair,
+//! using fixed size arrays on the stack. The results are returned in an inline buffer and
+//! the involved lines of each point are stored as a bit mask.
+
+use crate::oracle::{for_each_candidate, group_candidates, LineSet, PairGroups};
+use crate::order::sweep_order;
+use crate::{IntersectError, Tolerance};
+use num_traits::Float;
+
+/// The largest number of segments [`intersect_small()`] accepts
+pub const MAX_SMALL_LINES: usize = 8;
+
+// every end point, plus one point for each pair of lines
+const MAX_CANDIDATES: usize = 2 * MAX_SMALL_LINES + MAX_SMALL_LINES * (MAX_SMALL_LINES - 1) / 2;
+
+/// A set of line indices, all smaller than [`MAX_SMALL_LINES`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineMask(u8);
+
+impl LineMask {
+    pub fn contains(&self, line: usize) -> bool {
+        line < MAX_SMALL_LINES && self.0 & (1 << line) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the line indices in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let mask = self.0;
+        (0..MAX_SMALL_LINES).filter(move |i| mask & (1 << i) != 0)
+    }
+
+    fn insert(&mut self, line: usize) {
+        self.0 |= 1 << line;
+    }
+}
+
+/// An intersection point found by [`intersect_small()`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SmallIntersection<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    pub position: geo::Coordinate<T>,
+    pub lines: LineMask,
+}
+
+/// The inline result buffer of [`intersect_small()`], it dereferences to a slice of
+/// [`SmallIntersection`]s sorted in sweep-line order.
+#[derive(Clone, Copy)]
+pub struct SmallResults<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    len: usize,
+    items: [SmallIntersection<T>; MAX_CANDIDATES],
+}
+
+impl<T> std::ops::Deref for SmallResults<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    type Target = [SmallIntersection<T>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.items[..self.len]
+    }
+}
+
+impl<T> std::fmt::Debug for SmallResults<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl LineSet for LineMask {
+    fn insert(&mut self, line: usize) {
+        LineMask::insert(self, line)
+    }
+
+    fn contains(&self, line: usize) -> bool {
+        LineMask::contains(self, line)
+    }
+
+    fn union(&mut self, other: &Self) {
+        self.0 |= other.0;
+    }
+
+    fn for_each_pair<F: FnMut(usize, usize)>(&self, mut f: F) {
+        for l1 in self.iter() {
+            for l2 in self.iter().filter(|l2| *l2 > l1) {
+                f(l1, l2);
+            }
+        }
+    }
+}
+
+/// Stack table of the group where each pair of lines meet
+struct PairTable([usize; MAX_SMALL_LINES * MAX_SMALL_LINES]);
+
+impl PairGroups for PairTable {
+    fn get(&self, pair: (usize, usize)) -> Option<usize> {
+        let group = self.0[pair.0 * MAX_SMALL_LINES + pair.1];
+        if group == usize::MAX {
+            None
+        } else {
+            Some(group)
+        }
+    }
+
+    fn insert(&mut self, pair: (usize, usize), group: usize) {
+        self.0[pair.0 * MAX_SMALL_LINES + pair.1] = group;
+    }
+}
+
+/// Finds the intersections of up to [`MAX_SMALL_LINES`] segments without allocating.
+/// The results match those of [`AlgorithmData`](crate::algorithm::AlgorithmData) using the
+/// default tolerance, including the meaning of `ignore_end_point_intersections`.
+/// ```
+/// # use intersect2d::small::intersect_small;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [0., 10., 10., 0.],
+///     [10., 10., 20., 10.],
+/// ]);
+/// let rv = intersect_small(&lines, true)?;
+/// assert_eq!(rv.len(), 1);
+/// assert_eq!(rv[0].position, geo::Coordinate { x: 5., y: 5. });
+/// assert_eq!(rv[0].lines.iter().collect::<Vec<_>>(), vec![0, 1]);
+/// assert_eq!(intersect_small(&lines, false)?.len(), 2);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn intersect_small<T>(
+    lines: &[geo::Line<T>],
+    ignore_end_point_intersections: bool,
+) -> Result<SmallResults<T>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if lines.len() > MAX_SMALL_LINES {
+        return Err(IntersectError::InvalidParameter(format!(
+            "intersect_small() can't handle more than {} lines",
+            MAX_SMALL_LINES
+        )));
+    }
+    for a_line in lines.iter() {
+        if !(a_line.start.x.is_finite()
+            && a_line.start.y.is_finite()
+            && a_line.end.x.is_finite()
+            && a_line.end.y.is_finite())
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't check for intersections on non-finite data".to_string(),
+            ));
+        }
+    }
+    let tolerance = Tolerance::<T>::default();
+    let empty = SmallIntersection {
+        position: geo::Coordinate {
+            x: T::zero(),
+            y: T::zero(),
+        },
+        lines: LineMask::default(),
+    };
+    // candidate points, each with the lines known to pass through it
+    let mut candidates = [(empty.position, LineMask::default()); MAX_CANDIDATES];
+    let mut len = 0;
+    for_each_candidate(lines, &tolerance, |position, involved| {
+        let mut mask = LineMask::default();
+        for l in involved.iter() {
+            mask.insert(*l);
+        }
+        candidates[len] = (position, mask);
+        len += 1;
+    });
+    let candidates = &mut candidates[..len];
+    candidates.sort_unstable_by(|a, b| sweep_order(&a.0, &b.0));
+    let mut interior = [false; MAX_CANDIDATES];
+    let mut merged_into = [0; MAX_CANDIDATES];
+    let groups = group_candidates(
+        lines,
+        &tolerance,
+        candidates,
+        &mut interior,
+        &mut merged_into,
+        &mut PairTable([usize::MAX; MAX_SMALL_LINES * MAX_SMALL_LINES]),
+    );
+
+    let mut rv = SmallResults {
+        len: 0,
+        items: [empty; MAX_CANDIDATES],
+    };
+    for (g, (position, mask)) in candidates[..groups].iter().enumerate() {
+        if merged_into[g] != g {
+            continue;
+        }
+        let report = if ignore_end_point_intersections {
+            interior[g] && mask.len() > 1
+        } else {
+            mask.len() > 1
+        };
+        if report {
+            rv.items[rv.len] = SmallIntersection {
+                position: *position,
+                lines: *mask,
+            };
+            rv.len += 1;
+        }
+    }
+    Ok(rv)
+}
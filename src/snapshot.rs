@@ -0,0 +1,103 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Snapshots of the sweep-line state, for visualizations and debugging.
+//!
+//! [`AlgorithmData::snapshot()`](crate::algorithm::AlgorithmData::snapshot) copies the pending
+//! events, the active lines in sweep-line status order and the intersections reported so far
+//! into a [`SweepSnapshot`].
+//! [`AlgorithmData::snapshot_at()`](crate::algorithm::AlgorithmData::snapshot_at) first handles
+//! events until a given number of them are handled. Only available with the `snapshot`
+//! feature.
+
+use num_traits::Float;
+
+/// The events queued at one position
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingEvent<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub position: geo::Coordinate<T>,
+    /// The lines starting at the position
+    pub add: Vec<usize>,
+    /// The lines ending at the position
+    pub drop: Vec<usize>,
+    /// The lines already known to intersect at the position
+    pub intersection: Vec<usize>,
+}
+
+/// A copy of the sweep-line state between two events.
+/// All line indices are the positions of the lines in the input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepSnapshot<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The number of events handled so far
+    pub event_index: usize,
+    /// The position of the last handled event
+    pub sweepline_pos: geo::Coordinate<T>,
+    /// The unhandled events in the order they will be handled, events at the same position
+    /// are merged
+    pub pending: Vec<PendingEvent<T>>,
+    /// The active lines, left to right along the sweep-line
+    pub status: Vec<usize>,
+    /// The intersections reported so far, in sweep-line order
+    pub intersections: Vec<(geo::Coordinate<T>, Vec<usize>)>,
+}
+
+impl<T> SweepSnapshot<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Returns true if there are no more events to handle
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
@@ -0,0 +1,328 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Intersections between great-circle arcs on a unit sphere.
+//!
+//! In this module the coordinates of a `geo::Line` are interpreted as longitude (`x`) and
+//! latitude (`y`) in degrees, and each line is the shortest great-circle arc between its end
+//! points. This gives correct results for long segments and for segments crossing the
+//! antimeridian, where a planar interpretation of lon/lat data fails.
+//!
+//! The arcs can not be ordered by a planar sweep-line, so the collection test is a
+//! brute force O(n²) test with a cheap 3D bounding box rejection.
+
+use super::{IntersectError, Tolerance};
+use crate::algorithm::SiteEventKey;
+use num_traits::Float;
+
+type Vec3<T> = [T; 3];
+
+#[inline(always)]
+fn cross<T: Float>(a: &Vec3<T>, b: &Vec3<T>) -> Vec3<T> {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[inline(always)]
+fn dot<T: Float>(a: &Vec3<T>, b: &Vec3<T>) -> T {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// converts a lon/lat coordinate in degrees into a point on the unit sphere
+#[inline(always)]
+fn to_vec3<T>(c: &geo::Coordinate<T>) -> Vec3<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    let (lon, lat) = (c.x.to_radians(), c.y.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// converts a point on the unit sphere back into a lon/lat coordinate in degrees, with the
+/// longitude in (-180°,180°]. The poles are always reported at longitude 0°.
+#[inline(always)]
+fn to_lon_lat<T>(v: &Vec3<T>) -> geo::Coordinate<T>
+where
+    T: Float + geo::CoordFloat,
+{
+    let eps = angular_epsilon::<T>();
+    let half_turn = T::from(180.0).unwrap();
+    if v[0].hypot(v[1]) <= eps {
+        // every longitude is the same point at a pole
+        return geo::Coordinate {
+            x: T::zero(),
+            y: (half_turn / (T::one() + T::one())).copysign(v[2]),
+        };
+    }
+    let mut lon = v[1].atan2(v[0]).to_degrees();
+    if lon <= -half_turn + eps.to_degrees() {
+        // the antimeridian is reported at +180°, whatever side it was computed from
+        lon = half_turn;
+    }
+    geo::Coordinate {
+        x: lon,
+        y: v[2].max(-T::one()).min(T::one()).asin().to_degrees(),
+    }
+}
+
+/// An arc converted into 3D, with its plane normal and bounding box
+struct Arc<T: Float> {
+    start: Vec3<T>,
+    end: Vec3<T>,
+    normal: Vec3<T>,
+    min: Vec3<T>,
+    max: Vec3<T>,
+}
+
+impl<T> Arc<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn new(line: &geo::Line<T>) -> Self {
+        let start = to_vec3(&line.start);
+        let end = to_vec3(&line.end);
+        let normal = cross(&start, &end);
+        // The arc bulges out of the box spanned by its end points, but never further than
+        // the sagitta of the arc.
+        let half_angle =
+            dot(&start, &end).max(-T::one()).min(T::one()).acos() / (T::one() + T::one());
+        let sagitta = T::one() - half_angle.cos();
+        let mut min = start;
+        let mut max = start;
+        for i in 0..3 {
+            min[i] = start[i].min(end[i]) - sagitta;
+            max[i] = start[i].max(end[i]) + sagitta;
+        }
+        Self {
+            start,
+            end,
+            normal,
+            min,
+            max,
+        }
+    }
+
+    /// returns true if `p`, a point on the great circle of this arc, lies on the arc
+    #[inline(always)]
+    fn contains(&self, p: &Vec3<T>, eps: T) -> bool {
+        dot(&cross(&self.start, p), &self.normal) >= -eps
+            && dot(&cross(p, &self.end), &self.normal) >= -eps
+    }
+
+    #[inline(always)]
+    fn contains_point(&self, p: &Vec3<T>, eps: T) -> bool {
+        let n_len = dot(&self.normal, &self.normal).sqrt();
+        if n_len <= eps {
+            // the arc is a single point
+            return (0..3).all(|i| (self.start[i] - p[i]).abs() <= eps);
+        }
+        (dot(&self.normal, p) / n_len).abs() <= eps && self.contains(p, eps)
+    }
+
+    #[inline(always)]
+    fn aabb_overlaps(&self, other: &Self, eps: T) -> bool {
+        (0..3).all(|i| self.min[i] <= other.max[i] + eps && other.min[i] <= self.max[i] + eps)
+    }
+}
+
+fn validate<T>(line: &geo::Line<T>) -> Result<(), IntersectError>
+where
+    T: Float + geo::CoordFloat,
+{
+    let ninety = T::from(90.0).unwrap();
+    for c in [line.start, line.end].iter() {
+        if !c.x.is_finite() || !c.y.is_finite() {
+            return Err(IntersectError::InvalidData(
+                "Can't check for intersections on non-finite data".to_string(),
+            ));
+        }
+        if c.y.abs() > ninety {
+            return Err(IntersectError::InvalidData(
+                "Latitude must be within [-90°,90°]".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn intersect_arcs<T>(one: &Arc<T>, other: &Arc<T>, eps: T) -> Option<Vec3<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if !one.aabb_overlaps(other, eps) {
+        return None;
+    }
+    let direction = cross(&one.normal, &other.normal);
+    let length = dot(&direction, &direction).sqrt();
+    if length <= eps {
+        // The arcs are on the same great circle (or at least one of them is a point),
+        // report any end point touching the other arc.
+        for p in [one.start, one.end].iter() {
+            if other.contains_point(p, eps) {
+                return Some(*p);
+            }
+        }
+        for p in [other.start, other.end].iter() {
+            if one.contains_point(p, eps) {
+                return Some(*p);
+            }
+        }
+        return None;
+    }
+    let p = [
+        direction[0] / length,
+        direction[1] / length,
+        direction[2] / length,
+    ];
+    let antipode = [-p[0], -p[1], -p[2]];
+    [p, antipode]
+        .iter()
+        .find(|p| one.contains(p, eps) && other.contains(p, eps))
+        .copied()
+}
+
+/// Returns the intersection point between two great-circle arcs.
+/// The coordinates are longitude (`x`) and latitude (`y`) in degrees, the returned point uses
+/// the same representation with the longitude in the range (-180°,180°], and longitude 0° at
+/// the poles.
+/// ```
+/// # use intersect2d::spherical::intersect_great_circle;
+/// // two routes crossing the antimeridian
+/// let one: geo::Line<f64> = [(170., -10.), (-170., 10.)].into();
+/// let other: geo::Line<f64> = [(170., 10.), (-170., -10.)].into();
+/// let p = intersect_great_circle(&one, &other).unwrap().unwrap();
+/// assert!((p.x.abs() - 180.0).abs() < 1e-9);
+/// assert!(p.y.abs() < 1e-9);
+/// ```
+pub fn intersect_great_circle<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+) -> Result<Option<geo::Coordinate<T>>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    validate(one)?;
+    validate(other)?;
+    let eps = angular_epsilon::<T>();
+    Ok(intersect_arcs(&Arc::new(one), &Arc::new(other), eps).map(|p| to_lon_lat(&p)))
+}
+
+/// The tolerance used on unit sphere vectors
+#[inline(always)]
+fn angular_epsilon<T: Float>() -> T {
+    T::epsilon() * T::from(64.0).unwrap()
+}
+
+/// Returns all the intersections between the great-circle arcs, in the same format as
+/// the sweep-line algorithm: the lon/lat intersection position and the involved lines.
+/// If `ignore_end_point_intersections` is set, arcs only touching at their end points are not
+/// reported.
+/// ```
+/// # use intersect2d::spherical::great_circle_intersections;
+/// let lines: Vec<geo::Line<f64>> = vec![
+///     [(170., -10.), (-170., 10.)].into(),
+///     [(170., 10.), (-170., -10.)].into(),
+///     [(0., 0.), (10., 0.)].into(),
+/// ];
+/// let rv = great_circle_intersections(&lines, false).unwrap();
+/// assert_eq!(rv.len(), 1);
+/// assert_eq!(rv[0].1, vec![0_usize, 1]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn great_circle_intersections<T>(
+    lines: &[geo::Line<T>],
+    ignore_end_point_intersections: bool,
+) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    for a_line in lines.iter() {
+        validate(a_line)?;
+    }
+    let eps = angular_epsilon::<T>();
+    let arcs: Vec<Arc<T>> = lines.iter().map(Arc::new).collect();
+    let is_end_point = |arc: &Arc<T>, p: &Vec3<T>| {
+        (0..3).all(|i| (arc.start[i] - p[i]).abs() <= eps)
+            || (0..3).all(|i| (arc.end[i] - p[i]).abs() <= eps)
+    };
+    let tolerance = Tolerance::default();
+    let mut result = rb_tree::RBMap::<SiteEventKey<T>, Vec<usize>>::new();
+    for (i, one) in arcs.iter().enumerate() {
+        for (j, other) in arcs.iter().enumerate().skip(i + 1) {
+            if let Some(p) = intersect_arcs(one, other, eps) {
+                if ignore_end_point_intersections
+                    && is_end_point(one, &p)
+                    && is_end_point(other, &p)
+                {
+                    continue;
+                }
+                let key = SiteEventKey::with_tolerance(to_lon_lat(&p), tolerance);
+                let value = if let Some(value) = result.get_mut(&key) {
+                    value
+                } else {
+                    let _ = result.insert(key, Vec::default());
+                    result.get_mut(&key).unwrap()
+                };
+                value.push(i);
+                value.push(j);
+            }
+        }
+    }
+    Ok(result
+        .into_iter()
+        .map(|(k, mut v)| {
+            v.sort_unstable();
+            v.dedup();
+            (k.pos, v)
+        })
+        .collect())
+}
@@ -0,0 +1,252 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Mutual splitting of two polyline layers.
+//!
+//! [`split_layers()`] splits every `geo::LineString` of one layer at the points where it meets
+//! the other layer, and the other way around. Each resulting [`SplitPiece`] knows the feature
+//! it came from and the features of the other layer at its split end points, so the two
+//! re-noded layers can be matched piece by piece (e.g. when snapping routes to a reference
+//! network). Overlapping parts are split at both ends of the overlap, and at the vertices of
+//! either layer inside it, so they end up as pieces with identical end points in both layers.
+
+use crate::between::intersections_between_sets;
+use crate::vector::dot;
+use crate::{ulps_eq_c, IntersectError};
+use num_traits::Float;
+use std::cmp;
+
+/// A part of a feature, between two split points (or the ends of the feature)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitPiece<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The index of the feature in its layer
+    pub feature: usize,
+    /// The geometry of the piece, in the direction of the feature
+    pub line_string: geo::LineString<T>,
+    /// The features of the other layer at the start of the piece, empty if the piece starts
+    /// at an end of the feature not touching the other layer
+    pub start_counterparts: Vec<usize>,
+    /// The features of the other layer at the end of the piece
+    pub end_counterparts: Vec<usize>,
+}
+
+/// The two re-noded layers returned by [`split_layers()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MutualSplit<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The pieces of the first layer, in feature order
+    pub a: Vec<SplitPiece<T>>,
+    /// The pieces of the second layer, in feature order
+    pub b: Vec<SplitPiece<T>>,
+}
+
+/// The segments of a layer, as (segment, (feature, segment index in the feature))
+#[allow(clippy::type_complexity)]
+fn segments<T>(layer: &[geo::LineString<T>]) -> (Vec<geo::Line<T>>, Vec<(usize, usize)>)
+where
+    T: Float + geo::GeoFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let mut lines = Vec::<geo::Line<T>>::new();
+    let mut origin = Vec::<(usize, usize)>::new();
+    for (feature, a_line_string) in layer.iter().enumerate() {
+        for (segment, a_line) in a_line_string.lines().enumerate() {
+            if !ulps_eq_c(&a_line.start, &a_line.end) {
+                lines.push(a_line);
+                origin.push((feature, segment));
+            }
+        }
+    }
+    (lines, origin)
+}
+
+/// The split points of one feature, (segment index in the feature, parameter, position,
+/// counterparts)
+type Cut<T> = (usize, T, geo::Coordinate<T>, Vec<usize>);
+
+/// Splits one feature at its cuts
+fn split_feature<T>(
+    feature: usize,
+    line_string: &geo::LineString<T>,
+    mut cuts: Vec<Cut<T>>,
+) -> Vec<SplitPiece<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    cuts.sort_unstable_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(a.1.partial_cmp(&b.1).unwrap_or(cmp::Ordering::Equal))
+    });
+    let mut rv = Vec::<SplitPiece<T>>::new();
+    let vertices = &line_string.0;
+    if vertices.len() < 2 {
+        return rv;
+    }
+    let finish = |rv: &mut Vec<SplitPiece<T>>,
+                  coordinates: Vec<geo::Coordinate<T>>,
+                  start_counterparts: Vec<usize>,
+                  end_counterparts: Vec<usize>| {
+        rv.push(SplitPiece {
+            feature,
+            line_string: geo::LineString(coordinates),
+            start_counterparts,
+            end_counterparts,
+        })
+    };
+    let mut current = vec![vertices[0]];
+    let mut start_counterparts = Vec::<usize>::new();
+    let mut cuts = cuts.into_iter().peekable();
+    for (segment, end) in vertices.iter().skip(1).enumerate() {
+        while let Some((_, _, position, counterparts)) = cuts.next_if(|cut| cut.0 == segment) {
+            if ulps_eq_c(current.last().unwrap(), &position) {
+                if current.len() == 1 {
+                    start_counterparts.extend(counterparts);
+                    start_counterparts.sort_unstable();
+                    start_counterparts.dedup();
+                } else if let Some(piece) = rv.last_mut() {
+                    // cut twice at the same point, i.e. at the end of the last piece
+                    piece.end_counterparts.extend(counterparts);
+                    piece.end_counterparts.sort_unstable();
+                    piece.end_counterparts.dedup();
+                }
+                continue;
+            }
+            current.push(position);
+            let coordinates = std::mem::replace(&mut current, vec![position]);
+            let previous = std::mem::replace(&mut start_counterparts, counterparts.clone());
+            finish(&mut rv, coordinates, previous, counterparts);
+        }
+        if !ulps_eq_c(current.last().unwrap(), end) {
+            current.push(*end);
+        }
+    }
+    if current.len() > 1 {
+        finish(&mut rv, current, start_counterparts, Vec::new());
+    }
+    rv
+}
+
+/// Splits every feature of layer `a` at the points where it meets layer `b`, and the other way
+/// around, see the module documentation.
+/// ```
+/// # use intersect2d::split::split_layers;
+/// let a = vec![geo::LineString::from(vec![(0., 0.), (10., 0.)])];
+/// let b = vec![
+///     geo::LineString::from(vec![(5., -5.), (5., 5.)]),
+///     geo::LineString::from(vec![(10., 0.), (10., 5.)]),
+/// ];
+/// let split = split_layers(&a, &b)?;
+/// assert_eq!(split.a.len(), 2);
+/// assert_eq!(split.a[0].line_string, geo::LineString::from(vec![(0., 0.), (5., 0.)]));
+/// assert_eq!(split.a[0].end_counterparts, vec![0]);
+/// assert_eq!(split.a[1].start_counterparts, vec![0]);
+/// assert_eq!(split.a[1].end_counterparts, vec![1]);
+/// // the vertical line is split in two, the touching line is left as is
+/// assert_eq!(split.b.len(), 3);
+/// assert_eq!(split.b[2].start_counterparts, vec![0]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn split_layers<T>(
+    a: &[geo::LineString<T>],
+    b: &[geo::LineString<T>],
+) -> Result<MutualSplit<T>, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let (a_lines, a_origin) = segments(a);
+    let (b_lines, b_origin) = segments(b);
+    let mut a_cuts: Vec<Vec<Cut<T>>> = vec![Vec::new(); a.len()];
+    let mut b_cuts: Vec<Vec<Cut<T>>> = vec![Vec::new(); b.len()];
+    let parameter = |line: &geo::Line<T>, p: &geo::Coordinate<T>| {
+        let r = line.end - line.start;
+        dot(&(*p - line.start), &r) / dot(&r, &r)
+    };
+    for intersection in intersections_between_sets(&a_lines, &b_lines, false)? {
+        let features = |segments: &[usize], origin: &[(usize, usize)]| {
+            let mut rv: Vec<usize> = segments.iter().map(|s| origin[*s].0).collect();
+            rv.sort_unstable();
+            rv.dedup();
+            rv
+        };
+        let a_features = features(&intersection.a, &a_origin);
+        let b_features = features(&intersection.b, &b_origin);
+        for s in intersection.a.iter() {
+            let (feature, segment) = a_origin[*s];
+            let t = parameter(&a_lines[*s], &intersection.position);
+            a_cuts[feature].push((segment, t, intersection.position, b_features.clone()));
+        }
+        for s in intersection.b.iter() {
+            let (feature, segment) = b_origin[*s];
+            let t = parameter(&b_lines[*s], &intersection.position);
+            b_cuts[feature].push((segment, t, intersection.position, a_features.clone()));
+        }
+    }
+    let split = |layer: &[geo::LineString<T>], cuts: Vec<Vec<Cut<T>>>| {
+        layer
+            .iter()
+            .zip(cuts)
+            .enumerate()
+            .flat_map(|(feature, (line_string, cuts))| split_feature(feature, line_string, cuts))
+            .collect()
+    };
+    Ok(MutualSplit {
+        a: split(a, a_cuts),
+        b: split(b, b_cuts),
+    })
+}
@@ -0,0 +1,305 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! The sweep-line status: the lines currently crossing the sweep-line, ordered left to right.
+//!
+//! The status is a treap (a randomized balanced binary search tree) stored in an arena, with
+//! parent links so that the predecessor and successor of a line can be found without
+//! searching. The order of the lines depends on the sweep-line position, so no ordering is
+//! stored in the tree itself; insertions and searches are driven by closures comparing against
+//! the lines already in the tree.
+
+const NIL: usize = usize::MAX;
+
+struct Node {
+    line: usize,
+    priority: u64,
+    left: usize,
+    right: usize,
+    parent: usize,
+}
+
+pub(crate) struct SweepStatus {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: usize,
+    // maps a line index to its node
+    node_of_line: ahash::AHashMap<usize, usize>,
+    // xorshift state for the node priorities, fixed seed for deterministic behaviour
+    seed: u64,
+}
+
+impl Default for SweepStatus {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            node_of_line: ahash::AHashMap::default(),
+            seed: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+}
+
+impl SweepStatus {
+    #[inline(always)]
+    pub(crate) fn contains(&self, line: usize) -> bool {
+        self.node_of_line.contains_key(&line)
+    }
+
+    /// the number of lines in the status
+    #[cfg(feature = "console_trace")]
+    pub(crate) fn len(&self) -> usize {
+        self.node_of_line.len()
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    /// Inserts `line`, `goes_before(other)` must return true if `line` should be placed to the
+    /// left of `other`.
+    pub(crate) fn insert<F>(&mut self, line: usize, goes_before: F)
+    where
+        F: Fn(usize) -> bool,
+    {
+        if self.contains(line) {
+            return;
+        }
+        let node = Node {
+            line,
+            priority: self.next_priority(),
+            left: NIL,
+            right: NIL,
+            parent: NIL,
+        };
+        let x = if let Some(x) = self.free.pop() {
+            self.nodes[x] = node;
+            x
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        };
+        let _ = self.node_of_line.insert(line, x);
+
+        if self.root == NIL {
+            self.root = x;
+            return;
+        }
+        let mut current = self.root;
+        loop {
+            if goes_before(self.nodes[current].line) {
+                if self.nodes[current].left == NIL {
+                    self.nodes[current].left = x;
+                    break;
+                }
+                current = self.nodes[current].left;
+            } else {
+                if self.nodes[current].right == NIL {
+                    self.nodes[current].right = x;
+                    break;
+                }
+                current = self.nodes[current].right;
+            }
+        }
+        self.nodes[x].parent = current;
+        while self.nodes[x].parent != NIL
+            && self.nodes[self.nodes[x].parent].priority < self.nodes[x].priority
+        {
+            self.rotate_up(x);
+        }
+    }
+
+    /// Removes `line`, returns false if it wasn't in the status
+    pub(crate) fn remove(&mut self, line: usize) -> bool {
+        let x = if let Some(x) = self.node_of_line.remove(&line) {
+            x
+        } else {
+            return false;
+        };
+        // rotate the node down until it is a leaf
+        loop {
+            let (left, right) = (self.nodes[x].left, self.nodes[x].right);
+            let child = match (left, right) {
+                (NIL, NIL) => break,
+                (NIL, r) => r,
+                (l, NIL) => l,
+                (l, r) => {
+                    if self.nodes[l].priority > self.nodes[r].priority {
+                        l
+                    } else {
+                        r
+                    }
+                }
+            };
+            self.rotate_up(child);
+        }
+        let parent = self.nodes[x].parent;
+        if parent == NIL {
+            self.root = NIL;
+        } else if self.nodes[parent].left == x {
+            self.nodes[parent].left = NIL;
+        } else {
+            self.nodes[parent].right = NIL;
+        }
+        self.free.push(x);
+        true
+    }
+
+    /// Rotates `x` up one level, preserving the in-order sequence
+    fn rotate_up(&mut self, x: usize) {
+        let p = self.nodes[x].parent;
+        let g = self.nodes[p].parent;
+        if self.nodes[p].left == x {
+            let b = self.nodes[x].right;
+            self.nodes[p].left = b;
+            if b != NIL {
+                self.nodes[b].parent = p;
+            }
+            self.nodes[x].right = p;
+        } else {
+            let b = self.nodes[x].left;
+            self.nodes[p].right = b;
+            if b != NIL {
+                self.nodes[b].parent = p;
+            }
+            self.nodes[x].left = p;
+        }
+        self.nodes[p].parent = x;
+        self.nodes[x].parent = g;
+        if g == NIL {
+            self.root = x;
+        } else if self.nodes[g].left == p {
+            self.nodes[g].left = x;
+        } else {
+            self.nodes[g].right = x;
+        }
+    }
+
+    /// Returns the line to the left of `line`
+    pub(crate) fn predecessor(&self, line: usize) -> Option<usize> {
+        let mut x = *self.node_of_line.get(&line)?;
+        if self.nodes[x].left != NIL {
+            x = self.nodes[x].left;
+            while self.nodes[x].right != NIL {
+                x = self.nodes[x].right;
+            }
+            return Some(self.nodes[x].line);
+        }
+        loop {
+            let p = self.nodes[x].parent;
+            if p == NIL {
+                return None;
+            }
+            if self.nodes[p].right == x {
+                return Some(self.nodes[p].line);
+            }
+            x = p;
+        }
+    }
+
+    /// Returns the line to the right of `line`
+    pub(crate) fn successor(&self, line: usize) -> Option<usize> {
+        let mut x = *self.node_of_line.get(&line)?;
+        if self.nodes[x].right != NIL {
+            x = self.nodes[x].right;
+            while self.nodes[x].left != NIL {
+                x = self.nodes[x].left;
+            }
+            return Some(self.nodes[x].line);
+        }
+        loop {
+            let p = self.nodes[x].parent;
+            if p == NIL {
+                return None;
+            }
+            if self.nodes[p].left == x {
+                return Some(self.nodes[p].line);
+            }
+            x = p;
+        }
+    }
+
+    /// Returns the leftmost line for which `is_right_of(line)` is true. `is_right_of` must be
+    /// false for a (possibly empty) prefix of the lines and true for the rest.
+    pub(crate) fn first_where<F>(&self, is_right_of: F) -> Option<usize>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let mut rv = None;
+        let mut current = self.root;
+        while current != NIL {
+            if is_right_of(self.nodes[current].line) {
+                rv = Some(self.nodes[current].line);
+                current = self.nodes[current].left;
+            } else {
+                current = self.nodes[current].right;
+            }
+        }
+        rv
+    }
+
+    /// Returns the rightmost line for which `is_left_of(line)` is true. `is_left_of` must be
+    /// true for a (possibly empty) prefix of the lines and false for the rest.
+    pub(crate) fn last_where<F>(&self, is_left_of: F) -> Option<usize>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let mut rv = None;
+        let mut current = self.root;
+        while current != NIL {
+            if is_left_of(self.nodes[current].line) {
+                rv = Some(self.nodes[current].line);
+                current = self.nodes[current].right;
+            } else {
+                current = self.nodes[current].left;
+            }
+        }
+        rv
+    }
+}
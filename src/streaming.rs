@@ -0,0 +1,483 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Bounded-memory, streaming, intersection detection for data sets too large to hold in memory.
+//!
+//! The input segments are read from an iterator in chunks, each chunk is sorted in sweep-line
+//! order (see [`sweep_order()`](crate::order::sweep_order): primary `y`, secondary `x`) and
+//! handed over to a [`RunStorage`] as a sorted run. The runs are then merged lazily and the
+//! segments are swept in horizontal slabs of (at most) `slab_capacity` newly started segments.
+//! Segments extending past the end of a slab are carried over into the next one, so the memory
+//! used is bounded by the slab capacity plus the number of segments crossing a slab boundary.
+//!
+//! Each intersection is reported once, by the slab containing its `y` coordinate. The reported
+//! line indices refer to the position of the segment in the input iterator.
+
+use super::IntersectError;
+use crate::algorithm::{AlgorithmData, SiteEventKey};
+use crate::order::sweep_order;
+use num_traits::Float;
+use std::cmp;
+use std::collections::BinaryHeap;
+use std::io::{Read, Write};
+
+/// A segment and its position in the input iterator
+pub type IndexedLine<T> = (usize, geo::Line<T>);
+
+/// An iterator over a stored run
+pub type RunIterator<'a, T> = Box<dyn Iterator<Item = Result<IndexedLine<T>, IntersectError>> + 'a>;
+
+/// Storage of the sorted runs, implement this to put the runs wherever is convenient.
+pub trait RunStorage<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Stores a sorted run of segments, returns an identifier of the run
+    fn store_run(&mut self, run: &[IndexedLine<T>]) -> Result<usize, IntersectError>;
+
+    /// Returns the segments of a previously stored run, in the stored order
+    fn load_run(&self, run: usize) -> Result<RunIterator<'_, T>, IntersectError>;
+}
+
+/// Keeps the runs in memory, mostly useful for testing
+#[derive(Default)]
+pub struct MemoryRunStorage<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    runs: Vec<Vec<IndexedLine<T>>>,
+}
+
+impl<T> RunStorage<T> for MemoryRunStorage<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn store_run(&mut self, run: &[IndexedLine<T>]) -> Result<usize, IntersectError> {
+        self.runs.push(run.to_vec());
+        Ok(self.runs.len() - 1)
+    }
+
+    fn load_run(&self, run: usize) -> Result<RunIterator<'_, T>, IntersectError> {
+        let run = self.runs.get(run).ok_or_else(|| {
+            IntersectError::InvalidParameter(format!("There is no stored run #{}", run))
+        })?;
+        Ok(Box::new(run.iter().map(|l| Ok(*l))))
+    }
+}
+
+/// Stores each run as a binary file in a directory.
+/// The coordinates are stored as little endian `f64`.
+/// The files are removed when the storage is dropped.
+pub struct FileRunStorage {
+    directory: std::path::PathBuf,
+    files: Vec<std::path::PathBuf>,
+}
+
+impl FileRunStorage {
+    /// Creates a storage writing the run files into `directory`, which must exist.
+    pub fn new<P: AsRef<std::path::Path>>(directory: P) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+            files: Vec::new(),
+        }
+    }
+}
+
+impl Drop for FileRunStorage {
+    fn drop(&mut self) {
+        for file in self.files.iter() {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
+
+fn io_error(err: std::io::Error) -> IntersectError {
+    IntersectError::IoError(err.to_string())
+}
+
+impl<T> RunStorage<T> for FileRunStorage
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn store_run(&mut self, run: &[IndexedLine<T>]) -> Result<usize, IntersectError> {
+        let path = self.directory.join(format!(
+            "intersect2d_run_{}_{:p}_{}.bin",
+            std::process::id(),
+            self as *const Self,
+            self.files.len()
+        ));
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&path).map_err(io_error)?);
+        self.files.push(path);
+        for (index, l) in run.iter() {
+            writer
+                .write_all(&(*index as u64).to_le_bytes())
+                .map_err(io_error)?;
+            for v in [l.start.x, l.start.y, l.end.x, l.end.y].iter() {
+                writer
+                    .write_all(&v.to_f64().unwrap().to_le_bytes())
+                    .map_err(io_error)?;
+            }
+        }
+        writer.flush().map_err(io_error)?;
+        Ok(self.files.len() - 1)
+    }
+
+    fn load_run(&self, run: usize) -> Result<RunIterator<'_, T>, IntersectError> {
+        let path = self.files.get(run).ok_or_else(|| {
+            IntersectError::InvalidParameter(format!("There is no stored run #{}", run))
+        })?;
+        let mut reader = std::io::BufReader::new(std::fs::File::open(path).map_err(io_error)?);
+        Ok(Box::new(std::iter::from_fn(move || {
+            let mut buffer = [0_u8; 40];
+            match reader.read_exact(&mut buffer) {
+                Ok(()) => (),
+                Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return None,
+                Err(err) => return Some(Err(io_error(err))),
+            }
+            let mut word = [0_u8; 8];
+            let mut value = |i: usize| {
+                word.copy_from_slice(&buffer[i * 8..i * 8 + 8]);
+                word
+            };
+            let index = u64::from_le_bytes(value(0)) as usize;
+            let mut c = [T::zero(); 4];
+            for (i, c) in c.iter_mut().enumerate() {
+                *c = T::from(f64::from_le_bytes(value(i + 1))).unwrap();
+            }
+            Some(Ok((
+                index,
+                geo::Line::new(
+                    geo::Coordinate { x: c[0], y: c[1] },
+                    geo::Coordinate { x: c[2], y: c[3] },
+                ),
+            )))
+        })))
+    }
+}
+
+/// The head of one run in the k-way merge, ordered so that the `BinaryHeap` pops the
+/// segment with the smallest start key first.
+struct MergeHead<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    line: IndexedLine<T>,
+    run: usize,
+}
+
+impl<T> MergeHead<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn sort_cmp(&self, other: &Self) -> cmp::Ordering {
+        compare_start(&self.line, &other.line).then_with(|| self.line.0.cmp(&other.line.0))
+    }
+}
+
+impl<T> PartialEq for MergeHead<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.sort_cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<T> Eq for MergeHead<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+}
+
+impl<T> PartialOrd for MergeHead<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for MergeHead<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        // reversed, BinaryHeap is a max-heap
+        other.sort_cmp(self)
+    }
+}
+
+/// compares the start points of two (already re-arranged) segments in sweep-line order
+fn compare_start<T>(a: &IndexedLine<T>, b: &IndexedLine<T>) -> cmp::Ordering
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    sweep_order(&a.1.start, &b.1.start)
+}
+
+/// Streaming intersection detection, see the [module documentation](self).
+/// ```
+/// # use intersect2d::streaming::{MemoryRunStorage, StreamingSweep};
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 10.],
+///     [0., 10., 10., 0.],
+///     [0., 20., 10., 30.],
+///     [0., 30., 10., 20.],
+/// ]);
+/// let mut results = Vec::new();
+/// StreamingSweep::new(MemoryRunStorage::default())
+///     .with_run_length(2)?
+///     .with_slab_capacity(2)?
+///     .with_lines(lines.into_iter())?
+///     .compute(|pos, lines| results.push((pos, lines)))?;
+/// assert_eq!(results.len(), 2);
+/// assert_eq!(results[1].1, vec![2_usize, 3]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub struct StreamingSweep<T, S>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    S: RunStorage<T>,
+{
+    storage: S,
+    runs: Vec<usize>,
+    // number of segments in each sorted run
+    run_length: usize,
+    // max number of newly started segments in each slab
+    slab_capacity: usize,
+    ignore_end_point_intersections: bool,
+    stop_at_first_intersection: bool,
+    line_count: usize,
+    pd: std::marker::PhantomData<T>,
+}
+
+impl<T, S> StreamingSweep<T, S>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    S: RunStorage<T>,
+{
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            runs: Vec::new(),
+            run_length: 1 << 20,
+            slab_capacity: 1 << 16,
+            ignore_end_point_intersections: false,
+            stop_at_first_intersection: false,
+            line_count: 0,
+            pd: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the number of segments sorted in memory before they are stored as a run.
+    pub fn with_run_length(&mut self, value: usize) -> Result<&mut Self, IntersectError> {
+        if value == 0 {
+            return Err(IntersectError::InvalidParameter(
+                "The run length must be larger than zero".to_string(),
+            ));
+        }
+        self.run_length = value;
+        Ok(self)
+    }
+
+    /// Sets the max number of newly started segments swept in each slab.
+    pub fn with_slab_capacity(&mut self, value: usize) -> Result<&mut Self, IntersectError> {
+        if value == 0 {
+            return Err(IntersectError::InvalidParameter(
+                "The slab capacity must be larger than zero".to_string(),
+            ));
+        }
+        self.slab_capacity = value;
+        Ok(self)
+    }
+
+    pub fn with_ignore_end_point_intersections(
+        &mut self,
+        value: bool,
+    ) -> Result<&mut Self, IntersectError> {
+        self.ignore_end_point_intersections = value;
+        Ok(self)
+    }
+
+    pub fn with_stop_at_first_intersection(
+        &mut self,
+        value: bool,
+    ) -> Result<&mut Self, IntersectError> {
+        self.stop_at_first_intersection = value;
+        Ok(self)
+    }
+
+    /// Reads the segments from the iterator, sorts them and stores them as runs.
+    /// May be called several times, the line indices continue from the previous call.
+    pub fn with_lines<I>(&mut self, input_iter: I) -> Result<&mut Self, IntersectError>
+    where
+        I: Iterator<Item = geo::Line<T>>,
+    {
+        let mut run = Vec::<IndexedLine<T>>::with_capacity(self.run_length.min(1 << 20));
+        for mut aline in input_iter {
+            if !(aline.start.x.is_finite()
+                && aline.start.y.is_finite()
+                && aline.end.x.is_finite()
+                && aline.end.y.is_finite())
+            {
+                return Err(IntersectError::InvalidData(
+                    "Can't check for intersections on non-finite data".to_string(),
+                ));
+            }
+            // Re-arrange so that start < end, the same way as AlgorithmData does it
+            if !SiteEventKey::new(aline.start.x, aline.start.y)
+                .lt(&SiteEventKey::new(aline.end.x, aline.end.y))
+            {
+                std::mem::swap(&mut aline.start, &mut aline.end);
+            }
+            run.push((self.line_count, aline));
+            self.line_count += 1;
+            if run.len() >= self.run_length {
+                self.store_run(&mut run)?;
+            }
+        }
+        if !run.is_empty() {
+            self.store_run(&mut run)?;
+        }
+        Ok(self)
+    }
+
+    fn store_run(&mut self, run: &mut Vec<IndexedLine<T>>) -> Result<(), IntersectError> {
+        run.sort_unstable_by(compare_start);
+        self.runs.push(self.storage.store_run(run)?);
+        run.clear();
+        Ok(())
+    }
+
+    /// Sweeps all the stored segments, every intersection is handed to `sink` as soon as its
+    /// slab is done. The intersections are reported in sweep-line order.
+    pub fn compute<F>(&mut self, mut sink: F) -> Result<(), IntersectError>
+    where
+        F: FnMut(geo::Coordinate<T>, Vec<usize>),
+    {
+        let mut iterators = Vec::with_capacity(self.runs.len());
+        let mut heap = BinaryHeap::<MergeHead<T>>::with_capacity(self.runs.len());
+        for (run, id) in self.runs.iter().enumerate() {
+            let mut iter = self.storage.load_run(*id)?;
+            if let Some(line) = iter.next() {
+                heap.push(MergeHead { line: line?, run });
+            }
+            iterators.push(iter);
+        }
+
+        let mut carry = Vec::<IndexedLine<T>>::new();
+        let mut slab_start = -T::infinity();
+        loop {
+            // fill the slab with newly started segments
+            let mut slab = std::mem::take(&mut carry);
+            let mut added = 0;
+            while added < self.slab_capacity {
+                if let Some(head) = heap.pop() {
+                    if let Some(line) = iterators[head.run].next() {
+                        heap.push(MergeHead {
+                            line: line?,
+                            run: head.run,
+                        });
+                    }
+                    slab.push(head.line);
+                    added += 1;
+                } else {
+                    break;
+                }
+            }
+            if added == 0 {
+                break;
+            }
+            // the slab ends where the next segment starts
+            let slab_end = heap
+                .peek()
+                .map_or(T::infinity(), |head| head.line.1.start.y);
+
+            let mut ad = AlgorithmData::<T, usize>::default();
+            let mut results = ad
+                .with_ignore_end_point_intersections(self.ignore_end_point_intersections)?
+                .with_payload_lines(slab.iter().map(|(index, l)| (*l, *index)))?
+                .compute_with_payloads()?
+                .into_iter()
+                .filter(|(pos, _)| pos.y >= slab_start && pos.y < slab_end)
+                .map(|(pos, lines)| {
+                    let mut lines: Vec<usize> = lines.into_iter().copied().collect();
+                    lines.sort_unstable();
+                    (pos, lines)
+                });
+            if self.stop_at_first_intersection {
+                if let Some((pos, lines)) = results.next() {
+                    sink(pos, lines);
+                    return Ok(());
+                }
+            } else {
+                for (pos, lines) in results {
+                    sink(pos, lines);
+                }
+            }
+
+            carry = slab
+                .into_iter()
+                .filter(|(_, l)| l.end.y >= slab_end)
+                .collect();
+            slab_start = slab_end;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,482 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Tile based intersection detection.
+//!
+//! A [`TileGrid`] splits the input into a grid of rectangular tiles. Every line is assigned to
+//! each tile its bounding box overlaps, so each [`Tile`] can be processed independently (on
+//! other threads, or other machines) with [`TileGrid::compute_tile()`].
+//! An intersection is only reported by the tile owning its position: tiles own their lower
+//! and left borders, the last row and column also own their upper and right borders. The
+//! position is recomputed from the involved lines in a fixed order, so that the tiles on both
+//! sides of a seam agree on the owner.
+//! [`merge_tile_results()`] combines the per-tile results, merging points that ended up
+//! (virtually) identical on both sides of a seam. [`compute_tiled_parallel()`] spreads the
+//! tiles over a set of worker threads.
+
+use super::{intersect, ulps_eq_c, IntersectError, Intersection};
+use crate::algorithm::AlgorithmData;
+use crate::order::sweep_order;
+use num_traits::Float;
+use std::cmp;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// One tile of a [`TileGrid`], with the indices of the lines overlapping it.
+#[derive(Clone, Debug)]
+pub struct Tile<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub column: usize,
+    pub row: usize,
+    pub bounds: geo::Rect<T>,
+    /// indices into the input lines
+    pub lines: Vec<usize>,
+}
+
+/// A regular grid of tiles covering `bounds`
+#[derive(Clone, Debug)]
+pub struct TileGrid<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    bounds: geo::Rect<T>,
+    columns: usize,
+    rows: usize,
+}
+
+impl<T> TileGrid<T>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub fn new(bounds: geo::Rect<T>, columns: usize, rows: usize) -> Result<Self, IntersectError> {
+        if columns == 0 || rows == 0 {
+            return Err(IntersectError::InvalidParameter(
+                "A tile grid needs at least one column and one row".to_string(),
+            ));
+        }
+        let (min, max) = (bounds.min(), bounds.max());
+        if !(min.x.is_finite() && min.y.is_finite() && max.x.is_finite() && max.y.is_finite()) {
+            return Err(IntersectError::InvalidData(
+                "The tile grid bounds must be finite".to_string(),
+            ));
+        }
+        Ok(Self {
+            bounds,
+            columns,
+            rows,
+        })
+    }
+
+    /// Creates a grid covering the bounding box of all the lines
+    pub fn from_lines(
+        lines: &[geo::Line<T>],
+        columns: usize,
+        rows: usize,
+    ) -> Result<Self, IntersectError> {
+        let mut iter = lines.iter().flat_map(|l| [l.start, l.end].to_vec());
+        let first = iter.next().ok_or_else(|| {
+            IntersectError::InvalidParameter("Can't create a tile grid without lines".to_string())
+        })?;
+        let (min, max) = iter.fold((first, first), |(min, max), c| {
+            (
+                geo::Coordinate {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                geo::Coordinate {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        });
+        Self::new(geo::Rect::new(min, max), columns, rows)
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the bounds of a tile
+    pub fn tile_bounds(&self, column: usize, row: usize) -> geo::Rect<T> {
+        let (min, max) = (self.bounds.min(), self.bounds.max());
+        let x = |c: usize| {
+            if c == self.columns {
+                max.x
+            } else {
+                min.x + (max.x - min.x) * T::from(c).unwrap() / T::from(self.columns).unwrap()
+            }
+        };
+        let y = |r: usize| {
+            if r == self.rows {
+                max.y
+            } else {
+                min.y + (max.y - min.y) * T::from(r).unwrap() / T::from(self.rows).unwrap()
+            }
+        };
+        geo::Rect::new(
+            geo::Coordinate {
+                x: x(column),
+                y: y(row),
+            },
+            geo::Coordinate {
+                x: x(column + 1),
+                y: y(row + 1),
+            },
+        )
+    }
+
+    /// the range of cells covering [v0,v1] along one axis
+    fn cell_range(v0: T, v1: T, min: T, max: T, cells: usize) -> (usize, usize) {
+        (
+            cell_index(v0.min(v1), min, max, cells),
+            cell_index(v0.max(v1), min, max, cells),
+        )
+    }
+
+    /// Assigns the lines to the tiles. Tiles without lines are omitted.
+    pub fn split(&self, lines: &[geo::Line<T>]) -> Result<Vec<Tile<T>>, IntersectError> {
+        let (min, max) = (self.bounds.min(), self.bounds.max());
+        let mut tiles: Vec<Vec<usize>> = vec![Vec::new(); self.columns * self.rows];
+        for (index, l) in lines.iter().enumerate() {
+            if !(l.start.x.is_finite()
+                && l.start.y.is_finite()
+                && l.end.x.is_finite()
+                && l.end.y.is_finite())
+            {
+                return Err(IntersectError::InvalidData(
+                    "Can't check for intersections on non-finite data".to_string(),
+                ));
+            }
+            let (c0, c1) = Self::cell_range(l.start.x, l.end.x, min.x, max.x, self.columns);
+            let (r0, r1) = Self::cell_range(l.start.y, l.end.y, min.y, max.y, self.rows);
+            // A point on a tile border is computed by both neighbouring tiles, so the lines
+            // must be present in both.
+            let (c0, r0) = (c0.saturating_sub(1), r0.saturating_sub(1));
+            let (c1, r1) = ((c1 + 1).min(self.columns - 1), (r1 + 1).min(self.rows - 1));
+            for row in r0..=r1 {
+                for column in c0..=c1 {
+                    if self.overlaps(column, row, l) {
+                        tiles[row * self.columns + column].push(index);
+                    }
+                }
+            }
+        }
+        Ok(tiles
+            .into_iter()
+            .enumerate()
+            .filter(|(_, lines)| !lines.is_empty())
+            .map(|(i, lines)| Tile {
+                column: i % self.columns,
+                row: i / self.columns,
+                bounds: self.tile_bounds(i % self.columns, i / self.columns),
+                lines,
+            })
+            .collect())
+    }
+
+    /// returns true if the bounding box of the line overlaps the (closed) tile
+    fn overlaps(&self, column: usize, row: usize, l: &geo::Line<T>) -> bool {
+        let b = self.tile_bounds(column, row);
+        let (min, max) = (b.min(), b.max());
+        l.start.x.max(l.end.x) >= min.x
+            && l.start.x.min(l.end.x) <= max.x
+            && l.start.y.max(l.end.y) >= min.y
+            && l.start.y.min(l.end.y) <= max.y
+    }
+
+    /// returns true if the tile owns the position
+    fn owns(&self, tile: &Tile<T>, pos: &geo::Coordinate<T>) -> bool {
+        let (min, max) = (tile.bounds.min(), tile.bounds.max());
+        let in_x = pos.x >= min.x && (pos.x < max.x || tile.column + 1 == self.columns);
+        let in_y = pos.y >= min.y && (pos.y < max.y || tile.row + 1 == self.rows);
+        in_x && in_y
+    }
+
+    /// Computes the intersections owned by `tile`. The line indices of the result refer to
+    /// `lines`, i.e. the same slice given to [`TileGrid::split()`].
+    #[allow(clippy::type_complexity)]
+    pub fn compute_tile(
+        &self,
+        tile: &Tile<T>,
+        lines: &[geo::Line<T>],
+        ignore_end_point_intersections: bool,
+    ) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError> {
+        let mut ad = AlgorithmData::<T, usize>::default();
+        Ok(ad
+            .with_ignore_end_point_intersections(ignore_end_point_intersections)?
+            .with_payload_lines(tile.lines.iter().map(|i| (lines[*i], *i)))?
+            .compute_with_payloads()?
+            .into_iter()
+            .map(|(pos, involved)| {
+                let mut involved: Vec<usize> = involved.into_iter().copied().collect();
+                involved.sort_unstable();
+                (pos, involved)
+            })
+            .filter(|(pos, involved)| self.owns(tile, &canonical_position(pos, involved, lines)))
+            .collect())
+    }
+}
+
+/// Returns the cell containing `v` when the range [min,max] is divided into `cells` cells
+/// along one axis. Values are clamped to the first and last cell, so `max` belongs to the last
+/// cell.
+pub(crate) fn cell_index<T>(v: T, min: T, max: T, cells: usize) -> usize
+where
+    T: Float,
+{
+    if max <= min {
+        return 0;
+    }
+    let c = ((v - min) / (max - min) * T::from(cells).unwrap()).floor();
+    c.max(T::zero()).to_usize().unwrap_or(0).min(cells - 1)
+}
+
+/// Returns the position used to decide which tile owns an intersection.
+/// Each tile computes the intersections from its own set of lines, so the tiles on both sides
+/// of a seam may round a point differently (e.g. 7.5 and 7.499999999999999) and both disown
+/// it. The position recomputed from the first pair of lines, in index order, crossing in a
+/// single point is the same in every tile. `involved` must be sorted.
+fn canonical_position<T>(
+    pos: &geo::Coordinate<T>,
+    involved: &[usize],
+    lines: &[geo::Line<T>],
+) -> geo::Coordinate<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    for (i, l1) in involved.iter().enumerate() {
+        for l2 in involved.iter().skip(i + 1) {
+            if let Some(Intersection::Intersection(p)) = intersect(&lines[*l1], &lines[*l2]) {
+                return p;
+            }
+        }
+    }
+    *pos
+}
+
+/// Merges the results of several tiles into one list sorted in sweep-line order.
+/// Points that are virtually identical are merged into one, with the union of the involved
+/// lines.
+///
+/// The merge is deterministic: the output is bit-identical no matter in what order the tile
+/// results are given, or in what order the points of each tile are listed. That makes it safe
+/// to collect the tile results from parallel workers in completion order.
+/// ```
+/// # use intersect2d::tiles::merge_tile_results;
+/// let a = vec![(geo::Coordinate { x: 1.0, y: 1.0 }, vec![2, 0])];
+/// let b = vec![
+///     (geo::Coordinate { x: 1.0, y: 1.0 + f64::EPSILON }, vec![1, 0]),
+///     (geo::Coordinate { x: 0.0, y: 0.0 }, vec![3, 4]),
+/// ];
+/// let rv = merge_tile_results(vec![a.clone(), b.clone()]);
+/// assert_eq!(rv, merge_tile_results(vec![b, a]));
+/// assert_eq!(rv.len(), 2);
+/// assert_eq!(rv[1], (geo::Coordinate { x: 1.0, y: 1.0 }, vec![0, 1, 2]));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn merge_tile_results<T, I>(results: I) -> Vec<(geo::Coordinate<T>, Vec<usize>)>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    I: IntoIterator<Item = Vec<(geo::Coordinate<T>, Vec<usize>)>>,
+{
+    let mut all: Vec<(geo::Coordinate<T>, Vec<usize>)> = results
+        .into_iter()
+        .flatten()
+        .map(|(pos, mut involved)| {
+            involved.sort_unstable();
+            (pos, involved)
+        })
+        .collect();
+    // The tolerant sweep-line order is not a total order, so the result of sorting by it
+    // depends on the input order. Sorting by the exact values first gives every input order
+    // the same starting point, the stable sort by sweep-line order then keeps it that way.
+    let exact = |a: &geo::Coordinate<T>, b: &geo::Coordinate<T>| {
+        a.y.partial_cmp(&b.y)
+            .unwrap_or(cmp::Ordering::Equal)
+            .then_with(|| a.x.partial_cmp(&b.x).unwrap_or(cmp::Ordering::Equal))
+    };
+    all.sort_unstable_by(|a, b| exact(&a.0, &b.0).then_with(|| a.1.cmp(&b.1)));
+    all.sort_by(|a, b| sweep_order(&a.0, &b.0));
+    let mut rv = Vec::<(geo::Coordinate<T>, Vec<usize>)>::with_capacity(all.len());
+    for (pos, mut involved) in all.into_iter() {
+        if let Some(last) = rv.last_mut() {
+            if ulps_eq_c(&last.0, &pos) {
+                last.1.append(&mut involved);
+                last.1.sort_unstable();
+                last.1.dedup();
+                continue;
+            }
+        }
+        rv.push((pos, involved));
+    }
+    rv
+}
+
+/// Convenience function that splits the lines into `columns` x `rows` tiles, computes each
+/// tile in sequence and merges the result.
+/// ```
+/// # use intersect2d::tiles::compute_tiled;
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 100., 100.],
+///     [0., 100., 100., 0.],
+///     [0., 50., 100., 50.],
+/// ]);
+/// // all three lines meet at (50,50), right on the corner of four tiles
+/// let rv = compute_tiled(&lines, 2, 2, false)?;
+/// assert_eq!(rv.len(), 1);
+/// assert_eq!(rv[0].1, vec![0_usize, 1, 2]);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn compute_tiled<T>(
+    lines: &[geo::Line<T>],
+    columns: usize,
+    rows: usize,
+    ignore_end_point_intersections: bool,
+) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+    let grid = TileGrid::from_lines(lines, columns, rows)?;
+    let mut results = Vec::new();
+    for tile in grid.split(lines)?.iter() {
+        results.push(grid.compute_tile(tile, lines, ignore_end_point_intersections)?);
+    }
+    Ok(merge_tile_results(results))
+}
+
+/// Like [`compute_tiled()`], but the tiles are computed by `threads` worker threads.
+/// The tiles are queued largest first and an idle worker takes the next tile from the shared
+/// queue, so a few crowded tiles don't leave the other workers idle as long as there are
+/// plenty of tiles. For spatially clustered data use many more tiles than threads. The
+/// workers put the tile results in a shared sink that merges them with
+/// [`merge_tile_results()`], so the result is identical to that of [`compute_tiled()`].
+/// ```
+/// # use intersect2d::tiles::{compute_tiled, compute_tiled_parallel};
+/// let lines = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 100., 100.],
+///     [0., 100., 100., 0.],
+///     [0., 50., 100., 50.],
+///     [0., 1., 100., 2.],
+/// ]);
+/// let rv = compute_tiled_parallel(&lines, 8, 8, false, 3)?;
+/// assert_eq!(rv, compute_tiled(&lines, 8, 8, false)?);
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn compute_tiled_parallel<T>(
+    lines: &[geo::Line<T>],
+    columns: usize,
+    rows: usize,
+    ignore_end_point_intersections: bool,
+    threads: usize,
+) -> Result<Vec<(geo::Coordinate<T>, Vec<usize>)>, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq
+        + Send
+        + Sync,
+    T::Epsilon: Copy,
+{
+    if threads == 0 {
+        return Err(IntersectError::InvalidParameter(
+            "At least one worker thread is needed".to_string(),
+        ));
+    }
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+    let grid = TileGrid::from_lines(lines, columns, rows)?;
+    let mut tiles = grid.split(lines)?;
+    // the sweep is O((n+k)log(n)), so the number of lines is a fair estimate of the work
+    tiles.sort_by_key(|tile| cmp::Reverse(tile.lines.len()));
+
+    let next_tile = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let sink = Mutex::new(Vec::<Vec<(geo::Coordinate<T>, Vec<usize>)>>::new());
+    let first_error = Mutex::new(None::<IntersectError>);
+    thread::scope(|scope| {
+        for _ in 0..threads.min(tiles.len()) {
+            let _ = scope.spawn(|| {
+                while !failed.load(Ordering::Relaxed) {
+                    let tile = match tiles.get(next_tile.fetch_add(1, Ordering::Relaxed)) {
+                        Some(tile) => tile,
+                        None => break,
+                    };
+                    match grid.compute_tile(tile, lines, ignore_end_point_intersections) {
+                        Ok(rv) => sink.lock().unwrap().push(rv),
+                        Err(err) => {
+                            failed.store(true, Ordering::Relaxed);
+                            let _ = first_error.lock().unwrap().get_or_insert(err);
+                        }
+                    }
+                }
+            });
+        }
+    });
+    if let Some(err) = first_error.into_inner().unwrap() {
+        return Err(err);
+    }
+    Ok(merge_tile_results(sink.into_inner().unwrap()))
+}
@@ -0,0 +1,318 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Configurable tolerance used when deciding if two coordinates are identical.
+//!
+//! The default tolerance is identical to the defaults of `approx::ulps_eq!()`, i.e. an absolute
+//! epsilon of `T::epsilon()` and 4 ulps. Data sets in very large, or very small, units are better
+//! served by a tolerance relative to the extent of the data, see [`Tolerance::from_extent()`].
+//! Data with a known, limited, precision can be snapped to a grid with a [`PrecisionModel`],
+//! see [`Tolerance::with_precision_model()`].
+//! How `epsilon` and `max_ulps` are combined is selected by the [`EqualityMode`], see
+//! [`Tolerance::with_equality_mode()`].
+
+use crate::precision::PrecisionModel;
+use crate::IntersectError;
+use num_traits::Float;
+
+/// How two values are compared by a [`Tolerance`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EqualityMode {
+    /// The values are equal if they are within `epsilon` of each other, or if they are
+    /// within `max_ulps` units in the last place. This is what `approx::ulps_eq!()` does.
+    #[default]
+    Ulps,
+    /// The values are equal if they are within `epsilon` of each other.
+    /// Better suited than `Ulps` for data clustered around the origin, where units in the
+    /// last place are tiny.
+    Absolute,
+    /// The values are equal if they are within `epsilon` times the largest magnitude of the
+    /// two values of each other.
+    Relative,
+}
+
+/// The tolerance used when comparing coordinates.
+/// By default two values are considered equal if they are within `epsilon` of each other, or
+/// if they are within `max_ulps` units in the last place, see [`EqualityMode`].
+/// Coordinates are rounded to the `precision` model before they are compared.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// absolute distance tolerance
+    pub epsilon: T,
+    /// units in the last place tolerance
+    pub max_ulps: u32,
+    /// the precision model coordinates are rounded to
+    pub precision: PrecisionModel<T>,
+    /// how `epsilon` and `max_ulps` are used
+    pub mode: EqualityMode,
+    /// the distance below which two lines are considered parallel, if unset the cross
+    /// product of the line directions is tested with `approx::ulps_eq!()`
+    pub parallel: Option<T>,
+    /// the distance below which two parallel lines are considered collinear, if unset the
+    /// cross product is tested with `approx::ulps_eq!()`
+    pub collinear: Option<T>,
+}
+
+impl<T> Default for Tolerance<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The same tolerance as `approx::ulps_eq!()` uses
+    fn default() -> Self {
+        Self {
+            epsilon: T::epsilon(),
+            max_ulps: T::default_max_ulps(),
+            precision: PrecisionModel::Floating,
+            mode: EqualityMode::Ulps,
+            parallel: None,
+            collinear: None,
+        }
+    }
+}
+
+impl<T> Tolerance<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    pub fn new(epsilon: T, max_ulps: u32) -> Self {
+        Self {
+            epsilon,
+            max_ulps,
+            precision: PrecisionModel::Floating,
+            mode: EqualityMode::Ulps,
+            parallel: None,
+            collinear: None,
+        }
+    }
+
+    /// Returns a copy of this tolerance using the `precision` model.
+    /// ```
+    /// # use intersect2d::Tolerance;
+    /// # use intersect2d::precision::PrecisionModel;
+    /// let tolerance = Tolerance::<f64>::default().with_precision_model(PrecisionModel::ScaledInteger(100.0))?;
+    /// let a = geo::Coordinate { x: 1.001, y: 2.0 };
+    /// let b = geo::Coordinate { x: 0.999, y: 2.004 };
+    /// assert!(tolerance.ulps_eq_c(&a, &b));
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_precision_model(
+        mut self,
+        precision: PrecisionModel<T>,
+    ) -> Result<Self, IntersectError> {
+        precision.validate()?;
+        self.precision = precision;
+        Ok(self)
+    }
+
+    /// Returns a copy of this tolerance using the equality `mode`.
+    /// ```
+    /// # use intersect2d::{EqualityMode, Tolerance};
+    /// let ulps = Tolerance::<f64>::new(1e-20, 4);
+    /// let absolute = ulps.with_equality_mode(EqualityMode::Absolute);
+    /// let relative = Tolerance::<f64>::new(1e-3, 0).with_equality_mode(EqualityMode::Relative);
+    /// assert!(ulps.ulps_eq(1e-30, 1e-30 + 1e-46));
+    /// assert!(!absolute.ulps_eq(1e10, 1e10 + 1e-4));
+    /// assert!(relative.ulps_eq(1e10, 1e10 + 1e6));
+    /// assert!(!relative.ulps_eq(1.0, 1.01));
+    /// ```
+    pub fn with_equality_mode(mut self, mode: EqualityMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns a copy of this tolerance using `threshold` when deciding if two lines are
+    /// parallel. The threshold is a distance: a line is parallel to a longer line if it turns
+    /// away from the direction of the longer line by less than `threshold` over its length.
+    /// ```
+    /// # use intersect2d::{intersect_with_tolerance, Intersection, Tolerance};
+    /// let line1: geo::Line<f64> = [(0., 0.), (1000., 0.)].into();
+    /// let line2: geo::Line<f64> = [(0., 1e-3), (1000., -1e-3)].into();
+    /// let tolerance = Tolerance::default();
+    /// assert!(matches!(
+    ///     intersect_with_tolerance(&line1, &line2, &tolerance),
+    ///     Some(Intersection::Intersection(_))
+    /// ));
+    /// let tolerance = tolerance.with_parallel_threshold(0.01)?.with_collinear_threshold(0.01)?;
+    /// assert!(matches!(
+    ///     intersect_with_tolerance(&line1, &line2, &tolerance),
+    ///     Some(Intersection::OverLap(_))
+    /// ));
+    /// # Ok::<(), intersect2d::IntersectError>(())
+    /// ```
+    pub fn with_parallel_threshold(mut self, threshold: T) -> Result<Self, IntersectError> {
+        Self::validate_threshold(threshold, "parallel")?;
+        self.parallel = Some(threshold);
+        Ok(self)
+    }
+
+    /// Returns a copy of this tolerance using `threshold` when deciding if two parallel lines
+    /// are collinear. The threshold is the largest distance between the supporting lines.
+    pub fn with_collinear_threshold(mut self, threshold: T) -> Result<Self, IntersectError> {
+        Self::validate_threshold(threshold, "collinear")?;
+        self.collinear = Some(threshold);
+        Ok(self)
+    }
+
+    fn validate_threshold(threshold: T, name: &str) -> Result<(), IntersectError> {
+        if !threshold.is_finite() || threshold < T::zero() {
+            return Err(IntersectError::InvalidParameter(format!(
+                "The {} threshold must be a finite, positive value",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that the values of the tolerance are usable
+    pub(crate) fn validate(&self) -> Result<(), IntersectError> {
+        if !self.epsilon.is_finite() || self.epsilon < T::zero() {
+            return Err(IntersectError::InvalidParameter(
+                "The tolerance epsilon must be a finite, positive value".to_string(),
+            ));
+        }
+        if let Some(parallel) = self.parallel {
+            Self::validate_threshold(parallel, "parallel")?;
+        }
+        if let Some(collinear) = self.collinear {
+            Self::validate_threshold(collinear, "collinear")?;
+        }
+        self.precision.validate()
+    }
+
+    /// Returns the same tolerance in f64
+    pub(crate) fn to_f64(self) -> Tolerance<f64> {
+        Tolerance {
+            epsilon: self.epsilon.to_f64().unwrap(),
+            max_ulps: self.max_ulps,
+            precision: self.precision.to_f64(),
+            mode: self.mode,
+            parallel: self.parallel.map(|t| t.to_f64().unwrap()),
+            collinear: self.collinear.map(|t| t.to_f64().unwrap()),
+        }
+    }
+
+    /// Returns true if two lines are parallel, given the cross product of their directions
+    /// and the length of the longer line. Without a parallel threshold the cross product is
+    /// tested with `approx::ulps_eq!()`, otherwise it's measured as a distance.
+    #[inline(always)]
+    pub(crate) fn is_parallel(&self, cross: T, length: T) -> bool {
+        match self.parallel {
+            Some(threshold) => Self::distance(cross, length) <= threshold,
+            None => approx::ulps_eq!(&cross, &T::zero()),
+        }
+    }
+
+    /// Returns true if two parallel lines are collinear, given the cross product of the
+    /// offset between them with the direction of the line of `length`. Without a collinear
+    /// threshold the cross product is tested with `approx::ulps_eq!()`, otherwise it's
+    /// measured as a distance.
+    #[inline(always)]
+    pub(crate) fn is_collinear(&self, cross: T, length: T) -> bool {
+        match self.collinear {
+            Some(threshold) => Self::distance(cross, length) <= threshold,
+            None => approx::ulps_eq!(&cross, &T::zero()),
+        }
+    }
+
+    #[inline(always)]
+    fn distance(cross: T, length: T) -> T {
+        if length > T::zero() {
+            (cross / length).abs()
+        } else {
+            cross.abs()
+        }
+    }
+
+    /// Returns a tolerance relative to the size of the data extent.
+    /// The absolute epsilon will be `relative_eps` times the largest side of `bbox`.
+    /// ```
+    /// # use intersect2d::Tolerance;
+    /// use geo::algorithm::bounding_rect::BoundingRect;
+    ///
+    /// let line_string = geo::LineString::from(vec![(0., 0.), (2000., 1000.)]);
+    /// let tolerance = Tolerance::from_extent(&line_string.bounding_rect().unwrap(), 1e-9);
+    /// assert!(approx::ulps_eq!(tolerance.epsilon, 2e-6));
+    /// assert!(tolerance.ulps_eq(1000.0, 1000.000001));
+    /// assert!(!tolerance.ulps_eq(1000.0, 1000.00001));
+    /// ```
+    pub fn from_extent(bbox: &geo::Rect<T>, relative_eps: T) -> Self {
+        let extent = bbox.width().max(bbox.height());
+        Self {
+            epsilon: (relative_eps * extent).max(T::epsilon()),
+            max_ulps: T::default_max_ulps(),
+            precision: PrecisionModel::Floating,
+            mode: EqualityMode::Ulps,
+            parallel: None,
+            collinear: None,
+        }
+    }
+
+    /// Returns true if the two values are virtually identical
+    #[inline(always)]
+    pub fn ulps_eq(&self, a: T, b: T) -> bool {
+        match self.mode {
+            EqualityMode::Ulps => {
+                (a - b).abs() <= self.epsilon || a.ulps_eq(&b, T::default_epsilon(), self.max_ulps)
+            }
+            EqualityMode::Absolute => (a - b).abs() <= self.epsilon,
+            EqualityMode::Relative => (a - b).abs() <= self.epsilon * a.abs().max(b.abs()),
+        }
+    }
+
+    /// Returns true if the two coordinates are virtually identical once rounded to the
+    /// precision model
+    #[inline(always)]
+    pub fn ulps_eq_c(&self, a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> bool {
+        let a = self.precision.make_precise_c(*a);
+        let b = self.precision.make_precise_c(*b);
+        self.ulps_eq(a.x, b.x) && self.ulps_eq(a.y, b.y)
+    }
+}
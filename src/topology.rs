@@ -0,0 +1,300 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Topology validation of line layers.
+//!
+//! [`validate_topology()`] turns the raw intersection list of a collection of
+//! `geo::LineString`s into a list of [`TopologyIssue`]s, the kind of QA report GIS tools
+//! produce for line networks:
+//! * self intersections (the line string is not simple)
+//! * features intersecting other features at points that are not end points of both
+//! * collinear overlapping segments
+//! * dangles: feature end points not connected to any other feature
+//! * pseudo-nodes: end points where exactly two features meet (they could be merged)
+
+use super::{ulps_eq_c, Closedness, IntersectError};
+use crate::algorithm::AlgorithmData;
+use crate::collinear::overlap_interval;
+use crate::order::sweep_order;
+use num_traits::Float;
+
+/// A topology problem found by [`validate_topology()`].
+/// All the feature indices refer to the position of the line string in the input slice.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TopologyIssue<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The feature intersects itself, i.e. it is not simple.
+    SelfIntersection {
+        feature: usize,
+        position: geo::Coordinate<T>,
+    },
+    /// Two or more features intersect at a point that is not an end point of all of them.
+    InteriorIntersection {
+        features: Vec<usize>,
+        position: geo::Coordinate<T>,
+    },
+    /// A segment of one feature overlaps a segment of another (or the same) feature.
+    /// `overlap` is the shared part of the segments.
+    Overlap {
+        features: (usize, usize),
+        overlap: geo::Line<T>,
+    },
+    /// An end point of an open feature that is not connected to any other feature.
+    Dangle {
+        feature: usize,
+        position: geo::Coordinate<T>,
+    },
+    /// An end point shared by exactly two features, the features could be merged into one.
+    PseudoNode {
+        features: (usize, usize),
+        position: geo::Coordinate<T>,
+    },
+}
+
+/// Returns the part of `other` that lies on `one`, if the segments are collinear and overlapping
+pub(crate) fn shared_sub_segment<T>(
+    one: &geo::Line<T>,
+    other: &geo::Line<T>,
+) -> Option<geo::Line<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    overlap_interval(one, other).map(|(overlap, _, _)| overlap)
+}
+
+/// Validates the topology of a line layer, see [`TopologyIssue`] for the detected problems.
+/// The issues are reported in this order: intersections and overlaps sorted by position
+/// followed by dangles and pseudo-nodes sorted by position.
+/// ```
+/// # use intersect2d::topology::{validate_topology, TopologyIssue};
+/// let layer = vec![
+///     geo::LineString::from(vec![(0., 0.), (10., 0.)]),
+///     geo::LineString::from(vec![(10., 0.), (20., 0.)]),
+///     geo::LineString::from(vec![(15., -5.), (15., 5.)]),
+/// ];
+/// let issues = validate_topology(&layer).unwrap();
+/// assert!(issues.contains(&TopologyIssue::InteriorIntersection {
+///     features: vec![1, 2],
+///     position: geo::Coordinate { x: 15., y: 0. }
+/// }));
+/// assert!(issues.contains(&TopologyIssue::PseudoNode {
+///     features: (0, 1),
+///     position: geo::Coordinate { x: 10., y: 0. }
+/// }));
+/// ```
+pub fn validate_topology<T>(
+    line_strings: &[geo::LineString<T>],
+) -> Result<Vec<TopologyIssue<T>>, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    validate_topology_with_closedness(line_strings, Closedness::Infer)
+}
+
+/// Same as [`validate_topology()`] but `closedness` decides which features are rings, see
+/// [`Closedness`]. Rings have no end points, so they never produce dangles or pseudo-nodes.
+/// ```
+/// # use intersect2d::topology::{validate_topology_with_closedness, TopologyIssue};
+/// # use intersect2d::Closedness;
+/// let layer = vec![geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)])];
+/// let issues = validate_topology_with_closedness(&layer, Closedness::Closed).unwrap();
+/// assert!(issues.is_empty());
+/// let issues = validate_topology_with_closedness(&layer, Closedness::Open).unwrap();
+/// assert_eq!(issues.len(), 2);
+/// ```
+pub fn validate_topology_with_closedness<T>(
+    line_strings: &[geo::LineString<T>],
+    closedness: Closedness,
+) -> Result<Vec<TopologyIssue<T>>, IntersectError>
+where
+    T: Float
+        + num_traits::ToPrimitive
+        + geo::GeoFloat
+        + geo::CoordFloat
+        + approx::AbsDiffEq
+        + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    // flatten the layer into segments, remembering (feature, segment index) of each.
+    // Zero length segments are ignored, and not counted, so that the segments on both sides
+    // of a repeated vertex are still numbered as consecutive.
+    let mut lines = Vec::<geo::Line<T>>::new();
+    let mut origin = Vec::<(usize, usize)>::new();
+    let mut closed = Vec::<bool>::with_capacity(line_strings.len());
+    let mut segment_counts = Vec::<usize>::with_capacity(line_strings.len());
+    for (feature, a_line_string) in line_strings.iter().enumerate() {
+        let mut kept = 0;
+        for a_line in closedness.segments(a_line_string).into_iter() {
+            if ulps_eq_c(&a_line.start, &a_line.end) {
+                continue;
+            }
+            lines.push(a_line);
+            origin.push((feature, kept));
+            kept += 1;
+        }
+        segment_counts.push(kept);
+        closed.push(closedness.is_closed(a_line_string));
+    }
+    let segment_count = |feature: usize| segment_counts[feature];
+
+    let results = AlgorithmData::<T>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?
+        .compute()?;
+
+    let mut rv = Vec::<TopologyIssue<T>>::new();
+    let mut tested_overlaps = ahash::AHashSet::<(usize, usize)>::default();
+    // feature end points, (feature, is_last), touching the interior of some segment
+    let mut touching_interior = ahash::AHashSet::<(usize, bool)>::default();
+    for (position, involved) in results {
+        let is_end_point =
+            |l: usize| ulps_eq_c(&lines[l].start, &position) || ulps_eq_c(&lines[l].end, &position);
+        // a point is a feature end point if it is the first or last vertex of an open feature
+        let is_feature_end_point = |l: usize| {
+            let feature = origin[l].0;
+            let line = &line_strings[feature].0;
+            !closed[feature]
+                && (ulps_eq_c(&line[0], &position) || ulps_eq_c(line.last().unwrap(), &position))
+        };
+
+        let mut self_intersecting = Vec::<usize>::new();
+        for (i, l1) in involved.iter().enumerate() {
+            for l2 in involved.iter().skip(i + 1) {
+                let (l1, l2) = if l1 < l2 { (*l1, *l2) } else { (*l2, *l1) };
+                if tested_overlaps.insert((l1, l2)) {
+                    if let Some(overlap) = shared_sub_segment(&lines[l1], &lines[l2]) {
+                        rv.push(TopologyIssue::Overlap {
+                            features: (origin[l1].0, origin[l2].0),
+                            overlap,
+                        });
+                    }
+                }
+                let ((f1, s1), (f2, s2)) = (origin[l1], origin[l2]);
+                if f1 != f2 || self_intersecting.contains(&f1) {
+                    continue;
+                }
+                // consecutive segments always share a vertex, so do the first and last
+                // segment of a closed feature
+                let adjacent =
+                    s2 == s1 + 1 || (closed[f1] && s1 == 0 && s2 + 1 == segment_count(f1));
+                if !(adjacent && is_end_point(l1) && is_end_point(l2)) {
+                    self_intersecting.push(f1);
+                }
+            }
+        }
+        for feature in self_intersecting.into_iter() {
+            rv.push(TopologyIssue::SelfIntersection { feature, position });
+        }
+
+        if !involved.iter().all(|l| is_end_point(*l)) {
+            for l in involved.iter() {
+                let feature = origin[*l].0;
+                if is_feature_end_point(*l) {
+                    let is_last = ulps_eq_c(line_strings[feature].0.last().unwrap(), &position);
+                    let _ = touching_interior.insert((feature, is_last));
+                }
+            }
+        }
+
+        let mut features: Vec<usize> = involved.iter().map(|l| origin[*l].0).collect();
+        features.sort_unstable();
+        features.dedup();
+        if features.len() > 1 && !involved.iter().all(|l| is_feature_end_point(*l)) {
+            rv.push(TopologyIssue::InteriorIntersection { features, position });
+        }
+    }
+
+    // Find dangles and pseudo-nodes by grouping the end points of the open features
+    let mut end_points = Vec::<(geo::Coordinate<T>, usize, bool)>::new();
+    for (feature, a_line_string) in line_strings.iter().enumerate() {
+        if closed[feature] || a_line_string.0.len() < 2 {
+            continue;
+        }
+        end_points.push((*a_line_string.0.first().unwrap(), feature, false));
+        end_points.push((*a_line_string.0.last().unwrap(), feature, true));
+    }
+    end_points.sort_unstable_by(|a, b| sweep_order(&a.0, &b.0));
+    let mut group_start = 0;
+    while group_start < end_points.len() {
+        let position = end_points[group_start].0;
+        let mut group_end = group_start + 1;
+        while group_end < end_points.len() && ulps_eq_c(&end_points[group_end].0, &position) {
+            group_end += 1;
+        }
+        let group = &end_points[group_start..group_end];
+        // Only report the end points that aren't touching the interior of other features
+        let touches_interior = || {
+            group
+                .iter()
+                .any(|e| touching_interior.contains(&(e.1, e.2)))
+        };
+        match group.len() {
+            1 if !touches_interior() => rv.push(TopologyIssue::Dangle {
+                feature: group[0].1,
+                position,
+            }),
+            2 if group[0].1 != group[1].1 && !touches_interior() => {
+                let (a, b) = (group[0].1.min(group[1].1), group[0].1.max(group[1].1));
+                rv.push(TopologyIssue::PseudoNode {
+                    features: (a, b),
+                    position,
+                })
+            }
+            _ => (),
+        }
+        group_start = group_end;
+    }
+    Ok(rv)
+}
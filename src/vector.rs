@@ -0,0 +1,152 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Small 2D vector utilities operating on `geo::Coordinate`.
+//!
+//! These are the helpers the intersection code itself is built on, handy when post-processing
+//! the intersection results.
+
+use num_traits::{Float, Zero};
+
+/// Returns `point + scale * vector`
+/// ```
+/// # use intersect2d::vector::scale_to_coordinate;
+/// let point = geo::Coordinate { x: 1., y: 1. };
+/// let vector = geo::Coordinate { x: 2., y: -4. };
+/// assert_eq!(scale_to_coordinate(&point, &vector, 0.5), geo::Coordinate { x: 2., y: -1. });
+/// ```
+#[inline(always)]
+pub fn scale_to_coordinate<T>(
+    point: &geo::Coordinate<T>,
+    vector: &geo::Coordinate<T>,
+    scale: T,
+) -> geo::Coordinate<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    geo::Coordinate {
+        x: point.x + scale * vector.x,
+        y: point.y + scale * vector.y,
+    }
+}
+
+#[inline(always)]
+/// Divides a 'vector' by 'b'. Obviously, don't feed this with 'b' == 0
+pub fn div<T>(a: &geo::Coordinate<T>, b: T) -> geo::Coordinate<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    geo::Coordinate {
+        x: a.x / b,
+        y: a.y / b,
+    }
+}
+
+#[inline(always)]
+/// from https://stackoverflow.com/a/565282 :
+///  "Define the 2-dimensional vector cross product v × w to be vx wy − vy wx."
+/// This function returns the z component of v × w
+pub fn cross_z<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> T
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    a.x * b.y - a.y * b.x
+}
+
+#[inline(always)]
+/// calculate the dot product of two lines
+pub fn dot<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>) -> T
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    a.x * b.x + a.y * b.y
+}
+
+#[inline(always)]
+/// Returns the vector rotated 90° counter-clockwise, i.e. (-y, x)
+pub fn perp<T>(a: &geo::Coordinate<T>) -> geo::Coordinate<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    geo::Coordinate { x: -a.y, y: a.x }
+}
+
+#[inline(always)]
+/// Returns the euclidean length of the vector
+pub fn norm<T>(a: &geo::Coordinate<T>) -> T
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    a.x.hypot(a.y)
+}
+
+#[inline(always)]
+/// Linear interpolation between `a` (at `t` = 0) and `b` (at `t` = 1).
+/// The end points are returned exactly for `t` = 0 and `t` = 1.
+/// ```
+/// # use intersect2d::vector::lerp;
+/// let a = geo::Coordinate { x: 0.1, y: 0.2 };
+/// let b = geo::Coordinate { x: 0.7, y: 0.3 };
+/// assert_eq!(lerp(&a, &b, 1.0), b);
+/// assert_eq!(lerp(&a, &b, 0.0), a);
+/// ```
+pub fn lerp<T>(a: &geo::Coordinate<T>, b: &geo::Coordinate<T>, t: T) -> geo::Coordinate<T>
+where
+    T: Float + Zero + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    // a * (1 - t) + b * t is exact at both ends, a + t * (b - a) isn't at t = 1
+    let s = T::one() - t;
+    geo::Coordinate {
+        x: a.x * s + b.x * t,
+        y: a.y * s + b.y * t,
+    }
+}
@@ -0,0 +1,284 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Self-check of intersection results.
+//!
+//! [`verify_results()`] checks a result list, as returned by
+//! [`AlgorithmData::compute()`](crate::algorithm::AlgorithmData::compute), against the input:
+//! every reported point must lie on all its involved lines, and (for inputs small enough for
+//! a brute force pass) every pair of intersecting lines must be reported together at some
+//! point. The returned [`Discrepancy`] list is empty when the results check out.
+//! [`verify_directed_results()`] does the same for results computed with
+//! [`AlgorithmData::with_directed_end_points()`](crate::algorithm::AlgorithmData::with_directed_end_points).
+
+use crate::collinear::overlap_interval_with_tolerance;
+use crate::{
+    intersect_line_point_with_tolerance, intersect_with_tolerance, Intersection, Tolerance,
+};
+use num_traits::Float;
+
+/// A problem found by [`verify_results()`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Discrepancy<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// The point is reported as an intersection of `line`, but it isn't on the line
+    NotOnLine {
+        position: geo::Coordinate<T>,
+        line: usize,
+    },
+    /// The index is not a valid line index
+    InvalidLine {
+        position: geo::Coordinate<T>,
+        line: usize,
+    },
+    /// The two lines intersect at `position`, but no reported point involves both
+    Missed {
+        lines: (usize, usize),
+        position: geo::Coordinate<T>,
+    },
+}
+
+/// Checks the results of an intersection search of `lines`, see the module documentation.
+/// `ignore_end_point_intersections` and `tolerance` must be the settings the results were
+/// computed with. The brute force search for missed intersections is only done if there are
+/// at most `max_brute_force_lines` lines.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::verify::{verify_results, Discrepancy};
+/// # use intersect2d::Tolerance;
+/// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.], [20., 0., 20., 10.]]);
+/// let mut results: Vec<_> = AlgorithmData::<f64>::default()
+///     .with_ref_lines(lines.iter())?
+///     .compute()?
+///     .collect();
+/// assert!(verify_results(&lines, &results, false, &Tolerance::default(), 1000).is_empty());
+/// // a broken result list
+/// results[0].1.push(2);
+/// let discrepancies = verify_results(&lines, &results, false, &Tolerance::default(), 1000);
+/// assert!(matches!(discrepancies[0], Discrepancy::NotOnLine { line: 2, .. }));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn verify_results<T>(
+    lines: &[geo::Line<T>],
+    results: &[(geo::Coordinate<T>, Vec<usize>)],
+    ignore_end_point_intersections: bool,
+    tolerance: &Tolerance<T>,
+    max_brute_force_lines: usize,
+) -> Vec<Discrepancy<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    verify_results_(
+        lines,
+        results,
+        |_, _, _| ignore_end_point_intersections,
+        tolerance,
+        max_brute_force_lines,
+    )
+}
+
+/// Same as [`verify_results()`], but for results computed with directed end points: a point
+/// where exactly two lines meet, the end of one at the start of the other, is not expected to
+/// be reported. `lines` must be in the direction they were added.
+/// ```
+/// # use intersect2d::algorithm::AlgorithmData;
+/// # use intersect2d::verify::{verify_directed_results, Discrepancy};
+/// # use intersect2d::Tolerance;
+/// let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [10., 0., 10., 10.]]);
+/// let results: Vec<_> = AlgorithmData::<f64>::default()
+///     .with_directed_end_points(true)?
+///     .with_ref_lines(lines.iter())?
+///     .compute()?
+///     .collect();
+/// assert!(results.is_empty());
+/// assert!(verify_directed_results(&lines, &results, &Tolerance::default(), 1000).is_empty());
+/// // two lines ending at the same point are not a connection
+/// let reversed = [lines[0], geo::Line::new(lines[1].end, lines[1].start)];
+/// assert!(matches!(
+///     verify_directed_results(&reversed, &results, &Tolerance::default(), 1000)[..],
+///     [Discrepancy::Missed { lines: (0, 1), .. }]
+/// ));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn verify_directed_results<T>(
+    lines: &[geo::Line<T>],
+    results: &[(geo::Coordinate<T>, Vec<usize>)],
+    tolerance: &Tolerance<T>,
+    max_brute_force_lines: usize,
+) -> Vec<Discrepancy<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let is_connection = |i: usize, j: usize, p: &geo::Coordinate<T>| {
+        let flows_into = |a: &geo::Line<T>, b: &geo::Line<T>| {
+            tolerance.ulps_eq_c(p, &a.end)
+                && tolerance.ulps_eq_c(p, &b.start)
+                && !tolerance.ulps_eq_c(p, &a.start)
+                && !tolerance.ulps_eq_c(p, &b.end)
+        };
+        (flows_into(&lines[i], &lines[j]) || flows_into(&lines[j], &lines[i]))
+            && !lines.iter().enumerate().any(|(k, a_line)| {
+                k != i
+                    && k != j
+                    && intersect_line_point_with_tolerance(a_line, p, tolerance).is_some()
+            })
+    };
+    verify_results_(
+        lines,
+        results,
+        is_connection,
+        tolerance,
+        max_brute_force_lines,
+    )
+}
+
+/// `is_not_reported(i, j, p)` tells if lines `i` and `j` meeting at `p`, an end point of
+/// both, are not expected to be reported.
+fn verify_results_<T, F>(
+    lines: &[geo::Line<T>],
+    results: &[(geo::Coordinate<T>, Vec<usize>)],
+    is_not_reported: F,
+    tolerance: &Tolerance<T>,
+    max_brute_force_lines: usize,
+) -> Vec<Discrepancy<T>>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    F: Fn(usize, usize, &geo::Coordinate<T>) -> bool,
+{
+    let mut rv = Vec::<Discrepancy<T>>::new();
+    let mut reported = ahash::AHashSet::<(usize, usize)>::default();
+    for (position, involved) in results.iter() {
+        for l in involved.iter() {
+            match lines.get(*l) {
+                None => rv.push(Discrepancy::InvalidLine {
+                    position: *position,
+                    line: *l,
+                }),
+                Some(a_line) => {
+                    if intersect_line_point_with_tolerance(a_line, position, tolerance).is_none() {
+                        rv.push(Discrepancy::NotOnLine {
+                            position: *position,
+                            line: *l,
+                        })
+                    }
+                }
+            }
+        }
+        if lines.len() <= max_brute_force_lines {
+            for (i, l1) in involved.iter().enumerate() {
+                for l2 in involved.iter().skip(i + 1) {
+                    let _ = reported.insert((*l1.min(l2), *l1.max(l2)));
+                }
+            }
+        }
+    }
+    if lines.len() > max_brute_force_lines {
+        return rv;
+    }
+
+    for (i, l1) in lines.iter().enumerate() {
+        let is_end_point_of_l1 = |p: &geo::Coordinate<T>| {
+            tolerance.ulps_eq_c(p, &l1.start) || tolerance.ulps_eq_c(p, &l1.end)
+        };
+        for (j, l2) in lines.iter().enumerate().skip(i + 1) {
+            let is_end_point_of_both = |p: &geo::Coordinate<T>| {
+                is_end_point_of_l1(p)
+                    && (tolerance.ulps_eq_c(p, &l2.start) || tolerance.ulps_eq_c(p, &l2.end))
+            };
+            // the point the pair must be reported at, if any
+            let position = match intersect_with_tolerance(l1, l2, tolerance) {
+                None => continue,
+                Some(Intersection::Intersection(p)) => {
+                    if is_end_point_of_both(&p) && is_not_reported(i, j, &p) {
+                        continue;
+                    }
+                    p
+                }
+                Some(Intersection::OverLap(_)) => {
+                    match overlap_interval_with_tolerance(l1, l2, tolerance) {
+                        Some((overlap, _, _)) => {
+                            // the overlap is reported at the ends of the shared part
+                            if is_end_point_of_both(&overlap.start)
+                                && is_end_point_of_both(&overlap.end)
+                                && is_not_reported(i, j, &overlap.start)
+                                && is_not_reported(i, j, &overlap.end)
+                            {
+                                continue;
+                            }
+                            overlap.start
+                        }
+                        // collinear segments touching at an end point
+                        None => {
+                            let p = if tolerance.ulps_eq_c(&l1.start, &l2.start)
+                                || tolerance.ulps_eq_c(&l1.start, &l2.end)
+                            {
+                                l1.start
+                            } else {
+                                l1.end
+                            };
+                            if is_not_reported(i, j, &p) {
+                                continue;
+                            }
+                            p
+                        }
+                    }
+                }
+            };
+            if !reported.contains(&(i, j)) {
+                rv.push(Discrepancy::Missed {
+                    lines: (i, j),
+                    position,
+                });
+            }
+        }
+    }
+    rv
+}
@@ -0,0 +1,179 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Visibility polygon of a point amid a set of segments.
+//!
+//! [`visibility_polygon()`] does an angular sweep around the query point: a ray is cast
+//! towards every segment end point, and just past it on either side, and the closest
+//! [`intersect()`](crate::intersect) of each ray with the segments becomes a vertex of the
+//! polygon. The visible region is bounded by a maximum distance, so obstructed directions end
+//! at the first segment and the open ones at `max_distance`.
+
+use crate::vector::norm;
+use crate::{intersect, ulps_eq_c, IntersectError, Intersection};
+use num_traits::Float;
+use std::cmp;
+
+/// Returns the point where the ray from `origin` in direction `angle` is first stopped by one
+/// of the `lines`, or the point at `max_distance` if nothing is in the way.
+fn cast<T>(
+    origin: geo::Coordinate<T>,
+    angle: T,
+    max_distance: T,
+    lines: &[geo::Line<T>],
+) -> geo::Coordinate<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    let end = geo::Coordinate {
+        x: origin.x + angle.cos() * max_distance,
+        y: origin.y + angle.sin() * max_distance,
+    };
+    let ray = geo::Line::new(origin, end);
+    let mut rv = (max_distance, end);
+    for a_line in lines.iter() {
+        let candidates = match intersect(&ray, a_line) {
+            None => continue,
+            Some(Intersection::Intersection(p)) => [p, p],
+            // the ray runs along the segment
+            Some(Intersection::OverLap(overlap)) => [overlap.start, overlap.end],
+        };
+        for p in candidates.iter() {
+            let distance = norm(&(*p - origin));
+            if distance < rv.0 {
+                rv = (distance, *p);
+            }
+        }
+    }
+    rv.1
+}
+
+/// Returns the polygon visible from `point`, see the module documentation.
+/// Segments passing through `point` block everything, there are no visible parts in that
+/// case. Directions without any segments are sampled at the four axis directions.
+/// ```
+/// # use intersect2d::visibility::visibility_polygon;
+/// use geo::algorithm::area::Area;
+/// // a 10x10 room with a wall sticking out from the left side
+/// let walls = intersect2d::to_lines::<_, f64>(&[
+///     [0., 0., 10., 0.],
+///     [10., 0., 10., 10.],
+///     [10., 10., 0., 10.],
+///     [0., 10., 0., 0.],
+///     [0., 5., 5., 5.],
+/// ]);
+/// let visible = visibility_polygon(geo::Coordinate { x: 8., y: 2. }, &walls, 100.)?;
+/// // the part of the room behind the wall, as seen from (8,2), is hidden
+/// assert!(visible.unsigned_area() < 100.0);
+/// assert!(visible.unsigned_area() > 50.0);
+/// let visible = visibility_polygon(geo::Coordinate { x: 8., y: 8. }, &walls[0..4], 100.)?;
+/// assert!(approx::ulps_eq!(visible.unsigned_area(), 100.0, epsilon = 1e-6));
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub fn visibility_polygon<T>(
+    point: geo::Coordinate<T>,
+    lines: &[geo::Line<T>],
+    max_distance: T,
+) -> Result<geo::Polygon<T>, IntersectError>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    if !max_distance.is_finite() || max_distance <= T::zero() {
+        return Err(IntersectError::InvalidParameter(
+            "The maximum distance must be a finite, positive value".to_string(),
+        ));
+    }
+    if !point.x.is_finite() || !point.y.is_finite() {
+        return Err(IntersectError::InvalidData(
+            "The query point must be finite".to_string(),
+        ));
+    }
+    for a_line in lines.iter() {
+        if !a_line.start.x.is_finite()
+            || !a_line.start.y.is_finite()
+            || !a_line.end.x.is_finite()
+            || !a_line.end.y.is_finite()
+        {
+            return Err(IntersectError::InvalidData(
+                "Can't compute visibility on non-finite data".to_string(),
+            ));
+        }
+    }
+
+    // the critical angles: the end points, and just past them on both sides
+    let offset = T::epsilon().sqrt();
+    let two = T::one() + T::one();
+    let half_pi = T::from(std::f64::consts::FRAC_PI_2).unwrap();
+    let mut angles: Vec<T> = (0..4)
+        .map(|i| T::from(i).unwrap() * half_pi - two * half_pi)
+        .collect();
+    for a_line in lines.iter() {
+        for end_point in [a_line.start, a_line.end].iter() {
+            if ulps_eq_c(end_point, &point) {
+                continue;
+            }
+            let angle = (end_point.y - point.y).atan2(end_point.x - point.x);
+            angles.push(angle - offset);
+            angles.push(angle);
+            angles.push(angle + offset);
+        }
+    }
+    angles.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal));
+    angles.dedup();
+
+    let mut ring = Vec::<geo::Coordinate<T>>::with_capacity(angles.len() + 1);
+    for angle in angles.into_iter() {
+        let p = cast(point, angle, max_distance, lines);
+        if ring.last().map_or(true, |last| !ulps_eq_c(last, &p)) {
+            ring.push(p);
+        }
+    }
+    if ring.len() > 1 && ulps_eq_c(&ring[0], ring.last().unwrap()) {
+        let _ = ring.pop();
+    }
+    Ok(geo::Polygon::new(geo::LineString(ring), vec![]))
+}
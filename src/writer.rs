@@ -0,0 +1,234 @@
+/*
+Line segment intersection detection library.
+
+Copyright (C) 2021 eadf https://github.com/eadf
+
+This program is free software: you can redistribute it and/or modify it under
+the terms of the GNU General Public License as published by the Free Software
+Foundation, either version 3 of the License, or (at your option) any later
+version.
+
+This program is distributed in the hope that it will be useful, but WITHOUT
+ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+FOR A PARTICULAR PURPOSE. See the GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License along with
+this program. If not, see <https://www.gnu.org/licenses/>.
+
+Also add information on how to contact you by electronic and paper mail.
+
+If the program does terminal interaction, make it output a short notice like
+this when it starts in an interactive mode:
+
+intersection2d Copyright (C) 2021 eadf
+
+This program comes with ABSOLUTELY NO WARRANTY; for details type `show w'.
+
+This is free software, and you are welcome to redistribute it under certain
+conditions; type `show c' for details.
+
+The hypothetical commands `show w' and `show c' should show the appropriate
+parts of the General Public License. Of course, your program's commands might
+be different; for a GUI interface, you would use an "about box".
+
+You should also get your employer (if you work as a programmer) or school,
+if any, to sign a "copyright disclaimer" for the program, if necessary. For
+more information on this, and how to apply and follow the GNU GPL, see <https://www.gnu.org/licenses/>.
+
+The GNU General Public License does not permit incorporating your program
+into proprietary programs. If your program is a subroutine library, you may
+consider it more useful to permit linking proprietary applications with the
+library. If this is what you want to do, use the GNU Lesser General Public
+License instead of this License. But first, please read <https://www.gnu.org/
+licenses /why-not-lgpl.html>.
+ */
+
+//! Streaming result writers, enabled by the `writer` feature.
+//!
+//! An [`IntersectionSink`] receives the intersections one at a time, as they are produced, so
+//! the complete result never has to be held in memory. [`GeoJsonSeqWriter`] writes each
+//! intersection as a GeoJSON `Point` feature in a newline delimited GeoJSON text sequence
+//! (RFC 8142), a format accepted by GDAL/ogr2ogr and thereby convertible to FlatGeobuf or
+//! GeoPackage. Other formats can be supported by implementing [`IntersectionSink`].
+
+use super::IntersectError;
+use crate::streaming::{RunStorage, StreamingSweep};
+use num_traits::Float;
+use std::io::Write;
+
+/// Something that consumes intersection results one at a time
+pub trait IntersectionSink<T>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+{
+    /// Write one intersection point together with the indices of the involved lines
+    fn write(
+        &mut self,
+        position: geo::Coordinate<T>,
+        lines: &[usize],
+    ) -> Result<(), IntersectError>;
+
+    /// Flush whatever is buffered. Called once, after the last intersection.
+    fn finish(&mut self) -> Result<(), IntersectError> {
+        Ok(())
+    }
+}
+
+type AttributeFn<'a> = Box<dyn Fn(usize) -> String + 'a>;
+
+/// Writes intersections as newline delimited GeoJSON features.
+/// Every feature has a `lines` property with the indices of the involved lines, and one
+/// string array property per attribute added with [`GeoJsonSeqWriter::with_attribute()`].
+/// ```
+/// # use intersect2d::writer::{GeoJsonSeqWriter, IntersectionSink};
+/// let names = ["road", "river"];
+/// let mut buffer = Vec::<u8>::new();
+/// let mut writer = GeoJsonSeqWriter::new(&mut buffer).with_attribute("name", |i| names[i].to_string());
+/// writer.write(geo::Coordinate { x: 1.0_f64, y: 2.0 }, &[0, 1])?;
+/// IntersectionSink::<f64>::finish(&mut writer)?;
+/// drop(writer);
+/// assert_eq!(
+///     String::from_utf8(buffer).unwrap(),
+///     "{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[1,2]},\
+///      \"properties\":{\"lines\":[0,1],\"name\":[\"road\",\"river\"]}}\n"
+/// );
+/// # Ok::<(), intersect2d::IntersectError>(())
+/// ```
+pub struct GeoJsonSeqWriter<'a, W: Write> {
+    out: W,
+    attributes: Vec<(String, AttributeFn<'a>)>,
+    written: usize,
+}
+
+impl<'a, W: Write> GeoJsonSeqWriter<'a, W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            attributes: Vec::new(),
+            written: 0,
+        }
+    }
+
+    /// Adds a property named `name`, holding `attribute(line_index)` for each involved line.
+    pub fn with_attribute<F>(mut self, name: &str, attribute: F) -> Self
+    where
+        F: Fn(usize) -> String + 'a,
+    {
+        self.attributes
+            .push((name.to_string(), Box::new(attribute)));
+        self
+    }
+
+    /// Returns the number of features written so far
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    fn write_str(&mut self, s: &str) -> Result<(), IntersectError> {
+        self.out.write_all(s.as_bytes()).map_err(io_error)
+    }
+}
+
+impl<'a, T, W> IntersectionSink<T> for GeoJsonSeqWriter<'a, W>
+where
+    T: Float + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    W: Write,
+{
+    fn write(
+        &mut self,
+        position: geo::Coordinate<T>,
+        lines: &[usize],
+    ) -> Result<(), IntersectError> {
+        let (x, y) = match (position.x.to_f64(), position.y.to_f64()) {
+            (Some(x), Some(y)) if x.is_finite() && y.is_finite() => (x, y),
+            _ => {
+                return Err(IntersectError::InvalidData(
+                    "Can't write non-finite coordinates as GeoJSON".to_string(),
+                ))
+            }
+        };
+        let mut feature = format!(
+            "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"Point\",\"coordinates\":[{},{}]}},\"properties\":{{\"lines\":[",
+            x, y
+        );
+        feature.push_str(
+            &lines
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        feature.push(']');
+        for (name, attribute) in self.attributes.iter() {
+            feature.push_str(&format!(",{}:[", json_string(name)));
+            feature.push_str(
+                &lines
+                    .iter()
+                    .map(|l| json_string(&attribute(*l)))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+            feature.push(']');
+        }
+        feature.push_str("}}\n");
+        self.write_str(&feature)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), IntersectError> {
+        self.out.flush().map_err(io_error)
+    }
+}
+
+/// Quotes and escapes a string according to RFC 8259
+fn json_string(s: &str) -> String {
+    let mut rv = String::with_capacity(s.len() + 2);
+    rv.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => rv.push_str("\\\""),
+            '\\' => rv.push_str("\\\\"),
+            '\n' => rv.push_str("\\n"),
+            '\r' => rv.push_str("\\r"),
+            '\t' => rv.push_str("\\t"),
+            c if (c as u32) < 0x20 => rv.push_str(&format!("\\u{:04x}", c as u32)),
+            c => rv.push(c),
+        }
+    }
+    rv.push('"');
+    rv
+}
+
+fn io_error(err: std::io::Error) -> IntersectError {
+    IntersectError::IoError(err.to_string())
+}
+
+impl<T, S> StreamingSweep<T, S>
+where
+    T: Float + num_traits::ToPrimitive + geo::CoordFloat + approx::AbsDiffEq + approx::UlpsEq,
+    T::Epsilon: Copy,
+    S: RunStorage<T>,
+{
+    /// Like [`StreamingSweep::compute()`], but hands every intersection to `sink`.
+    /// The first error returned by the sink is returned, later intersections are not written.
+    pub fn compute_into<W>(&mut self, sink: &mut W) -> Result<(), IntersectError>
+    where
+        W: IntersectionSink<T>,
+    {
+        let mut error = None;
+        self.compute(|position, lines| {
+            if error.is_none() {
+                if let Err(err) = sink.write(position, &lines) {
+                    error = Some(err);
+                }
+            }
+        })?;
+        if let Some(err) = error {
+            return Err(err);
+        }
+        sink.finish()
+    }
+}
@@ -0,0 +1,128 @@
+use intersect2d::aggregate::{CutLengths, IntersectionCounter, LayerCrossingCounter};
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::handler::{SweepContext, SweepEventHandler};
+use intersect2d::IntersectError;
+
+fn lines() -> Vec<geo::Line<f64>> {
+    let mut seed = 7_u64;
+    let mut random = || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) % 1000) as f64 / 10.0
+    };
+    (0..60)
+        .map(|_| geo::Line::new((random(), random()), (random(), random())))
+        .collect()
+}
+
+#[test]
+fn aggregate_1() -> Result<(), IntersectError> {
+    let lines = lines();
+    let results: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert!(results.len() > 50);
+
+    let mut counter = IntersectionCounter::default();
+    let mut layers = LayerCrossingCounter::new(|line| line % 3);
+    let mut lengths = CutLengths::<f64>::default();
+    for handler in [
+        &mut counter as &mut dyn SweepEventHandler<f64>,
+        &mut layers,
+        &mut lengths,
+    ] {
+        AlgorithmData::<f64>::default()
+            .with_ignore_end_point_intersections(true)?
+            .with_ref_lines(lines.iter())?
+            .aggregate(handler)?;
+    }
+
+    assert_eq!(counter.points(), results.len());
+    let pairs: usize = results
+        .iter()
+        .map(|r| r.1.len() * (r.1.len() - 1) / 2)
+        .sum();
+    assert_eq!(counter.pairs(), pairs);
+    for (line, count) in counter.line_counts().iter().enumerate() {
+        assert_eq!(
+            *count,
+            results.iter().filter(|r| r.1.contains(&line)).count()
+        );
+    }
+
+    let total: usize = layers.counts().iter().map(|c| c.1).sum();
+    assert_eq!(total, results.len());
+    let between_0_and_1 = results
+        .iter()
+        .filter(|r| r.1.iter().any(|l| l % 3 == 0) && r.1.iter().any(|l| l % 3 == 1))
+        .count();
+    assert_eq!(layers.count(1, 0), between_0_and_1);
+
+    // every intersection cuts the lines involved in two
+    let on_lines: usize = results.iter().map(|r| r.1.len()).sum();
+    assert_eq!(lengths.pieces(), lines.len() + on_lines);
+    let length: f64 = lines
+        .iter()
+        .map(|l| (l.end.x - l.start.x).hypot(l.end.y - l.start.y))
+        .sum();
+    assert!(approx::ulps_eq!(lengths.total(), length, epsilon = 1e-9));
+    assert!(lengths.shortest().unwrap() <= lengths.mean().unwrap());
+    assert!(lengths.mean().unwrap() <= lengths.longest().unwrap());
+    Ok(())
+}
+
+/// Records the number of lines meeting at each intersection
+struct Peek(Vec<usize>);
+
+impl SweepEventHandler<f64> for Peek {
+    fn on_intersection(
+        &mut self,
+        _position: geo::Coordinate<f64>,
+        lines: &[usize],
+        _context: &SweepContext<'_, f64>,
+    ) {
+        self.0.push(lines.len());
+    }
+}
+
+#[test]
+fn aggregate_2() -> Result<(), IntersectError> {
+    let lines = lines();
+    let mut ad = AlgorithmData::<f64>::default();
+    let mut peek = Peek(Vec::new());
+    let _ = ad.with_ref_lines(lines.iter())?;
+    ad.aggregate(&mut peek)?;
+    assert!(!peek.0.is_empty());
+    // the results were handed over and dropped, nothing is left to take
+    assert!(ad.get_results().is_none());
+
+    // the instance can still be reset and computed normally
+    let rv = ad.reset()?.compute()?;
+    assert_eq!(rv.len(), peek.0.len());
+    Ok(())
+}
+
+#[test]
+fn aggregate_3() -> Result<(), IntersectError> {
+    // the results are dropped, but the sweep still stops at the first intersection
+    let lines = lines();
+    let mut counter = IntersectionCounter::default();
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad
+        .with_stop_at_first_intersection(true)?
+        .with_ref_lines(lines.iter())?;
+    ad.aggregate(&mut counter)?;
+    assert_eq!(counter.points(), 1);
+
+    let mut reference = AlgorithmData::<f64>::default();
+    let rv = reference
+        .with_stop_at_first_intersection(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?;
+    assert_eq!(rv.len(), 1);
+    assert_eq!(ad.get_handled_events(), reference.get_handled_events());
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::IntersectError;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Counts the number of times the task asked to be polled again
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        let _ = self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// A minimal executor, polling the future until it is ready
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(rv) = future.as_mut().poll(&mut cx) {
+            return (rv, counter.0.load(Ordering::SeqCst));
+        }
+    }
+}
+
+fn grid() -> Vec<geo::Line<f64>> {
+    let mut rv = Vec::new();
+    for i in 0..10 {
+        let i = i as f64;
+        rv.push(geo::Line::from([(i, -1.), (i + 0.5, 10.)]));
+        rv.push(geo::Line::from([(-1., i), (10., i + 0.5)]));
+    }
+    rv
+}
+
+#[test]
+fn async_1() {
+    let lines = grid();
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(expected.len(), 100);
+
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter()).unwrap();
+    let (rv, yields) = block_on(ad.compute_async(10));
+    assert_eq!(rv.unwrap().collect::<Vec<_>>(), expected);
+    assert!(yields > 10);
+
+    // a single call with a large budget
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter()).unwrap();
+    let (rv, yields) = block_on(ad.compute_async(usize::MAX));
+    assert_eq!(rv.unwrap().collect::<Vec<_>>(), expected);
+    assert_eq!(yields, 0);
+    assert!(matches!(
+        ad.poll_compute(1),
+        Err(IntersectError::ResultsAlreadyTaken(_))
+    ));
+}
+
+#[test]
+fn async_2() -> Result<(), IntersectError> {
+    // the budget loop stops at the first intersection, just like compute()
+    let lines = grid();
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_stop_at_first_intersection(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(expected.len(), 1);
+
+    for budget in [1, 3, usize::MAX] {
+        let mut ad = AlgorithmData::<f64>::default();
+        let _ = ad
+            .with_stop_at_first_intersection(true)?
+            .with_ref_lines(lines.iter())?;
+        let rv = loop {
+            if let Poll::Ready(rv) = ad.poll_compute(budget)? {
+                break rv;
+            }
+        };
+        assert_eq!(rv.collect::<Vec<_>>(), expected);
+
+        let mut ad = AlgorithmData::<f64>::default();
+        let _ = ad
+            .with_stop_at_first_intersection(true)?
+            .with_ref_lines(lines.iter())?;
+        let (rv, _) = block_on(ad.compute_async(budget));
+        assert_eq!(rv?.collect::<Vec<_>>(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn async_budget_1() -> Result<(), IntersectError> {
+    let lines = grid();
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter())?;
+    assert!(matches!(
+        ad.poll_compute(0),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("budget")
+    ));
+    let (rv, _) = block_on(ad.compute_async(0));
+    assert!(matches!(rv, Err(IntersectError::InvalidParameter(m)) if m.contains("budget")));
+    // no event was handled, the computation can still be run to completion
+    let (rv, _) = block_on(ad.compute_async(10));
+    assert_eq!(rv?.count(), 100);
+    Ok(())
+}
+
+#[test]
+fn async_upcast_1() -> Result<(), IntersectError> {
+    let lines = grid();
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_f64_upcast(true)?.with_ref_lines(lines.iter())?;
+    assert!(matches!(
+        ad.poll_compute(1),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("f64 upcast")
+    ));
+    let _ = ad.with_f64_upcast(false)?;
+    let (rv, _) = block_on(ad.compute_async(10));
+    assert_eq!(rv?.count(), 100);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::IntersectError;
+
+fn grid(n: usize) -> Vec<geo::Line<f64>> {
+    let mut rv = Vec::new();
+    for i in 0..n {
+        let i = i as f64;
+        rv.push(geo::Line::from([(i, -1.), (i + 0.5, n as f64)]));
+        rv.push(geo::Line::from([(-1., i), (n as f64, i + 0.5)]));
+    }
+    rv
+}
+
+#[test]
+fn background_1() {
+    let lines = grid(40);
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter()).unwrap();
+    let handle = ad.compute_background().unwrap();
+    let mut last = 0.0;
+    while !handle.is_done() {
+        let progress = handle.progress();
+        assert!((0.0..=1.0).contains(&progress));
+        assert!(progress >= last);
+        last = progress;
+        std::thread::yield_now();
+    }
+    assert!(approx::ulps_eq!(handle.progress(), 1.0));
+    assert_eq!(handle.join().unwrap().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn background_2() {
+    let lines = grid(300);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter()).unwrap();
+    let handle = ad.compute_background().unwrap();
+    handle.cancel();
+    // the worker may, in theory, finish before it notices the cancellation
+    match handle.join() {
+        Err(IntersectError::Cancelled(_)) => (),
+        Ok(rv) => assert_eq!(rv.len(), 90000),
+        Err(err) => panic!("unexpected error {:?}", err),
+    }
+}
+
+#[test]
+fn background_3() -> Result<(), IntersectError> {
+    // the worker stops at the first intersection, just like compute()
+    let lines = grid(40);
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_stop_at_first_intersection(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(expected.len(), 1);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad
+        .with_stop_at_first_intersection(true)?
+        .with_ref_lines(lines.iter())?;
+    let rv = ad.compute_background()?.join()?;
+    assert_eq!(rv.collect::<Vec<_>>(), expected);
+    Ok(())
+}
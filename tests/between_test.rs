@@ -0,0 +1,258 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::between::{
+    boundaries_intersect, candidate_lines, intersections_between, intersections_between_sets,
+    LineStringLocation, SetIntersection,
+};
+use intersect2d::{to_lines, IntersectError};
+
+/// a diagonal grid of 100x100 short segments
+fn base_layer() -> Vec<geo::Line<f64>> {
+    let mut coords = Vec::new();
+    for i in 0..100 {
+        for j in 0..100 {
+            let (x, y) = (i as f64 * 10.0, j as f64 * 10.0);
+            coords.push([x, y, x + 7.0, y + 3.0]);
+            coords.push([x + 1.0, y + 6.0, x + 6.0, y - 1.0]);
+        }
+    }
+    to_lines::<_, f64>(&coords)
+}
+
+/// the intersections between the sets, computed by a sweep over both sets combined
+fn combined(a: &[geo::Line<f64>], b: &[geo::Line<f64>]) -> Vec<SetIntersection<f64>> {
+    let lines: Vec<_> = a.iter().chain(b.iter()).copied().collect();
+    let split = a.len();
+    AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .filter_map(|(position, involved)| {
+            let a: Vec<usize> = involved.iter().copied().filter(|l| *l < split).collect();
+            let b: Vec<usize> = involved
+                .iter()
+                .filter(|l| **l >= split)
+                .map(|l| l - split)
+                .collect();
+            if a.is_empty() || b.is_empty() {
+                None
+            } else {
+                Some(SetIntersection { position, a, b })
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn between_1() -> Result<(), IntersectError> {
+    let base = base_layer();
+    let query = to_lines::<_, f64>(&[
+        [502., 498., 531., 530.],
+        [531., 530., 560., 501.],
+        [560., 501., 502., 498.],
+    ]);
+    let (candidates_query, candidates_base) = candidate_lines(&query, &base)?;
+    assert_eq!(candidates_query, vec![0, 1, 2]);
+    // the slabs keep most of the base layer inside the bounding box of the query out
+    assert!(candidates_base.len() < 60, "{}", candidates_base.len());
+
+    let rv = intersections_between_sets(&query, &base, false)?;
+    assert!(!rv.is_empty());
+    assert_eq!(rv, combined(&query, &base));
+    // the argument order only changes what set is `a`
+    let swapped = intersections_between_sets(&base, &query, false)?;
+    assert_eq!(swapped.len(), rv.len());
+    for (s, r) in swapped.iter().zip(rv.iter()) {
+        assert_eq!((&s.a, &s.b), (&r.b, &r.a));
+    }
+    Ok(())
+}
+
+#[test]
+fn between_2() -> Result<(), IntersectError> {
+    let base = base_layer();
+    // disjoint bounding boxes
+    let query = to_lines::<_, f64>(&[[2000., 0., 2100., 100.]]);
+    assert_eq!(candidate_lines(&query, &base)?, (vec![], vec![]));
+    assert!(intersections_between_sets(&query, &base, false)?.is_empty());
+    // the bounding boxes overlap, but the query doesn't reach the base layer in any slab
+    let query = to_lines::<_, f64>(&[[-50., -50., -1., 900.], [-50., -50., -1., -50.]]);
+    let (_, candidates_base) = candidate_lines(&query, &base)?;
+    assert!(candidates_base.is_empty());
+    assert!(intersections_between_sets::<f64>(&[], &base, false)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn between_3() -> Result<(), IntersectError> {
+    // the sets only touch at end points, and intersections within a set are not reported
+    let a = to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., -5., 5., 5.]]);
+    let b = to_lines::<_, f64>(&[[10., 0., 20., 0.]]);
+    let rv = intersections_between_sets(&a, &b, false)?;
+    assert_eq!(
+        rv,
+        vec![SetIntersection {
+            position: geo::Coordinate { x: 10., y: 0. },
+            a: vec![0],
+            b: vec![0],
+        }]
+    );
+    assert!(intersections_between_sets(&a, &b, true)?.is_empty());
+    let bad = to_lines::<_, f64>(&[[0., 0., f64::NAN, 0.]]);
+    assert!(matches!(
+        intersections_between_sets(&a, &bad, false),
+        Err(IntersectError::InvalidData(_))
+    ));
+    Ok(())
+}
+
+fn circle(cx: f64, cy: f64, r: f64, n: usize) -> geo::LineString<f64> {
+    let mut points: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let a = i as f64 * std::f64::consts::PI * 2.0 / n as f64;
+            (cx + r * a.cos(), cy + r * a.sin())
+        })
+        .collect();
+    points.push(points[0]);
+    geo::LineString::from(points)
+}
+
+#[test]
+fn boundaries_intersect_1() -> Result<(), IntersectError> {
+    let big = geo::Polygon::new(circle(0., 0., 10., 200), vec![]);
+    let small = geo::Polygon::new(circle(0., 0., 5., 200), vec![]);
+    let shifted = geo::Polygon::new(circle(12., 0., 5., 200), vec![]);
+    let far = geo::Polygon::new(circle(100., 0., 5., 200), vec![]);
+    assert!(!boundaries_intersect(&big, &small)?);
+    assert!(boundaries_intersect(&big, &shifted)?);
+    assert!(boundaries_intersect(&shifted, &big)?);
+    assert!(!boundaries_intersect(&big, &far)?);
+    // a polygon inside a hole
+    let holed = geo::Polygon::new(circle(0., 0., 20., 200), vec![circle(0., 0., 11., 200)]);
+    assert!(!boundaries_intersect(&holed, &big)?);
+    let holed = geo::Polygon::new(circle(0., 0., 20., 200), vec![circle(3., 0., 9., 200)]);
+    assert!(boundaries_intersect(&holed, &big)?);
+    Ok(())
+}
+
+#[test]
+fn boundaries_intersect_2() -> Result<(), IntersectError> {
+    let big = geo::Polygon::new(circle(0., 0., 10., 200), vec![]);
+    // touching at a shared vertex only
+    let mut points = vec![(10., 0.)];
+    points.extend((0..100).map(|i| (20., -50. + i as f64)));
+    points.push((10., 0.));
+    let touching = geo::Polygon::new(geo::LineString::from(points), vec![]);
+    assert!(boundaries_intersect(&big, &touching)?);
+    // touching the interior of an edge of the other polygon with a vertex, the top edge of
+    // the square is split into many short edges for the sweep to handle
+    let mut points = vec![(-10., -10.), (10., -10.)];
+    points.extend((0..=40).map(|i| (10. - i as f64 * 0.5 + 0.25, 10.)));
+    points.push((-10., 10.));
+    points.push((-10., -10.));
+    let square = geo::Polygon::new(geo::LineString::from(points), vec![]);
+    let mut points = vec![(0., 10.)];
+    points.extend((0..100).map(|i| (-50. + i as f64, 50.)));
+    points.push((0., 10.));
+    let above = geo::Polygon::new(geo::LineString::from(points), vec![]);
+    assert!(boundaries_intersect(&square, &above)?);
+    let lifted = geo::Polygon::new(
+        geo::LineString::from(
+            above
+                .exterior()
+                .0
+                .iter()
+                .map(|c| (c.x, c.y + 1.))
+                .collect::<Vec<_>>(),
+        ),
+        vec![],
+    );
+    assert!(!boundaries_intersect(&square, &lifted)?);
+
+    // squares with finely subdivided edges, placed next to each other
+    let subdivided = |x0: f64, y0: f64| {
+        let mut points = Vec::new();
+        for i in 0..100 {
+            points.push((x0 + i as f64 * 0.1, y0));
+        }
+        for i in 0..100 {
+            points.push((x0 + 10., y0 + i as f64 * 0.1));
+        }
+        for i in 0..100 {
+            points.push((x0 + 10. - i as f64 * 0.1, y0 + 10.));
+        }
+        for i in 0..=100 {
+            points.push((x0, y0 + 10. - i as f64 * 0.1));
+        }
+        geo::Polygon::new(geo::LineString::from(points), vec![])
+    };
+    let left = subdivided(0., 0.);
+    // the shared edge only has end point intersections
+    assert!(boundaries_intersect(&left, &subdivided(10., 0.))?);
+    assert!(boundaries_intersect(&left, &subdivided(10., 0.05))?);
+    assert!(!boundaries_intersect(&left, &subdivided(10.000001, 0.05))?);
+    Ok(())
+}
+
+#[test]
+fn intersections_between_1() -> Result<(), IntersectError> {
+    // a self intersecting bow tie, (5, 5) is only reported because b passes through it
+    let a = geo::LineString::from(vec![(0., 0.), (10., 10.), (10., 0.), (0., 10.)]);
+    // b has a repeated vertex at the self intersection of a, the zero length segment still
+    // counts when indexing the segments
+    let b = geo::LineString::from(vec![(-5., 5.), (5., 5.), (5., 5.), (15., 5.)]);
+    let rv = intersections_between(&a, &b)?;
+    assert_eq!(rv.len(), 2);
+
+    assert_eq!(rv[0].position, geo::Coordinate { x: 5., y: 5. });
+    let mut segments: Vec<usize> = rv[0].a.iter().map(|l| l.segment).collect();
+    segments.sort_unstable();
+    assert_eq!(segments, vec![0, 2]);
+    let mut segments: Vec<usize> = rv[0].b.iter().map(|l| l.segment).collect();
+    segments.sort_unstable();
+    assert_eq!(segments, vec![0, 2]);
+    assert!(rv[0].b.iter().all(|l| l.measure == 10.));
+
+    assert_eq!(rv[1].position, geo::Coordinate { x: 10., y: 5. });
+    assert_eq!(rv[1].a.len(), 1);
+    assert_eq!(rv[1].a[0].segment, 1);
+    approx::assert_ulps_eq!(rv[1].a[0].measure, 200_f64.sqrt() + 5.);
+    assert_eq!(
+        rv[1].b,
+        vec![LineStringLocation {
+            segment: 2,
+            measure: 15.
+        }]
+    );
+
+    // without b the self intersection of a isn't reported at all
+    let far = geo::LineString::from(vec![(100., 100.), (110., 100.)]);
+    assert!(intersections_between(&a, &far)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn intersections_between_2() -> Result<(), IntersectError> {
+    let a = circle(0., 0., 10., 50);
+    let b = circle(12., 3., 7., 40);
+    let rv = intersections_between(&a, &b)?;
+    assert_eq!(rv.len(), 2);
+    for intersection in rv.iter() {
+        assert_eq!(intersection.a.len(), 1);
+        assert_eq!(intersection.b.len(), 1);
+        for (line_string, location) in [(&a, &intersection.a[0]), (&b, &intersection.b[0])] {
+            let start = line_string.0[location.segment];
+            let end = line_string.0[location.segment + 1];
+            let travelled: f64 = line_string.0[..=location.segment]
+                .windows(2)
+                .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+                .sum();
+            let along =
+                (intersection.position.x - start.x).hypot(intersection.position.y - start.y);
+            approx::assert_ulps_eq!(location.measure, travelled + along, max_ulps = 8);
+            assert!(along <= (end.x - start.x).hypot(end.y - start.y));
+        }
+    }
+    assert!(intersections_between(&a, &circle(100., 0., 7., 40))?.is_empty());
+    Ok(())
+}
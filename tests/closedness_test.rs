@@ -0,0 +1,101 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::topology::{validate_topology, validate_topology_with_closedness, TopologyIssue};
+use intersect2d::Closedness;
+
+/// a square ring whose closure point is a few ulps away from its first point
+fn almost_closed_square(ulps: i64) -> geo::LineString<f64> {
+    let first = 0.1_f64;
+    let last = f64::from_bits((first.to_bits() as i64 + ulps) as u64);
+    geo::LineString::from(vec![
+        (first, 0.),
+        (10., 0.),
+        (10., 10.),
+        (0., 10.),
+        (0., 0.),
+        (last, 0.),
+    ])
+}
+
+#[test]
+fn closedness_1() {
+    let ring = almost_closed_square(1);
+    let open = geo::LineString::from(vec![(0., 0.), (1., 0.), (1., 1.)]);
+    assert!(Closedness::Infer.is_closed(&ring));
+    assert!(Closedness::Closed.is_closed(&ring));
+    assert!(!Closedness::Open.is_closed(&ring));
+    assert!(!Closedness::Infer.is_closed(&open));
+    assert!(Closedness::Closed.is_closed(&open));
+
+    // the closure point is snapped, the ring ends exactly where it starts
+    let segments = Closedness::Closed.segments(&ring);
+    assert_eq!(segments.len(), 5);
+    assert_eq!(segments[4].end, segments[0].start);
+    let segments = Closedness::Open.segments(&ring);
+    assert_eq!(segments.len(), 5);
+    assert_ne!(segments[4].end, segments[0].start);
+
+    // too short to be a ring
+    let point = geo::LineString::from(vec![(0., 0.), (0., 0.)]);
+    assert!(!Closedness::Closed.is_closed(&point));
+    assert_eq!(Closedness::Closed.segments(&point).len(), 1);
+    let empty = geo::LineString::<f64>(vec![]);
+    assert!(Closedness::Closed.segments(&empty).is_empty());
+}
+
+#[test]
+fn closedness_2() {
+    // the same ring, a few ulps apart or not, gets the same report when the intent is explicit
+    for ulps in [0, 1, 3, -1000] {
+        let layer = vec![almost_closed_square(ulps)];
+        let issues = validate_topology_with_closedness(&layer, Closedness::Closed).unwrap();
+        assert!(issues.is_empty(), "ulps: {}, issues: {:?}", ulps, issues);
+    }
+    // inferring treats the far off closure point as an open path
+    let layer = vec![almost_closed_square(-1000)];
+    let issues = validate_topology(&layer).unwrap();
+    assert!(!issues.is_empty());
+    assert!(issues
+        .iter()
+        .all(|i| matches!(i, TopologyIssue::Dangle { feature: 0, .. })));
+}
+
+#[test]
+fn closedness_3() {
+    // an explicitly open path touching its own start point is an end point, not a self intersection
+    let layer = vec![geo::LineString::from(vec![
+        (0., 0.),
+        (10., 0.),
+        (10., 10.),
+        (0., 0.),
+    ])];
+    let issues = validate_topology(&layer).unwrap();
+    assert!(issues.is_empty());
+    let issues = validate_topology_with_closedness(&layer, Closedness::Open).unwrap();
+    assert!(!issues.is_empty());
+    assert!(issues
+        .iter()
+        .all(|i| matches!(i, TopologyIssue::SelfIntersection { feature: 0, .. })));
+}
+
+#[test]
+fn closedness_4() -> Result<(), intersect2d::IntersectError> {
+    // a "Z" shape, only the closing segment crosses the diagonal
+    let z = geo::LineString::from(vec![(0., 0.), (10., 0.), (0., 10.), (10., 10.)]);
+    assert!(AlgorithmData::<f64>::for_linestring(&z)?
+        .compute()?
+        .next()
+        .is_none());
+    let rv: Vec<_> = AlgorithmData::<f64>::for_linestring_with_closedness(&z, Closedness::Closed)?
+        .compute()?
+        .collect();
+    assert_eq!(rv, vec![(geo::Coordinate { x: 5., y: 5. }, vec![1, 3])]);
+
+    let square = geo::LineString::from(vec![(20., 0.), (30., 0.), (30., 10.), (20., 10.)]);
+    let rv: Vec<_> =
+        AlgorithmData::<f64>::for_rings_with_closedness(&[z, square], Closedness::Closed)?
+            .compute()?
+            .collect();
+    // the line indices count the closing segments too
+    assert_eq!(rv, vec![(geo::Coordinate { x: 5., y: 5. }, vec![1, 3])]);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::collection::{into_geometry_collection, Provenance};
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn collection_1() -> Result<(), IntersectError> {
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 10.], [10., 0., 0., 10.], [0., 0., -5., -5.]]);
+    let (collection, provenance) = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?
+        .into_geometry_collection()?;
+    assert_eq!(collection.0.len(), provenance.len());
+    assert_eq!(
+        collection.0,
+        vec![
+            geo::Geometry::Point(geo::Point::new(0., 0.)),
+            geo::Geometry::Point(geo::Point::new(5., 5.)),
+        ]
+    );
+    assert_eq!(
+        provenance,
+        vec![
+            Provenance {
+                lines: vec![0, 2],
+                end_point: vec![true, true]
+            },
+            Provenance {
+                lines: vec![0, 1],
+                end_point: vec![false, false]
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn collection_2() {
+    // a line completely covered by another one
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 0.], [2., 0., 4., 0.]]);
+    let results = vec![
+        (geo::Coordinate { x: 2., y: 0. }, vec![0, 1]),
+        (geo::Coordinate { x: 4., y: 0. }, vec![0, 1]),
+    ];
+    let (collection, provenance) = into_geometry_collection(&lines, results);
+    assert_eq!(collection.0.len(), 3);
+    assert_eq!(
+        collection.0[2],
+        geo::Geometry::Line([(2., 0.), (4., 0.)].into())
+    );
+    assert_eq!(provenance[2].end_point, vec![false, true]);
+}
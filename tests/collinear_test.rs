@@ -0,0 +1,168 @@
+use intersect2d::collinear::{collinear_bundles, collinear_clusters};
+use intersect2d::{to_lines, IntersectError, Tolerance};
+
+#[test]
+fn collinear_1() -> Result<(), IntersectError> {
+    // hundreds of overlapping segments on one diagonal, in random directions and order
+    let mut coords = Vec::new();
+    let mut max_b = 0.0_f64;
+    for i in 0..500_u64 {
+        let a = (i * 7919 % 500) as f64 / 10.0;
+        let b = a + 1.0 + (i % 13) as f64;
+        max_b = max_b.max(b);
+        if i % 2 == 0 {
+            coords.push([a, a * 0.5 + 1.0, b, b * 0.5 + 1.0]);
+        } else {
+            coords.push([b, b * 0.5 + 1.0, a, a * 0.5 + 1.0]);
+        }
+    }
+    // a parallel segment on another supporting line
+    coords.push([0., 0., 10., 5.]);
+    // a segment only touching the end of the bundle
+    coords.push([max_b, max_b * 0.5 + 1.0, 70., 36.]);
+    let lines = to_lines::<_, f64>(&coords);
+    let bundles = collinear_bundles(&lines, &Tolerance::default())?;
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].lines, (0..500).collect::<Vec<usize>>());
+    assert_eq!(bundles[0].extent.start, geo::Coordinate { x: 0.0, y: 1.0 });
+    assert_eq!(bundles[0].extent.end.x, max_b);
+    Ok(())
+}
+
+#[test]
+fn collinear_2() -> Result<(), IntersectError> {
+    // vertical segments, the direction angle wraps around at PI
+    let lines = to_lines::<_, f64>(&[
+        [1., 0., 1., 10.],
+        [1., 8., 1., 2.],
+        [1.000000000000001, 9., 1., 20.],
+        [2., 0., 2., 10.],
+        [1., 30., 1., 40.],
+    ]);
+    let bundles = collinear_bundles(&lines, &Tolerance::default())?;
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].lines, vec![0, 1, 2]);
+    let bundles = collinear_bundles(&lines, &Tolerance::new(1e-9, 4))?;
+    assert_eq!(bundles.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn collinear_3() -> Result<(), IntersectError> {
+    // two separate overlap runs on the same supporting line, and segments that only touch
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 2., 0.],
+        [1., 0., 3., 0.],
+        [3., 0., 4., 0.],
+        [5., 0., 7., 0.],
+        [6., 0., 6.5, 0.],
+        [7., 0., 7., 0.],
+    ]);
+    let bundles = collinear_bundles(&lines, &Tolerance::default())?;
+    assert_eq!(bundles.len(), 2);
+    assert_eq!(bundles[0].lines, vec![0, 1]);
+    assert_eq!(
+        bundles[0].extent,
+        to_lines::<_, f64>(&[[0., 0., 3., 0.]])[0]
+    );
+    assert_eq!(bundles[1].lines, vec![3, 4]);
+    assert_eq!(
+        bundles[1].extent,
+        to_lines::<_, f64>(&[[5., 0., 7., 0.]])[0]
+    );
+    Ok(())
+}
+
+#[test]
+fn collinear_non_finite_1() {
+    for bad in [f64::NAN, f64::INFINITY] {
+        // the bad line is found also after valid, overlapping lines
+        let lines = to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., 0., 15., 0.], [0., 0., bad, 0.]]);
+        assert!(matches!(
+            collinear_bundles(&lines, &Tolerance::default()),
+            Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+        ));
+        assert!(matches!(
+            collinear_clusters(&lines, &Tolerance::default()),
+            Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+        ));
+    }
+}
+
+#[test]
+fn collinear_empty_1() -> Result<(), IntersectError> {
+    assert!(collinear_bundles::<f64>(&[], &Tolerance::default())?.is_empty());
+    assert!(collinear_clusters::<f64>(&[], &Tolerance::default())?.is_empty());
+    // zero length segments have no supporting line
+    let lines = to_lines::<_, f64>(&[[1., 1., 1., 1.], [1., 1., 1., 1.]]);
+    assert!(collinear_bundles(&lines, &Tolerance::default())?.is_empty());
+    assert!(collinear_clusters(&lines, &Tolerance::default())?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn overlap_interval_1() {
+    use intersect2d::collinear::overlap_interval;
+    let one: geo::Line<f64> = [(0., 0.), (8., 4.)].into();
+    // other inside one
+    let other: geo::Line<f64> = [(2., 1.), (6., 3.)].into();
+    let (shared, on_one, on_other) = overlap_interval(&one, &other).unwrap();
+    assert_eq!(shared, other);
+    assert_eq!(on_one, (0.25, 0.75));
+    assert_eq!(on_other, (0.0, 1.0));
+    // one inside other, reversed
+    let (shared, on_other, on_one) = overlap_interval(&other, &one).unwrap();
+    assert_eq!(shared, other);
+    assert_eq!(on_other, (0.0, 1.0));
+    assert_eq!(on_one, (0.25, 0.75));
+    let other: geo::Line<f64> = [(12., 6.), (4., 2.)].into();
+    let (shared, on_one, on_other) = overlap_interval(&one, &other).unwrap();
+    assert_eq!(shared, [(4., 2.), (8., 4.)].into());
+    assert_eq!(on_one, (0.5, 1.0));
+    assert_eq!(on_other, (1.0, 0.5));
+    // only touching at an end point
+    let other: geo::Line<f64> = [(8., 4.), (10., 5.)].into();
+    assert!(overlap_interval(&one, &other).is_none());
+    // parallel
+    let other: geo::Line<f64> = [(0., 1.), (8., 5.)].into();
+    assert!(overlap_interval(&one, &other).is_none());
+    // crossing
+    let other: geo::Line<f64> = [(0., 4.), (8., 0.)].into();
+    assert!(overlap_interval(&one, &other).is_none());
+}
+
+#[test]
+fn collinear_clusters_1() -> Result<(), IntersectError> {
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 2., 0.],
+        [1., 0., 3., 0.],
+        [3., 0., 4., 0.],
+        [7., 0., 5., 0.],
+        [6., 0., 6.5, 0.],
+        [0., 1., 1., 2.],
+        [5., 6., 3., 4.],
+        [0., 5., 0., 6.],
+        [7., 7., 7., 7.],
+    ]);
+    let clusters = collinear_clusters(&lines, &Tolerance::default())?;
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0].lines, vec![0, 1, 2, 3, 4]);
+    assert_eq!(clusters[0].extent, to_lines(&[[0., 0., 7., 0.]])[0]);
+    assert_eq!(
+        clusters[0]
+            .bundles
+            .iter()
+            .map(|b| b.lines.clone())
+            .collect::<Vec<_>>(),
+        vec![vec![0, 1], vec![3, 4]]
+    );
+    assert_eq!(clusters[1].lines, vec![5, 6]);
+    assert_eq!(clusters[1].extent, to_lines(&[[0., 1., 5., 6.]])[0]);
+    assert!(clusters[1].bundles.is_empty());
+
+    // the bundles of all the clusters are the bundles of the input
+    let mut bundles: Vec<_> = clusters.into_iter().flat_map(|c| c.bundles).collect();
+    bundles.sort_unstable_by_key(|b| b.lines[0]);
+    assert_eq!(bundles, collinear_bundles(&lines, &Tolerance::default())?);
+    Ok(())
+}
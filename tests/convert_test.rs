@@ -0,0 +1,52 @@
+use intersect2d::convert::{CollectLines, Lines, ToLine};
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn convert_1() -> Result<(), IntersectError> {
+    let expected = to_lines::<_, f64>(&[[0, 0, 1, 2], [3, 4, 5, 6]]);
+    let lines: Lines<f64> = vec![[0, 0, 1, 2], [3, 4, 5, 6]].into_iter().collect();
+    assert_eq!(*lines, expected);
+    let lines: Lines<f64> = vec![(0_u8, 0, 1, 2), (3, 4, 5, 6)].into_iter().collect();
+    assert_eq!(lines.into_inner(), expected);
+    let lines: Vec<geo::Line<f64>> = vec![[[0., 0.], [1., 2.]], [[3., 4.], [5., 6.]]]
+        .into_iter()
+        .try_collect_lines()?;
+    assert_eq!(lines, expected);
+    let lines: Vec<geo::Line<f64>> = vec![((0_i32, 0), (1, 2)), ((3, 4), (5, 6))]
+        .into_iter()
+        .try_collect_lines()?;
+    assert_eq!(lines, expected);
+    let lines: Lines<f64> = expected.iter().map(|l| (l.start, l.end)).collect();
+    assert_eq!(Vec::from(lines), expected);
+    let lines: Vec<geo::Line<f64>> = expected.iter().copied().try_collect_lines()?;
+    assert_eq!(lines, expected);
+    Ok(())
+}
+
+/// Checks that converting to f32 failed because of the overflow
+fn is_overflow(rv: Result<geo::Line<f32>, IntersectError>) -> bool {
+    matches!(rv, Err(IntersectError::InvalidData(m)) if m.contains("convert a coordinate value"))
+}
+
+#[test]
+fn convert_overflow_1() {
+    // a finite value overflowing f32 is rejected by every item type
+    assert!(is_overflow([0., 0., 1e300, 0.].to_line()));
+    assert!(is_overflow((0., 1e300, 0., 0.).to_line()));
+    assert!(is_overflow([[0., 0.], [0., -1e300]].to_line()));
+    assert!(is_overflow(((1e300, 0.), (0., 0.)).to_line()));
+    // a value that already was non-finite is passed on as it is
+    let line = ToLine::<f32>::to_line([0., 0., f64::INFINITY, 0.]).unwrap();
+    assert_eq!(line.end.x, f32::INFINITY);
+}
+
+#[test]
+fn convert_overflow_2() {
+    let rv: Result<Vec<geo::Line<f32>>, _> = vec![(0., 0., 1., 1.), (0., 0., 1e300, 0.)]
+        .into_iter()
+        .try_collect_lines();
+    assert!(matches!(
+        rv,
+        Err(IntersectError::InvalidData(m)) if m.contains("convert a coordinate value")
+    ));
+}
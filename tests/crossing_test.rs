@@ -0,0 +1,152 @@
+use intersect2d::crossing::{crossing_angle, min_crossing_angle};
+
+#[test]
+fn crossing_angle_1() {
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [0., 0., 0., 10.],
+        [10., 10., 0., 0.],
+        [0., 0., 10., 1e-10],
+        [3., 3., 3., 3.],
+    ]);
+    assert!(approx::ulps_eq!(
+        crossing_angle(&lines[0], &lines[1]),
+        std::f64::consts::FRAC_PI_2
+    ));
+    // the direction of the lines doesn't matter
+    assert!(approx::ulps_eq!(
+        crossing_angle(&lines[0], &lines[2]),
+        std::f64::consts::FRAC_PI_4
+    ));
+    assert!(approx::ulps_eq!(
+        crossing_angle(&lines[2], &lines[0]),
+        std::f64::consts::FRAC_PI_4
+    ));
+    // tiny angles are still accurate
+    assert!(approx::relative_eq!(
+        crossing_angle(&lines[0], &lines[3]),
+        1e-11,
+        max_relative = 1e-9
+    ));
+    assert_eq!(crossing_angle(&lines[0], &lines[4]), 0.0);
+
+    assert_eq!(min_crossing_angle(&lines, &[0]), None);
+    assert!(approx::ulps_eq!(
+        min_crossing_angle(&lines, &[0, 1, 2]).unwrap(),
+        std::f64::consts::FRAC_PI_4
+    ));
+}
+
+#[test]
+fn crossing_direction_1() {
+    use intersect2d::crossing::{
+        crossing_direction, crossing_direction_with_tolerance, CrossingDirection,
+    };
+    use intersect2d::Tolerance;
+
+    let one: geo::Line<f64> = [(0., 0.), (10., 10.)].into();
+    let other: geo::Line<f64> = [(0., 10.), (10., 0.)].into();
+    assert_eq!(
+        crossing_direction(&one, &other),
+        Some(CrossingDirection::LeftToRight)
+    );
+    let reversed: geo::Line<f64> = [(10., 0.), (0., 10.)].into();
+    assert_eq!(
+        crossing_direction(&one, &reversed),
+        Some(CrossingDirection::RightToLeft)
+    );
+    // not reaching the other line
+    let short: geo::Line<f64> = [(0., 10.), (4., 6.)].into();
+    assert_eq!(crossing_direction(&one, &short), None);
+    // collinear
+    let collinear: geo::Line<f64> = [(2., 2.), (12., 12.)].into();
+    assert_eq!(crossing_direction(&one, &collinear), None);
+    // barely crossing, unless the tolerance says it's touching
+    let barely: geo::Line<f64> = [(0., 10.), (5.001, 4.999)].into();
+    assert_eq!(
+        crossing_direction(&one, &barely),
+        Some(CrossingDirection::LeftToRight)
+    );
+    assert_eq!(
+        crossing_direction_with_tolerance(&one, &barely, &Tolerance::new(0.01, 4)),
+        None
+    );
+}
+
+#[test]
+fn winding_contribution_1() {
+    use intersect2d::crossing::{winding_contribution, winding_contribution_with_tolerance};
+    use intersect2d::Tolerance;
+
+    let ray: geo::Line<f64> = [(0., 0.), (100., 0.)].into();
+    // a polyline passing through the ray at a vertex is counted once
+    let below: geo::Line<f64> = [(5., -5.), (5., 0.)].into();
+    let above: geo::Line<f64> = [(5., 0.), (6., 5.)].into();
+    assert_eq!(
+        winding_contribution(&ray, &below) + winding_contribution(&ray, &above),
+        1
+    );
+    // touching from above is not counted, touching from below is counted twice with opposite signs
+    let touch: geo::Line<f64> = [(5., 5.), (5., 0.)].into();
+    let back: geo::Line<f64> = [(5., 0.), (6., 5.)].into();
+    assert_eq!(winding_contribution(&ray, &touch), 0);
+    assert_eq!(winding_contribution(&ray, &back), 0);
+    let touch: geo::Line<f64> = [(5., -5.), (5., 0.)].into();
+    let back: geo::Line<f64> = [(5., 0.), (6., -5.)].into();
+    assert_eq!(
+        winding_contribution(&ray, &touch) + winding_contribution(&ray, &back),
+        0
+    );
+    // behind the ray origin, beyond its end and collinear
+    assert_eq!(
+        winding_contribution(&ray, &[(-1., -1.), (-1., 1.)].into()),
+        0
+    );
+    assert_eq!(
+        winding_contribution(&ray, &[(101., -1.), (101., 1.)].into()),
+        0
+    );
+    assert_eq!(winding_contribution(&ray, &[(1., 0.), (2., 0.)].into()), 0);
+    // at the ray origin
+    assert_eq!(winding_contribution(&ray, &[(0., -1.), (0., 1.)].into()), 1);
+    // within the tolerance of the ray line counts as on the line
+    let barely: geo::Line<f64> = [(5., -5.), (5., 0.001)].into();
+    assert_eq!(winding_contribution(&ray, &barely), 1);
+    assert_eq!(
+        winding_contribution_with_tolerance(&ray, &barely, &Tolerance::new(0.01, 4)),
+        1
+    );
+    let barely: geo::Line<f64> = [(5., -5.), (5., -0.001)].into();
+    assert_eq!(winding_contribution(&ray, &barely), 0);
+    assert_eq!(
+        winding_contribution_with_tolerance(&ray, &barely, &Tolerance::new(0.01, 4)),
+        1
+    );
+}
+
+#[test]
+fn winding_crossings_1() {
+    use intersect2d::crossing::winding_crossings;
+    use intersect2d::Tolerance;
+
+    // two counter clockwise squares, one inside the other, both around (5, 5)
+    let mut lines = Vec::new();
+    for (min, max) in [(0., 10.), (2., 8.)] {
+        lines.extend(intersect2d::to_lines::<_, f64>(&[
+            [min, min, max, min],
+            [max, min, max, max],
+            [max, max, min, max],
+            [min, max, min, min],
+        ]));
+    }
+    let ray: geo::Line<f64> = [(5., 5.), (1000., 5.)].into();
+    let rv = winding_crossings(&ray, &lines, &Tolerance::default());
+    let crossed: Vec<_> = rv.iter().map(|c| (c.line, c.contribution)).collect();
+    assert_eq!(crossed, vec![(5, 1), (1, 1)]);
+    assert_eq!(rv[0].position, geo::Coordinate { x: 8., y: 5. });
+    // through the corners, each vertex is counted once
+    let ray: geo::Line<f64> = [(5., 8.), (1000., 8.)].into();
+    let rv = winding_crossings(&ray, &lines, &Tolerance::default());
+    let crossed: Vec<_> = rv.iter().map(|c| (c.line, c.contribution)).collect();
+    assert_eq!(crossed, vec![(5, 1), (1, 1)]);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "csv")]
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::csv::{
+    read_lines_csv, read_results_csv, write_lines_csv, write_results_csv, CsvResultWriter,
+};
+use intersect2d::IntersectError;
+
+#[test]
+fn csv_1() {
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0.1, 0.2, 10.3, 10.4],
+        [0., 10., 10., 0.],
+        [-1e-7, 5., 1e3, 5.],
+    ]);
+    let mut buffer = Vec::<u8>::new();
+    write_lines_csv(&mut buffer, &lines).unwrap();
+    assert_eq!(read_lines_csv::<_, f64>(buffer.as_slice()).unwrap(), lines);
+    // the values survive the round trip exactly
+    let extreme = intersect2d::to_lines::<_, f64>(&[[1e300, -1e-300, 0.1 + 0.2, 1. / 3.]]);
+    let mut buffer = Vec::<u8>::new();
+    write_lines_csv(&mut buffer, &extreme).unwrap();
+    assert_eq!(
+        read_lines_csv::<_, f64>(buffer.as_slice()).unwrap(),
+        extreme
+    );
+
+    let results: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(results.len(), 3);
+    let mut buffer = Vec::<u8>::new();
+    write_results_csv(&mut buffer, &results).unwrap();
+    assert_eq!(
+        read_results_csv::<_, f64>(buffer.as_slice()).unwrap(),
+        results
+    );
+}
+
+#[test]
+fn csv_2() {
+    // no header, blank rows and spaces
+    let text = "0, 0, 1, 1\n\n 2,2,3,3 \n";
+    let lines = read_lines_csv::<_, f32>(text.as_bytes()).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1], geo::Line::from([(2., 2.), (3., 3.)]));
+
+    let mut writer = CsvResultWriter::new(Vec::<u8>::new()).unwrap();
+    writer
+        .write_row(geo::Coordinate { x: 1.5_f32, y: 2. }, &[3])
+        .unwrap();
+    writer
+        .write_row(geo::Coordinate { x: 0_f32, y: 0. }, &[])
+        .unwrap();
+    assert_eq!(writer.written(), 2);
+    let text = "x,y,lines\n1.5,2,3\n0,0,\n";
+    assert_eq!(
+        read_results_csv::<_, f32>(text.as_bytes()).unwrap(),
+        vec![
+            (geo::Coordinate { x: 1.5, y: 2. }, vec![3]),
+            (geo::Coordinate { x: 0., y: 0. }, vec![])
+        ]
+    );
+}
+
+#[test]
+fn csv_lines_field_count_1() {
+    // the row numbers count the header row
+    assert!(matches!(
+        read_lines_csv::<_, f64>("x1,y1,x2,y2\n0,0,1\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m == "Row 2: expected 4 values, found 3"
+    ));
+    assert!(matches!(
+        read_lines_csv::<_, f64>("0,0,1,1\n\n0,0,1,1,1\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m == "Row 3: expected 4 values, found 5"
+    ));
+}
+
+#[test]
+fn csv_lines_number_1() {
+    // only the first row can be a header
+    assert!(matches!(
+        read_lines_csv::<_, f64>("0,0,1,1\n0,a,1,1\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m == "Row 2: 'a' is not a number"
+    ));
+    // too large for f32
+    assert!(matches!(
+        read_lines_csv::<_, f32>("0,0,1,1e300\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m.contains("convert a coordinate value")
+    ));
+}
+
+#[test]
+fn csv_results_1() {
+    assert!(matches!(
+        read_results_csv::<_, f64>("1,1,0;x\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m == "Row 1: 'x' is not a line index"
+    ));
+    assert!(matches!(
+        read_results_csv::<_, f64>("x,y,lines\n1,1\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m == "Row 2: expected 3 values, found 2"
+    ));
+    assert!(matches!(
+        read_results_csv::<_, f64>("1,b,0;1\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m == "Row 1: 'b' is not a number"
+    ));
+}
@@ -0,0 +1,329 @@
+use intersect2d::curve::{
+    arc_intersections, quadratic_bezier_intersections, segment_arc_intersections,
+    segment_ellipse_intersections, segment_quadratic_bezier_intersections, ArcIntersection,
+    CircularArc, Ellipse, QuadraticBezier,
+};
+use intersect2d::{IntersectError, Tolerance};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn ellipse_1() {
+    let tolerance = Tolerance::default();
+    let ellipse = Ellipse::new(geo::Coordinate { x: 1., y: 2. }, 3., 1.).unwrap();
+    // vertical line through the center
+    let rv = segment_ellipse_intersections(
+        &geo::Line::from([(1., 5.), (1., -5.)]),
+        &ellipse,
+        &tolerance,
+    );
+    assert_eq!(rv.len(), 2);
+    assert!(close(rv[0].position.y, 3.) && close(rv[1].position.y, 1.));
+    assert!(close(rv[0].curve_parameter, std::f64::consts::FRAC_PI_2));
+    assert!(close(
+        rv[1].curve_parameter,
+        3. * std::f64::consts::FRAC_PI_2
+    ));
+    // tangent at the top
+    let rv = segment_ellipse_intersections(
+        &geo::Line::from([(-5., 3.), (5., 3.)]),
+        &ellipse,
+        &tolerance,
+    );
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].position.x, 1.) && close(rv[0].position.y, 3.));
+    // a segment ending inside the ellipse
+    let rv = segment_ellipse_intersections(
+        &geo::Line::from([(1., 2.), (10., 2.)]),
+        &ellipse,
+        &tolerance,
+    );
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].position.x, 4.));
+    assert!(close(rv[0].segment_parameter, 1. / 3.));
+    // missing
+    let rv = segment_ellipse_intersections(
+        &geo::Line::from([(-5., 3.5), (5., 3.5)]),
+        &ellipse,
+        &tolerance,
+    );
+    assert!(rv.is_empty());
+}
+
+#[test]
+fn ellipse_2() {
+    // a rotated ellipse, every reported point must lie on both the segment and the ellipse
+    let ellipse = Ellipse::rotated(geo::Coordinate { x: -2., y: 1. }, 5., 2., 0.7).unwrap();
+    let mut hits = 0;
+    for i in 0..36 {
+        let angle = i as f64 * 10_f64.to_radians();
+        let (sin, cos) = angle.sin_cos();
+        let line = geo::Line::from([
+            (-2. - 8. * cos, 1.3 - 8. * sin),
+            (-2. + 8. * cos, 1.3 + 8. * sin),
+        ]);
+        let rv = segment_ellipse_intersections(&line, &ellipse, &Tolerance::default());
+        hits += rv.len();
+        for i in rv.iter() {
+            let on_curve = ellipse.point_at(i.curve_parameter);
+            assert!(close(on_curve.x, i.position.x) && close(on_curve.y, i.position.y));
+            let on_line = line.start + (line.end - line.start) * i.segment_parameter;
+            assert!(close(on_line.x, i.position.x) && close(on_line.y, i.position.y));
+        }
+    }
+    // the lines all pass through a point inside the ellipse
+    assert_eq!(hits, 72);
+}
+
+#[test]
+fn ellipse_radii_1() {
+    let center = geo::Coordinate { x: 0., y: 0. };
+    for bad in [0., -1., f64::NAN, f64::INFINITY] {
+        for (radius_x, radius_y) in [(bad, 1.), (1., bad)] {
+            assert!(matches!(
+                Ellipse::new(center, radius_x, radius_y),
+                Err(IntersectError::InvalidParameter(m)) if m.contains("radii")
+            ));
+            assert!(matches!(
+                Ellipse::rotated(center, radius_x, radius_y, 1.),
+                Err(IntersectError::InvalidParameter(m)) if m.contains("radii")
+            ));
+        }
+    }
+}
+
+#[test]
+fn ellipse_non_finite_1() {
+    let center = geo::Coordinate { x: 0., y: 0. };
+    assert!(matches!(
+        Ellipse::rotated(center, 1., 1., f64::NAN),
+        Err(IntersectError::InvalidData(m)) if m.contains("must be finite")
+    ));
+    let center = geo::Coordinate {
+        x: f64::INFINITY,
+        y: 0.,
+    };
+    assert!(matches!(
+        Ellipse::new(center, 1., 1.),
+        Err(IntersectError::InvalidData(m)) if m.contains("must be finite")
+    ));
+}
+
+fn c(x: f64, y: f64) -> geo::Coordinate<f64> {
+    geo::Coordinate { x, y }
+}
+
+#[test]
+fn bezier_1() {
+    let tolerance = Tolerance::default();
+    let curve = QuadraticBezier::new(c(0., 0.), c(5., 10.), c(10., 0.)).unwrap();
+    // tangent at the top of the curve, y = 5
+    let rv = segment_quadratic_bezier_intersections(
+        &geo::Line::from([(0., 5.), (10., 5.)]),
+        &curve,
+        &tolerance,
+    );
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].position.x, 5.) && close(rv[0].curve_parameter, 0.5));
+    // a segment touching the start of the curve
+    let rv = segment_quadratic_bezier_intersections(
+        &geo::Line::from([(-1., 0.), (0., 0.)]),
+        &curve,
+        &tolerance,
+    );
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].curve_parameter, 0.);
+    assert_eq!(rv[0].segment_parameter, 1.);
+    // a degenerate (straight) curve crossed by a vertical segment
+    let straight = QuadraticBezier::new(c(0., 0.), c(5., 5.), c(10., 10.)).unwrap();
+    let rv = segment_quadratic_bezier_intersections(
+        &geo::Line::from([(3., -1.), (3., 11.)]),
+        &straight,
+        &tolerance,
+    );
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].position.y, 3.) && close(rv[0].curve_parameter, 0.3));
+    // every point lies on both
+    for i in 0..20 {
+        let y = i as f64 * 0.3;
+        let line = geo::Line::from([(-1., y - 1.), (11., y + 1.)]);
+        for hit in segment_quadratic_bezier_intersections(&line, &curve, &tolerance) {
+            let on_curve = curve.point_at(hit.curve_parameter);
+            assert!(close(on_curve.x, hit.position.x) && close(on_curve.y, hit.position.y));
+            let on_line = line.start + (line.end - line.start) * hit.segment_parameter;
+            assert!(close(on_line.x, hit.position.x) && close(on_line.y, hit.position.y));
+        }
+    }
+}
+
+#[test]
+fn bezier_2() {
+    let tolerance = Tolerance::default();
+    // a narrow spike crossing a wide hump twice
+    let a = QuadraticBezier::new(c(0., 0.), c(10., 20.), c(20., 0.)).unwrap();
+    let b = QuadraticBezier::new(c(8., -2.), c(10., 40.), c(12., -2.)).unwrap();
+    let rv = quadratic_bezier_intersections(&a, &b, &tolerance);
+    assert_eq!(rv.len(), 2);
+    for hit in rv.iter() {
+        let (pa, pb) = (a.point_at(hit.parameter_a), b.point_at(hit.parameter_b));
+        assert!(close(pa.x, pb.x) && close(pa.y, pb.y));
+    }
+    assert!(rv[0].parameter_a < rv[1].parameter_a);
+    // separate curves
+    let far = QuadraticBezier::new(c(0., 30.), c(10., 40.), c(20., 30.)).unwrap();
+    assert!(quadratic_bezier_intersections(&a, &far, &tolerance).is_empty());
+    // curves sharing an end point
+    let next = QuadraticBezier::new(c(20., 0.), c(30., -20.), c(40., 0.)).unwrap();
+    let rv = quadratic_bezier_intersections(&a, &next, &tolerance);
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].position.x, 20.) && close(rv[0].position.y, 0.));
+    assert!(close(rv[0].parameter_a, 1.) && close(rv[0].parameter_b, 0.));
+}
+
+#[test]
+fn bezier_non_finite_1() {
+    let bad = c(f64::NAN, 1.);
+    for points in [
+        [bad, c(0., 1.), c(1., 0.)],
+        [c(0., 0.), bad, c(1., 0.)],
+        [c(0., 0.), c(0., 1.), bad],
+    ] {
+        assert!(matches!(
+            QuadraticBezier::new(points[0], points[1], points[2]),
+            Err(IntersectError::InvalidData(m)) if m.contains("control points")
+        ));
+    }
+    // a degenerate curve is still a valid curve
+    assert!(QuadraticBezier::new(c(1., 1.), c(1., 1.), c(1., 1.)).is_ok());
+}
+
+fn points(rv: &[ArcIntersection<f64>]) -> Vec<geo::Coordinate<f64>> {
+    rv.iter()
+        .map(|i| match i {
+            ArcIntersection::Point(p) => p.position,
+            ArcIntersection::Overlap(_) => panic!("unexpected overlap {:?}", i),
+        })
+        .collect()
+}
+
+#[test]
+fn arc_1() {
+    let tolerance = Tolerance::default();
+    let pi = std::f64::consts::PI;
+    let full = CircularArc::new(c(0., 0.), 2., 0., 2. * pi).unwrap();
+    // two crossing circles
+    let other = CircularArc::new(c(2., 0.), 2., pi, -2. * pi).unwrap();
+    let rv = points(&arc_intersections(&full, &other, &tolerance));
+    assert_eq!(rv.len(), 2);
+    assert!(close(rv[0].x, 1.) && close(rv[0].y, 3_f64.sqrt()));
+    assert!(close(rv[1].x, 1.) && close(rv[1].y, -(3_f64.sqrt())));
+    // externally tangent
+    let tangent = CircularArc::new(c(3., 0.), 1., pi / 2., pi).unwrap();
+    let rv = points(&arc_intersections(&full, &tangent, &tolerance));
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].x, 2.) && close(rv[0].y, 0.));
+    // internally tangent
+    let inner = CircularArc::new(c(1., 0.), 1., -pi / 2., pi).unwrap();
+    let rv = points(&arc_intersections(&full, &inner, &tolerance));
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].x, 2.) && close(rv[0].y, 0.));
+    // the circles cross, but not on the arcs
+    let short = CircularArc::new(c(2., 0.), 2., 3. * pi / 4., -pi / 2.).unwrap();
+    let quarter = CircularArc::new(c(0., 0.), 2., pi / 2., pi / 2.).unwrap();
+    assert!(arc_intersections(&quarter, &short, &tolerance).is_empty());
+    // concentric
+    let small = CircularArc::new(c(0., 0.), 1., 0., pi).unwrap();
+    assert!(arc_intersections(&full, &small, &tolerance).is_empty());
+}
+
+#[test]
+fn arc_2() {
+    let tolerance = Tolerance::default();
+    let pi = std::f64::consts::PI;
+    let a = CircularArc::new(c(1., 1.), 1., 0., pi).unwrap();
+    // a clockwise arc over the same circle, from 3π/2 back to π/2
+    let b = CircularArc::new(c(1., 1.), 1., 3. * pi / 2., -pi).unwrap();
+    let rv = arc_intersections(&a, &b, &tolerance);
+    assert_eq!(rv.len(), 1);
+    match rv[0] {
+        ArcIntersection::Overlap(o) => {
+            assert!(close(o.start_angle(), pi / 2.));
+            assert!(close(o.sweep_angle(), pi / 2.));
+        }
+        _ => panic!(),
+    }
+    // arcs of the same circle touching at an end point
+    let c2 = CircularArc::new(c(1., 1.), 1., pi, pi / 2.).unwrap();
+    let rv = points(&arc_intersections(&a, &c2, &tolerance));
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].x, 0.) && close(rv[0].y, 1.));
+    // arcs of the same circle overlapping at both ends
+    let d = CircularArc::new(c(1., 1.), 1., 3. * pi / 4., 3. * pi / 2.).unwrap();
+    let rv = arc_intersections(&a, &d, &tolerance);
+    assert_eq!(rv.len(), 2);
+    assert!(rv.iter().all(|i| matches!(i, ArcIntersection::Overlap(_))));
+    // disjoint arcs of the same circle
+    let e = CircularArc::new(c(1., 1.), 1., 1.2 * pi, pi / 2.).unwrap();
+    assert!(arc_intersections(&a, &e, &tolerance).is_empty());
+}
+
+#[test]
+fn arc_3() {
+    let tolerance = Tolerance::default();
+    let pi = std::f64::consts::PI;
+    // a clockwise quarter arc, from the top to the right
+    let arc = CircularArc::new(c(0., 0.), 5., pi / 2., -pi / 2.).unwrap();
+    let rv = segment_arc_intersections(&geo::Line::from([(0., 0.), (10., 10.)]), &arc, &tolerance);
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].curve_parameter, 0.5));
+    // the other half of the diagonal misses the arc
+    let rv =
+        segment_arc_intersections(&geo::Line::from([(0., 0.), (-10., -10.)]), &arc, &tolerance);
+    assert!(rv.is_empty());
+    // at the end point of the arc
+    let rv = segment_arc_intersections(&geo::Line::from([(5., -1.), (5., 1.)]), &arc, &tolerance);
+    assert_eq!(rv.len(), 1);
+    assert!(close(rv[0].curve_parameter, 1.));
+}
+
+#[test]
+fn arc_sweep_1() {
+    let tau = std::f64::consts::TAU;
+    for sweep in [0., 7., -7.] {
+        assert!(matches!(
+            CircularArc::new(c(0., 0.), 1., 0., sweep),
+            Err(IntersectError::InvalidParameter(m)) if m.contains("sweep")
+        ));
+    }
+    // a full turn in either direction is allowed
+    assert!(CircularArc::new(c(0., 0.), 1., 0., tau).is_ok());
+    assert!(CircularArc::new(c(0., 0.), 1., 0., -tau).is_ok());
+}
+
+#[test]
+fn arc_radius_1() {
+    for radius in [0., -1., f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            CircularArc::new(c(0., 0.), radius, 0., 1.),
+            Err(IntersectError::InvalidParameter(m)) if m.contains("radius")
+        ));
+    }
+}
+
+#[test]
+fn arc_non_finite_1() {
+    assert!(matches!(
+        CircularArc::new(c(f64::NAN, 0.), 1., 0., 1.),
+        Err(IntersectError::InvalidData(m)) if m.contains("must be finite")
+    ));
+    assert!(matches!(
+        CircularArc::new(c(0., 0.), 1., f64::INFINITY, 1.),
+        Err(IntersectError::InvalidData(m)) if m.contains("must be finite")
+    ));
+    assert!(matches!(
+        CircularArc::new(c(0., 0.), 1., 0., f64::NAN),
+        Err(IntersectError::InvalidData(m)) if m.contains("must be finite")
+    ));
+}
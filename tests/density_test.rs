@@ -0,0 +1,55 @@
+use intersect2d::density::DensityGrid;
+use intersect2d::IntersectError;
+
+#[test]
+fn density_1() -> Result<(), IntersectError> {
+    let bounds = geo::Rect::new((0., 0.), (4., 2.));
+    let mut grid = DensityGrid::<f64>::new(bounds, 4, 2)?;
+    let points: Vec<geo::Coordinate<f64>> = vec![
+        (0., 0.).into(),
+        (0.5, 0.5).into(),
+        (3.5, 1.5).into(),
+        // the upper and right borders belong to the last row and column
+        (4., 2.).into(),
+        (1., 1.).into(),
+        (5., 1.).into(),
+        (2., -0.1).into(),
+        (f64::NAN, 1.).into(),
+    ];
+    grid.extend(points);
+    assert_eq!(grid.counts(), &[2, 0, 0, 0, 0, 1, 0, 2]);
+    assert_eq!(grid.count(3, 1), 2);
+    assert_eq!(grid.outside(), 3);
+    assert_eq!(grid.max_count(), 2);
+    assert_eq!(grid.cell_of(&(2., 1.).into()), Some((2, 1)));
+    Ok(())
+}
+
+#[test]
+fn density_size_1() {
+    let bounds = geo::Rect::new((0., 0.), (4., 2.));
+    for (columns, rows) in [(0, 2), (4, 0), (0, 0)] {
+        assert!(matches!(
+            DensityGrid::<f64>::new(bounds, columns, rows),
+            Err(IntersectError::InvalidParameter(m)) if m.contains("one column and one row")
+        ));
+        assert!(matches!(
+            DensityGrid::<f64>::from_results(bounds, columns, rows, Vec::new()),
+            Err(IntersectError::InvalidParameter(_))
+        ));
+    }
+}
+
+#[test]
+fn density_bounds_1() {
+    for bounds in [
+        geo::Rect::new((0., 0.), (f64::INFINITY, 2.)),
+        geo::Rect::new((f64::NEG_INFINITY, 0.), (4., 2.)),
+        geo::Rect::new((0., f64::NAN), (4., 2.)),
+    ] {
+        assert!(matches!(
+            DensityGrid::<f64>::new(bounds, 1, 1),
+            Err(IntersectError::InvalidData(m)) if m.contains("bounds must be finite")
+        ));
+    }
+}
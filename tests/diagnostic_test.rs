@@ -0,0 +1,95 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::diagnostic::HISTORY_LENGTH;
+use intersect2d::IntersectError;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+/// A sink the test can read back
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn lines() -> Vec<geo::Line<f64>> {
+    (0..40)
+        .map(|i| {
+            let x = i as f64;
+            geo::Line::new((x, 0.0), (40.0 - x, 10.0))
+        })
+        .collect()
+}
+
+#[test]
+fn diagnostic_1() -> Result<(), IntersectError> {
+    let sink = SharedBuffer::default();
+    let mut ad = AlgorithmData::<f64>::default();
+    let results = ad
+        .with_diagnostic_sink(sink.clone())?
+        .with_ignore_end_point_intersections(true)?
+        .with_lines(lines().into_iter())?
+        .compute()?;
+    assert_eq!(results.len(), 1);
+    // nothing is written when the computation succeeds
+    assert!(sink.0.lock().unwrap().is_empty());
+
+    let dump: serde_json::Value = serde_json::from_str(&ad.diagnostic_dump("a \"quoted\"\nerror"))
+        .expect("the dump must be valid JSON");
+    assert_eq!(dump["version"], 1);
+    assert_eq!(dump["error"], "a \"quoted\"\nerror");
+    assert_eq!(dump["options"]["ignore_end_point_intersections"], true);
+    assert_eq!(dump["options"]["snap_radius"], serde_json::Value::Null);
+    assert_eq!(dump["segments"].as_array().unwrap().len(), 40);
+    assert_eq!(
+        dump["segments"][0],
+        serde_json::json!([0.0, 0.0, 40.0, 10.0])
+    );
+    // 80 end point events and one intersection event, only the last ones are kept
+    let history = dump["event_history"].as_array().unwrap();
+    assert_eq!(history.len(), HISTORY_LENGTH);
+    let last = history.last().unwrap();
+    assert_eq!(last["position"], serde_json::json!([40.0, 10.0]));
+    assert_eq!(last["drop"], serde_json::json!([0]));
+    assert!(history.iter().any(|e| e["intersection"]
+        .as_array()
+        .map_or(false, |i| i.len() == 40)));
+
+    // the history starts over when the instance is reset
+    let _ = ad.reset()?;
+    let dump: serde_json::Value =
+        serde_json::from_str(&ad.diagnostic_dump("")).expect("the dump must be valid JSON");
+    assert!(dump["event_history"].as_array().unwrap().is_empty());
+    Ok(())
+}
+
+#[test]
+fn diagnostic_2() -> Result<(), IntersectError> {
+    // no history is recorded without a sink
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_lines(lines().into_iter())?.compute()?;
+    let dump: serde_json::Value =
+        serde_json::from_str(&ad.diagnostic_dump("error")).expect("the dump must be valid JSON");
+    assert!(dump["event_history"].as_array().unwrap().is_empty());
+    assert_eq!(
+        dump["sweepline_pos"],
+        serde_json::json!([f64::MAX, f64::MAX])
+    );
+
+    // computing again after the results are taken is an error, not a panic
+    assert!(matches!(
+        ad.compute(),
+        Err(IntersectError::ResultsAlreadyTaken(_))
+    ));
+    assert!(matches!(
+        ad.compute_iterative(),
+        Err(IntersectError::ResultsAlreadyTaken(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::{IntersectError, Tolerance};
+
+type Results = Vec<(geo::Coordinate<f64>, Vec<usize>)>;
+
+fn directed(lines: &[geo::Line<f64>]) -> Result<Results, IntersectError> {
+    Ok(AlgorithmData::<f64>::default()
+        .with_directed_end_points(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect())
+}
+
+#[test]
+fn directed_1() -> Result<(), IntersectError> {
+    // a chain flowing in every direction of the sweep-line
+    let chain = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [10., 0., 10., 10.],
+        [10., 10., 0., 10.],
+        [0., 10., 5., 5.],
+        [5., 5., 3., 1.],
+    ]);
+    assert!(directed(&chain)?.is_empty());
+
+    // reversing one line turns both of its junctions into errors
+    let mut reversed = chain.clone();
+    reversed[2] = geo::Line::new(reversed[2].end, reversed[2].start);
+    let rv = directed(&reversed)?;
+    assert_eq!(
+        rv,
+        vec![
+            (geo::Coordinate { x: 0., y: 10. }, vec![2, 3]),
+            (geo::Coordinate { x: 10., y: 10. }, vec![1, 2]),
+        ]
+    );
+
+    // a fork, a merge and a junction of three lines
+    let network = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 5., 5.],
+        [0., 0., -5., 5.],
+        [20., 0., 25., 5.],
+        [30., 0., 25., 5.],
+        [40., 0., 45., 5.],
+        [45., 5., 50., 10.],
+        [45., 5., 40., 10.],
+    ]);
+    let rv = directed(&network)?;
+    assert_eq!(rv.len(), 3);
+    assert_eq!(rv[0], (geo::Coordinate { x: 0., y: 0. }, vec![0, 1]));
+    assert_eq!(rv[1], (geo::Coordinate { x: 25., y: 5. }, vec![2, 3]));
+    assert_eq!(rv[2].0, geo::Coordinate { x: 45., y: 5. });
+
+    // a line ending in the interior of another line is always reported
+    let t_junction = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., 5., 5., 0.]]);
+    assert_eq!(directed(&t_junction)?.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn directed_2() -> Result<(), IntersectError> {
+    // the direction survives a reset with another tolerance and the f64 upcast
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 10., 0., 0.],
+        [0., 0., 10., 0.],
+        [10., 0., 10., 10.],
+        [20., 0., 10., 10.],
+    ]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let rv: Vec<_> = ad
+        .with_directed_end_points(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(rv, vec![(geo::Coordinate { x: 10., y: 10. }, vec![2, 3])]);
+    let rv: Vec<_> = ad
+        .reset_with_tolerance(Tolerance::new(1e-6, 4))?
+        .compute()?
+        .collect();
+    assert_eq!(rv, vec![(geo::Coordinate { x: 10., y: 10. }, vec![2, 3])]);
+
+    let lines_f32: Vec<geo::Line<f32>> = lines
+        .iter()
+        .map(|l| {
+            geo::Line::from([
+                (l.start.x as f32, l.start.y as f32),
+                (l.end.x as f32, l.end.y as f32),
+            ])
+        })
+        .collect();
+    let rv: Vec<_> = AlgorithmData::<f32>::default()
+        .with_f64_upcast(true)?
+        .with_directed_end_points(true)?
+        .with_ref_lines(lines_f32.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(rv, vec![(geo::Coordinate { x: 10., y: 10. }, vec![2, 3])]);
+    Ok(())
+}
+
+#[test]
+fn directed_3() -> Result<(), IntersectError> {
+    // the self-check knows the connections aren't reported
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [10., 0., 10., 10.],
+        [10., 10., 0., 10.],
+        [5., 10., 5., -5.],
+        [20., 0., 25., 5.],
+        [30., 0., 25., 5.],
+        [40., 0., 45., 5.],
+        [45., 5., 50., 10.],
+        [45., 5., 40., 10.],
+    ]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let rv: Vec<_> = ad
+        .with_directed_end_points(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    // the crossing and the touch of line 3, the merge and the junction of three lines
+    assert_eq!(rv.len(), 4);
+    assert_eq!(ad.verify_results(&rv, 1000), vec![]);
+
+    // dropping a reported junction is still caught
+    let missing: Vec<_> = rv
+        .iter()
+        .filter(|r| r.0 != geo::Coordinate { x: 25., y: 5. })
+        .cloned()
+        .collect();
+    assert_eq!(ad.verify_results(&missing, 1000).len(), 1);
+    Ok(())
+}
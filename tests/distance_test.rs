@@ -0,0 +1,155 @@
+use intersect2d::distance::{
+    classify_touch, classify_touch_with_tolerance, segment_segment_closest,
+    segment_segment_closest_with_tolerance, TouchClassification,
+};
+use intersect2d::{IntersectError, Tolerance};
+
+#[test]
+fn distance_1() {
+    // crossing
+    let one: geo::Line<f64> = [(0., 0.), (10., 10.)].into();
+    let other: geo::Line<f64> = [(0., 10.), (10., 0.)].into();
+    let (distance, on_one, on_two, t_one, t_two) = segment_segment_closest(&one, &other);
+    assert_eq!(distance, 0.0);
+    assert_eq!(on_one, geo::Coordinate { x: 5., y: 5. });
+    assert_eq!(on_one, on_two);
+    assert!(approx::ulps_eq!(t_one, 0.5));
+    assert!(approx::ulps_eq!(t_two, 0.5));
+
+    // parallel, the end of `one` and the start of `other` are equally close, the first wins
+    let other: geo::Line<f64> = [(1., 0.), (11., 10.)].into();
+    let (distance, on_one, on_two, t_one, t_two) = segment_segment_closest(&one, &other);
+    assert!(approx::ulps_eq!(distance, 0.5f64.sqrt()));
+    assert_eq!(on_one, one.end);
+    assert!(approx::ulps_eq!(on_two.x, 10.5));
+    assert!(approx::ulps_eq!(on_two.y, 9.5));
+    assert_eq!(t_one, 1.0);
+    assert!(approx::ulps_eq!(t_two, 0.95));
+
+    // end point to end point, arguments swapped
+    let other: geo::Line<f64> = [(13., 14.), (20., 20.)].into();
+    let (distance, on_one, on_two, t_one, t_two) = segment_segment_closest(&other, &one);
+    assert_eq!(distance, 5.0);
+    assert_eq!(on_one, other.start);
+    assert_eq!(on_two, one.end);
+    assert_eq!((t_one, t_two), (0.0, 1.0));
+}
+
+#[test]
+fn distance_2() {
+    // points
+    let point: geo::Line<f64> = [(3., 4.), (3., 4.)].into();
+    let origin: geo::Line<f64> = [(0., 0.), (0., 0.)].into();
+    let (distance, _, _, t_one, t_two) = segment_segment_closest(&point, &origin);
+    assert_eq!(distance, 5.0);
+    assert_eq!((t_one, t_two), (0.0, 0.0));
+
+    // the tolerance decides what's touching
+    let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    let other: geo::Line<f64> = [(5., 0.001), (5., 5.)].into();
+    let (distance, on_one, on_two, ..) = segment_segment_closest(&one, &other);
+    assert!(approx::ulps_eq!(distance, 0.001));
+    assert_eq!(on_one, geo::Coordinate { x: 5., y: 0. });
+    assert_eq!(on_two, other.start);
+    let (distance, ..) =
+        segment_segment_closest_with_tolerance(&one, &other, &Tolerance::new(0.01, 4));
+    assert_eq!(distance, 0.0);
+}
+
+#[test]
+fn project_point_1() {
+    use intersect2d::distance::{
+        project_point_onto_segment, project_point_onto_segment_with_tolerance,
+    };
+    use intersect2d::precision::PrecisionModel;
+
+    let line: geo::Line<f64> = [(1., 1.), (5., 4.)].into();
+    let (position, t) = project_point_onto_segment(&line, &geo::Coordinate { x: 1.5, y: 5. });
+    assert!(approx::ulps_eq!(t, 0.56));
+    assert!(approx::ulps_eq!(position.x, 3.24));
+    assert!(approx::ulps_eq!(position.y, 2.68));
+    // beyond the end
+    let (position, t) = project_point_onto_segment(&line, &geo::Coordinate { x: 9., y: 4. });
+    assert_eq!((position, t), (line.end, 1.0));
+    // virtually at the end point
+    let point = geo::Coordinate {
+        x: 4.9996,
+        y: 4.0003,
+    };
+    let (_, t) = project_point_onto_segment(&line, &point);
+    assert!(t < 1.0);
+    let tolerance = Tolerance::new(0.001, 4);
+    let (position, t) = project_point_onto_segment_with_tolerance(&line, &point, &tolerance);
+    assert_eq!((position, t), (line.end, 1.0));
+    // snapped to the precision model
+    let tolerance = Tolerance::<f64>::default()
+        .with_precision_model(PrecisionModel::ScaledInteger(10.0))
+        .unwrap();
+    let (position, _) = project_point_onto_segment_with_tolerance(
+        &line,
+        &geo::Coordinate { x: 1.5, y: 5. },
+        &tolerance,
+    );
+    assert_eq!(position, geo::Coordinate { x: 3.2, y: 2.7 });
+    // zero length line
+    let point_line: geo::Line<f64> = [(1., 1.), (1., 1.)].into();
+    let (position, t) = project_point_onto_segment(&point_line, &geo::Coordinate { x: 4., y: 5. });
+    assert_eq!((position, t), (point_line.start, 0.0));
+}
+
+#[test]
+fn classify_touch_1() -> Result<(), IntersectError> {
+    let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    let touch = |other: [(f64, f64); 2], distance: f64| {
+        classify_touch(&one, &other.into(), distance).unwrap()
+    };
+    // shared end points and collinear overlaps touch at distance zero
+    assert_eq!(
+        touch([(10., 0.), (12., 3.)], 0.),
+        TouchClassification::TouchingWithin(0.)
+    );
+    assert_eq!(
+        touch([(5., 0.), (15., 0.)], 0.),
+        TouchClassification::TouchingWithin(0.)
+    );
+    // parallel and end point gaps
+    assert_eq!(
+        touch([(2., 3.), (8., 3.)], 3.),
+        TouchClassification::TouchingWithin(3.)
+    );
+    assert_eq!(
+        touch([(2., 3.), (8., 3.)], 2.9),
+        TouchClassification::Disjoint
+    );
+    assert_eq!(
+        touch([(13., 4.), (20., 4.)], 5.),
+        TouchClassification::TouchingWithin(5.)
+    );
+    // a crossing stays a crossing, no matter the distance
+    assert_eq!(
+        touch([(0., -1.), (10., 1.)], 0.),
+        TouchClassification::ProperlyIntersecting(geo::Coordinate { x: 5., y: 0. })
+    );
+    assert_eq!(
+        touch([(0., -1.), (10., 1.)], 100.),
+        TouchClassification::ProperlyIntersecting(geo::Coordinate { x: 5., y: 0. })
+    );
+
+    // an end point within the tolerance of the other segment is a touch
+    let barely: geo::Line<f64> = [(5., -1e-9), (5., 5.)].into();
+    assert!(matches!(
+        classify_touch(&one, &barely, 0.)?,
+        TouchClassification::ProperlyIntersecting(_)
+    ));
+    assert_eq!(
+        classify_touch_with_tolerance(&one, &barely, 0., &Tolerance::new(1e-6, 4))?,
+        TouchClassification::TouchingWithin(0.)
+    );
+
+    assert!(matches!(
+        classify_touch(&one, &barely, -1.),
+        Err(IntersectError::InvalidParameter(_))
+    ));
+    assert!(classify_touch(&one, &barely, f64::NAN).is_err());
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::double::{intersect_double_double, DoubleDouble};
+use intersect2d::exact::intersect_exact;
+use intersect2d::{intersect, Intersection, Tolerance};
+
+fn random_lines(size: usize, seed: u64) -> Vec<geo::Line<f64>> {
+    let mut seed = seed;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 1_000_000) as f64 / 997.0
+    };
+    (0..size)
+        .map(|_| {
+            geo::Line::new(
+                geo::Coordinate {
+                    x: next(),
+                    y: next(),
+                },
+                geo::Coordinate {
+                    x: next(),
+                    y: next(),
+                },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn double_double_1() {
+    let a = DoubleDouble::from_sum(1e16_f64, 1.0);
+    assert_eq!(a.hi(), 1e16);
+    assert_eq!(a.lo(), 1.0);
+    assert_eq!((a - DoubleDouble::new(1e16)).value(), 1.0);
+    let p = DoubleDouble::from_product(1.0 + f64::EPSILON, 1.0 + f64::EPSILON);
+    assert_eq!(p.lo(), f64::EPSILON * f64::EPSILON);
+    assert!(DoubleDouble::from_sum(1.0, 1e-20) > DoubleDouble::new(1.0));
+    assert!(DoubleDouble::new(1.0) / DoubleDouble::new(0.0) > DoubleDouble::new(1.0));
+}
+
+#[test]
+fn double_double_2() {
+    let lines = random_lines(300, 0x1234_5678_9abc_def1);
+    let tolerance = Tolerance::default();
+    let (mut crossings, mut plain, mut double) = (0, 0, 0);
+    for (i, l1) in lines.iter().enumerate() {
+        for l2 in lines.iter().skip(i + 1) {
+            let exact = match intersect_exact(l1, l2).intersection {
+                Some(Intersection::Intersection(p)) => p,
+                _ => continue,
+            };
+            crossings += 1;
+            if let Some(Intersection::Intersection(p)) = intersect(l1, l2) {
+                if p != exact {
+                    plain += 1;
+                }
+            }
+            if let Some(Intersection::Intersection(p)) = intersect_double_double(l1, l2, &tolerance)
+            {
+                if p != exact {
+                    double += 1;
+                }
+            }
+        }
+    }
+    assert!(crossings > 1000);
+    assert!(double * 100 < plain);
+}
+
+#[test]
+fn double_double_3() {
+    let lines = random_lines(200, 42);
+    let compute = |double_double: bool| {
+        let mut ad = AlgorithmData::<f64>::default();
+        let rv: Vec<_> = ad
+            .with_double_double(double_double)
+            .unwrap()
+            .with_ref_lines(lines.iter())
+            .unwrap()
+            .compute()
+            .unwrap()
+            .collect();
+        assert!(ad.verify_results(&rv, 1000).is_empty());
+        rv
+    };
+    let plain = compute(false);
+    let double = compute(true);
+    assert_eq!(plain.len(), double.len());
+    for (a, b) in plain.iter().zip(double.iter()) {
+        assert_eq!(a.1, b.1);
+        // the points of the double-double sweep are correctly rounded
+        if let Some(Intersection::Intersection(p)) =
+            intersect_exact(&lines[b.1[0]], &lines[b.1[1]]).intersection
+        {
+            assert_eq!(p, b.0);
+        }
+    }
+}
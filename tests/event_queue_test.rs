@@ -0,0 +1,83 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn event_queue_1() -> Result<(), IntersectError> {
+    // a star of lines sharing the center point, and a line crossing them all
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [0., 0., -10., 10.],
+        [0., 0., 0., 10.],
+        [0., 0., 10., 0.],
+        [-10., 5., 10., 5.],
+    ]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?;
+    // the input end points at identical positions are merged while the lines are added
+    assert_eq!(ad.get_site_events().as_ref().unwrap().len(), 7);
+    assert!(ad
+        .get_site_events()
+        .as_ref()
+        .unwrap()
+        .iter()
+        .all(|(_, e)| e.get_intersections().is_none()));
+
+    // the center point is handled as one event
+    assert!(!ad.compute_iterative()?);
+    assert_eq!(ad.get_results().as_ref().unwrap().len(), 1);
+    while !ad.compute_iterative()? {}
+    let rv: Vec<_> = ad.take_results()?.collect();
+    assert_eq!(
+        rv,
+        vec![
+            (geo::Coordinate { x: 0., y: 0. }, vec![0, 1, 2, 3]),
+            (geo::Coordinate { x: -5., y: 5. }, vec![1, 4]),
+            (geo::Coordinate { x: 0., y: 5. }, vec![2, 4]),
+            (geo::Coordinate { x: 5., y: 5. }, vec![0, 4]),
+        ]
+    );
+    assert!(ad.get_site_events().as_ref().unwrap().is_empty());
+    Ok(())
+}
+
+#[test]
+fn event_queue_setup_1() -> Result<(), IntersectError> {
+    // a grid of shared end points, added in one or two batches
+    let mut coords = Vec::new();
+    for i in 0..10 {
+        let v = i as f64;
+        coords.push([v, 0., v, 10.]);
+        coords.push([0., v, 10., v]);
+        coords.push([v, 10., 10., v]);
+    }
+    let lines = to_lines::<_, f64>(&coords);
+    let once: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    let (first, second) = lines.split_at(13);
+    let twice: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(first.iter())?
+        .with_ref_lines(second.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(once, twice);
+    Ok(())
+}
+
+#[test]
+fn event_queue_setup_2() -> Result<(), IntersectError> {
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(to_lines::<_, f64>(&[[0., 0., 10., 10.]]).iter())?;
+    // a failed batch doesn't leave any of its lines behind
+    let bad = to_lines::<_, f64>(&[[0., 10., 10., 0.], [0., 0., f64::NAN, 1.]]);
+    assert!(matches!(
+        ad.with_ref_lines(bad.iter()),
+        Err(IntersectError::InvalidData(_))
+    ));
+    assert_eq!(ad.get_lines().len(), 1);
+    assert_eq!(ad.get_site_events().as_ref().unwrap().len(), 2);
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use intersect2d::exact::intersect_exact;
+use intersect2d::Intersection;
+
+#[test]
+fn exact_1() {
+    // small integer coordinates: the numerators and denominator of the intersection point
+    // are exact in f64, so a plain f64 division is the correctly rounded reference
+    let mut seed = 0x853c_49e6_748f_ea9b_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 1000) as i64 - 500
+    };
+    let mut crossings = 0;
+    for _ in 0..5000 {
+        let c: Vec<i64> = (0..8).map(|_| next()).collect();
+        let one: geo::Line<f64> = [(c[0] as f64, c[1] as f64), (c[2] as f64, c[3] as f64)].into();
+        let other: geo::Line<f64> = [(c[4] as f64, c[5] as f64), (c[6] as f64, c[7] as f64)].into();
+        let (rx, ry, sx, sy) = (c[2] - c[0], c[3] - c[1], c[6] - c[4], c[7] - c[5]);
+        let (qpx, qpy) = (c[4] - c[0], c[5] - c[1]);
+        let d = rx * sy - ry * sx;
+        let rv = intersect_exact(&one, &other);
+        if d == 0 {
+            continue;
+        }
+        let (tn, un) = (qpx * sy - qpy * sx, qpx * ry - qpy * rx);
+        let (tn, un, d) = if d < 0 { (-tn, -un, -d) } else { (tn, un, d) };
+        if tn < 0 || tn > d || un < 0 || un > d {
+            assert!(rv.intersection.is_none());
+            continue;
+        }
+        crossings += 1;
+        let x = (c[0] * d + tn * rx) as f64 / d as f64;
+        let y = (c[1] * d + tn * ry) as f64 / d as f64;
+        match rv.intersection {
+            Some(Intersection::Intersection(p)) => {
+                assert_eq!(p.x.to_bits(), x.to_bits());
+                assert_eq!(p.y.to_bits(), y.to_bits());
+            }
+            _ => panic!("expected a crossing of {:?} and {:?}", one, other),
+        }
+    }
+    assert!(crossings > 500);
+}
+
+#[test]
+fn exact_2() {
+    // an end point just above the other line, intersect() has to be tolerant there
+    let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    let other: geo::Line<f64> = [(5., 1e-17), (5., 5.)].into();
+    assert!(intersect2d::intersect(&one, &other).is_some());
+    let rv = intersect_exact(&one, &other);
+    assert!(rv.intersection.is_none());
+    assert!(rv.disagrees);
+
+    // exactly on the line
+    let other: geo::Line<f64> = [(5., 0.), (5., 5.)].into();
+    let rv = intersect_exact(&one, &other);
+    assert!(matches!(
+        rv.intersection,
+        Some(Intersection::Intersection(geo::Coordinate { x, y })) if x == 5. && y == 0.
+    ));
+    assert!(!rv.disagrees);
+}
+
+#[test]
+fn exact_3() {
+    // collinear segments, the overlap is the shared part
+    let one: geo::Line<f64> = [(0., 0.), (10., 10.)].into();
+    let other: geo::Line<f64> = [(15., 15.), (5., 5.)].into();
+    let rv = intersect_exact(&one, &other);
+    match rv.intersection {
+        Some(Intersection::OverLap(l)) => {
+            assert_eq!(l, [(5., 5.), (10., 10.)].into());
+        }
+        _ => panic!(),
+    }
+    assert!(!rv.disagrees);
+    // end to end
+    let other: geo::Line<f64> = [(10., 10.), (20., 20.)].into();
+    assert!(matches!(
+        intersect_exact(&one, &other).intersection,
+        Some(Intersection::Intersection(_))
+    ));
+    // parallel
+    let other: geo::Line<f64> = [(0., 1e-300), (10., 10.)].into();
+    assert!(intersect_exact(&one, &other).intersection.is_some());
+    let other: geo::Line<f64> = [(1., 1.0000000000000002), (11., 11.0000000000000002)].into();
+    assert!(intersect_exact(&one, &other).intersection.is_none());
+    // a point
+    let point: geo::Line<f64> = [(3., 3.), (3., 3.)].into();
+    assert!(intersect_exact(&one, &point).intersection.is_some());
+    let point: geo::Line<f64> = [(3., 3.0000000000000004), (3., 3.0000000000000004)].into();
+    assert!(intersect_exact(&one, &point).intersection.is_none());
+}
@@ -0,0 +1,112 @@
+use intersect2d::fill::{classify_faces, filled_faces, FillRule};
+use intersect2d::IntersectError;
+
+/// a counter clockwise square
+fn square(x: f64, y: f64, size: f64) -> Vec<geo::Line<f64>> {
+    intersect2d::to_lines::<_, f64>(&[
+        [x, y, x + size, y],
+        [x + size, y, x + size, y + size],
+        [x + size, y + size, x, y + size],
+        [x, y + size, x, y],
+    ])
+}
+
+fn reversed(lines: Vec<geo::Line<f64>>) -> Vec<geo::Line<f64>> {
+    lines
+        .into_iter()
+        .map(|l| geo::Line::new(l.end, l.start))
+        .collect()
+}
+
+fn area(polygon: &geo::Polygon<f64>) -> f64 {
+    let ring_area = |ring: &geo::LineString<f64>| {
+        ring.0
+            .windows(2)
+            .fold(0., |sum, w| sum + w[0].x * w[1].y - w[1].x * w[0].y)
+            / 2.
+    };
+    ring_area(polygon.exterior()) + polygon.interiors().iter().map(ring_area).sum::<f64>()
+}
+
+#[test]
+fn fill_1() -> Result<(), IntersectError> {
+    let faces = classify_faces(&square(0., 0., 10.))?;
+    assert_eq!(faces.len(), 1);
+    assert_eq!(faces[0].winding, 1);
+    assert!(approx::ulps_eq!(area(&faces[0].polygon), 100.));
+
+    let faces = classify_faces(&reversed(square(0., 0., 10.)))?;
+    assert_eq!(faces.len(), 1);
+    assert_eq!(faces[0].winding, -1);
+    assert!(faces[0].is_filled(FillRule::EvenOdd));
+
+    assert!(classify_faces::<f64>(&[])?.is_empty());
+    // a single segment bounds nothing
+    assert!(classify_faces(&intersect2d::to_lines::<_, f64>(&[[0., 0., 1., 1.]]))?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn fill_2() -> Result<(), IntersectError> {
+    // a square inside a square, not touching
+    let mut lines = square(0., 0., 10.);
+    lines.extend(square(2., 2., 2.));
+    let mut faces = classify_faces(&lines)?;
+    faces.sort_by_key(|f| f.winding);
+    assert_eq!(faces.len(), 2);
+    assert_eq!(faces[0].winding, 1);
+    assert_eq!(faces[0].polygon.interiors().len(), 1);
+    assert!(approx::ulps_eq!(area(&faces[0].polygon), 96.));
+    assert_eq!(faces[1].winding, 2);
+    assert!(approx::ulps_eq!(area(&faces[1].polygon), 4.));
+
+    let even_odd = filled_faces(&lines, FillRule::EvenOdd)?;
+    assert_eq!(even_odd.len(), 1);
+    assert_eq!(even_odd[0].interiors().len(), 1);
+    assert_eq!(filled_faces(&lines, FillRule::NonZero)?.len(), 2);
+
+    // the inner square reversed is a hole under both rules
+    let mut lines = square(0., 0., 10.);
+    lines.extend(reversed(square(2., 2., 2.)));
+    let filled = filled_faces(&lines, FillRule::NonZero)?;
+    assert_eq!(filled.len(), 1);
+    assert!(approx::ulps_eq!(area(&filled[0]), 96.));
+    Ok(())
+}
+
+#[test]
+fn fill_3() -> Result<(), IntersectError> {
+    // a pentagram drawn as one closed path, the center has winding number 2
+    let points: Vec<geo::Coordinate<f64>> = (0..5)
+        .map(|i| {
+            let angle = 0.1 + (i as f64) * 4. * std::f64::consts::PI / 5.;
+            geo::Coordinate {
+                x: 10. * angle.cos(),
+                y: 10. * angle.sin(),
+            }
+        })
+        .collect();
+    let lines: Vec<_> = (0..5)
+        .map(|i| geo::Line::new(points[i], points[(i + 1) % 5]))
+        .collect();
+    let faces = classify_faces(&lines)?;
+    assert_eq!(faces.len(), 6);
+    let mut windings: Vec<_> = faces.iter().map(|f| f.winding).collect();
+    windings.sort_unstable();
+    assert_eq!(windings, vec![1, 1, 1, 1, 1, 2]);
+    assert_eq!(filled_faces(&lines, FillRule::EvenOdd)?.len(), 5);
+    assert_eq!(filled_faces(&lines, FillRule::NonZero)?.len(), 6);
+    Ok(())
+}
+
+#[test]
+fn fill_4() -> Result<(), IntersectError> {
+    // two squares sharing an edge, and a dangle sticking into one of them
+    let mut lines = square(0., 0., 10.);
+    lines.extend(square(10., 0., 10.));
+    lines.extend(intersect2d::to_lines::<_, f64>(&[[10., 5., 15., 5.]]));
+    let faces = classify_faces(&lines)?;
+    assert_eq!(faces.len(), 2);
+    assert!(faces.iter().all(|f| f.winding == 1));
+    Ok(())
+}
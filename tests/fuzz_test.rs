@@ -0,0 +1,52 @@
+#![cfg(feature = "fuzz")]
+use intersect2d::fuzz::{shrink, FuzzInput};
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn fuzz_1() -> Result<(), IntersectError> {
+    // any bytes decode
+    assert!(FuzzInput::<f64>::decode(&[]).lines.is_empty());
+    let input = FuzzInput::<f64>::decode(&[2, 0, 16, 0, 0, 0, 240, 255, 255, 77]);
+    assert!(input.fine_grid);
+    assert!(!input.ignore_end_point_intersections);
+    assert_eq!(input.lines, to_lines(&[[1., 0., -1., -1. / 4096.]]));
+    // the trailing byte is dropped by the round trip
+    assert_eq!(FuzzInput::decode(&input.encode()?), input);
+
+    let off_grid = FuzzInput {
+        lines: to_lines::<_, f64>(&[[0.1, 0., 1., 1.]]),
+        ignore_end_point_intersections: false,
+        fine_grid: false,
+    };
+    assert!(matches!(
+        off_grid.encode(),
+        Err(IntersectError::InvalidData(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn fuzz_2() -> Result<(), IntersectError> {
+    // a pseudo random input on the coarse grid
+    let mut seed = 0x1234_5678_9abc_def1_u64;
+    let bytes: Vec<u8> = (0..1 + 8 * 40)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % 7) as u8
+        })
+        .collect();
+    let input = FuzzInput::<f64>::decode(&bytes);
+    assert_eq!(input.lines.len(), 40);
+    let results = input.run()?.len();
+    assert!(results > 3);
+
+    // shrink to an input with at least three intersection points
+    let minimal = shrink(&input, |i| i.run().map_or(false, |r| r.len() >= 3));
+    assert!(minimal.run()?.len() >= 3);
+    assert!(minimal.lines.len() <= 6, "{:?}", minimal.lines);
+    // the shrunk input can be stored as a regression case
+    assert_eq!(FuzzInput::decode(&minimal.encode()?), minimal);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::exact::intersect_exact;
+use intersect2d::generators::{collinear_stack, concurrent, lattice, near_parallel_fan};
+use intersect2d::oracle::self_intersections_bruteforce;
+
+fn sweep(
+    lines: &[geo::Line<f64>],
+    ignore_end_point_intersections: bool,
+) -> Vec<(geo::Coordinate<f64>, Vec<usize>)> {
+    AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(ignore_end_point_intersections)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect()
+}
+
+#[test]
+fn generators_1() {
+    // the generators are deterministic
+    assert_eq!(concurrent::<f64>(20, 5), concurrent::<f64>(20, 5));
+    assert_ne!(concurrent::<f64>(20, 5), concurrent::<f64>(20, 6));
+    assert_eq!(lattice::<f64>(4, 5), lattice::<f64>(4, 5));
+    assert_eq!(collinear_stack::<f64>(9, 5), collinear_stack::<f64>(9, 5));
+    assert_eq!(
+        near_parallel_fan::<f64>(9, 5),
+        near_parallel_fan::<f64>(9, 5)
+    );
+    assert!(concurrent::<f32>(5, 5).len() == 5);
+}
+
+#[test]
+fn generators_2() {
+    for seed in 0..5 {
+        // a single point, through every line. The crossings computed before the sweep-line
+        // reaches the point are rounded, they are merged with the point by the snap radius.
+        let lines = concurrent::<f64>(30, seed);
+        let rv: Vec<_> = AlgorithmData::<f64>::default()
+            .with_ignore_end_point_intersections(true)
+            .unwrap()
+            .with_snap_radius(1e-9)
+            .unwrap()
+            .with_ref_lines(lines.iter())
+            .unwrap()
+            .compute()
+            .unwrap()
+            .collect();
+        assert_eq!(rv.len(), 1, "seed {}", seed);
+        assert_eq!(rv[0].1, (0..30).collect::<Vec<_>>());
+        approx::assert_abs_diff_eq!(rv[0].0.x, lines[0].start.x, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(rv[0].0.y, lines[0].start.y, epsilon = 1e-9);
+
+        // every lattice point is an end point, and no piece crosses another
+        let lines = lattice::<f64>(5, seed);
+        assert!(sweep(&lines, true).is_empty());
+        let rv = sweep(&lines, false);
+        assert_eq!(rv.len(), 36);
+        let oracle: Vec<_> = self_intersections_bruteforce(&lines, false)
+            .unwrap()
+            .collect();
+        assert_eq!(rv, oracle);
+    }
+}
+
+#[test]
+fn generators_3() {
+    for seed in 0..5 {
+        let lines = collinear_stack::<f64>(10, seed);
+        let oracle: Vec<_> = self_intersections_bruteforce(&lines, false)
+            .unwrap()
+            .collect();
+        assert_eq!(sweep(&lines, false), oracle, "seed {}", seed);
+
+        // every pair crosses at most once, and no three lines share a point
+        let lines = near_parallel_fan::<f64>(20, seed);
+        let mut crossings = 0;
+        for (i, a) in lines.iter().enumerate() {
+            for b in lines.iter().skip(i + 1) {
+                if intersect_exact(a, b).intersection.is_some() {
+                    crossings += 1;
+                }
+            }
+        }
+        assert_eq!(sweep(&lines, true).len(), crossings, "seed {}", seed);
+    }
+}
@@ -0,0 +1,137 @@
+use intersect2d::geometry::{decompose, GeometryIntersections, GeometryLocation};
+use intersect2d::IntersectError;
+
+fn location(member: usize, ring: usize, segment: Option<usize>) -> GeometryLocation {
+    GeometryLocation {
+        member,
+        ring,
+        segment,
+    }
+}
+
+#[test]
+fn geometry_1() {
+    // members of nested collections are counted depth first
+    let g = geo::Geometry::GeometryCollection(geo::GeometryCollection(vec![
+        geo::Geometry::Point((1., 1.).into()),
+        geo::Geometry::MultiLineString(geo::MultiLineString(vec![
+            vec![(0., 0.), (1., 0.)].into(),
+            vec![(0., 1.), (0., 1.), (1., 1.)].into(),
+        ])),
+        geo::Geometry::GeometryCollection(geo::GeometryCollection(vec![geo::Geometry::Polygon(
+            geo::Polygon::new(
+                vec![(0., 0.), (9., 0.), (9., 9.), (0., 0.)].into(),
+                vec![vec![(5., 1.), (8., 1.), (8., 4.), (5., 1.)].into()],
+            ),
+        )])),
+    ]));
+    let d = decompose(&g);
+    assert_eq!(d.points, vec![((1., 1.).into(), location(0, 0, None))]);
+    let locations: Vec<_> = d.lines.iter().map(|l| l.1).collect();
+    assert_eq!(
+        locations,
+        vec![
+            location(1, 0, Some(0)),
+            // the zero length segment is skipped
+            location(2, 0, Some(1)),
+            location(3, 0, Some(0)),
+            location(3, 0, Some(1)),
+            location(3, 0, Some(2)),
+            location(3, 1, Some(0)),
+            location(3, 1, Some(1)),
+            location(3, 1, Some(2)),
+        ]
+    );
+}
+
+#[test]
+fn geometry_2() -> Result<(), IntersectError> {
+    let polygon = geo::Geometry::Polygon(geo::Polygon::new(
+        vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)].into(),
+        vec![vec![(4., 4.), (6., 4.), (6., 6.), (4., 6.), (4., 4.)].into()],
+    ));
+    let other = geo::Geometry::MultiPoint(vec![(5., 4.), (10., 10.), (20., 20.)].into());
+    let rv = polygon.intersections_with(&other)?;
+    assert_eq!(rv.len(), 2);
+    assert_eq!(rv[0].position, geo::Coordinate { x: 5., y: 4. });
+    assert_eq!(rv[0].a, vec![location(0, 1, Some(0))]);
+    assert_eq!(rv[0].b, vec![location(0, 0, None)]);
+    // the corner is on two segments
+    assert_eq!(rv[1].position, geo::Coordinate { x: 10., y: 10. });
+    assert_eq!(
+        rv[1].a,
+        vec![location(0, 0, Some(1)), location(0, 0, Some(2))]
+    );
+    assert_eq!(rv[1].b, vec![location(1, 0, None)]);
+
+    // the same, seen from the other side
+    let rv2 = other.intersections_with(&polygon)?;
+    assert_eq!(rv2.len(), 2);
+    assert_eq!(rv2[1].a, rv[1].b);
+    assert_eq!(rv2[1].b, rv[1].a);
+    Ok(())
+}
+
+#[test]
+fn geometry_3() -> Result<(), IntersectError> {
+    let triangle = geo::Geometry::Triangle(geo::Triangle(
+        (0., 0.).into(),
+        (10., 0.).into(),
+        (0., 10.).into(),
+    ));
+    let lines = geo::Geometry::MultiLineString(geo::MultiLineString(vec![
+        vec![(-5., 5.), (15., 5.)].into(),
+        vec![(20., 0.), (30., 0.)].into(),
+    ]));
+    let rv = triangle.intersections_with(&lines)?;
+    let positions: Vec<_> = rv.iter().map(|i| i.position).collect();
+    assert_eq!(
+        positions,
+        vec![
+            geo::Coordinate { x: 0., y: 5. },
+            geo::Coordinate { x: 5., y: 5. }
+        ]
+    );
+    assert!(rv.iter().all(|i| i.b == vec![location(0, 0, Some(0))]));
+
+    // points meeting points
+    let a = geo::Geometry::Point((1., 2.).into());
+    let rv = a.intersections_with(&geo::Geometry::MultiPoint(vec![(1., 2.), (1., 2.)].into()))?;
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].a, vec![location(0, 0, None)]);
+    assert_eq!(rv[0].b, vec![location(0, 0, None), location(1, 0, None)]);
+    Ok(())
+}
+
+/// Checks that the intersection failed because of non-finite data, in both directions
+fn rejects_non_finite(a: &geo::Geometry<f64>, b: &geo::Geometry<f64>) -> bool {
+    [a.intersections_with(b), b.intersections_with(a)]
+        .iter()
+        .all(|rv| matches!(rv, Err(IntersectError::InvalidData(m)) if m.contains("non-finite")))
+}
+
+#[test]
+fn geometry_non_finite_1() {
+    // non-finite points
+    let line = geo::Geometry::Line(geo::Line::from([(0., 0.), (1., 1.)]));
+    let point = geo::Geometry::Point((f64::NAN, 2.).into());
+    assert!(rejects_non_finite(&point, &line));
+    let points = geo::Geometry::MultiPoint(vec![(0., 0.), (1., f64::INFINITY)].into());
+    assert!(rejects_non_finite(&points, &line));
+    let collection =
+        geo::Geometry::GeometryCollection(geo::GeometryCollection(vec![line.clone(), point]));
+    assert!(rejects_non_finite(&collection, &line));
+}
+
+#[test]
+fn geometry_non_finite_2() {
+    // non-finite segments
+    let line = geo::Geometry::Line(geo::Line::from([(0., 0.), (1., 1.)]));
+    let bad_line = geo::Geometry::Line(geo::Line::from([(0., 0.), (f64::INFINITY, 1.)]));
+    assert!(rejects_non_finite(&bad_line, &line));
+    let polygon = geo::Geometry::Polygon(geo::Polygon::new(
+        vec![(0., 0.), (10., 0.), (10., f64::NAN), (0., 0.)].into(),
+        vec![],
+    ));
+    assert!(rejects_non_finite(&polygon, &line));
+}
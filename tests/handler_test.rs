@@ -0,0 +1,142 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::handler::{SweepContext, SweepEventHandler};
+use intersect2d::IntersectError;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Start(usize, Vec<usize>),
+    End(usize, Vec<usize>),
+    Intersection(geo::Coordinate<f64>, Vec<usize>, Vec<usize>),
+}
+
+/// Records every event together with the active lines in left to right order
+#[derive(Default)]
+struct Recorder(Vec<Event>);
+
+impl SweepEventHandler<f64> for Recorder {
+    fn on_segment_start(&mut self, line: usize, context: &SweepContext<'_, f64>) {
+        assert!(context.is_active(line));
+        self.0.push(Event::Start(line, context.active_lines()));
+    }
+
+    fn on_segment_end(&mut self, line: usize, context: &SweepContext<'_, f64>) {
+        assert!(context.is_active(line));
+        let l = context.line(line).unwrap();
+        assert_eq!(l.end, context.position());
+        self.0.push(Event::End(line, context.active_lines()));
+    }
+
+    fn on_intersection(
+        &mut self,
+        position: geo::Coordinate<f64>,
+        lines: &[usize],
+        context: &SweepContext<'_, f64>,
+    ) {
+        self.0.push(Event::Intersection(
+            position,
+            lines.to_vec(),
+            context.active_lines(),
+        ));
+    }
+}
+
+#[test]
+fn handler_1() -> Result<(), IntersectError> {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [10., 0., 0., 10.]]);
+    let mut recorder = Recorder::default();
+    let rv: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute_with_handler(&mut recorder)?
+        .collect();
+    assert_eq!(rv, [(geo::Coordinate { x: 5., y: 5. }, vec![0, 1])]);
+    // the lines swap places in the status at the intersection
+    assert_eq!(
+        recorder.0,
+        [
+            Event::Start(0, vec![0]),
+            Event::Start(1, vec![0, 1]),
+            Event::Intersection(geo::Coordinate { x: 5., y: 5. }, vec![0, 1], vec![1, 0]),
+            Event::End(1, vec![1, 0]),
+            Event::End(0, vec![0]),
+        ]
+    );
+    Ok(())
+}
+
+/// Checks the neighbours of each starting line and counts the events
+#[derive(Default)]
+struct Counter {
+    starts: usize,
+    ends: usize,
+    intersections: Vec<(geo::Coordinate<f64>, Vec<usize>)>,
+}
+
+impl SweepEventHandler<f64> for Counter {
+    fn on_segment_start(&mut self, line: usize, context: &SweepContext<'_, f64>) {
+        self.starts += 1;
+        let active = context.active_lines();
+        let i = active.iter().position(|l| *l == line).unwrap();
+        let left = if i > 0 { Some(active[i - 1]) } else { None };
+        assert_eq!(context.left_neighbour(line), left);
+        assert_eq!(context.right_neighbour(line), active.get(i + 1).copied());
+    }
+
+    fn on_segment_end(&mut self, _line: usize, _context: &SweepContext<'_, f64>) {
+        self.ends += 1;
+    }
+
+    fn on_intersection(
+        &mut self,
+        position: geo::Coordinate<f64>,
+        lines: &[usize],
+        _context: &SweepContext<'_, f64>,
+    ) {
+        self.intersections.push((position, lines.to_vec()));
+    }
+}
+
+#[test]
+fn handler_2() -> Result<(), IntersectError> {
+    let lines: Vec<geo::Line<f64>> = (0..30)
+        .map(|i| {
+            let x = i as f64;
+            geo::Line::new((x, (i % 4) as f64), (30.0 - x * 0.5, 20.0 + (i % 7) as f64))
+        })
+        .collect();
+    for ignore_end_points in [true, false] {
+        let mut counter = Counter::default();
+        let expected: Vec<_> = AlgorithmData::<f64>::default()
+            .with_ignore_end_point_intersections(ignore_end_points)?
+            .with_ref_lines(lines.iter())?
+            .compute()?
+            .collect();
+        let rv: Vec<_> = AlgorithmData::<f64>::default()
+            .with_ignore_end_point_intersections(ignore_end_points)?
+            .with_ref_lines(lines.iter())?
+            .compute_with_handler(&mut counter)?
+            .collect();
+        assert_eq!(rv, expected);
+        assert_eq!(counter.starts, lines.len());
+        assert_eq!(counter.ends, lines.len());
+        assert_eq!(counter.intersections, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn handler_upcast_1() -> Result<(), IntersectError> {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [10., 0., 0., 10.]]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_f64_upcast(true)?.with_ref_lines(lines.iter())?;
+    let mut recorder = Recorder::default();
+    assert!(matches!(
+        ad.compute_with_handler(&mut recorder),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("f64 upcast")
+    ));
+    // the handler is not called before the error
+    assert!(recorder.0.is_empty());
+    let _ = ad.with_f64_upcast(false)?;
+    assert_eq!(ad.compute_with_handler(&mut recorder)?.count(), 1);
+    assert_eq!(recorder.0.len(), 5);
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::intern::{intern_results, CoordinateInterner};
+use intersect2d::IntersectError;
+
+#[test]
+fn intern_1() {
+    let mut interner = CoordinateInterner::<f64>::new(0.5).unwrap();
+    // points on both sides of a cell border are still merged
+    let a = interner.intern(geo::Coordinate { x: 0.49, y: 0.0 });
+    let b = interner.intern(geo::Coordinate { x: 0.51, y: 0.0 });
+    assert_eq!(a, b);
+    // the distance is Euclidean, not per axis
+    let c = interner.intern(geo::Coordinate { x: 0.85, y: 0.36 });
+    assert_ne!(a, c);
+    // the closest canonical point wins
+    let d = interner.intern(geo::Coordinate { x: 0.8, y: 0.3 });
+    assert_eq!(d, c);
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.find(geo::Coordinate { x: 10.0, y: 10.0 }), None);
+}
+
+#[test]
+fn intern_2() {
+    // coordinates too large for the cell index are still interned correctly
+    let mut interner = CoordinateInterner::<f64>::new(1e-12).unwrap();
+    let a = interner.intern(geo::Coordinate {
+        x: 1e300,
+        y: -1e300,
+    });
+    let b = interner.intern(geo::Coordinate {
+        x: 1e300,
+        y: -1e300,
+    });
+    let c = interner.intern(geo::Coordinate {
+        x: 2e300,
+        y: -1e300,
+    });
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn intern_radius_1() {
+    let results = vec![(geo::Coordinate { x: 1., y: 1. }, vec![0_usize, 1])];
+    for radius in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            CoordinateInterner::<f64>::new(radius),
+            Err(IntersectError::InvalidParameter(m)) if m.contains("snap radius")
+        ));
+        assert!(matches!(
+            intern_results(results.clone().into_iter(), radius),
+            Err(IntersectError::InvalidParameter(m)) if m.contains("snap radius")
+        ));
+    }
+    // any positive radius is accepted
+    let interner = CoordinateInterner::<f64>::new(f64::MIN_POSITIVE).unwrap();
+    assert_eq!(interner.radius(), f64::MIN_POSITIVE);
+    assert!(interner.is_empty());
+}
+
+#[test]
+fn intern_results_1() {
+    let results = vec![
+        (geo::Coordinate { x: 1.0, y: 1.0 }, vec![0, 1]),
+        (geo::Coordinate { x: 5.0, y: 1.0 }, vec![3, 4]),
+        (
+            geo::Coordinate {
+                x: 1.0000001,
+                y: 1.0,
+            },
+            vec![1, 0],
+        ),
+        (
+            geo::Coordinate {
+                x: 5.0,
+                y: 1.0000001,
+            },
+            vec![4, 5],
+        ),
+    ];
+    let merged = intern_results(results.into_iter(), 1e-6).unwrap();
+    assert_eq!(
+        merged,
+        vec![
+            (geo::Coordinate { x: 1.0, y: 1.0 }, vec![0, 1]),
+            (geo::Coordinate { x: 5.0, y: 1.0 }, vec![3, 4, 5]),
+        ]
+    );
+}
+
+#[test]
+fn snap_radius_1() {
+    // five lines almost passing through (5,5), and one line far away
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 5., 10., 5.],
+        [5., 0., 5., 10.],
+        [0.0000003, 0., 10.0000003, 10.],
+        [0., 10.0000002, 10., 0.0000002],
+        [0., 2.5000001, 10., 7.5000001],
+        [20., 0., 20., 10.],
+    ]);
+    let plain: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert!(plain.len() > 1);
+
+    let snapped: Vec<_> = AlgorithmData::<f64>::default()
+        .with_snap_radius(1e-5)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(snapped.len(), 1);
+    assert_eq!(snapped[0].1, vec![0, 1, 2, 3, 4]);
+    assert!((snapped[0].0.x - 5.0).abs() < 1e-5);
+    assert!((snapped[0].0.y - 5.0).abs() < 1e-5);
+}
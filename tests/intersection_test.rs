@@ -251,3 +251,123 @@ fn intersection_10() {
         }
     }
 }
+
+#[test]
+fn intersection_11() {
+    // The bounding boxes overlap, but the supporting lines cross outside of `other`
+    let one = geo::Line::<f64>::from([(0., 0.), (10., 10.)]);
+    let other = geo::Line::<f64>::from([(10., 0.), (6., 3.)]);
+    assert!(!one.intersects(&other));
+    assert!(intersect(&one, &other).is_none());
+    assert!(intersect(&other, &one).is_none());
+
+    // a crossing inside both segments is still reported
+    let other = geo::Line::<f64>::from([(10., 0.), (2., 6.)]);
+    match intersect(&one, &other) {
+        Some(Intersection::Intersection(p)) => {
+            almost_equal(p.x, 30. / 7., p.y, 30. / 7.);
+        }
+        rv => panic!("expected an intersection, got {:?}", rv),
+    }
+}
+
+#[test]
+fn intersect_with_1() {
+    use intersect2d::intersect_with;
+    // nearly parallel lines, the decision follows the tolerance and not the approx defaults
+    let line1: geo::Line<f64> = [(0., 0.), (1000., 1.)].into();
+    let line2: geo::Line<f64> = [(0., 0.0001), (1000., 1.0002)].into();
+    assert!(intersect(&line1, &line2).is_none());
+    assert!(intersect_with(&line1, &line2, 1e-9, 4).is_none());
+    match intersect_with(&line1, &line2, 1e-3, 4) {
+        Some(Intersection::OverLap(overlap)) => {
+            assert!(approx::abs_diff_eq!(overlap.start.x, 0.0, epsilon = 1e-3));
+            assert!(approx::abs_diff_eq!(overlap.end.x, 1000.0, epsilon = 1e-3));
+        }
+        _ => panic!("expected an overlap"),
+    }
+    // a crossing is still a crossing
+    let line3: geo::Line<f64> = [(500., -10.), (500., 10.)].into();
+    let p = intersect_with(&line1, &line3, 1e-3, 4).unwrap().single();
+    assert!(approx::ulps_eq!(p.x, 500.0));
+    assert!(approx::ulps_eq!(p.y, 0.5));
+    // the same tolerance as the default
+    let line4: geo::Line<f64> = [(0., 1.), (1., 0.)].into();
+    assert_eq!(
+        intersect(&line1, &line4).map(|i| i.single()),
+        intersect_with(&line1, &line4, f64::EPSILON, 4).map(|i| i.single())
+    );
+}
+
+#[test]
+fn line_point_relation_1() {
+    use intersect2d::{line_point_relation, line_point_relation_with_tolerance, Tolerance};
+    let line: geo::Line<f64> = [(1., 1.), (5., 4.)].into();
+    let relation = line_point_relation(&line, &geo::Coordinate { x: 3., y: 2.5 });
+    assert!(approx::ulps_eq!(relation.t, 0.5));
+    assert!(approx::ulps_eq!(relation.offset, 0.0));
+    assert!(relation.on_line);
+
+    // outside the segment, to the left
+    let relation = line_point_relation(&line, &geo::Coordinate { x: 6., y: 8. });
+    assert!(approx::ulps_eq!(relation.t, 1.64));
+    assert!(approx::ulps_eq!(relation.offset, 2.6));
+    assert!(!relation.on_line);
+
+    // to the right, but within the tolerance
+    let point = geo::Coordinate {
+        x: 3.0006,
+        y: 2.4992,
+    };
+    let relation = line_point_relation(&line, &point);
+    assert!(approx::abs_diff_eq!(
+        relation.offset,
+        -0.001,
+        epsilon = 1e-12
+    ));
+    assert!(!relation.on_line);
+    let relation = line_point_relation_with_tolerance(&line, &point, &Tolerance::new(0.01, 4));
+    assert!(relation.on_line);
+
+    // zero length segment
+    let point_line: geo::Line<f64> = [(1., 1.), (1., 1.)].into();
+    let relation = line_point_relation(&point_line, &geo::Coordinate { x: 4., y: 5. });
+    assert_eq!(relation.t, 0.0);
+    assert_eq!(relation.offset, 5.0);
+}
+
+#[test]
+fn intersect_with_2() {
+    use intersect2d::{intersect_with, intersect_with_tolerance, EqualityMode, Tolerance};
+    // short, nearly parallel, lines sharing the start point. Without thresholds the cross
+    // product is tested and finds them collinear, whatever the tolerance, as a distance they
+    // aren't parallel.
+    let line1: geo::Line<f64> = [(0., 0.), (0.01, 0.)].into();
+    let line2: geo::Line<f64> = [(0., 0.), (0.01, 1e-14)].into();
+    assert!(matches!(
+        intersect(&line1, &line2),
+        Some(Intersection::OverLap(_))
+    ));
+    assert!(matches!(
+        intersect_with_tolerance(&line1, &line2, &Tolerance::new(1e-16, 4)),
+        Some(Intersection::OverLap(_))
+    ));
+    // a relative tolerance compares the cross product to zero like the other modes
+    let relative = Tolerance::new(1e-3, 0).with_equality_mode(EqualityMode::Relative);
+    assert!(matches!(
+        intersect_with_tolerance(&line1, &line2, &relative),
+        Some(Intersection::OverLap(_))
+    ));
+    // intersect_with() uses the epsilon as the thresholds
+    assert!(matches!(
+        intersect_with(&line1, &line2, 1e-16, 4),
+        Some(Intersection::Intersection(_))
+    ));
+    let parallel = Tolerance::new(1e-16, 4)
+        .with_parallel_threshold(1e-16)
+        .unwrap();
+    assert!(matches!(
+        intersect_with_tolerance(&line1, &line2, &parallel),
+        Some(Intersection::Intersection(_))
+    ));
+}
@@ -0,0 +1,58 @@
+use intersect2d::layer::{LayerCrossing, LayerIntersection, LayerIntersections};
+use intersect2d::{IntersectError, SelfIntersectingExclusive};
+
+#[test]
+fn layer_self_intersections_1() -> Result<(), IntersectError> {
+    // the empty and the single vertex line strings have no segments but keep their index
+    let layer = vec![
+        geo::LineString::<f64>::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.)]),
+        geo::LineString::from(Vec::<(f64, f64)>::new()),
+        geo::LineString::from(vec![(3., 3.)]),
+        geo::LineString::from(vec![(-5., 5.), (5., 5.), (15., 5.)]),
+    ];
+    assert_eq!(layer.segment_location(2), Some((0, 2)));
+    assert_eq!(layer.segment_location(3), Some((3, 0)));
+    assert_eq!(layer.segment_location(4), Some((3, 1)));
+    assert_eq!(layer.segment_location(5), None);
+
+    assert!(layer.is_self_intersecting()?);
+    assert_eq!(
+        layer.layer_self_intersections()?,
+        vec![LayerIntersection {
+            position: geo::Coordinate { x: 10., y: 5. },
+            segments: vec![(0, 1), (3, 1)],
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn layer_intersections_with_1() -> Result<(), IntersectError> {
+    let a = vec![
+        geo::LineString::<f64>::from(vec![(0., 0.), (10., 0.)]),
+        geo::LineString::from(vec![(0., 5.), (10., 5.)]),
+    ];
+    let b = vec![
+        geo::LineString::from(vec![(20., 20.), (30., 20.)]),
+        geo::LineString::from(vec![(5., -5.), (5., 0.), (5., 10.)]),
+    ];
+    let rv = a.layer_intersections_with(&b)?;
+    assert_eq!(
+        rv,
+        vec![
+            LayerCrossing {
+                position: geo::Coordinate { x: 5., y: 0. },
+                a: vec![(0, 0)],
+                b: vec![(1, 0), (1, 1)],
+            },
+            LayerCrossing {
+                position: geo::Coordinate { x: 5., y: 5. },
+                a: vec![(1, 0)],
+                b: vec![(1, 1)],
+            },
+        ]
+    );
+    // the layers don't intersect themselves, so the inner intersections are not reported
+    assert!(a.layer_self_intersections()?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::list::IntersectionList;
+use intersect2d::{IntersectError, Tolerance};
+
+#[test]
+fn list_1() -> Result<(), IntersectError> {
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [0., 10., 10., 0.],
+        [0., 1., 10., 1.],
+        [0., 9., 10., 9.],
+    ]);
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    let list = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute_collected()?;
+    assert_eq!(list.len(), 5);
+    assert!(!list.is_empty());
+    assert_eq!(list.get(0), expected.first());
+    assert!(list.get(5).is_none());
+    assert_eq!(&list[1..3], &expected[1..3]);
+    assert_eq!(
+        list.points().collect::<Vec<_>>(),
+        expected.iter().map(|(p, _)| *p).collect::<Vec<_>>()
+    );
+    assert_eq!(list.iter().count(), 5);
+    assert_eq!(list.clone().into_inner(), expected);
+    assert_eq!(IntersectionList::from(expected.clone()), list);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), expected);
+    assert!(IntersectionList::<f64>::default().is_empty());
+    Ok(())
+}
+
+#[test]
+fn intersections_iter_1() -> Result<(), IntersectError> {
+    use intersect2d::SelfIntersectingInclusive;
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [0., 10., 10., 0.],
+        [0., 1., 10., 1.],
+        [0., 9., 10., 9.],
+    ]);
+    let results = lines.self_intersections_inclusive()?;
+    let forward: Vec<_> = results.clone().collect();
+    let mut backward: Vec<_> = results.clone().rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(results.len(), 5);
+    assert_eq!(results.as_slice(), &forward[..]);
+    let mut results = results;
+    let first = results.next();
+    let last = results.next_back();
+    assert_eq!(results.len(), 3);
+    assert_eq!(first.as_ref(), forward.first());
+    assert_eq!(last.as_ref(), forward.last());
+    Ok(())
+}
+
+#[test]
+fn list_dot_1() {
+    let list: IntersectionList<f64> = vec![
+        (geo::Coordinate { x: 1., y: 2.5 }, vec![3, 1]),
+        (geo::Coordinate { x: 4., y: 0. }, vec![1, 3]),
+        (geo::Coordinate { x: -2., y: 7. }, vec![5, 0, 1]),
+    ]
+    .into();
+    let expected = [
+        "graph intersections {",
+        "    0;",
+        "    1;",
+        "    3;",
+        "    5;",
+        "    1 -- 3 [label=\"(1, 2.5)\"];",
+        "    1 -- 3 [label=\"(4, 0)\"];",
+        "    0 -- 1 [label=\"(-2, 7)\"];",
+        "    0 -- 5 [label=\"(-2, 7)\"];",
+        "    1 -- 5 [label=\"(-2, 7)\"];",
+        "}",
+    ];
+    assert_eq!(list.to_dot().lines().collect::<Vec<_>>(), expected);
+    assert_eq!(
+        IntersectionList::<f64>::default().to_dot(),
+        "graph intersections {\n}\n"
+    );
+}
+
+#[test]
+fn list_unique_points_1() -> Result<(), IntersectError> {
+    let c = |x: f64, y: f64| geo::Coordinate { x, y };
+    // the near duplicates of (0, 1) are not next to each other in sweep-line order
+    let results: IntersectionList<f64> = vec![
+        (c(0., 1.), vec![0, 1]),
+        (c(10., 1. + 1e-9), vec![2, 3]),
+        (c(1e-9, 1. + 2e-9), vec![4, 5]),
+        (c(0., 1. + 2e-9), vec![6, 7]),
+    ]
+    .into();
+    let tolerance = Tolerance::new(1e-6, 4);
+    assert_eq!(
+        results.unique_points(&tolerance),
+        vec![c(0., 1.), c(10., 1. + 1e-9)]
+    );
+    assert_eq!(results.unique_points(&Tolerance::default()).len(), 4);
+    assert!(IntersectionList::<f64>::default()
+        .unique_points(&tolerance)
+        .is_empty());
+
+    // three lines almost meeting at one point
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [0., 10., 10., 0.],
+        [0., 5.000001, 10., 5.000001],
+    ]);
+    let results = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute()?;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results.unique_points(&Tolerance::default()).len(), 3);
+    let rounded = Tolerance::default()
+        .with_precision_model(intersect2d::precision::PrecisionModel::ScaledInteger(1000.))?;
+    assert_eq!(
+        results.unique_points(&rounded),
+        vec![results.as_slice()[0].0]
+    );
+    Ok(())
+}
+
+#[test]
+fn list_by_segment_1() -> Result<(), IntersectError> {
+    let mut lines = intersect2d::generators::lattice::<f64>(8, 5);
+    lines.extend(intersect2d::to_lines::<_, f64>(&[
+        [-1., 0.5, 9., 7.5],
+        [8.5, -1., 0.2, 8.1],
+    ]));
+    let results = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute_collected()?;
+    let by_segment = results.by_segment(&lines);
+    assert_eq!(by_segment.len(), lines.len());
+    let mut count = 0;
+    for (l, points) in by_segment.iter().enumerate() {
+        for pair in points.windows(2) {
+            assert!(pair[0].t <= pair[1].t);
+        }
+        for point in points.iter() {
+            assert!((0. ..=1.).contains(&point.t));
+            let (position, involved) = &results[point.result];
+            assert_eq!(*position, point.position);
+            assert!(involved.contains(&l));
+            let line = &lines[l];
+            let expected = line.start + (line.end - line.start) * point.t;
+            approx::assert_ulps_eq!(expected.x, position.x, epsilon = 1e-12);
+            approx::assert_ulps_eq!(expected.y, position.y, epsilon = 1e-12);
+        }
+        count += points.len();
+    }
+    assert_eq!(
+        count,
+        results
+            .iter()
+            .map(|(_, involved)| involved.len())
+            .sum::<usize>()
+    );
+    // the iterator variant refers to the remaining results
+    let mut remaining = intersect2d::list::Intersections::from(results.clone().into_inner());
+    let _ = remaining.next();
+    let by_segment = remaining.by_segment(&lines[..1]);
+    assert_eq!(by_segment.len(), 1);
+    for point in by_segment[0].iter() {
+        assert_eq!(remaining.as_slice()[point.result].0, point.position);
+    }
+    Ok(())
+}
+
+#[test]
+fn list_end_point_intersections_1() -> Result<(), IntersectError> {
+    let mut lines = intersect2d::generators::lattice::<f64>(6, 2);
+    lines.extend(intersect2d::to_lines::<_, f64>(&[[-1., 0.5, 7., 5.5]]));
+    let tolerance = Tolerance::default();
+    let inclusive = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute_collected()?;
+    let exclusive = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute_collected()?;
+    // the interior view of an inclusive search is what an exclusive search reports
+    let interior: Vec<_> = inclusive
+        .interior_intersections(&lines, &tolerance)
+        .cloned()
+        .collect();
+    assert_eq!(interior, exclusive.into_inner());
+    let end_points = inclusive
+        .end_point_intersections(&lines, &tolerance)
+        .count();
+    assert!(end_points > 0);
+    assert_eq!(end_points + interior.len(), inclusive.len());
+
+    let remaining = intersect2d::list::Intersections::from(inclusive.clone().into_inner());
+    assert_eq!(
+        remaining
+            .end_point_intersections(&lines, &tolerance)
+            .count(),
+        end_points
+    );
+    assert_eq!(
+        remaining
+            .interior_intersections(&lines, &tolerance)
+            .cloned()
+            .collect::<Vec<_>>(),
+        interior
+    );
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use intersect2d::generators::lattice;
+use intersect2d::location::{Location, PointLocator};
+use intersect2d::IntersectError;
+
+/// The brute force answer for a point not on any segment
+fn brute_force(lines: &[geo::Line<f64>], p: &geo::Coordinate<f64>) -> Location {
+    let mut below: Option<(f64, usize)> = None;
+    let mut above: Option<(f64, usize)> = None;
+    for (i, l) in lines.iter().enumerate() {
+        let (min_x, max_x) = (l.start.x.min(l.end.x), l.start.x.max(l.end.x));
+        if l.start.x == l.end.x || p.x < min_x || p.x > max_x {
+            continue;
+        }
+        let y = l.start.y + (p.x - l.start.x) * (l.end.y - l.start.y) / (l.end.x - l.start.x);
+        if y < p.y && below.map_or(true, |b| y > b.0) {
+            below = Some((y, i));
+        }
+        if y > p.y && above.map_or(true, |a| y < a.0) {
+            above = Some((y, i));
+        }
+    }
+    Location {
+        on: None,
+        below: below.map(|b| b.1),
+        above: above.map(|a| a.1),
+    }
+}
+
+#[test]
+fn location_1() {
+    let mut lines = lattice::<f64>(6, 3);
+    // diagonals of every second cell
+    for x in 0..6 {
+        for y in 0..6 {
+            if (x + y) % 2 == 0 {
+                lines.push(geo::Line::new(
+                    geo::Coordinate {
+                        x: x as f64,
+                        y: y as f64,
+                    },
+                    geo::Coordinate {
+                        x: x as f64 + 1.0,
+                        y: y as f64 + 1.0,
+                    },
+                ));
+            }
+        }
+    }
+    let locator = PointLocator::new(&lines).unwrap();
+    assert_eq!(locator.len(), lines.len());
+    let mut seed = 7_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 7001) as f64 / 1000.0 - 0.3 + 1e-7
+    };
+    for _ in 0..1000 {
+        let p = geo::Coordinate {
+            x: next(),
+            y: next(),
+        };
+        assert_eq!(locator.locate(&p), brute_force(&lines, &p), "{:?}", p);
+    }
+}
+
+#[test]
+fn location_2() {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 0., 10.], [0., 10., 10., 10.]]);
+    let locator = PointLocator::new(&lines).unwrap();
+    let location = locator.locate(&geo::Coordinate { x: 0., y: 5. });
+    assert_eq!(location.on, Some(0));
+    let location = locator.locate(&geo::Coordinate { x: 5., y: 10. });
+    assert_eq!(location.on, Some(1));
+    assert_eq!(
+        locator.locate(&geo::Coordinate { x: 11., y: 5. }),
+        Location::default()
+    );
+}
+
+#[test]
+fn location_not_noded_1() {
+    let crossing = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    let t_junction = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., 0., 5., 10.]]);
+    let overlap = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., 0., 15., 0.]]);
+    for lines in [crossing, t_junction, overlap] {
+        assert!(matches!(
+            PointLocator::new(&lines),
+            Err(IntersectError::InvalidData(m)) if m.contains("noded")
+        ));
+    }
+    // touching at the end points only is fine
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [10., 0., 5., 10.]]);
+    assert_eq!(PointLocator::new(&lines).unwrap().len(), 2);
+}
+
+#[test]
+fn location_non_finite_1() {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [0., 5., f64::NAN, 5.]]);
+    assert!(matches!(
+        PointLocator::new(&lines),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+}
+
+#[test]
+fn location_empty_1() {
+    let locator = PointLocator::<f64>::new(&[]).unwrap();
+    assert!(locator.is_empty());
+    assert_eq!(
+        locator.locate(&geo::Coordinate { x: 1., y: 1. }),
+        Location {
+            on: None,
+            below: None,
+            above: None
+        }
+    );
+}
+
+#[test]
+fn crossings_1() {
+    // a comb shaped polygon, the vertical rays through its vertices must keep the parity
+    let ring: Vec<(f64, f64)> = vec![
+        (0., 0.),
+        (6., 0.),
+        (6., 3.),
+        (5., 1.),
+        (4., 3.),
+        (3., 1.),
+        (2., 3.),
+        (1., 1.),
+        (0., 3.),
+    ];
+    let lines: Vec<geo::Line<f64>> = (0..ring.len())
+        .map(|i| geo::Line::from([ring[i], ring[(i + 1) % ring.len()]]))
+        .collect();
+    let locator = PointLocator::new(&lines).unwrap();
+    let polygon = geo::Polygon::new(geo::LineString::from(ring), vec![]);
+    for x in 0..=14 {
+        for y in 0..=8 {
+            let p = geo::Coordinate {
+                x: x as f64 * 0.5 - 0.5,
+                y: y as f64 * 0.5 - 0.25,
+            };
+            // the half open rule of the locator, counted by brute force
+            let crossing: Vec<(f64, usize)> = lines
+                .iter()
+                .enumerate()
+                .filter_map(|(i, l)| {
+                    let (left, right) = if l.start.x <= l.end.x {
+                        (l.start, l.end)
+                    } else {
+                        (l.end, l.start)
+                    };
+                    if left.x > p.x || right.x <= p.x {
+                        return None;
+                    }
+                    let y = left.y + (p.x - left.x) * (right.y - left.y) / (right.x - left.x);
+                    Some((y, i))
+                })
+                .collect();
+            let below = crossing.iter().filter(|c| c.0 < p.y).count();
+            let above = crossing.iter().filter(|c| c.0 > p.y).count();
+            assert_eq!(locator.crossings_below(&p), below, "{:?}", p);
+            assert_eq!(locator.crossings_above(&p), above, "{:?}", p);
+            assert_eq!(locator.segments_below(&p).len(), below, "{:?}", p);
+            assert_eq!(locator.segments_above(&p).len(), above, "{:?}", p);
+            if locator.locate(&p).on.is_none() {
+                let inside = geo::algorithm::contains::Contains::contains(&polygon, &p);
+                assert_eq!(below % 2 == 1, inside, "{:?}", p);
+            }
+        }
+    }
+    assert_eq!(
+        locator.crossings_below(&geo::Coordinate { x: 7., y: 9. }),
+        0
+    );
+    assert!(locator
+        .segments_above(&geo::Coordinate { x: 6., y: -1. })
+        .is_empty());
+}
@@ -0,0 +1,81 @@
+use intersect2d::loops::ring_loops;
+use intersect2d::IntersectError;
+
+fn signed_area(points: &[(f64, f64)]) -> f64 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        / 2.
+}
+
+#[test]
+fn loops_1() -> Result<(), IntersectError> {
+    // a square with a tiny twist at the upper right corner, the right edge overshoots the
+    // corner and the top edge starts right of it
+    let points: Vec<(f64, f64)> = vec![
+        (0., 0.),
+        (10., 0.),
+        (10., 10.002),
+        (10.002, 10.),
+        (0., 10.),
+        (0., 0.),
+    ];
+    let loops = ring_loops(&geo::LineString::from(points.clone()))?;
+    assert_eq!(loops.len(), 2);
+    let (pinholes, significant): (Vec<_>, Vec<_>) =
+        loops.iter().partition(|l| l.signed_area.abs() < 1e-3);
+    assert_eq!(pinholes.len(), 1);
+    assert!(pinholes[0].signed_area < 0.);
+    assert_eq!(significant.len(), 1);
+    approx::assert_ulps_eq!(significant[0].signed_area, 100., epsilon = 1e-3);
+    let total: f64 = loops.iter().map(|l| l.signed_area).sum();
+    approx::assert_ulps_eq!(total, signed_area(&points[..5]), epsilon = 1e-9);
+    for a_loop in loops.iter() {
+        assert_eq!(a_loop.ring.0.first(), a_loop.ring.0.last());
+    }
+    Ok(())
+}
+
+#[test]
+fn loops_2() -> Result<(), IntersectError> {
+    // two triangles sharing a vertex, the ring touches itself without crossing
+    let points = vec![
+        (0., 0.),
+        (5., 5.),
+        (10., 0.),
+        (10., 10.),
+        (5., 5.),
+        (0., 10.),
+    ];
+    let loops = ring_loops(&geo::LineString::from(points))?;
+    assert_eq!(loops.len(), 2);
+    // both triangles are counter clockwise, the right one is closed first
+    assert_eq!(loops[0].signed_area, 25.);
+    assert_eq!(loops[0].ring.0[1], geo::Coordinate { x: 10., y: 0. });
+    assert_eq!(loops[1].signed_area, 25.);
+
+    // a spike doesn't enclose any area and is dropped
+    let spike = vec![
+        (0., 0.),
+        (10., 0.),
+        (10., 10.),
+        (10., 15.),
+        (10., 10.),
+        (0., 10.),
+    ];
+    let loops = ring_loops(&geo::LineString::from(spike))?;
+    assert_eq!(loops.len(), 1);
+    assert_eq!(loops[0].signed_area, 100.);
+
+    // a simple ring is a single loop, with or without the closing point
+    let square = vec![(0., 0.), (0., 1.), (1., 1.), (1., 0.)];
+    let loops = ring_loops(&geo::LineString::from(square))?;
+    assert_eq!(loops.len(), 1);
+    assert_eq!(loops[0].signed_area, -1.);
+    assert_eq!(loops[0].ring.0.len(), 5);
+    Ok(())
+}
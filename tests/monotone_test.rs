@@ -0,0 +1,57 @@
+use intersect2d::monotone::{is_monotone, monotone_pieces, MonotoneAxis};
+
+#[test]
+fn monotone_1() {
+    // vertical segments and repeated vertices don't split x-monotone pieces
+    let path = geo::LineString::from(vec![
+        (0., 0.),
+        (0., 5.),
+        (2., 5.),
+        (2., 5.),
+        (2., 0.),
+        (1., -1.),
+        (1., -3.),
+        (4., -3.),
+    ]);
+    let pieces = monotone_pieces(&path, MonotoneAxis::X);
+    assert_eq!(pieces.len(), 3);
+    assert_eq!((pieces[0].first_vertex, pieces[0].last_vertex), (0, 4));
+    assert!(pieces[0].increasing);
+    assert_eq!((pieces[1].first_vertex, pieces[1].last_vertex), (4, 6));
+    assert!(!pieces[1].increasing);
+    assert_eq!((pieces[2].first_vertex, pieces[2].last_vertex), (6, 7));
+    assert!(pieces[2].increasing);
+
+    // the pieces cover the line string, sharing the turning vertices
+    let mut rebuilt = pieces[0].line_string.0.clone();
+    for piece in pieces.iter().skip(1) {
+        assert_eq!(piece.line_string.0[0], *rebuilt.last().unwrap());
+        rebuilt.extend(piece.line_string.0.iter().skip(1));
+    }
+    assert_eq!(rebuilt, path.0);
+    for piece in pieces.iter() {
+        assert!(is_monotone(&piece.line_string, MonotoneAxis::X));
+    }
+    assert!(!is_monotone(&path, MonotoneAxis::X));
+}
+
+#[test]
+fn monotone_2() {
+    // a closed ring is split at its first vertex
+    let square = geo::LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]);
+    let pieces = monotone_pieces(&square, MonotoneAxis::Y);
+    assert_eq!(pieces.len(), 2);
+    assert_eq!((pieces[0].first_vertex, pieces[0].last_vertex), (0, 3));
+    assert_eq!((pieces[1].first_vertex, pieces[1].last_vertex), (3, 4));
+
+    // a line string that never changes the coordinate is one increasing piece
+    let horizontal = geo::LineString::from(vec![(0., 0.), (1., 0.), (-1., 0.)]);
+    let pieces = monotone_pieces(&horizontal, MonotoneAxis::Y);
+    assert_eq!(pieces.len(), 1);
+    assert!(pieces[0].increasing);
+    assert_eq!(pieces[0].line_string, horizontal);
+
+    let single = geo::LineString::from(vec![(0., 0.)]);
+    assert!(monotone_pieces(&single, MonotoneAxis::X).is_empty());
+    assert!(is_monotone(&single, MonotoneAxis::X));
+}
@@ -0,0 +1,45 @@
+use intersect2d::motion::time_of_contact;
+
+fn c(x: f64, y: f64) -> geo::Coordinate<f64> {
+    geo::Coordinate { x, y }
+}
+
+#[test]
+fn motion_1() {
+    let one: geo::Line<f64> = [(0., 0.), (2., 0.)].into();
+    let other: geo::Line<f64> = [(5., -1.), (5., 1.)].into();
+    // both moving towards each other
+    let (t, point) = time_of_contact(&one, c(4., 0.), &other, c(-2., 0.)).unwrap();
+    assert!(approx::ulps_eq!(t, 0.5));
+    assert!(approx::ulps_eq!(point.x, 4.) && approx::ulps_eq!(point.y, 0.));
+    // moving in parallel, never touching
+    assert!(time_of_contact(&one, c(4., 1.), &other, c(4., 1.)).is_none());
+    // not reaching each other within the interval
+    assert!(time_of_contact(&one, c(1., 0.), &other, c(0., 0.)).is_none());
+    // already intersecting
+    let crossing: geo::Line<f64> = [(1., -1.), (1., 1.)].into();
+    let (t, point) = time_of_contact(&one, c(4., 0.), &crossing, c(0., 0.)).unwrap();
+    assert_eq!((t, point), (0., c(1., 0.)));
+}
+
+#[test]
+fn motion_2() {
+    // a segment sliding along the line of another segment
+    let one: geo::Line<f64> = [(0., 0.), (1., 0.)].into();
+    let other: geo::Line<f64> = [(3., 0.), (5., 0.)].into();
+    let (t, point) = time_of_contact(&one, c(4., 0.), &other, c(0., 0.)).unwrap();
+    assert!(approx::ulps_eq!(t, 0.5));
+    assert!(approx::ulps_eq!(point.x, 3.));
+    // a rotated contact: the corner of the moving segment hits the interior of the other
+    let one: geo::Line<f64> = [(0., 0.), (1., 1.)].into();
+    let other: geo::Line<f64> = [(4., -5.), (4., 5.)].into();
+    let (t, point) = time_of_contact(&one, c(6., 2.), &other, c(0., 0.)).unwrap();
+    assert!(approx::ulps_eq!(t, 0.5));
+    assert!(approx::ulps_eq!(point.x, 4.) && approx::ulps_eq!(point.y, 2.));
+    // a brute force check of the contact time
+    let hit_at = |t: f64| {
+        let moved: geo::Line<f64> = [(0. + 6. * t, 0. + 2. * t), (1. + 6. * t, 1. + 2. * t)].into();
+        intersect2d::intersect(&moved, &other).is_some()
+    };
+    assert!(!hit_at(0.49) && hit_at(0.5) && hit_at(0.51));
+}
@@ -0,0 +1,47 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::generators;
+use intersect2d::multiplicity::{multiplicity_report, with_multiplicity};
+
+#[test]
+fn multiplicity_1() {
+    // all the lines pass through one point, nothing else intersects
+    let lines = generators::concurrent::<f64>(12, 7);
+    let results: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    let report = multiplicity_report(results.iter().cloned(), 3);
+    assert_eq!(report.max_multiplicity(), report.points[0].1.len());
+    assert_eq!(report.count_at_least(3), report.points.len());
+    assert_eq!(report.counts.values().sum::<usize>(), results.len());
+    assert_eq!(
+        with_multiplicity(results.into_iter(), 3).collect::<Vec<_>>(),
+        report.points
+    );
+}
+
+#[test]
+fn multiplicity_2() {
+    let report = multiplicity_report(Vec::<(geo::Coordinate<f64>, Vec<usize>)>::new(), 2);
+    assert!(report.counts.is_empty());
+    assert!(report.points.is_empty());
+    assert_eq!(report.max_multiplicity(), 0);
+    assert_eq!(report.count_at_least(0), 0);
+
+    let results = vec![
+        (geo::Coordinate { x: 0., y: 0. }, vec![0, 1]),
+        (geo::Coordinate { x: 1., y: 0. }, vec![1, 2, 3, 4]),
+        (geo::Coordinate { x: 2., y: 0. }, vec![2, 3]),
+        (geo::Coordinate { x: 3., y: 0. }, vec![0, 2, 4]),
+    ];
+    let report = multiplicity_report(results, 4);
+    assert_eq!(report.counts.get(&2), Some(&2));
+    assert_eq!(report.counts.get(&3), Some(&1));
+    assert_eq!(report.counts.get(&4), Some(&1));
+    assert_eq!(report.count_at_least(3), 2);
+    assert_eq!(report.max_multiplicity(), 4);
+    assert_eq!(report.points.len(), 1);
+    assert_eq!(report.points[0].1, vec![1, 2, 3, 4]);
+}
@@ -0,0 +1,90 @@
+use intersect2d::distance::project_point_onto_segment;
+use intersect2d::nearest::SegmentIndex;
+use intersect2d::IntersectError;
+
+fn brute_force(lines: &[geo::Line<f64>], point: &geo::Coordinate<f64>) -> Vec<(usize, f64)> {
+    let mut rv: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            let d = project_point_onto_segment(l, point).0 - *point;
+            (i, d.x.hypot(d.y))
+        })
+        .collect();
+    rv.sort_unstable_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+    rv
+}
+
+#[test]
+fn nearest_1() -> Result<(), IntersectError> {
+    let mut seed = 3_u64;
+    let mut random = || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) % 2000) as f64 / 10.0 - 100.0
+    };
+    let lines: Vec<geo::Line<f64>> = (0..300)
+        .map(|_| {
+            let (x, y) = (random(), random());
+            geo::Line::new((x, y), (x + random() / 10.0, y + random() / 10.0))
+        })
+        .collect();
+    let index = SegmentIndex::new(&lines)?;
+    assert_eq!(index.len(), 300);
+    for (i, point) in [
+        (0., 0.),
+        (13.5, -7.25),
+        (-1000., 250.),
+        (99.9, 99.9),
+        (1e6, -1e6),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let point = geo::Coordinate::from(*point);
+        let expected = brute_force(&lines, &point);
+        let k = 1 + i * 7;
+        assert_eq!(index.k_nearest_segments(&point, k), expected[..k].to_vec());
+        let radius = expected[k].1;
+        let within: Vec<_> = expected
+            .iter()
+            .filter(|(_, d)| *d <= radius)
+            .copied()
+            .collect();
+        assert_eq!(index.segments_within(&point, radius), within);
+    }
+    // asking for more segments than there are returns them all
+    let point = geo::Coordinate { x: 1., y: 2. };
+    assert_eq!(
+        index.k_nearest_segments(&point, 1000),
+        brute_force(&lines, &point)
+    );
+    Ok(())
+}
+
+#[test]
+fn nearest_2() -> Result<(), IntersectError> {
+    let index = SegmentIndex::<f64>::new(&[])?;
+    assert!(index.is_empty());
+    assert!(index
+        .k_nearest_segments(&geo::Coordinate { x: 0., y: 0. }, 3)
+        .is_empty());
+    // degenerate extent, all the segments on one vertical line
+    let lines = intersect2d::to_lines::<_, f64>(&[[1., 0., 1., 1.], [1., 3., 1., 4.]]);
+    let index = SegmentIndex::new(&lines)?;
+    assert_eq!(
+        index.k_nearest_segments(&geo::Coordinate { x: 2., y: 3.5 }, 1),
+        vec![(1, 1.)]
+    );
+    assert!(index
+        .segments_within(&geo::Coordinate { x: 2., y: 3.5 }, 0.5)
+        .is_empty());
+
+    let lines = intersect2d::to_lines::<_, f64>(&[[1., 0., f64::NAN, 1.]]);
+    assert!(matches!(
+        SegmentIndex::new(&lines),
+        Err(IntersectError::InvalidData(_))
+    ));
+    Ok(())
+}
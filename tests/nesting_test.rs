@@ -0,0 +1,124 @@
+use intersect2d::nesting::{assemble_polygons, ring_nesting};
+use intersect2d::IntersectError;
+
+fn circle(cx: f64, cy: f64, r: f64) -> geo::LineString<f64> {
+    let mut points: Vec<(f64, f64)> = (0..64)
+        .map(|i| {
+            let a = i as f64 * std::f64::consts::PI / 32.0;
+            (cx + r * a.cos(), cy + r * a.sin())
+        })
+        .collect();
+    points.push(points[0]);
+    geo::LineString::from(points)
+}
+
+#[test]
+fn nesting_1() -> Result<(), IntersectError> {
+    let rings = vec![
+        circle(0., 0., 1.),
+        circle(0., 0., 100.),
+        circle(0., 0., 50.),
+        circle(30., 0., 10.),
+        circle(-30., 0., 10.),
+        circle(30., 0., 5.),
+        circle(200., 0., 10.),
+    ];
+    let nesting = ring_nesting(&rings)?;
+    assert_eq!(
+        nesting.parent,
+        vec![Some(2), None, Some(1), Some(2), Some(2), Some(3), None]
+    );
+    assert_eq!(nesting.depth, vec![2, 0, 1, 2, 2, 3, 0]);
+    assert_eq!(nesting.roots(), vec![1, 6]);
+    assert_eq!(nesting.children(2), vec![0, 3, 4]);
+    assert!(nesting.is_hole(2));
+    assert!(!nesting.is_hole(3));
+    assert!(nesting.is_hole(5));
+    Ok(())
+}
+
+#[test]
+fn nesting_polygons_1() -> Result<(), IntersectError> {
+    let signed_area = |ring: &geo::LineString<f64>| {
+        ring.0
+            .windows(2)
+            .fold(0., |sum, w| sum + w[0].x * w[1].y - w[1].x * w[0].y)
+    };
+    let reversed = |mut ring: geo::LineString<f64>| {
+        ring.0.reverse();
+        ring
+    };
+    // the same layout as nesting_1, with some of the rings clockwise
+    let rings = vec![
+        reversed(circle(0., 0., 1.)),
+        circle(0., 0., 100.),
+        circle(0., 0., 50.),
+        circle(30., 0., 10.),
+        reversed(circle(-30., 0., 10.)),
+        reversed(circle(30., 0., 5.)),
+        reversed(circle(200., 0., 10.)),
+    ];
+    let polygons = assemble_polygons(&rings)?;
+    // one polygon for each ring at an even depth
+    assert_eq!(polygons.len(), 5);
+    let holes: Vec<_> = polygons.iter().map(|p| p.interiors().len()).collect();
+    assert_eq!(holes, vec![0, 1, 1, 0, 0]);
+    for polygon in polygons.iter() {
+        assert!(signed_area(polygon.exterior()) > 0.);
+        assert!(polygon.interiors().iter().all(|h| signed_area(h) < 0.));
+    }
+    assert_eq!(polygons[1].exterior(), &rings[1]);
+    assert_eq!(polygons[1].interiors()[0], reversed(rings[2].clone()));
+    assert_eq!(polygons[2].interiors()[0], rings[5]);
+    assert_eq!(polygons[4].exterior(), &reversed(rings[6].clone()));
+
+    assert!(assemble_polygons::<f64>(&[])?.is_empty());
+    assert!(matches!(
+        assemble_polygons(&[circle(0., 0., 10.), circle(15., 0., 10.)]),
+        Err(IntersectError::InvalidData(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn nesting_intersecting_1() {
+    let rings = vec![
+        circle(0., 50., 10.),
+        circle(0., 0., 10.),
+        circle(15., 0., 10.),
+    ];
+    assert!(matches!(
+        ring_nesting(&rings),
+        Err(IntersectError::InvalidData(m)) if m == "Rings 1 and 2 intersect"
+    ));
+    assert!(matches!(
+        assemble_polygons(&rings),
+        Err(IntersectError::InvalidData(m)) if m == "Rings 1 and 2 intersect"
+    ));
+    // rings touching at a single vertex also meet
+    let rings = vec![
+        geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+        geo::LineString::from(vec![(10., 10.), (20., 10.), (20., 20.), (10., 10.)]),
+    ];
+    assert!(matches!(
+        ring_nesting(&rings),
+        Err(IntersectError::InvalidData(m)) if m == "Rings 0 and 1 intersect"
+    ));
+}
+
+#[test]
+fn nesting_not_closed_1() {
+    let open = geo::LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+    let too_short = geo::LineString::from(vec![(0., 0.), (1., 0.), (0., 0.)]);
+    for ring in [open, too_short] {
+        let rings = vec![circle(0., 50., 10.), ring];
+        assert!(matches!(
+            ring_nesting(&rings),
+            Err(IntersectError::InvalidData(m)) if m == "Ring 1 is not closed"
+        ));
+        assert!(matches!(
+            assemble_polygons(&rings),
+            Err(IntersectError::InvalidData(m)) if m == "Ring 1 is not closed"
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::noding::{node_lines, NodedSegment};
+use intersect2d::Tolerance;
+
+#[test]
+fn noding_1() {
+    // a star of lines through (5,5), and a line touching the center line at an end point
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 5., 10., 5.],
+        [5., 0., 5., 10.],
+        [0., 0., 10., 10.],
+        [3., 5., 3., 8.],
+    ]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let (rv, pieces) = ad
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute_noded()
+        .unwrap();
+    assert_eq!(rv.len(), 2);
+    let parents: Vec<usize> = pieces.iter().map(|p| p.parent).collect();
+    assert_eq!(parents, vec![0, 0, 0, 1, 1, 2, 2, 3]);
+    let horizontal: Vec<geo::Line<f64>> = pieces[..3].iter().map(|p| p.line).collect();
+    assert_eq!(
+        horizontal,
+        vec![
+            geo::Line::from([(0., 5.), (3., 5.)]),
+            geo::Line::from([(3., 5.), (5., 5.)]),
+            geo::Line::from([(5., 5.), (10., 5.)]),
+        ]
+    );
+    // the touching line is not split
+    assert_eq!(
+        pieces[7],
+        NodedSegment {
+            line: ad.get_line(3).unwrap(),
+            parent: 3
+        }
+    );
+}
+
+#[test]
+fn noding_2() {
+    // the pieces of every line are connected and add up to the line
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 3.],
+        [0., 3., 10., 0.],
+        [1., -1., 2., 9.],
+        [9., -1., 6., 9.],
+        [0., 1., 10., 1.],
+    ]);
+    let results: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    let pieces = node_lines(&lines, &results, &Tolerance::default());
+    let splits: usize = results.iter().map(|r| r.1.len()).sum();
+    assert_eq!(pieces.len(), lines.len() + splits);
+    for (parent, line) in lines.iter().enumerate() {
+        let mine: Vec<_> = pieces.iter().filter(|p| p.parent == parent).collect();
+        assert_eq!(mine.first().unwrap().line.start, line.start);
+        assert_eq!(mine.last().unwrap().line.end, line.end);
+        for pair in mine.windows(2) {
+            assert_eq!(pair[0].line.end, pair[1].line.start);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::oracle::self_intersections_bruteforce;
+use intersect2d::{to_lines, IntersectError};
+
+fn differential(lines: &[geo::Line<f64>], ignore_end_point_intersections: bool) {
+    let sweep: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(ignore_end_point_intersections)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    let oracle: Vec<_> = self_intersections_bruteforce(lines, ignore_end_point_intersections)
+        .unwrap()
+        .collect();
+    assert_eq!(oracle.len(), sweep.len(), "{:?} {:?}", oracle, sweep);
+    for (o, s) in oracle.iter().zip(sweep.iter()) {
+        approx::assert_abs_diff_eq!(o.0.x, s.0.x, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(o.0.y, s.0.y, epsilon = 1e-9);
+        assert_eq!(o.1, s.1);
+    }
+}
+
+#[test]
+fn oracle_1() {
+    // more segments than the size switch of the traits, random coordinates
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 100_000) as f64 / 1000.0
+    };
+    for _ in 0..40 {
+        let coords: Vec<[f64; 4]> = (0..40).map(|_| [next(), next(), next(), next()]).collect();
+        let lines = to_lines::<_, f64>(&coords);
+        differential(&lines, false);
+        differential(&lines, true);
+    }
+}
+
+#[test]
+fn oracle_2() {
+    // a lattice of horizontal and vertical pieces, sharing end points and crossing at them
+    let mut coords = Vec::<[f64; 4]>::new();
+    for i in 0..6 {
+        for j in 0..5 {
+            let (i, j) = (i as f64, j as f64);
+            coords.push([i * 2., j * 2., i * 2., j * 2. + 2.]);
+            coords.push([j * 2., i * 2., j * 2. + 2., i * 2.]);
+        }
+    }
+    // and some long diagonals through the lattice points
+    coords.push([0., 0., 10., 10.]);
+    coords.push([0., 10., 10., 0.]);
+    coords.push([1., 0., 1., 10.]);
+    let lines = to_lines::<_, f64>(&coords);
+    differential(&lines, false);
+    differential(&lines, true);
+}
+
+#[test]
+fn oracle_3() -> Result<(), IntersectError> {
+    // the end point of the second line touches the interior of the first line, the crossing
+    // of the two lines is computed a few ulps away from that end point
+    let lines = to_lines::<_, f64>(&[[4., 10., 10., 1.], [11., 1., 8., 4.]]);
+    for ignore_end_point_intersections in [true, false] {
+        let oracle: Vec<_> =
+            self_intersections_bruteforce(&lines, ignore_end_point_intersections)?.collect();
+        assert_eq!(oracle.len(), 1);
+        assert_eq!(oracle[0].1, vec![0, 1]);
+        differential(&lines, ignore_end_point_intersections);
+    }
+    Ok(())
+}
+
+#[test]
+fn oracle_non_finite_1() {
+    for bad in [f64::NAN, f64::INFINITY] {
+        let lines = to_lines::<_, f64>(&[[0., 0., 1., 1.], [0., 1., 1., 0.], [0., 0., bad, 1.]]);
+        for ignore_end_point_intersections in [false, true] {
+            assert!(matches!(
+                self_intersections_bruteforce(&lines, ignore_end_point_intersections),
+                Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+            ));
+        }
+    }
+}
+
+#[test]
+fn oracle_empty_1() -> Result<(), IntersectError> {
+    assert_eq!(self_intersections_bruteforce::<f64>(&[], true)?.len(), 0);
+    assert_eq!(self_intersections_bruteforce::<f64>(&[], false)?.len(), 0);
+    let lines = to_lines::<_, f64>(&[[0., 0., 1., 1.]]);
+    assert_eq!(self_intersections_bruteforce(&lines, false)?.len(), 0);
+    Ok(())
+}
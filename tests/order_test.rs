@@ -0,0 +1,60 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::order::{sweep_order, sweep_order_with_tolerance, SweepOrdered};
+use intersect2d::{IntersectError, Tolerance};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+#[test]
+fn order_1() {
+    let c = |x: f64, y: f64| geo::Coordinate { x, y };
+    assert_eq!(sweep_order(&c(0., 0.), &c(0., 0.)), Ordering::Equal);
+    assert_eq!(sweep_order(&c(100., -1.), &c(-100., 0.)), Ordering::Less);
+    assert_eq!(sweep_order(&c(-1., 3.), &c(1., 3.)), Ordering::Less);
+    assert_eq!(sweep_order(&c(1., 3.), &c(-1., 3.)), Ordering::Greater);
+    assert_eq!(
+        sweep_order(&c(1., 3.), &c(1., 3. + f64::EPSILON)),
+        Ordering::Equal
+    );
+
+    let tolerance = Tolerance::new(0.5, 4);
+    assert_eq!(
+        sweep_order_with_tolerance(&c(2., 0.), &c(0., 0.4), &tolerance),
+        Ordering::Greater
+    );
+    assert_eq!(
+        sweep_order_with_tolerance(&c(0.2, 0.), &c(0., 0.4), &tolerance),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn order_2() -> Result<(), IntersectError> {
+    // the results of the sweep come in sweep order
+    let lines: Vec<geo::Line<f64>> = (0..12)
+        .map(|i| {
+            let x = i as f64 * 1.5;
+            geo::Line::new((x, 0.0), (20.0 - x * 0.7, 10.0 + (i % 3) as f64))
+        })
+        .collect();
+    let results: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert!(results.len() > 10);
+    let mut sorted = results.clone();
+    sorted.reverse();
+    sorted.sort_by(|a, b| sweep_order(&a.0, &b.0));
+    assert_eq!(sorted, results);
+
+    // merge points into a map keyed by the sweep order
+    let mut merged = BTreeMap::<SweepOrdered<f64>, usize>::new();
+    for (position, involved) in results.iter() {
+        *merged.entry(SweepOrdered(*position)).or_default() += involved.len();
+    }
+    assert_eq!(merged.len(), results.len());
+    let keys: Vec<_> = merged.keys().map(|k| k.0).collect();
+    let positions: Vec<_> = results.iter().map(|r| r.0).collect();
+    assert_eq!(keys, positions);
+    Ok(())
+}
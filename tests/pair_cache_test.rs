@@ -0,0 +1,34 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::IntersectError;
+
+#[test]
+fn pair_cache_1() -> Result<(), IntersectError> {
+    // a bundle of near parallel lines, fanning out from a common region
+    let lines: Vec<geo::Line<f64>> = (0..20)
+        .map(|i| {
+            let i = i as f64;
+            geo::Line::new(
+                geo::Coordinate { x: i, y: 0.0 },
+                geo::Coordinate {
+                    x: 19.0 - i + 0.01 * i * i,
+                    y: 1000.0,
+                },
+            )
+        })
+        .collect();
+    let mut ad = AlgorithmData::<f64>::default();
+    let rv = ad
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?;
+    let pairs: usize = rv.map(|(_, l)| l.len() * (l.len() - 1) / 2).sum();
+    assert_eq!(pairs, 20 * 19 / 2);
+    assert!(ad.get_pair_cache_hits() > 0);
+    // no pair is ever calculated twice
+    assert!(ad.get_intersection_calls() <= 20 * 19 / 2);
+
+    let p = ad.get_cached_intersection(3, 2).unwrap().unwrap();
+    assert_eq!(ad.get_cached_intersection(2, 3), Some(Some(p)));
+    assert_eq!(ad.get_cached_intersection(2, 2), None);
+    Ok(())
+}
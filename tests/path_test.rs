@@ -0,0 +1,46 @@
+use intersect2d::path::first_intersection_along;
+
+fn assert_hit(hit: Option<(f64, geo::Coordinate<f64>, usize)>, expected: (f64, f64, f64, usize)) {
+    let (distance, point, obstacle) = hit.unwrap();
+    assert!(approx::ulps_eq!(distance, expected.0, epsilon = 1e-12));
+    assert!(approx::ulps_eq!(point.x, expected.1, epsilon = 1e-12));
+    assert!(approx::ulps_eq!(point.y, expected.2, epsilon = 1e-12));
+    assert_eq!(obstacle, expected.3);
+}
+
+#[test]
+fn path_1() {
+    // a zig-zag route, with a repeated vertex
+    let path = geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+    let obstacles = intersect2d::to_lines::<_, f64>(&[
+        [2., 12., 2., 8.],
+        [9., 5., 11., 5.],
+        [4., 4., 4., 6.],
+        [7., 0., 7., 5.],
+    ]);
+    // the obstacle touching the path is met first
+    assert_hit(first_intersection_along(&path, &obstacles), (7., 7., 0., 3));
+    assert_hit(
+        first_intersection_along(&path, &obstacles[..3]),
+        (15., 10., 5., 1),
+    );
+    assert_hit(
+        first_intersection_along(&path, &obstacles[..1]),
+        (28., 2., 10., 0),
+    );
+    assert!(first_intersection_along(&path, &obstacles[2..3]).is_none());
+    assert!(first_intersection_along(&geo::LineString::<f64>(vec![]), &obstacles).is_none());
+}
+
+#[test]
+fn path_2() {
+    let path = geo::LineString::from(vec![(0., 0.), (10., 0.)]);
+    // overlapping the path, in the opposite direction, and two obstacles at the same point
+    let obstacles =
+        intersect2d::to_lines::<_, f64>(&[[12., 0., 6., 0.], [3., -1., 3., 1.], [2., -1., 4., 1.]]);
+    assert_hit(first_intersection_along(&path, &obstacles), (3., 3., 0., 1));
+    assert_hit(
+        first_intersection_along(&path, &obstacles[..1]),
+        (6., 6., 0., 0),
+    );
+}
@@ -0,0 +1,84 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn payload_1() {
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [0., 10., 10., 0.],
+        [20., 0., 20., 10.],
+        [15., 5., 25., 5.],
+    ]);
+    let mut ad = AlgorithmData::<f64, u64>::default();
+    let _ = ad
+        .with_payload_lines(lines[..2].iter().copied().zip(vec![100_u64, 101]))
+        .unwrap();
+    // payloads are appended to the already added lines
+    let _ = ad
+        .with_payload_lines(lines[2..].iter().copied().zip(vec![102_u64, 103]))
+        .unwrap();
+    assert_eq!(ad.get_payload(3), Some(&103));
+    assert_eq!(ad.get_payload(4), None);
+    let rv = ad.compute_with_payloads().unwrap();
+    assert_eq!(rv.len(), 2);
+    assert_eq!(rv[0].1, vec![&100, &101]);
+    assert_eq!(rv[1].1, vec![&102, &103]);
+}
+
+#[test]
+fn payload_2() {
+    // indices continue across several calls to with_lines()
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    let rv: Vec<_> = AlgorithmData::<f64>::default()
+        .with_lines(lines[..1].iter().copied())
+        .unwrap()
+        .with_ref_lines(lines[1..].iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].1, vec![0_usize, 1]);
+}
+
+#[test]
+fn payload_mixed_lines_1() {
+    // payloads can't be added to lines without payloads
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    let mut ad = AlgorithmData::<f64, u64>::default();
+    let _ = ad.with_ref_lines(lines.iter()).unwrap();
+    assert!(matches!(
+        ad.with_payload_lines(lines.iter().copied().zip(vec![1_u64, 2])),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("after lines without payloads")
+    ));
+    assert_eq!(ad.get_payload(0), None);
+    assert!(matches!(
+        ad.compute_with_payloads(),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("not added with payloads")
+    ));
+    // the rejected lines were not added, the plain lines still work
+    let rv: Vec<_> = ad.compute().unwrap().collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].1, vec![0_usize, 1]);
+}
+
+#[test]
+fn payload_mixed_lines_2() {
+    // lines without payloads can't be added to lines with payloads
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    let mut ad = AlgorithmData::<f64, u64>::default();
+    let _ = ad
+        .with_payload_lines(lines.iter().copied().zip(vec![1_u64, 2]))
+        .unwrap();
+    assert!(matches!(
+        ad.with_ref_lines(lines.iter()),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("after lines with payloads")
+    ));
+    assert!(matches!(
+        ad.with_lines(lines.iter().copied()),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("after lines with payloads")
+    ));
+    let rv = ad.compute_with_payloads().unwrap();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].1, vec![&1, &2]);
+}
@@ -0,0 +1,86 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::precision::PrecisionModel;
+use intersect2d::{intersect_with_tolerance, to_lines, IntersectError, Tolerance};
+
+#[test]
+fn precision_1() -> Result<(), IntersectError> {
+    // three lines that should meet at (1,1), but the data has some noise
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 2., 2.0000003],
+        [0., 2., 2., 0.],
+        [1., 0., 1.0000002, 2.],
+    ]);
+    let rv: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert!(rv.len() > 1);
+
+    let tolerance =
+        Tolerance::default().with_precision_model(PrecisionModel::ScaledInteger(1000.0))?;
+    let rv: Vec<_> = AlgorithmData::<f64>::default()
+        .with_tolerance(tolerance)?
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].0, geo::Coordinate { x: 1.0, y: 1.0 });
+    assert_eq!(rv[0].1, vec![0, 1, 2]);
+    Ok(())
+}
+
+#[test]
+fn precision_2() -> Result<(), IntersectError> {
+    let tolerance = Tolerance::default().with_precision_model(PrecisionModel::FixedGrid(0.25))?;
+    let line1: geo::Line<f64> = [(0., 0.), (10., 1.)].into();
+    let line2: geo::Line<f64> = [(3.1, -5.), (3.1, 5.)].into();
+    let p = intersect_with_tolerance(&line1, &line2, &tolerance)
+        .unwrap()
+        .single();
+    assert_eq!(p, geo::Coordinate { x: 3.0, y: 0.25 });
+    Ok(())
+}
+
+#[test]
+fn precision_invalid_1() {
+    for v in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        for pm in [
+            PrecisionModel::FixedGrid(v),
+            PrecisionModel::ScaledInteger(v),
+        ] {
+            assert!(matches!(
+                pm.validate(),
+                Err(IntersectError::InvalidParameter(m)) if m.contains("grid size and scale")
+            ));
+            assert!(matches!(
+                Tolerance::default().with_precision_model(pm),
+                Err(IntersectError::InvalidParameter(_))
+            ));
+        }
+    }
+    assert!(PrecisionModel::<f64>::Floating.validate().is_ok());
+    assert!(PrecisionModel::FixedGrid(0.001).validate().is_ok());
+    assert!(PrecisionModel::ScaledInteger(1000.0).validate().is_ok());
+}
+
+#[test]
+fn precision_invalid_2() -> Result<(), IntersectError> {
+    // an invalid model set directly on the tolerance is caught by the algorithm
+    let tolerance = Tolerance::<f64> {
+        precision: PrecisionModel::FixedGrid(0.0),
+        ..Default::default()
+    };
+    let lines = to_lines::<_, f64>(&[[0., 0., 0.5, 0.5], [0., 0.5, 0.5, 0.]]);
+    let mut ad = AlgorithmData::<f64>::default();
+    assert!(matches!(
+        ad.with_tolerance(tolerance),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("grid size and scale")
+    ));
+    // the lines are not rounded to any grid
+    let rv: Vec<_> = ad.with_ref_lines(lines.iter())?.compute()?.collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].0, geo::Coordinate { x: 0.25, y: 0.25 });
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::interval::Interval;
+use intersect2d::predicates::{
+    compare_x_at, is_convex, is_convex_with_tolerance, orient2d, orient2d_interval,
+    orient2d_with_tolerance, point_side_of_line, point_side_of_line_with_tolerance, Orientation,
+};
+use intersect2d::{intersect, EqualityMode, Tolerance};
+use std::cmp::Ordering;
+
+#[test]
+fn predicates_1() {
+    // the classic example where the naive determinant fails
+    let p = geo::Coordinate { x: 0.5, y: 0.5 };
+    let q = geo::Coordinate { x: 12., y: 12. };
+    let r = geo::Coordinate { x: 24., y: 24. };
+    let mut x = 0.5f64;
+    let mut results = Vec::new();
+    for _ in 0..32 {
+        let p = geo::Coordinate { x, y: 0.5 };
+        results.push(orient2d(&p, &q, &r));
+        x = f64::from_bits(x.to_bits() + 1);
+    }
+    // all the moved points are below the line through q and r, so p->q->r turns right
+    assert_eq!(results[0], Orientation::Collinear);
+    assert!(results[1..].iter().all(|o| *o == Orientation::Clockwise));
+    assert_eq!(orient2d(&r, &q, &p), Orientation::Collinear);
+
+    let line: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    let left = geo::Coordinate { x: 3., y: 1e-300 };
+    let right = geo::Coordinate { x: 3., y: -1e-300 };
+    assert_eq!(
+        point_side_of_line(&line, &left),
+        Orientation::CounterClockwise
+    );
+    assert_eq!(point_side_of_line(&line, &right), Orientation::Clockwise);
+}
+
+#[test]
+fn predicates_2() {
+    // the tolerant predicate agrees with intersect()
+    let line: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    for offset in [1e-17f64, -1e-17, 1e-15, -1e-14, 1e-3].iter() {
+        let point = geo::Coordinate { x: 5., y: *offset };
+        let other = geo::Line::new(
+            point,
+            geo::Coordinate {
+                x: 5.,
+                y: 5. * offset.signum(),
+            },
+        );
+        let touching = intersect(&line, &other).is_some();
+        let side = point_side_of_line_with_tolerance(&line, &point, &Tolerance::default());
+        assert_eq!(
+            touching,
+            side == Orientation::Collinear,
+            "offset {}",
+            offset
+        );
+    }
+    let p = geo::Coordinate { x: 1., y: 1. };
+    assert_eq!(
+        orient2d_with_tolerance(
+            &p,
+            &p,
+            &geo::Coordinate { x: 5., y: 3. },
+            &Tolerance::default()
+        ),
+        Orientation::Collinear
+    );
+}
+
+#[test]
+fn interval_1() {
+    let i = Interval::new;
+    assert_eq!((i(1.0_f64) + i(2.0)).lo(), 3.0);
+    let a = i(1.0_f64) - i(1e-30);
+    assert!(a.lo() < 1.0 && a.hi() >= 1.0);
+    assert_eq!((i(f64::NAN) * i(1.0)).sign(), None);
+    assert_eq!((i(f64::INFINITY) * i(2.0)).sign(), Some(Ordering::Greater));
+    assert_eq!((-(i(1.0_f64) - i(2.0))).sign(), Some(Ordering::Greater));
+}
+
+#[test]
+fn interval_predicates_1() {
+    // integer coordinates, where the exact answers are easy to compute
+    let mut seed = 0x5eed_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 2001) as i64 - 1000
+    };
+    let c = |x: i64, y: i64| geo::Coordinate {
+        x: x as f64 * 1e6 + 0.5,
+        y: y as f64 * 1e6 + 0.5,
+    };
+    for _ in 0..2000 {
+        let (px, py, qx, qy) = (next(), next(), next(), next());
+        // r is often collinear with p and q
+        let k = next() % 3;
+        let (rx, ry) = if k == 0 {
+            (next(), next())
+        } else {
+            (px + (qx - px) * k, py + (qy - py) * k)
+        };
+        let exact =
+            ((qx - px) as i128 * (ry - py) as i128 - (qy - py) as i128 * (rx - px) as i128).cmp(&0);
+        let (p, q, r) = (c(px, py), c(qx, qy), c(rx, ry));
+        let orientation = orient2d_interval(&p, &q, &r);
+        assert_eq!(orientation, orient2d(&p, &q, &r));
+        assert_eq!(
+            orientation,
+            match exact {
+                Ordering::Greater => Orientation::CounterClockwise,
+                Ordering::Less => Orientation::Clockwise,
+                Ordering::Equal => Orientation::Collinear,
+            }
+        );
+
+        // the lines p-q and p-r at the height of q: a tie exactly when collinear
+        let a = geo::Line::new(p, q);
+        let b = geo::Line::new(p, r);
+        if py != qy && py != ry {
+            let expected = {
+                // x_a(y) - x_b(y) at y = qy, multiplied by the positive (qy - py)^2 (ry - py)^2
+                let (dya, dyb) = ((qy - py) as i128, (ry - py) as i128);
+                let t = (qy - py) as i128;
+                let xa = (qx - px) as i128 * t * dyb;
+                let xb = (rx - px) as i128 * t * dya;
+                ((xa - xb) * dya.signum() * dyb.signum()).cmp(&0)
+            };
+            assert_eq!(compare_x_at(&a, &b, q.y), Some(expected));
+        }
+    }
+}
+
+#[test]
+fn interval_predicates_sweep_1() {
+    // random lines in general position
+    let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 1_000_000) as f64 / 997.0
+    };
+    let lines: Vec<geo::Line<f64>> = (0..200)
+        .map(|_| [(next(), next()), (next(), next())].into())
+        .collect();
+    for tolerance in [
+        Tolerance::default(),
+        Tolerance::new(1e-12, 0).with_equality_mode(EqualityMode::Absolute),
+    ] {
+        let compute = |filtered: bool| {
+            let mut ad = AlgorithmData::<f64>::default();
+            let rv: Vec<_> = ad
+                .with_tolerance(tolerance)
+                .unwrap()
+                .with_filtered_predicates(filtered)
+                .unwrap()
+                .with_ref_lines(lines.iter())
+                .unwrap()
+                .compute()
+                .unwrap()
+                .collect();
+            assert!(ad.verify_results(&rv, 1000).is_empty());
+            rv
+        };
+        assert_eq!(compute(true), compute(false));
+    }
+}
+
+#[test]
+fn convex_1() {
+    // regular polygons in both orientations, closed and open
+    for n in 3..12 {
+        let mut points: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let a = i as f64 * std::f64::consts::PI * 2.0 / n as f64 + 0.3;
+                (100. + 50. * a.cos(), -20. + 50. * a.sin())
+            })
+            .collect();
+        assert!(is_convex(&geo::LineString::from(points.clone())), "{}", n);
+        points.push(points[0]);
+        assert!(is_convex(&geo::LineString::from(points.clone())), "{}", n);
+        points.reverse();
+        assert!(is_convex(&geo::LineString::from(points)), "{}", n);
+    }
+    // repeated vertices and collinear runs
+    let ring = geo::LineString::from(vec![
+        (0., 0.),
+        (0., 0.),
+        (1., 0.),
+        (2., 0.),
+        (3., 0.),
+        (3., 3.),
+        (3., 3.),
+        (0., 3.),
+        (0., 1.),
+        (0., 0.),
+    ]);
+    assert!(is_convex(&ring));
+    // a spike along the bottom edge
+    let spike = geo::LineString::from(vec![(0., 0.), (3., 0.), (2., 0.), (3., 0.), (3., 3.)]);
+    assert!(!is_convex(&spike));
+    // degenerate rings
+    let line = geo::LineString::from(vec![(0., 0.), (1., 1.), (2., 2.), (0., 0.)]);
+    assert!(!is_convex(&line));
+    assert!(!is_convex(&geo::LineString::from(vec![(0., 0.), (1., 0.)])));
+    assert!(!is_convex(&geo::LineString::<f64>::from(
+        Vec::<(f64, f64)>::new()
+    )));
+    // a square walked around twice
+    let twice = geo::LineString::from(vec![
+        (0., 0.),
+        (1., 0.),
+        (1., 1.),
+        (0., 1.),
+        (0., 0.),
+        (1., 0.),
+        (1., 1.),
+        (0., 1.),
+    ]);
+    assert!(!is_convex(&twice));
+}
+
+#[test]
+fn convex_2() {
+    // the bottom vertex is below the line by much less than the tolerance
+    let ring = geo::LineString::from(vec![
+        (0., 0.),
+        (5., -1e-9),
+        (10., 0.),
+        (10., 10.),
+        (0., 10.),
+    ]);
+    assert!(is_convex(&ring));
+    let ring = geo::LineString::from(vec![(0., 0.), (5., 1e-9), (10., 0.), (10., 10.), (0., 10.)]);
+    assert!(!is_convex(&ring));
+    assert!(is_convex_with_tolerance(&ring, &Tolerance::new(1e-6, 4)));
+    // within the tolerance the vertices are identical
+    let ring = geo::LineString::from(vec![
+        (0., 0.),
+        (10., 0.),
+        (10., 1e-9),
+        (10., 10.),
+        (0., 10.),
+    ]);
+    assert!(is_convex_with_tolerance(&ring, &Tolerance::new(1e-6, 4)));
+}
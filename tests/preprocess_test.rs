@@ -0,0 +1,92 @@
+use intersect2d::preprocess::{filter_short_segments, ShortSegmentMode};
+use intersect2d::{to_lines, ulps_eq_c, IntersectError};
+
+#[test]
+fn preprocess_drop_1() {
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [10., 0., 10., 0.001],
+        [10., 0.001, 20., 0.],
+        [5., 5., 5., 5.],
+    ]);
+    let filtered = filter_short_segments(&lines, 0.01, ShortSegmentMode::Drop).unwrap();
+    assert_eq!(filtered.lines.len(), 2);
+    assert_eq!(filtered.original_indices, vec![0_usize, 2]);
+    assert_eq!(filtered.original_index(1), Some(2));
+    assert_eq!(filtered.original_index(2), None);
+    assert!(ulps_eq_c(&filtered.lines[1].start, &lines[2].start));
+}
+
+#[test]
+fn preprocess_merge_1() {
+    // a chain of two short segments collapse into the centroid of three points
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [10., 0., 10., 0.003],
+        [10., 0.003, 10., 0.006],
+        [10., 0.006, 20., 0.],
+        [0., 10., 10., 10.],
+    ]);
+    let filtered = filter_short_segments(&lines, 0.01, ShortSegmentMode::Merge).unwrap();
+    assert_eq!(filtered.original_indices, vec![0_usize, 3, 4]);
+    let merged = geo::Coordinate { x: 10., y: 0.003 };
+    assert!(ulps_eq_c(&filtered.lines[0].end, &merged));
+    assert!(ulps_eq_c(&filtered.lines[1].start, &merged));
+    assert!(ulps_eq_c(&filtered.lines[2].start, &lines[4].start));
+    assert!(ulps_eq_c(&filtered.lines[2].end, &lines[4].end));
+}
+
+#[test]
+fn preprocess_remap_1() {
+    use intersect2d::SelfIntersectingExclusive;
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 0.001, 0.],
+        [0., 0., 10., 10.],
+        [1., 1., 1., 1.],
+        [0., 10., 10., 0.],
+    ]);
+    let filtered = filter_short_segments(&lines, 0.01, ShortSegmentMode::Drop).unwrap();
+    let rv: Vec<_> = filtered
+        .remap(filtered.lines.self_intersections().unwrap())
+        .collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].1, vec![1_usize, 3]);
+    assert!(ulps_eq_c(&rv[0].0, &geo::Coordinate { x: 5., y: 5. }));
+}
+
+#[test]
+fn preprocess_min_length_1() -> Result<(), IntersectError> {
+    let lines = to_lines::<_, f64>(&[[0., 0., 1., 0.], [1., 0., 1., 0.]]);
+    for mode in [ShortSegmentMode::Drop, ShortSegmentMode::Merge] {
+        for min_length in [-1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                filter_short_segments(&lines, min_length, mode),
+                Err(IntersectError::InvalidParameter(_))
+            ));
+        }
+    }
+    // nothing is shorter than zero, even the zero length segment is kept
+    let filtered = filter_short_segments(&lines, 0.0, ShortSegmentMode::Drop)?;
+    assert_eq!(filtered.lines, lines);
+    assert_eq!(filtered.original_indices, vec![0_usize, 1]);
+    // a segment that already is a single point is removed when merging
+    let filtered = filter_short_segments(&lines, 0.0, ShortSegmentMode::Merge)?;
+    assert_eq!(filtered.lines, lines[..1]);
+    assert_eq!(filtered.original_indices, vec![0_usize]);
+    Ok(())
+}
+
+#[test]
+fn preprocess_non_finite_1() {
+    // every line is checked, also the short ones that would be dropped anyway
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 0.], [10., 0., 10., f64::NAN]]);
+    assert!(matches!(
+        filter_short_segments(&lines, 0.01, ShortSegmentMode::Drop),
+        Err(IntersectError::InvalidData(_))
+    ));
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 0.], [f64::INFINITY, 0., 20., 0.]]);
+    assert!(matches!(
+        filter_short_segments(&lines, 0.01, ShortSegmentMode::Merge),
+        Err(IntersectError::InvalidData(_))
+    ));
+}
@@ -0,0 +1,32 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::{IntersectError, SelfIntersectingExclusive};
+
+#[test]
+fn preset_1() -> Result<(), IntersectError> {
+    let shell = geo::LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]);
+    let hole = geo::LineString::from(vec![(2., 2.), (12., 5.), (2., 8.), (2., 2.)]);
+    assert!(AlgorithmData::<f64>::for_linestring(&shell)?
+        .compute()?
+        .next()
+        .is_none());
+    let rv: Vec<_> = AlgorithmData::<f64>::for_rings(&[shell.clone(), hole.clone()])?
+        .compute()?
+        .collect();
+    assert_eq!(rv.len(), 2);
+    assert!(rv
+        .iter()
+        .all(|(p, involved)| p.x == 10. && involved.len() == 2));
+    // the presets agree with the traits
+    let zigzag = geo::LineString::from(
+        (0..40)
+            .map(|i| (i as f64, if i & 1 == 0 { 0. } else { 10. + i as f64 }))
+            .chain(std::iter::once((0., 5.)))
+            .collect::<Vec<_>>(),
+    );
+    let rv: Vec<_> = AlgorithmData::<f64>::for_linestring(&zigzag)?
+        .compute()?
+        .collect();
+    assert_eq!(rv, zigzag.self_intersections()?.collect::<Vec<_>>());
+    assert!(!rv.is_empty());
+    Ok(())
+}
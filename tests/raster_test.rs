@@ -0,0 +1,184 @@
+#![cfg(feature = "image")]
+use intersect2d::raster::{rasterize, DefaultStyle, RasterStyle, Rgba, RgbaImage};
+use intersect2d::IntersectError;
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Splits a PNG file into (chunk type, chunk data), checking the chunk CRCs
+fn chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+    assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    let mut rv = Vec::new();
+    let mut pos = 8;
+    while pos < png.len() {
+        let len = be_u32(&png[pos..]) as usize;
+        let body = &png[pos + 4..pos + 8 + len];
+        let mut crc = 0xffff_ffff_u32;
+        for b in body.iter() {
+            crc ^= *b as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        assert_eq!(!crc, be_u32(&png[pos + 8 + len..]));
+        rv.push((
+            String::from_utf8(body[..4].to_vec()).unwrap(),
+            body[4..].to_vec(),
+        ));
+        pos += 12 + len;
+    }
+    rv
+}
+
+/// Unpacks a zlib stream made of stored deflate blocks
+fn inflate_stored(data: &[u8]) -> Vec<u8> {
+    assert_eq!(&data[..2], &[0x78, 0x01]);
+    let mut rv = Vec::new();
+    let mut pos = 2;
+    loop {
+        let last = data[pos] == 1;
+        let len = u16::from_le_bytes([data[pos + 1], data[pos + 2]]) as usize;
+        let nlen = u16::from_le_bytes([data[pos + 3], data[pos + 4]]) as usize;
+        assert_eq!(len, !nlen & 0xffff);
+        rv.extend_from_slice(&data[pos + 5..pos + 5 + len]);
+        pos += 5 + len;
+        if last {
+            break;
+        }
+    }
+    assert_eq!(pos + 4, data.len());
+    rv
+}
+
+#[test]
+fn raster_1() -> Result<(), IntersectError> {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 0.], [5., -5., 5., 5.]]);
+    let results = vec![(geo::Coordinate { x: 5., y: 0. }, vec![0, 1])];
+    let image = rasterize(&lines, &results, 23, 23, &DefaultStyle)?;
+    let style = DefaultStyle;
+    // the drawing is 10 x 10 units, scaled to 18 x 18 pixels with a margin of 2 pixels
+    assert_eq!(image.pixel(11, 11), Some(style.marker_color(&[0, 1])));
+    assert_eq!(image.pixel(2, 11), Some(style.segment_color(0)));
+    assert_eq!(image.pixel(20, 11), Some(style.segment_color(0)));
+    assert_eq!(image.pixel(11, 2), Some(style.segment_color(1)));
+    assert_eq!(image.pixel(11, 20), Some(style.segment_color(1)));
+    assert_eq!(image.pixel(2, 2), Some(style.background()));
+    assert_eq!(image.pixel(23, 0), None);
+    Ok(())
+}
+
+#[test]
+fn raster_png_1() -> Result<(), IntersectError> {
+    // rows of 300 * 4 bytes, the image data needs several deflate blocks
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 300., 100.], [0., 100., 300., 0.]]);
+    let image = rasterize(&lines, &[], 300, 100, &DefaultStyle)?;
+    let mut png = Vec::new();
+    image.write_png(&mut png)?;
+    let chunks = chunks(&png);
+    let kinds: Vec<_> = chunks.iter().map(|c| c.0.as_str()).collect();
+    assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+    assert_eq!(be_u32(&chunks[0].1), 300);
+    assert_eq!(be_u32(&chunks[0].1[4..]), 100);
+    assert_eq!(&chunks[0].1[8..], &[8, 6, 0, 0, 0]);
+
+    let scanlines = inflate_stored(&chunks[1].1);
+    assert_eq!(scanlines.len(), 100 * (300 * 4 + 1));
+    for (row, scanline) in scanlines.chunks(300 * 4 + 1).enumerate() {
+        assert_eq!(scanline[0], 0);
+        assert_eq!(
+            &scanline[1..],
+            &image.as_raw()[row * 300 * 4..(row + 1) * 300 * 4]
+        );
+    }
+    Ok(())
+}
+
+struct Highlight;
+
+impl RasterStyle for Highlight {
+    fn background(&self) -> Rgba {
+        [0, 0, 0, 0]
+    }
+
+    fn segment_color(&self, index: usize) -> Rgba {
+        if index == 1 {
+            [0, 255, 0, 255]
+        } else {
+            [128, 128, 128, 255]
+        }
+    }
+
+    fn marker_radius(&self) -> u32 {
+        0
+    }
+}
+
+#[test]
+fn raster_style_1() -> Result<(), IntersectError> {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 4., 0.], [0., 2., 4., 2.]]);
+    let results = vec![(geo::Coordinate { x: 4., y: 2. }, vec![1])];
+    let image = rasterize(&lines, &results, 5, 5, &Highlight)?;
+    // the drawing is centered vertically
+    assert_eq!(image.pixel(0, 3), Some([128, 128, 128, 255]));
+    assert_eq!(image.pixel(0, 1), Some([0, 255, 0, 255]));
+    assert_eq!(image.pixel(4, 1), Some(Highlight.marker_color(&[1])));
+    assert_eq!(image.pixel(0, 0), Some([0, 0, 0, 0]));
+    Ok(())
+}
+
+#[test]
+fn raster_size_1() {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 1., 1.]]);
+    assert!(matches!(
+        rasterize(&lines, &[], 0, 10, &DefaultStyle),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("got 0x10")
+    ));
+    assert!(matches!(
+        RgbaImage::new(10, 0, [0; 4]),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("got 10x0")
+    ));
+    // nothing to draw is not an error
+    let image = rasterize::<f64, _>(&[], &[], 1, 1, &DefaultStyle).unwrap();
+    assert_eq!(image.pixel(0, 0), Some(DefaultStyle.background()));
+}
+
+#[test]
+fn raster_non_finite_1() {
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 1., 1.]]);
+    let results = vec![(geo::Coordinate { x: f64::NAN, y: 0. }, vec![0])];
+    assert!(matches!(
+        rasterize(&lines, &results, 10, 10, &DefaultStyle),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 1., f64::INFINITY]]);
+    assert!(matches!(
+        rasterize(&lines, &[], 10, 10, &DefaultStyle),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+}
+
+struct FailingWrite;
+
+impl std::io::Write for FailingWrite {
+    fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn raster_write_error_1() {
+    let image = RgbaImage::new(2, 2, [0; 4]).unwrap();
+    assert!(matches!(
+        image.write_png(FailingWrite),
+        Err(IntersectError::IoError(m)) if m.contains("broken pipe")
+    ));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "rayon")]
+use intersect2d::algorithm::AlgorithmData;
+use rayon::prelude::*;
+
+fn lines() -> Vec<geo::Line<f64>> {
+    let mut rv = Vec::new();
+    for i in 0..30 {
+        let i = i as f64;
+        rv.push(geo::Line::from([(i, -1.), (i + 0.5, 30.)]));
+        rv.push(geo::Line::from([(-1., i), (30., i + 0.5)]));
+    }
+    rv
+}
+
+#[test]
+fn rayon_1() {
+    let lines = lines();
+    let list = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute_collected()
+        .unwrap();
+    assert_eq!(list.len(), 900);
+    let sequential: f64 = list.iter().map(|(p, _)| p.x + p.y).sum();
+
+    let parallel: f64 = (&list).into_par_iter().map(|(p, _)| p.x + p.y).sum();
+    assert!(approx::ulps_eq!(parallel, sequential, max_ulps = 64));
+    let counts: usize = list.clone().into_par_iter().map(|(_, l)| l.len()).sum();
+    assert_eq!(counts, 1800);
+
+    let results = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap();
+    let collected: Vec<_> = results.into_par_iter().collect();
+    assert_eq!(collected, list.into_inner());
+}
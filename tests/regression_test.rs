@@ -0,0 +1,105 @@
+use intersect2d::precision::PrecisionModel;
+use intersect2d::regression::RegressionCase;
+use intersect2d::{to_lines, IntersectError, Tolerance};
+
+#[test]
+fn regression_1() -> Result<(), IntersectError> {
+    // awkward values that don't survive a round trip through a few decimals
+    let lines = to_lines::<_, f64>(&[
+        [0.1, 0.2, 1. / 3., 17.000000000000004],
+        [1e-300, 5., 0.30000000000000004, -3.],
+        [-0., 1., 1., 0.],
+        [12345678.9, -0.5, -12345678.9, 0.5],
+    ]);
+    let tolerance =
+        Tolerance::new(1e-12, 2).with_precision_model(PrecisionModel::FixedGrid(1e-9))?;
+    let case = RegressionCase::capture(lines.clone(), false, tolerance)?;
+    assert!(!case.results.is_empty());
+
+    let path = std::env::temp_dir().join("intersect2d_regression_1.case");
+    case.save(&path)?;
+    let loaded = RegressionCase::<f64>::load(&path)?;
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded, case);
+    for (a, b) in loaded.lines.iter().zip(lines.iter()) {
+        assert_eq!(a.start.x.to_bits(), b.start.x.to_bits());
+        assert_eq!(a.end.y.to_bits(), b.end.y.to_bits());
+    }
+    assert!(loaded.reproduces()?);
+
+    // a recorded result that no longer matches
+    let mut stale = loaded;
+    stale.results.pop();
+    assert!(!stale.reproduces()?);
+    Ok(())
+}
+
+const VALID: &str = "intersect2d-case 1\nignore_end_point_intersections true\ntolerance 1e-9 4\nprecision floating\nlines 1\n0.0 0.0 1.0 1.0\nresults 0\n";
+
+/// Reads `VALID` with `from` replaced by `to`
+fn read_modified(from: &str, to: &str) -> Result<RegressionCase<f64>, IntersectError> {
+    assert!(VALID.contains(from));
+    RegressionCase::<f64>::read(VALID.replace(from, to).as_bytes())
+}
+
+#[test]
+fn regression_read_header_1() {
+    assert!(RegressionCase::<f64>::read(VALID.as_bytes()).is_ok());
+    assert!(matches!(
+        RegressionCase::<f64>::read("".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m.contains("unexpected end of file")
+    ));
+    assert!(matches!(
+        RegressionCase::<f64>::read("something else\n".as_bytes()),
+        Err(IntersectError::InvalidData(m)) if m == "Regression case line 1: not an intersect2d regression case"
+    ));
+    // a truncated case
+    assert!(matches!(
+        read_modified("results 0\n", ""),
+        Err(IntersectError::InvalidData(m)) if m.contains("unexpected end of file")
+    ));
+}
+
+#[test]
+fn regression_read_lines_1() {
+    assert!(matches!(
+        read_modified("0.0 0.0 1.0 1.0", "0.0 0.0 1.0"),
+        Err(IntersectError::InvalidData(m)) if m == "Regression case line 6: expected four coordinates"
+    ));
+    assert!(matches!(
+        read_modified("0.0 0.0 1.0 1.0", "0.0 NaN 1.0 1.0"),
+        Err(IntersectError::InvalidData(m)) if m == "Regression case line 6: non-finite number"
+    ));
+    assert!(matches!(
+        read_modified("0.0 0.0 1.0 1.0", "0.0 zero 1.0 1.0"),
+        Err(IntersectError::InvalidData(m)) if m == "Regression case line 6: invalid number"
+    ));
+}
+
+#[test]
+fn regression_read_results_1() {
+    assert!(matches!(
+        read_modified("results 0", "results 1\n0.5 0.5 0 7"),
+        Err(IntersectError::InvalidData(m)) if m == "Regression case line 8: line index out of range"
+    ));
+}
+
+#[test]
+fn regression_read_precision_1() {
+    assert!(matches!(
+        read_modified("floating", "fixed_grid -1.0"),
+        Err(IntersectError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        read_modified("floating", "double"),
+        Err(IntersectError::InvalidData(m)) if m == "Regression case line 4: unknown precision model"
+    ));
+}
+
+#[test]
+fn regression_load_1() {
+    assert!(matches!(
+        RegressionCase::<f64>::load("/nonexistent/intersect2d.case"),
+        Err(IntersectError::IoError(_))
+    ));
+}
@@ -0,0 +1,82 @@
+use intersect2d::relate::{relate, relate_lines, Dimension, Location};
+use intersect2d::{to_lines, IntersectError};
+
+fn ls(points: &[(f64, f64)]) -> geo::LineString<f64> {
+    geo::LineString::from(points.to_vec())
+}
+
+#[test]
+fn relate_1() -> Result<(), IntersectError> {
+    // disjoint
+    let m = relate(&[ls(&[(0., 0.), (1., 0.)])], &[ls(&[(0., 1.), (1., 1.)])])?;
+    assert_eq!(m.to_string(), "FF1FF0102");
+    assert!(m.is_disjoint());
+    assert!(!m.is_intersects());
+
+    // touching at end points
+    let m = relate(&[ls(&[(0., 0.), (1., 0.)])], &[ls(&[(1., 0.), (1., 1.)])])?;
+    assert_eq!(m.to_string(), "FF1F00102");
+    assert!(m.is_touches());
+    assert!(!m.is_crosses());
+
+    // end point touching interior
+    let m = relate(&[ls(&[(0., 0.), (2., 0.)])], &[ls(&[(1., 0.), (1., 1.)])])?;
+    assert_eq!(m.to_string(), "F01FF0102");
+    assert!(m.is_touches());
+    Ok(())
+}
+
+#[test]
+fn relate_2() -> Result<(), IntersectError> {
+    // overlapping
+    let m = relate(&[ls(&[(0., 0.), (2., 0.)])], &[ls(&[(1., 0.), (3., 0.)])])?;
+    assert_eq!(m.to_string(), "1010F0102");
+    assert!(m.is_overlaps());
+    assert!(!m.is_within());
+
+    // within, B has an extra vertex
+    let a = [ls(&[(1., 0.), (2., 0.)])];
+    let b = [ls(&[(0., 0.), (1.5, 0.), (3., 0.)])];
+    let m = relate(&a, &b)?;
+    assert_eq!(m.to_string(), "1FF0FF102");
+    assert!(m.is_within());
+    assert!(relate(&b, &a)?.is_contains());
+
+    // equal, but split differently
+    let a = [ls(&[(0., 0.), (1., 1.), (2., 2.)])];
+    let b = [ls(&[(2., 2.), (0.5, 0.5)]), ls(&[(0.5, 0.5), (0., 0.)])];
+    let m = relate(&a, &b)?;
+    assert!(m.is_equals());
+    assert_eq!(
+        m.get(Location::Interior, Location::Interior),
+        Dimension::Curve
+    );
+    Ok(())
+}
+
+#[test]
+fn relate_3() -> Result<(), IntersectError> {
+    // closed rings have no boundary
+    let ring = [ls(&[(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)])];
+    let m = relate(&ring, &[ls(&[(1., 1.), (3., 1.)])])?;
+    assert_eq!(m.to_string(), "0F1FFF102");
+    assert!(m.is_crosses());
+
+    let m = relate_lines(
+        &to_lines::<_, f64>(&[[0., 0., 10., 10.], [10., 0., 0., 10.]]),
+        &to_lines(&[[5., 0., 5., 10.]]),
+    )?;
+    assert_eq!(
+        m.get(Location::Interior, Location::Interior),
+        Dimension::Point
+    );
+    assert!(m.matches("0********")?);
+    assert!(m.matches("T*T***T**")?);
+    assert!(m.matches("T*T*").is_err());
+    assert!(m.matches("T*T***X**").is_err());
+
+    // empty input
+    let m = relate(&[], &ring)?;
+    assert_eq!(m.to_string(), "FFFFFF1F2");
+    Ok(())
+}
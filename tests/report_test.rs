@@ -0,0 +1,35 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::report::{format_report, intersection_kind, IntersectionKind};
+use intersect2d::IntersectError;
+
+#[test]
+fn report_1() -> Result<(), IntersectError> {
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [5., 0., 15., 0.],
+        [3., -1., 3., 1.],
+        [12., 0., 12., 5.],
+        [15., 0., 20., 5.],
+    ]);
+    let results: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    for (position, involved) in results.iter() {
+        let kind = intersection_kind(&lines, position, involved);
+        let expected = match (position.x as i32, involved.len()) {
+            (3, _) => IntersectionKind::Crossing,
+            (15, 2) => IntersectionKind::EndPoints,
+            (12, _) => IntersectionKind::Touch,
+            _ => IntersectionKind::Overlap,
+        };
+        assert_eq!(kind, expected, "at {:?} {:?}", position, involved);
+    }
+    let report = format_report(&lines, results.iter().cloned());
+    assert_eq!(report.lines().count(), results.len() + 1);
+    assert!(report.starts_with(" #  position"));
+    assert!(report.contains("  crossing\n"));
+    assert!(report.contains("  touch\n"));
+    assert!(format_report::<f64, _>(&lines, Vec::new()).ends_with("kind\n"));
+    Ok(())
+}
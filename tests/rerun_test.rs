@@ -0,0 +1,115 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::Tolerance;
+
+/// a small fan of lines sharing end points, also crossing each other
+fn fan() -> Vec<geo::Line<f64>> {
+    intersect2d::to_lines(&[
+        [0., 0., 10., 10.],
+        [0., 0., 10., 0.],
+        [10., 0., 0., 10.],
+        [10., 10., 10., 0.],
+        [0., 10., 10., 10.],
+        [0., 5., 10., 5.],
+    ])
+}
+
+fn fresh(
+    lines: &[geo::Line<f64>],
+    ignore_end_points: bool,
+    tolerance: Tolerance<f64>,
+) -> Vec<(geo::Coordinate<f64>, Vec<usize>)> {
+    AlgorithmData::<f64>::default()
+        .with_tolerance(tolerance)
+        .unwrap()
+        .with_ignore_end_point_intersections(ignore_end_points)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect()
+}
+
+#[test]
+fn rerun_1() {
+    let lines = fan();
+    let inclusive = fresh(&lines, false, Tolerance::default());
+    let exclusive = fresh(&lines, true, Tolerance::default());
+    assert_ne!(inclusive, exclusive);
+
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter()).unwrap();
+    for _ in 0..2 {
+        let rv: Vec<_> = ad
+            .reset()
+            .unwrap()
+            .with_ignore_end_point_intersections(false)
+            .unwrap()
+            .compute()
+            .unwrap()
+            .collect();
+        assert_eq!(rv, inclusive);
+        let rv: Vec<_> = ad
+            .reset()
+            .unwrap()
+            .with_ignore_end_point_intersections(true)
+            .unwrap()
+            .compute()
+            .unwrap()
+            .collect();
+        assert_eq!(rv, exclusive);
+    }
+}
+
+#[test]
+fn rerun_2() {
+    // stop at first intersection, then the complete run on the same lines
+    let lines = fan();
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad
+        .with_stop_at_first_intersection(true)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap();
+    while !ad.compute_iterative().unwrap() {}
+    assert_eq!(ad.take_results().unwrap().len(), 1);
+
+    let rv: Vec<_> = ad
+        .reset()
+        .unwrap()
+        .with_stop_at_first_intersection(false)
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(rv, fresh(&lines, false, Tolerance::default()));
+    assert!(ad.get_intersection_calls() > 0);
+}
+
+#[test]
+fn rerun_3() {
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [10.001, -5., 10.001, 5.],
+        [3., -1., 3., 1.],
+    ]);
+    let coarse = Tolerance::new(0.01, 4);
+    let mut ad = AlgorithmData::<f64>::default();
+    assert_eq!(
+        ad.with_ref_lines(lines.iter())
+            .unwrap()
+            .compute()
+            .unwrap()
+            .len(),
+        1
+    );
+    let rv: Vec<_> = ad
+        .reset_with_tolerance(coarse)
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(rv, fresh(&lines, false, coarse));
+    assert_eq!(rv.len(), 2);
+    assert!(ad.reset_with_tolerance(Tolerance::new(-1.0, 4)).is_err());
+}
@@ -0,0 +1,30 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn segment_store_1() -> Result<(), IntersectError> {
+    let lines = to_lines::<_, f64>(&[[10., 10., 0., 0.], [0., 10., 10., 0.], [3., 1., 7., 1.]]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter())?;
+    assert_eq!(ad.line_count(), 3);
+    // the lines are re-arranged so that the start point comes first in sweep order
+    assert_eq!(
+        ad.get_line(0),
+        Some(to_lines::<_, f64>(&[[0., 0., 10., 10.]])[0])
+    );
+    assert_eq!(
+        ad.get_line(1),
+        Some(to_lines::<_, f64>(&[[10., 0., 0., 10.]])[0])
+    );
+    assert_eq!(ad.get_line(2), Some(lines[2]));
+    assert_eq!(ad.get_line(3), None);
+    let all = ad.get_lines();
+    assert_eq!(all.len(), 3);
+    for (i, line) in all.iter().enumerate() {
+        assert_eq!(ad.get_line(i).as_ref(), Some(line));
+    }
+
+    let rv: Vec<_> = ad.compute()?.collect();
+    assert_eq!(rv, vec![(geo::Coordinate { x: 5., y: 5. }, vec![0, 1])]);
+    Ok(())
+}
@@ -143,3 +143,48 @@ fn self_intersection_8() -> Result<(), IntersectError> {
     assert!(lines.is_self_intersecting_inclusive()?);
     Ok(())
 }
+
+#[test]
+fn lazy_setup_1() -> Result<(), IntersectError> {
+    use intersect2d::algorithm::AlgorithmData;
+    use intersect2d::{SelfIntersectingExclusive, SelfIntersectingInclusive};
+
+    // a grid of crossing lines, with shared end points and duplicated end points
+    let mut lines = Vec::new();
+    for i in 0..20 {
+        let f = i as f64;
+        lines.push(geo::Line::new(
+            geo::Coordinate { x: f, y: 0. },
+            geo::Coordinate { x: f + 5., y: 30. },
+        ));
+        lines.push(geo::Line::new(
+            geo::Coordinate { x: 30. - f, y: 0. },
+            geo::Coordinate { x: f + 5., y: 30. },
+        ));
+        lines.push(geo::Line::new(
+            geo::Coordinate { x: -5., y: f },
+            geo::Coordinate { x: 40., y: f },
+        ));
+    }
+    for ignore_end_points in [false, true] {
+        let eager: Vec<_> = AlgorithmData::<f64>::default()
+            .with_ignore_end_point_intersections(ignore_end_points)?
+            .with_ref_lines(lines.iter())?
+            .compute()?
+            .collect();
+        let mut ad = AlgorithmData::<f64>::default();
+        let lazy: Vec<_> = ad
+            .with_lazy_setup(true)?
+            .with_ignore_end_point_intersections(ignore_end_points)?
+            .with_ref_lines(lines.iter())?
+            .compute()?
+            .collect();
+        assert_eq!(eager, lazy);
+        // again, after a reset
+        let lazy: Vec<_> = ad.reset()?.compute()?.collect();
+        assert_eq!(eager, lazy);
+    }
+    assert!(lines.is_self_intersecting()?);
+    assert!(lines.is_self_intersecting_inclusive()?);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use intersect2d::self_overlap::{self_intersection_kinds, SelfIntersectionKind};
+use intersect2d::{IntersectError, SelfIntersectingExclusive};
+
+#[test]
+fn self_overlap_1() -> Result<(), IntersectError> {
+    // a square walked once and a half, the second lap retraces the first edge
+    let line_string = geo::LineString::from(vec![
+        (0., 0.),
+        (10., 0.),
+        (10., 10.),
+        (0., 10.),
+        (0., 0.),
+        (5., 0.),
+    ]);
+    let rv = self_intersection_kinds(&line_string)?;
+    assert!(rv.contains(&SelfIntersectionKind::Retrace {
+        overlap: [(0., 0.), (5., 0.)].into(),
+        segments: (0, 4)
+    }));
+    // the second lap revisits the first vertex
+    assert!(rv.contains(&SelfIntersectionKind::Crossing {
+        position: geo::Coordinate { x: 0., y: 0. },
+        segments: (0, 3)
+    }));
+    assert!(!rv
+        .iter()
+        .any(|k| matches!(k, SelfIntersectionKind::Spike { .. })));
+    assert!(!rv.iter().any(|k| matches!(
+        k,
+        SelfIntersectionKind::Crossing {
+            segments: (0, 4),
+            ..
+        }
+    )));
+
+    // a spike hidden behind a repeated vertex
+    let line_string = geo::LineString::from(vec![(0., 0.), (0., 8.), (0., 8.), (0., 2.)]);
+    // the plain self intersection test doesn't see it
+    assert!(!line_string.is_self_intersecting()?);
+    let rv = self_intersection_kinds(&line_string)?;
+    assert_eq!(
+        rv,
+        vec![SelfIntersectionKind::Spike {
+            overlap: [(0., 2.), (0., 8.)].into(),
+            segments: (0, 2)
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn self_overlap_2() -> Result<(), IntersectError> {
+    // a closed ring starting in the middle of a spike, the first and the last segment are
+    // connected at the closing vertex
+    let ring = geo::LineString::from(vec![
+        (5., 0.),
+        (10., 0.),
+        (10., 10.),
+        (0., 10.),
+        (0., 0.),
+        (12., 0.),
+        (5., 0.),
+    ]);
+    let rv = self_intersection_kinds(&ring)?;
+    assert!(rv.contains(&SelfIntersectionKind::Spike {
+        overlap: [(5., 0.), (10., 0.)].into(),
+        segments: (0, 5)
+    }));
+    assert!(rv.contains(&SelfIntersectionKind::Spike {
+        overlap: [(5., 0.), (12., 0.)].into(),
+        segments: (4, 5)
+    }));
+    assert!(rv.contains(&SelfIntersectionKind::Retrace {
+        overlap: [(5., 0.), (10., 0.)].into(),
+        segments: (0, 4)
+    }));
+    // the simple square has nothing to report
+    let square = geo::LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]);
+    assert!(self_intersection_kinds(&square)?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "serde")]
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::list::IntersectionList;
+use intersect2d::nearest::SegmentIndex;
+use intersect2d::{to_lines, SERIALIZATION_VERSION};
+
+#[test]
+fn serde_1() {
+    let lines = to_lines(&[
+        [0., 0., 10., 10.],
+        [0., 10., 10., 0.],
+        [5., -1., 5., 11.],
+        [-1., 2., 11., 2.],
+    ]);
+    let results = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute_collected()
+        .unwrap();
+    assert!(!results.is_empty());
+    let json = serde_json::to_string(&results).unwrap();
+    let decoded: IntersectionList<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, results);
+}
+
+#[test]
+fn serde_2() {
+    let lines: Vec<geo::Line<f64>> = (0..50)
+        .map(|i| {
+            let x = i as f64;
+            geo::Line::new((x, 0.0), (x + 3.0, 7.0 + (i % 5) as f64))
+        })
+        .collect();
+    let index = SegmentIndex::new(&lines).unwrap();
+    let json = serde_json::to_string(&index).unwrap();
+    let decoded: SegmentIndex<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.len(), index.len());
+    for point in [(0.5, 0.5), (20.0, 3.0), (60.0, -4.0)] {
+        let point = geo::Coordinate::from(point);
+        assert_eq!(
+            decoded.k_nearest_segments(&point, 4),
+            index.k_nearest_segments(&point, 4)
+        );
+        assert_eq!(
+            decoded.segments_within(&point, 2.5),
+            index.segments_within(&point, 2.5)
+        );
+    }
+}
+
+#[test]
+fn serde_version_1() {
+    let current = format!("\"version\":{}", SERIALIZATION_VERSION);
+    let future = format!("\"version\":{}", SERIALIZATION_VERSION + 1);
+    let expected = format!(
+        "unsupported format version {}, expected {}",
+        SERIALIZATION_VERSION + 1,
+        SERIALIZATION_VERSION
+    );
+
+    let results: IntersectionList<f64> =
+        vec![(geo::Coordinate { x: 1., y: 2. }, vec![0, 1])].into();
+    let json = serde_json::to_string(&results).unwrap();
+    assert!(json.contains(&current));
+    let rv = serde_json::from_str::<IntersectionList<f64>>(&json.replace(&current, &future));
+    assert!(rv.unwrap_err().to_string().contains(&expected));
+
+    let index = SegmentIndex::<f64>::new(&to_lines(&[[0., 0., 1., 1.]])).unwrap();
+    let json = serde_json::to_string(&index).unwrap();
+    assert!(json.contains(&current));
+    let rv = serde_json::from_str::<SegmentIndex<f64>>(&json.replace(&current, &future));
+    assert!(rv.unwrap_err().to_string().contains(&expected));
+}
+
+#[test]
+fn serde_segment_index_1() {
+    let index = SegmentIndex::<f64>::new(&to_lines(&[[0., 0., 1., 1.], [0., 1., 1., 0.]])).unwrap();
+    let value = serde_json::to_value(&index).unwrap();
+    assert!(serde_json::from_value::<SegmentIndex<f64>>(value.clone()).is_ok());
+    let rejected = |key: &str, replacement: serde_json::Value| {
+        let mut value = value.clone();
+        value[key] = replacement;
+        serde_json::from_value::<SegmentIndex<f64>>(value)
+            .unwrap_err()
+            .to_string()
+    };
+    // a cell referring to a line that doesn't exist
+    let mut cells = value["cells"].clone();
+    cells[0] = serde_json::json!([7]);
+    assert_eq!(rejected("cells", cells), "inconsistent segment index cells");
+    // a cell count not matching the grid size
+    assert_eq!(
+        rejected("cells", serde_json::json!([[0, 1]])),
+        "inconsistent segment index cells"
+    );
+    assert_eq!(
+        rejected("columns", serde_json::json!(0)),
+        "inconsistent segment index cells"
+    );
+    // the cell size must be positive
+    assert_eq!(
+        rejected("cell", serde_json::json!([0.0, 1.0])),
+        "invalid segment index coordinates"
+    );
+}
@@ -0,0 +1,91 @@
+use intersect2d::simplify::simplify_vw_preserve_topology;
+use intersect2d::{IntersectError, SelfIntersectingExclusive};
+
+#[test]
+fn simplify_1() -> Result<(), IntersectError> {
+    // a zig-zag line with a second feature inside one of its spikes
+    let mut zigzag = Vec::new();
+    for i in 0..20 {
+        zigzag.push((i as f64, if i % 2 == 0 { 0.0 } else { 1.0 }));
+    }
+    let layer = vec![
+        geo::LineString::from(zigzag),
+        geo::LineString::from(vec![(4.8, 0.5), (5.2, 0.5)]),
+        geo::LineString::from(vec![(0., 3.), (1., 3.1), (2., 3.), (3., 3.1), (4., 3.)]),
+    ];
+    let simplified = simplify_vw_preserve_topology(&layer, 1.5)?;
+    assert!(simplified[0].0.len() < layer[0].0.len());
+    assert_eq!(simplified[1], layer[1]);
+    assert_eq!(
+        simplified[2],
+        geo::LineString::from(vec![(0., 3.), (4., 3.)])
+    );
+    // nothing new intersects
+    let mut lines: Vec<geo::Line<f64>> = Vec::new();
+    for l in simplified.iter() {
+        lines.extend(l.lines());
+    }
+    for (i, one) in lines.iter().enumerate() {
+        for other in lines.iter().skip(i + 1) {
+            if let Some(intersect2d::Intersection::Intersection(p)) =
+                intersect2d::intersect(one, other)
+            {
+                assert!(
+                    intersect2d::ulps_eq_c(&p, &one.start) || intersect2d::ulps_eq_c(&p, &one.end)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn simplify_2() -> Result<(), IntersectError> {
+    // a ring with a narrow notch, removing the notch tip would cut through the ring
+    let ring = geo::LineString::from(vec![
+        (0., 0.),
+        (10., 0.),
+        (10., 10.),
+        (5.1, 10.),
+        (5., 0.1),
+        (4.9, 10.),
+        (0., 10.),
+        (0., 0.),
+    ]);
+    let simplified = simplify_vw_preserve_topology(std::slice::from_ref(&ring), 1.)?;
+    assert!(!simplified[0].is_self_intersecting()?);
+    assert_eq!(simplified[0].0.first(), ring.0.first());
+    assert_eq!(simplified[0].0.last(), ring.0.last());
+    // a huge epsilon still leaves a valid ring
+    let simplified = simplify_vw_preserve_topology(&[ring], 1e9)?;
+    assert_eq!(simplified[0].0.len(), 4);
+    assert!(!simplified[0].is_self_intersecting()?);
+    Ok(())
+}
+
+#[test]
+fn simplify_epsilon_1() -> Result<(), IntersectError> {
+    let layer = vec![geo::LineString::from(vec![(0., 0.), (5., 1.), (10., 0.)])];
+    for epsilon in [-1., f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            simplify_vw_preserve_topology(&layer, epsilon),
+            Err(IntersectError::InvalidParameter(m)) if m.contains("epsilon")
+        ));
+    }
+    // a zero epsilon is allowed, and removes nothing
+    assert_eq!(simplify_vw_preserve_topology(&layer, 0.)?, layer);
+    Ok(())
+}
+
+#[test]
+fn simplify_non_finite_1() {
+    // the bad coordinate is found in any of the line strings
+    let layer = vec![
+        geo::LineString::from(vec![(0., 0.), (5., 1.), (10., 0.)]),
+        geo::LineString::from(vec![(0., 5.), (f64::NAN, 6.), (10., 5.)]),
+    ];
+    assert!(matches!(
+        simplify_vw_preserve_topology(&layer, 1.),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+}
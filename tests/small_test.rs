@@ -0,0 +1,99 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::small::{intersect_small, MAX_SMALL_LINES};
+use intersect2d::{to_lines, IntersectError};
+
+fn compare(lines: &[geo::Line<f64>], ignore_end_point_intersections: bool) {
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(ignore_end_point_intersections)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    let rv = intersect_small(lines, ignore_end_point_intersections).unwrap();
+    assert_eq!(
+        rv.len(),
+        expected.len(),
+        "{:?} {:?} {:?}",
+        lines,
+        rv,
+        expected
+    );
+    for (r, e) in rv.iter().zip(expected.iter()) {
+        // crossings may be computed from another pair of lines than in the sweep
+        approx::assert_abs_diff_eq!(r.position.x, e.0.x, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(r.position.y, e.0.y, epsilon = 1e-9);
+        assert_eq!(r.lines.iter().collect::<Vec<_>>(), e.1);
+    }
+}
+
+#[test]
+fn small_1() {
+    // random segments on a coarse grid, so that end points and crossings coincide often
+    let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 5) as f64 * 2.5
+    };
+    for _ in 0..300 {
+        let coords: Vec<[f64; 4]> = (0..MAX_SMALL_LINES)
+            .map(|_| [next(), next(), next(), next()])
+            .filter(|c| c[0] != c[2] || c[1] != c[3])
+            .collect();
+        let lines = to_lines::<_, f64>(&coords);
+        compare(&lines, false);
+        compare(&lines, true);
+    }
+}
+
+#[test]
+fn small_2() {
+    // an end point touching the interior of another line, and overlapping lines
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [5., 0., 5., 5.],
+        [2., 0., 12., 0.],
+        [5., 5., 6., 6.],
+    ]);
+    compare(&lines, false);
+    compare(&lines, true);
+    assert!(intersect_small::<f64>(&[], false).unwrap().is_empty());
+}
+
+#[test]
+fn small_3() {
+    // the crossing computed from the lines rounds to a point further away from the end point
+    // (8,4) than the tolerance, it must still be reported as one point
+    let lines = to_lines::<_, f64>(&[[4., 10., 10., 1.], [11., 1., 8., 4.]]);
+    compare(&lines, false);
+    compare(&lines, true);
+    assert_eq!(intersect_small(&lines, false).unwrap().len(), 1);
+}
+
+#[test]
+fn small_capacity_1() -> Result<(), IntersectError> {
+    let lines: Vec<geo::Line<f64>> = (0..=MAX_SMALL_LINES)
+        .map(|i| [(0., i as f64), (10., i as f64)].into())
+        .collect();
+    assert!(matches!(
+        intersect_small(&lines, false),
+        Err(IntersectError::InvalidParameter(m)) if m.contains(&MAX_SMALL_LINES.to_string())
+    ));
+    // exactly MAX_SMALL_LINES lines are accepted
+    assert!(intersect_small(&lines[..MAX_SMALL_LINES], false)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn small_non_finite_1() {
+    for bad in [f64::NAN, f64::INFINITY] {
+        let lines = to_lines::<_, f64>(&[[0., 0., 1., 1.], [0., 1., 1., 0.], [0., 0., bad, 1.]]);
+        assert!(matches!(
+            intersect_small(&lines, false),
+            Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+        ));
+    }
+}
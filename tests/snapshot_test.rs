@@ -0,0 +1,57 @@
+#![cfg(feature = "snapshot")]
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::IntersectError;
+
+#[test]
+fn snapshot_1() -> Result<(), IntersectError> {
+    let lines = intersect2d::to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [10., 0., 0., 10.],
+        [5., -1., 5., 11.],
+        [20., 0., 20., 1.],
+    ]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter())?;
+    let mut index = 0;
+    loop {
+        let snapshot = ad.snapshot_at(index)?;
+        assert_eq!(snapshot.event_index, index);
+        assert_eq!(ad.get_handled_events(), index);
+        // the pending events are in sweep-line order
+        for w in snapshot.pending.windows(2) {
+            assert_eq!(
+                intersect2d::order::sweep_order(&w[0].position, &w[1].position),
+                std::cmp::Ordering::Less
+            );
+        }
+        // every active line is in the status once
+        let mut status = snapshot.status.clone();
+        status.dedup();
+        assert_eq!(status, snapshot.status);
+        if snapshot.is_done() {
+            assert_eq!(snapshot.intersections.len(), 1);
+            let (position, involved) = &snapshot.intersections[0];
+            assert!(intersect2d::ulps_eq_c(
+                position,
+                &geo::Coordinate { x: 5., y: 5. }
+            ));
+            assert_eq!(involved, &vec![0, 1, 2]);
+            assert!(snapshot.status.is_empty());
+            break;
+        }
+        index += 1;
+    }
+    // one start at y = -1, three starts at y = 0, the end at y = 1, the intersection and
+    // the ends at y = 10 and y = 11
+    assert_eq!(index, 9);
+
+    // no going back, but the sweep can be restarted
+    assert!(matches!(
+        ad.snapshot_at(2),
+        Err(IntersectError::InvalidParameter(_))
+    ));
+    let _ = ad.reset()?;
+    let snapshot = ad.snapshot_at(4)?;
+    assert_eq!(snapshot.status, vec![0, 2, 1, 3]);
+    Ok(())
+}
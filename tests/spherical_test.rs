@@ -0,0 +1,122 @@
+use intersect2d::spherical::{great_circle_intersections, intersect_great_circle};
+use intersect2d::IntersectError;
+
+#[test]
+fn great_circle_1() {
+    // crossing at the origin
+    let one: geo::Line<f64> = [(-10., 0.), (10., 0.)].into();
+    let other: geo::Line<f64> = [(0., -10.), (0., 10.)].into();
+    let p = intersect_great_circle(&one, &other).unwrap().unwrap();
+    assert!(p.x.abs() < 1e-9);
+    assert!(p.y.abs() < 1e-9);
+}
+
+#[test]
+fn great_circle_2() {
+    // the planar interpretation of these would cross at (0,0)
+    let one: geo::Line<f64> = [(170., -10.), (-170., 10.)].into();
+    let other: geo::Line<f64> = [(170., 10.), (-170., -10.)].into();
+    let p = intersect_great_circle(&one, &other).unwrap().unwrap();
+    assert!((p.x.abs() - 180.).abs() < 1e-9);
+    assert!(p.y.abs() < 1e-9);
+
+    // the great circle between these points passes north of the planar crossing
+    let one: geo::Line<f64> = [(-60., 50.), (60., 50.)].into();
+    let other: geo::Line<f64> = [(0., 50.), (0., 55.)].into();
+    assert!(intersect_great_circle(&one, &other).unwrap().is_none());
+    let other: geo::Line<f64> = [(0., 50.), (0., 70.)].into();
+    let p = intersect_great_circle(&one, &other).unwrap().unwrap();
+    assert!(p.x.abs() < 1e-9);
+    assert!(p.y > 60.0);
+}
+
+#[test]
+fn great_circle_3() {
+    // arcs on the same great circle touching at an end point
+    let one: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    let other: geo::Line<f64> = [(10., 0.), (20., 0.)].into();
+    let p = intersect_great_circle(&one, &other).unwrap().unwrap();
+    assert!((p.x - 10.).abs() < 1e-9);
+    // disjoint arcs on the same great circle
+    let other: geo::Line<f64> = [(11., 0.), (20., 0.)].into();
+    assert!(intersect_great_circle(&one, &other).unwrap().is_none());
+}
+
+#[test]
+fn great_circle_4() {
+    let lines: Vec<geo::Line<f64>> = vec![
+        [(0., 0.), (10., 0.)].into(),
+        [(10., 0.), (10., 10.)].into(),
+        [(5., -5.), (5., 5.)].into(),
+        [(5., 5.), (15., 5.)].into(),
+    ];
+    let rv = great_circle_intersections(&lines, false).unwrap();
+    assert_eq!(rv.len(), 4);
+    let rv = great_circle_intersections(&lines, true).unwrap();
+    assert_eq!(rv.len(), 2);
+    assert_eq!(rv[0].1, vec![0_usize, 2]);
+    assert_eq!(rv[1].1, vec![1_usize, 3]);
+}
+
+#[test]
+fn great_circle_5() {
+    // three arcs through the north pole, every pair computes the pole at another longitude
+    let lines: Vec<geo::Line<f64>> = vec![
+        [(0., 80.), (180., 80.)].into(),
+        [(90., 80.), (-90., 80.)].into(),
+        [(45., 80.), (-135., 80.)].into(),
+    ];
+    let rv = great_circle_intersections(&lines, false).unwrap();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].0, geo::Coordinate { x: 0., y: 90. });
+    assert_eq!(rv[0].1, vec![0, 1, 2]);
+
+    // three arcs through (180, 0) on the antimeridian, reported once at +180°
+    let lines: Vec<geo::Line<f64>> = vec![
+        [(170., -10.), (-170., 10.)].into(),
+        [(170., 10.), (-170., -10.)].into(),
+        [(170., 0.), (-170., 0.)].into(),
+        [(180., -20.), (-180., 20.)].into(),
+    ];
+    let rv = great_circle_intersections(&lines, false).unwrap();
+    assert_eq!(rv.len(), 1);
+    assert!(approx::ulps_eq!(rv[0].0.x, 180.));
+    assert!(rv[0].0.y.abs() < 1e-9);
+    assert_eq!(rv[0].1, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn great_circle_latitude_1() {
+    let other: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    for lat in [91., -90.5] {
+        let one: geo::Line<f64> = [(0., 0.), (10., lat)].into();
+        assert!(matches!(
+            intersect_great_circle(&one, &other),
+            Err(IntersectError::InvalidData(m)) if m.contains("Latitude")
+        ));
+        assert!(matches!(
+            intersect_great_circle(&other, &one),
+            Err(IntersectError::InvalidData(m)) if m.contains("Latitude")
+        ));
+    }
+    // the poles themselves are valid, the longitude is not restricted
+    let one: geo::Line<f64> = [(370., -10.), (10., 90.)].into();
+    assert!(intersect_great_circle(&one, &other).is_ok());
+}
+
+#[test]
+fn great_circle_non_finite_1() {
+    let other: geo::Line<f64> = [(0., 0.), (10., 0.)].into();
+    for bad in [f64::NAN, f64::INFINITY] {
+        let one: geo::Line<f64> = [(0., 0.), (bad, 0.)].into();
+        assert!(matches!(
+            intersect_great_circle(&other, &one),
+            Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+        ));
+        // the batch version checks every line, not only the first
+        assert!(matches!(
+            great_circle_intersections(&[other, other, one], false),
+            Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+        ));
+    }
+}
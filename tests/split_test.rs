@@ -0,0 +1,95 @@
+use geo::algorithm::euclidean_length::EuclideanLength;
+use intersect2d::split::split_layers;
+
+#[test]
+fn split_1() {
+    // a route with a vertex exactly on the network, and a part running along the network
+    let route = vec![geo::LineString::from(vec![
+        (0., 0.),
+        (5., 5.),
+        (10., 5.),
+        (15., 5.),
+        (20., 0.),
+    ])];
+    let network = vec![
+        geo::LineString::from(vec![(5., 0.), (5., 10.)]),
+        geo::LineString::from(vec![(8., 5.), (12., 5.)]),
+    ];
+    let split = split_layers(&route, &network).unwrap();
+    let pieces: Vec<_> = split.a.iter().map(|p| p.line_string.clone()).collect();
+    assert_eq!(
+        pieces,
+        vec![
+            geo::LineString::from(vec![(0., 0.), (5., 5.)]),
+            geo::LineString::from(vec![(5., 5.), (8., 5.)]),
+            geo::LineString::from(vec![(8., 5.), (10., 5.)]),
+            geo::LineString::from(vec![(10., 5.), (12., 5.)]),
+            geo::LineString::from(vec![(12., 5.), (15., 5.), (20., 0.)]),
+        ]
+    );
+    assert_eq!(split.a[0].start_counterparts, Vec::<usize>::new());
+    assert_eq!(split.a[0].end_counterparts, vec![0]);
+    assert_eq!(split.a[1].end_counterparts, vec![1]);
+    assert_eq!(split.a[2].start_counterparts, vec![1]);
+    assert_eq!(split.a[3].start_counterparts, vec![1]);
+    assert_eq!(split.a[4].end_counterparts, Vec::<usize>::new());
+    assert!(split.a.iter().all(|p| p.feature == 0));
+
+    // the network: the vertical line is split at (5,5), the overlapping part at the vertex
+    // of the route inside the overlap
+    assert_eq!(split.b.len(), 4);
+    assert_eq!(
+        split.b[0].line_string,
+        geo::LineString::from(vec![(5., 0.), (5., 5.)])
+    );
+    assert_eq!(split.b[2].feature, 1);
+    assert_eq!(
+        split.b[2].line_string,
+        geo::LineString::from(vec![(8., 5.), (10., 5.)])
+    );
+    assert_eq!(split.b[2].start_counterparts, vec![0]);
+    assert_eq!(split.b[3].end_counterparts, vec![0]);
+}
+
+#[test]
+fn split_2() {
+    // the total length is unchanged by the split
+    let mut seed = 99_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 1000) as f64 / 10.0
+    };
+    let mut layer = || -> Vec<geo::LineString<f64>> {
+        (0..10)
+            .map(|_| (0..5).map(|_| (next(), next())).collect::<Vec<_>>().into())
+            .collect()
+    };
+    let (a, b) = (layer(), layer());
+    let split = split_layers(&a, &b).unwrap();
+    for (layer, pieces) in [(&a, &split.a), (&b, &split.b)].iter() {
+        for (feature, line_string) in layer.iter().enumerate() {
+            let length: f64 = pieces
+                .iter()
+                .filter(|p| p.feature == feature)
+                .map(|p| p.line_string.euclidean_length())
+                .sum();
+            assert!(approx::ulps_eq!(
+                length,
+                line_string.euclidean_length(),
+                epsilon = 1e-9
+            ));
+        }
+        assert!(pieces.len() > layer.len());
+    }
+    // every split point of a is also a split point of b
+    let count = |pieces: &[intersect2d::split::SplitPiece<f64>]| {
+        pieces
+            .iter()
+            .filter(|p| !p.end_counterparts.is_empty())
+            .count()
+    };
+    assert!(count(&split.a) > 0);
+    assert_eq!(split.a.len() - a.len(), split.b.len() - b.len());
+}
@@ -0,0 +1,139 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::streaming::{FileRunStorage, MemoryRunStorage, RunStorage, StreamingSweep};
+use intersect2d::IntersectError;
+
+/// vertical lines split into pieces, crossed by horizontal lines
+fn grid() -> Vec<geo::Line<f64>> {
+    let mut rv = Vec::new();
+    for x in 0..21 {
+        for y in 0..10 {
+            rv.push(geo::Line::new(
+                geo::Coordinate {
+                    x: x as f64,
+                    y: (y * 10) as f64,
+                },
+                geo::Coordinate {
+                    x: x as f64,
+                    y: (y * 10 + 10) as f64,
+                },
+            ));
+        }
+    }
+    for y in 0..10 {
+        rv.push(geo::Line::new(
+            geo::Coordinate {
+                x: 21.5,
+                y: (y * 10) as f64 + 5.0,
+            },
+            geo::Coordinate {
+                x: -1.5,
+                y: (y * 10) as f64 + 5.0,
+            },
+        ));
+    }
+    rv
+}
+
+#[allow(clippy::type_complexity)]
+fn streamed<S: RunStorage<f64>>(
+    storage: S,
+    lines: &[geo::Line<f64>],
+) -> Result<Vec<(geo::Coordinate<f64>, Vec<usize>)>, IntersectError> {
+    let mut rv = Vec::new();
+    StreamingSweep::new(storage)
+        .with_run_length(17)?
+        .with_slab_capacity(13)?
+        .with_ignore_end_point_intersections(true)?
+        .with_lines(lines.iter().copied())?
+        .compute(|pos, lines| rv.push((pos, lines)))?;
+    Ok(rv)
+}
+
+#[test]
+fn streaming_1() {
+    let lines = grid();
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(expected.len(), 210);
+
+    let rv = streamed(MemoryRunStorage::default(), &lines).unwrap();
+    assert_eq!(rv, expected);
+
+    let rv = streamed(FileRunStorage::new(std::env::temp_dir()), &lines).unwrap();
+    assert_eq!(rv, expected);
+}
+
+#[test]
+fn streaming_2() {
+    let lines = grid();
+    let mut rv = Vec::new();
+    StreamingSweep::new(MemoryRunStorage::default())
+        .with_slab_capacity(5)
+        .unwrap()
+        .with_ignore_end_point_intersections(true)
+        .unwrap()
+        .with_stop_at_first_intersection(true)
+        .unwrap()
+        .with_lines(lines.into_iter())
+        .unwrap()
+        .compute(|pos, lines| rv.push((pos, lines)))
+        .unwrap();
+    assert_eq!(rv.len(), 1);
+    assert!(approx::ulps_eq!(rv[0].0.y, 5.0));
+}
+
+#[test]
+fn streaming_settings_1() {
+    let mut sweep = StreamingSweep::<f64, _>::new(MemoryRunStorage::default());
+    assert!(matches!(
+        sweep.with_run_length(0),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("run length")
+    ));
+    assert!(matches!(
+        sweep.with_slab_capacity(0),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("slab capacity")
+    ));
+    // the rejected values are not used
+    let lines = intersect2d::to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.]]);
+    let mut rv = Vec::new();
+    sweep
+        .with_lines(lines.into_iter())
+        .unwrap()
+        .compute(|pos, lines| rv.push((pos, lines)))
+        .unwrap();
+    assert_eq!(
+        rv,
+        vec![(geo::Coordinate { x: 5., y: 5. }, vec![0_usize, 1])]
+    );
+}
+
+#[test]
+fn streaming_non_finite_1() {
+    for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        for line in [[bad, 0., 1., 0.], [0., 0., 1., bad]] {
+            let mut sweep = StreamingSweep::<f64, _>::new(MemoryRunStorage::default());
+            assert!(matches!(
+                sweep.with_lines(intersect2d::to_lines(&[line]).into_iter()),
+                Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+            ));
+        }
+    }
+}
+
+#[test]
+fn streaming_missing_run_1() {
+    assert!(matches!(
+        RunStorage::<f64>::load_run(&MemoryRunStorage::default(), 0),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("#0")
+    ));
+    assert!(matches!(
+        RunStorage::<f64>::load_run(&FileRunStorage::new(std::env::temp_dir()), 3),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("#3")
+    ));
+}
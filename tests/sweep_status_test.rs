@@ -0,0 +1,143 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::oracle::self_intersections_bruteforce;
+use intersect2d::{intersect, to_lines, IntersectError};
+
+#[test]
+fn sweep_status_1() -> Result<(), IntersectError> {
+    // a grid where some intersections are computed slightly below the sweep-line
+    let mut coords = Vec::new();
+    for i in 0..=10 {
+        let v = i as f64 * 10.0;
+        coords.push([-1.0, v, 101.0, v]);
+        coords.push([v, -1.0, v, 101.0]);
+    }
+    let lines = to_lines(&coords);
+    let rv = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?
+        .compute()?;
+    assert_eq!(rv.len(), 121);
+    Ok(())
+}
+
+#[test]
+fn sweep_status_2() -> Result<(), IntersectError> {
+    // two lines starting at the same point, the right one of them must be the neighbour
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 0., -5., 5.], [10., 0., 0., 10.]]);
+    let rv: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(rv, vec![(geo::Coordinate { x: 5., y: 5. }, vec![0, 2])]);
+    Ok(())
+}
+
+#[test]
+fn sweep_status_4() -> Result<(), IntersectError> {
+    // The crossing of lines 1 and 2 is rounded, and line 1 is so flat that it misses the
+    // rounded point by more than the tolerance. The lines must still swap places, so that
+    // line 2 becomes the neighbour of line 0.
+    let lines = to_lines::<_, f64>(&[[10., 7.5, 0., 2.5], [0., 2.5, 10., 5.], [0., 0., 2.5, 5.]]);
+    let rv: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    assert_eq!(rv.len(), 2);
+    assert_eq!(rv[0].1, vec![1, 2]);
+    assert_eq!(rv[1].1, vec![0, 2]);
+    approx::assert_abs_diff_eq!(rv[1].0.x, 5. / 3., epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(rv[1].0.y, 10. / 3., epsilon = 1e-9);
+    Ok(())
+}
+
+#[test]
+fn sweep_status_3() -> Result<(), IntersectError> {
+    // compare against brute force on pseudo random data
+    let mut seed = 12345_u64;
+    let mut random = || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as f64 / (1_u64 << 31) as f64 * 1000.0
+    };
+    let lines: Vec<geo::Line<f64>> = (0..300)
+        .map(|_| {
+            let (x, y) = (random(), random());
+            geo::Line::new(
+                geo::Coordinate { x, y },
+                geo::Coordinate {
+                    x: x + random() / 10.0 - 50.0,
+                    y: y + random() / 10.0 - 50.0,
+                },
+            )
+        })
+        .collect();
+    let mut expected = Vec::new();
+    for i in 0..lines.len() {
+        for j in i + 1..lines.len() {
+            if intersect(&lines[i], &lines[j]).is_some() {
+                expected.push((i, j));
+            }
+        }
+    }
+    let mut found = Vec::new();
+    for (_, involved) in AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(true)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+    {
+        for (n, i) in involved.iter().enumerate() {
+            for j in involved.iter().skip(n + 1) {
+                found.push((*i.min(j), *i.max(j)));
+            }
+        }
+    }
+    found.sort_unstable();
+    assert!(!expected.is_empty());
+    assert_eq!(found, expected);
+    Ok(())
+}
+
+#[test]
+fn sweep_status_5() -> Result<(), IntersectError> {
+    // random segments on a small integer grid, every sixth of them a point. A point
+    // segment is added and dropped by the same event, it must never stay in the status.
+    for seed in 1..=200_u64 {
+        let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 12) as f64
+        };
+        let coords: Vec<[f64; 4]> = (0..12)
+            .map(|i| {
+                let (x, y) = (next(), next());
+                if i % 6 == 2 {
+                    [x, y, x, y]
+                } else {
+                    [x, y, next(), next()]
+                }
+            })
+            .collect();
+        let lines = to_lines::<_, f64>(&coords);
+        for ignore_end_point_intersections in [true, false] {
+            let sweep: Vec<_> = AlgorithmData::<f64>::default()
+                .with_ignore_end_point_intersections(ignore_end_point_intersections)?
+                .with_ref_lines(lines.iter())?
+                .compute()?
+                .collect();
+            let oracle: Vec<_> =
+                self_intersections_bruteforce(&lines, ignore_end_point_intersections)?.collect();
+            assert_eq!(oracle.len(), sweep.len(), "seed {}: {:?}", seed, coords);
+            for (o, s) in oracle.iter().zip(sweep.iter()) {
+                approx::assert_abs_diff_eq!(o.0.x, s.0.x, epsilon = 1e-9);
+                approx::assert_abs_diff_eq!(o.0.y, s.0.y, epsilon = 1e-9);
+                assert_eq!(o.1, s.1, "seed {}: {:?}", seed, coords);
+            }
+        }
+    }
+    Ok(())
+}
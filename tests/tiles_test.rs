@@ -0,0 +1,158 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::tiles::{compute_tiled, compute_tiled_parallel, merge_tile_results, TileGrid};
+use intersect2d::to_lines;
+
+#[test]
+fn tiles_1() -> Result<(), intersect2d::IntersectError> {
+    // a grid of horizontal and vertical lines, crossing on the tile seams as well
+    let mut coords = Vec::new();
+    for i in 0..=25 {
+        let v = i as f64 * 4.0;
+        coords.push([-28.0, v, 100.0, v]);
+        coords.push([v, -28.0, v, 100.0]);
+    }
+    let lines = to_lines(&coords);
+    let mut expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ignore_end_point_intersections(false)?
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .map(|(p, mut l)| {
+            l.sort_unstable();
+            (p, l)
+        })
+        .collect();
+    expected.sort_by(|a, b| {
+        a.0.y
+            .partial_cmp(&b.0.y)
+            .unwrap()
+            .then(a.0.x.partial_cmp(&b.0.x).unwrap())
+    });
+
+    for (columns, rows) in [(1, 1), (2, 2), (3, 7), (5, 5)].iter() {
+        let rv = compute_tiled(&lines, *columns, *rows, false)?;
+        assert_eq!(rv.len(), 26 * 26);
+        assert_eq!(rv, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn tiles_2() -> Result<(), intersect2d::IntersectError> {
+    let lines = to_lines(&[[0., 0., 10., 10.], [10., 0., 0., 10.], [20., 0., 20., 10.]]);
+    let grid = TileGrid::from_lines(&lines, 4, 4)?;
+    let tiles = grid.split(&lines)?;
+    assert!(tiles.iter().all(|t| !t.lines.is_empty()));
+    let results: Vec<_> = tiles
+        .iter()
+        .map(|t| grid.compute_tile(t, &lines, true))
+        .collect::<Result<_, _>>()?;
+    // only one tile owns the (5,5) crossing
+    assert_eq!(results.iter().filter(|r| !r.is_empty()).count(), 1);
+    let rv = merge_tile_results(results);
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].0, geo::Coordinate { x: 5., y: 5. });
+    assert_eq!(rv[0].1, vec![0, 1]);
+
+    assert!(TileGrid::from_lines(&lines, 0, 1).is_err());
+    Ok(())
+}
+
+#[test]
+fn tiles_3() -> Result<(), intersect2d::IntersectError> {
+    // random lines, the merged result must not depend on the order the tiles are merged in
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 10_000) as f64 / 100.0
+    };
+    let coords: Vec<[f64; 4]> = (0..300).map(|_| [next(), next(), next(), next()]).collect();
+    let lines = to_lines(&coords);
+    let grid = TileGrid::from_lines(&lines, 5, 3)?;
+    let results: Vec<_> = grid
+        .split(&lines)?
+        .iter()
+        .map(|t| grid.compute_tile(t, &lines, false))
+        .collect::<Result<_, _>>()?;
+    let expected = merge_tile_results(results.clone());
+    let bits = |r: &[(geo::Coordinate<f64>, Vec<usize>)]| -> Vec<(u64, u64, Vec<usize>)> {
+        r.iter()
+            .map(|(p, l)| (p.x.to_bits(), p.y.to_bits(), l.clone()))
+            .collect()
+    };
+    for rotation in 0..results.len() {
+        let mut shuffled = results.clone();
+        shuffled.rotate_left(rotation);
+        if rotation % 2 == 1 {
+            shuffled.reverse();
+            for r in shuffled.iter_mut() {
+                r.reverse();
+                for (_, l) in r.iter_mut() {
+                    l.reverse();
+                }
+            }
+        }
+        assert_eq!(bits(&merge_tile_results(shuffled)), bits(&expected));
+    }
+    Ok(())
+}
+
+#[test]
+fn tiles_parallel_1() -> Result<(), intersect2d::IntersectError> {
+    // clustered data: most of the lines are crowded into one corner
+    let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 10_000) as f64 / 10_000.0
+    };
+    let mut coords = Vec::<[f64; 4]>::new();
+    for i in 0..400 {
+        let scale = if i % 10 == 0 { 100.0 } else { 10.0 };
+        coords.push([
+            next() * scale,
+            next() * scale,
+            next() * scale,
+            next() * scale,
+        ]);
+    }
+    let lines = to_lines::<_, f64>(&coords);
+    for threads in [1, 2, 5].iter() {
+        for (columns, rows) in [(1, 1), (4, 4), (16, 16)].iter() {
+            let expected = compute_tiled(&lines, *columns, *rows, false)?;
+            let rv = compute_tiled_parallel(&lines, *columns, *rows, false, *threads)?;
+            assert_eq!(rv, expected);
+        }
+    }
+    assert!(compute_tiled_parallel(&lines, 2, 2, false, 0).is_err());
+    Ok(())
+}
+
+#[test]
+fn tiles_4() -> Result<(), intersect2d::IntersectError> {
+    // Some points on the seams are rounded differently by the tiles on each side, e.g. the
+    // crossing at x = 7.5 is computed as 7.499999999999999 by one of them. They must still be
+    // reported once.
+    let mut seed = 10_u64.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 16) as f64
+    };
+    let coords: Vec<[f64; 4]> = (0..40).map(|_| [next(), next(), next(), next()]).collect();
+    let lines = to_lines(&coords);
+    let expected: Vec<_> = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())?
+        .compute()?
+        .collect();
+    let rv = compute_tiled(&lines, 4, 4, false)?;
+    assert_eq!(rv.len(), expected.len());
+    for ((p1, l1), (p2, l2)) in rv.iter().zip(expected.iter()) {
+        assert!(intersect2d::ulps_eq_c(p1, p2));
+        assert_eq!(l1, l2);
+    }
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::{
+    intersect_with_tolerance, to_lines, ulps_eq_c, ulps_eq_c_with, EqualityMode, IntersectError,
+    Intersection, Tolerance,
+};
+
+#[test]
+fn tolerance_default_1() {
+    let tolerance = Tolerance::<f64>::default();
+    for (a, b) in [
+        (1.0, 1.0 + f64::EPSILON),
+        (0.0, 1e-300),
+        (1e10, 1e10 + 1e-5),
+        (100.0, 100.1),
+        (f64::INFINITY, f64::INFINITY),
+    ]
+    .iter()
+    {
+        assert_eq!(tolerance.ulps_eq(*a, *b), approx::ulps_eq!(a, b));
+    }
+}
+
+#[test]
+fn tolerance_from_extent_1() {
+    let rect = geo::Rect::new(
+        geo::Coordinate { x: -1000.0, y: 0.0 },
+        geo::Coordinate { x: 1000.0, y: 1.0 },
+    );
+    let tolerance = Tolerance::from_extent(&rect, 1e-6);
+    assert!(approx::ulps_eq!(tolerance.epsilon, 2e-3));
+    assert!(tolerance.ulps_eq_c(
+        &geo::Coordinate { x: 0.0, y: 0.0 },
+        &geo::Coordinate {
+            x: 0.001,
+            y: -0.001
+        }
+    ));
+    assert!(!tolerance.ulps_eq(0.0, 0.003));
+
+    // a degenerate extent never goes below the machine epsilon
+    let rect = geo::Rect::new(
+        geo::Coordinate { x: 1.0, y: 1.0 },
+        geo::Coordinate { x: 1.0, y: 1.0 },
+    );
+    let tolerance = Tolerance::from_extent(&rect, 1e-9);
+    assert!(approx::ulps_eq!(tolerance.epsilon, f64::EPSILON));
+}
+
+#[test]
+fn tolerance_algorithm_1() {
+    // two segments separated by a small gap at (10,0)
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [10.001, -5., 10.001, 5.],
+        [20., 10., 30., 10.],
+    ]);
+    let rv = AlgorithmData::<f64>::default()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap();
+    assert_eq!(rv.len(), 0);
+
+    let rv: Vec<_> = AlgorithmData::<f64>::default()
+        .with_tolerance(Tolerance::new(0.01, 4))
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].1, vec![0_usize, 1]);
+}
+
+#[test]
+fn tolerance_equality_mode_1() {
+    // the same gap as in tolerance_algorithm_1, close to the origin
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10e-10, 0.],
+        [10.001e-10, -5e-10, 10.001e-10, 5e-10],
+        [20e-10, 10e-10, 30e-10, 10e-10],
+    ]);
+    let count = |tolerance: Tolerance<f64>| {
+        AlgorithmData::<f64>::default()
+            .with_tolerance(tolerance)
+            .unwrap()
+            .with_ref_lines(lines.iter())
+            .unwrap()
+            .compute()
+            .unwrap()
+            .count()
+    };
+    assert_eq!(count(Tolerance::default()), 0);
+    let absolute = Tolerance::new(1e-12, 4).with_equality_mode(EqualityMode::Absolute);
+    assert_eq!(count(absolute), 1);
+    let relative = Tolerance::new(1e-3, 4).with_equality_mode(EqualityMode::Relative);
+    assert_eq!(count(relative), 1);
+
+    assert!(intersect_with_tolerance(&lines[0], &lines[1], &Tolerance::default()).is_none());
+    assert!(intersect_with_tolerance(&lines[0], &lines[1], &absolute).is_some());
+    assert!(intersect_with_tolerance(&lines[0], &lines[1], &relative).is_some());
+}
+
+#[test]
+fn tolerance_after_lines_1() {
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 0.], [10.001, -5., 10.001, 5.]]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter()).unwrap();
+    assert!(matches!(
+        ad.with_tolerance(Tolerance::new(0.01, 4)),
+        Err(IntersectError::InvalidParameter(_))
+    ));
+    // the rejected tolerance is not used, the gap is still reported as a miss
+    assert_eq!(ad.compute().unwrap().count(), 0);
+}
+
+#[test]
+fn tolerance_epsilon_1() {
+    for epsilon in [-0.01, f64::NAN, f64::INFINITY] {
+        let mut ad = AlgorithmData::<f64>::default();
+        assert!(matches!(
+            ad.with_tolerance(Tolerance::new(epsilon, 4)),
+            Err(IntersectError::InvalidParameter(_))
+        ));
+    }
+    let mut ad = AlgorithmData::<f64>::default();
+    assert!(ad.with_tolerance(Tolerance::new(0.0, 4)).is_ok());
+}
+
+#[test]
+fn tolerance_thresholds_1() {
+    assert!(matches!(
+        Tolerance::<f64>::default().with_parallel_threshold(-1.0),
+        Err(IntersectError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        Tolerance::<f64>::default().with_collinear_threshold(f64::INFINITY),
+        Err(IntersectError::InvalidParameter(_))
+    ));
+    let tolerance = Tolerance::<f64> {
+        parallel: Some(f64::NAN),
+        ..Default::default()
+    };
+    let mut ad = AlgorithmData::<f64>::default();
+    assert!(matches!(
+        ad.with_tolerance(tolerance),
+        Err(IntersectError::InvalidParameter(_))
+    ));
+
+    // nearly parallel lines, far apart
+    let line1: geo::Line<f64> = [(0., 0.), (1000., 0.)].into();
+    let line2: geo::Line<f64> = [(0., 0.001), (1000., -0.001)].into();
+    assert!(matches!(
+        intersect_with_tolerance(&line1, &line2, &Tolerance::default()),
+        Some(Intersection::Intersection(_))
+    ));
+    let parallel = Tolerance::default().with_parallel_threshold(0.01).unwrap();
+    assert!(intersect_with_tolerance(&line1, &line2, &parallel).is_none());
+    let collinear = parallel.with_collinear_threshold(1.0).unwrap();
+    assert!(matches!(
+        intersect_with_tolerance(&line1, &line2, &collinear),
+        Some(Intersection::OverLap(_))
+    ));
+}
+
+#[test]
+fn ulps_eq_c_with_1() {
+    let c = |x: f64, y: f64| geo::Coordinate { x, y };
+    // near zero the default epsilon is far too tight for projected coordinates
+    let (a, b) = (c(1.0e-9, 0.0), c(0.0, -1.0e-9));
+    assert!(!ulps_eq_c(&a, &b));
+    assert!(ulps_eq_c_with(&a, &b, 1.0e-8, 4));
+    // far from zero the ulps decide, one ulp of 1e6 is about 1.2e-10
+    let (a, b) = (c(1.0e6, 1.0e6), c(1.0e6 + 1.0e-9, 1.0e6));
+    assert!(!ulps_eq_c(&a, &b));
+    assert!(!ulps_eq_c_with(&a, &b, 0.0, 4));
+    assert!(ulps_eq_c_with(&a, &b, 0.0, 16));
+    // the defaults are the ones of ulps_eq_c
+    for (a, b) in [
+        (c(1.0, 2.0), c(1.0 + f64::EPSILON, 2.0)),
+        (c(0.0, 0.0), c(1.0e-20, 0.0)),
+        (c(3.0, 4.0), c(3.0, 4.0001)),
+    ] {
+        assert_eq!(ulps_eq_c(&a, &b), ulps_eq_c_with(&a, &b, f64::EPSILON, 4));
+    }
+}
+
+#[test]
+fn self_intersecting_end_points_1() -> Result<(), IntersectError> {
+    use intersect2d::SelfIntersectingExclusive;
+    // the lines meet at end points one ulp apart, the default tolerance skips the pair
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 0.],
+        [9.999999999999998, 0., 10., 10.],
+        [10., 10., 0., 10.],
+    ]);
+    let tolerance = Tolerance::default();
+    assert!(!lines.is_self_intersecting()?);
+    assert!(!lines.is_self_intersecting_with_tolerance(&tolerance)?);
+    assert_eq!(lines.self_intersections()?.count(), 0);
+    assert_eq!(
+        lines.self_intersections_with_tolerance(&tolerance)?.count(),
+        0
+    );
+    // without a tolerance the second line starts inside the first one
+    let exact = Tolerance::new(0.0, 0).with_equality_mode(EqualityMode::Absolute);
+    assert!(lines.is_self_intersecting_with_tolerance(&exact)?);
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use intersect2d::topology::{validate_topology, TopologyIssue};
+
+#[test]
+fn topology_1() {
+    // a clean network: three features meeting at a node, closed ring elsewhere
+    let layer = vec![
+        geo::LineString::from(vec![(0., 0.), (10., 0.)]),
+        geo::LineString::from(vec![(10., 0.), (20., 0.)]),
+        geo::LineString::from(vec![(10., 0.), (10., 10.)]),
+        geo::LineString::from(vec![(100., 100.), (200., 100.), (200., 200.), (100., 100.)]),
+    ];
+    let issues = validate_topology(&layer).unwrap();
+    assert_eq!(
+        issues,
+        vec![
+            TopologyIssue::Dangle {
+                feature: 0,
+                position: geo::Coordinate { x: 0., y: 0. }
+            },
+            TopologyIssue::Dangle {
+                feature: 1,
+                position: geo::Coordinate { x: 20., y: 0. }
+            },
+            TopologyIssue::Dangle {
+                feature: 2,
+                position: geo::Coordinate { x: 10., y: 10. }
+            },
+        ]
+    );
+}
+
+#[test]
+fn topology_2() {
+    let layer = vec![geo::LineString::from(vec![
+        (100., 100.),
+        (200., 100.),
+        (200., 200.),
+        (150., 50.),
+    ])];
+    let issues = validate_topology(&layer).unwrap();
+    assert_eq!(
+        issues
+            .iter()
+            .filter(|i| matches!(i, TopologyIssue::SelfIntersection { feature: 0, .. }))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn topology_3() {
+    // a T-junction is not a dangle, but an interior intersection
+    let layer = vec![
+        geo::LineString::from(vec![(0., 0.), (20., 0.)]),
+        geo::LineString::from(vec![(10., 0.), (10., 10.)]),
+    ];
+    let issues = validate_topology(&layer).unwrap();
+    assert!(issues.contains(&TopologyIssue::InteriorIntersection {
+        features: vec![0, 1],
+        position: geo::Coordinate { x: 10., y: 0. }
+    }));
+    assert!(!issues.contains(&TopologyIssue::Dangle {
+        feature: 1,
+        position: geo::Coordinate { x: 10., y: 0. }
+    }));
+    assert!(issues.contains(&TopologyIssue::Dangle {
+        feature: 1,
+        position: geo::Coordinate { x: 10., y: 10. }
+    }));
+}
+
+#[test]
+fn topology_4() {
+    // collinear overlap
+    let layer = vec![
+        geo::LineString::from(vec![(0., 0.), (20., 0.)]),
+        geo::LineString::from(vec![(10., 0.), (30., 0.)]),
+    ];
+    let issues = validate_topology(&layer).unwrap();
+    let overlaps: Vec<_> = issues
+        .iter()
+        .filter_map(|i| match i {
+            TopologyIssue::Overlap { features, overlap } => Some((*features, *overlap)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(overlaps.len(), 1);
+    assert_eq!(overlaps[0].0, (0, 1));
+    assert!(intersect2d::ulps_eq_c(
+        &overlaps[0].1.start,
+        &geo::Coordinate { x: 10., y: 0. }
+    ));
+    assert!(intersect2d::ulps_eq_c(
+        &overlaps[0].1.end,
+        &geo::Coordinate { x: 20., y: 0. }
+    ));
+}
+
+#[test]
+fn topology_5() {
+    // repeated vertices don't make the neighbouring segments non-adjacent
+    let layer = vec![
+        geo::LineString::from(vec![(0., 0.), (1., 0.), (1., 0.), (2., 1.)]),
+        geo::LineString::from(vec![
+            (10., 0.),
+            (10., 0.),
+            (20., 0.),
+            (20., 10.),
+            (20., 10.),
+            (10., 0.),
+        ]),
+    ];
+    let issues = validate_topology(&layer).unwrap();
+    // only the two end points of the open feature dangle
+    assert_eq!(
+        issues,
+        vec![
+            TopologyIssue::Dangle {
+                feature: 0,
+                position: geo::Coordinate { x: 0., y: 0. }
+            },
+            TopologyIssue::Dangle {
+                feature: 0,
+                position: geo::Coordinate { x: 2., y: 1. }
+            },
+        ]
+    );
+}
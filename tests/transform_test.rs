@@ -0,0 +1,66 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn transform_1() -> Result<(), IntersectError> {
+    // lon/lat to a crude local metric projection around (10E, 60N)
+    let lines = to_lines::<_, f64>(&[
+        [9.9, 59.9, 10.1, 60.1],
+        [9.9, 60.1, 10.1, 59.9],
+        [10.2, 59.9, 10.2, 60.1],
+    ]);
+    let cos_lat = 60.0_f64.to_radians().cos();
+    let mut ad = AlgorithmData::<f64>::default();
+    let rv: Vec<_> = ad
+        .with_transform(move |c| geo::Coordinate {
+            x: (c.x - 10.0) * 111_320.0 * cos_lat,
+            y: (c.y - 60.0) * 111_320.0,
+        })?
+        .with_lines(lines.into_iter())?
+        .compute()?
+        .collect();
+    assert_eq!(rv.len(), 1);
+    approx::assert_abs_diff_eq!(rv[0].0.x, 0.0, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(rv[0].0.y, 0.0, epsilon = 1e-6);
+    assert_eq!(rv[0].1, vec![0, 1]);
+    // the stored lines are transformed as well
+    approx::assert_abs_diff_eq!(ad.get_lines()[0].end.y, 11_132.0, epsilon = 1e-6);
+    Ok(())
+}
+
+#[test]
+fn transform_after_lines_1() -> Result<(), IntersectError> {
+    let lines = to_lines::<_, f64>(&[[0., 0., 1., 1.], [0., 1., 1., 0.]]);
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_ref_lines(lines.iter())?;
+    assert!(matches!(
+        ad.with_transform(|c| geo::Coordinate { x: c.x * 100.0, y: c.y }),
+        Err(IntersectError::InvalidParameter(m)) if m.contains("transform")
+    ));
+    // the rejected transform is not applied
+    let rv: Vec<_> = ad.compute()?.collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].0, geo::Coordinate { x: 0.5, y: 0.5 });
+    Ok(())
+}
+
+#[test]
+fn transform_non_finite_1() -> Result<(), IntersectError> {
+    // the transform is only non-finite at x == 0
+    let mut ad = AlgorithmData::<f64>::default();
+    let _ = ad.with_transform(|c| geo::Coordinate {
+        x: 1.0 / c.x,
+        y: c.y,
+    })?;
+    let lines = to_lines::<_, f64>(&[[1., 0., 2., 1.], [0., 1., 1., 0.]]);
+    assert!(matches!(
+        ad.with_ref_lines(lines.iter()),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+    // none of the lines of the rejected call are kept
+    let lines = to_lines::<_, f64>(&[[1., 1., 0.5, 0.], [0.5, 1., 1., 0.]]);
+    let rv: Vec<_> = ad.with_ref_lines(lines.iter())?.compute()?.collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].1, vec![0_usize, 1]);
+    Ok(())
+}
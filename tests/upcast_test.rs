@@ -0,0 +1,86 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::Tolerance;
+
+/// random segments on a large coordinate map, where f32 has a resolution of ~0.01
+fn game_map(size: usize) -> Vec<geo::Line<f32>> {
+    let mut seed = 12345_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 100_000) as f32 / 100.0 + 100000.0
+    };
+    let coords: Vec<[f32; 4]> = (0..size)
+        .map(|_| [next(), next(), next(), next()])
+        .collect();
+    intersect2d::to_lines::<_, f32>(&coords)
+}
+
+#[test]
+fn upcast_1() {
+    let lines = game_map(200);
+    let compute = |upcast: bool| {
+        let mut ad = AlgorithmData::<f32>::default();
+        let results: Vec<_> = ad
+            .with_f64_upcast(upcast)
+            .unwrap()
+            .with_ref_lines(lines.iter())
+            .unwrap()
+            .compute()
+            .unwrap()
+            .collect();
+        let discrepancies = ad.verify_results(&results, 1000).len();
+        (results, discrepancies)
+    };
+    let (plain, plain_discrepancies) = compute(false);
+    let (upcast, upcast_discrepancies) = compute(true);
+    assert!(upcast.len() > plain.len());
+    assert!(upcast_discrepancies * 100 < plain_discrepancies);
+}
+
+#[test]
+fn upcast_2() {
+    let lines = game_map(40);
+    // the tolerance is carried over to the f64 run
+    let gap = intersect2d::to_lines::<_, f32>(&[[0., 0., 10., 0.], [10.001, -5., 10.001, 5.]]);
+    let rv: Vec<_> = AlgorithmData::<f32>::default()
+        .with_tolerance(Tolerance::new(0.01, 4))
+        .unwrap()
+        .with_f64_upcast(true)
+        .unwrap()
+        .with_ref_lines(gap.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(rv.len(), 1);
+    assert_eq!(rv[0].1, vec![0, 1]);
+
+    // already f64 data is unchanged by the upcast
+    let lines: Vec<geo::Line<f64>> = lines
+        .iter()
+        .map(|l| {
+            geo::Line::new(
+                geo::Coordinate {
+                    x: l.start.x as f64,
+                    y: l.start.y as f64,
+                },
+                geo::Coordinate {
+                    x: l.end.x as f64,
+                    y: l.end.y as f64,
+                },
+            )
+        })
+        .collect();
+    let compute = |upcast: bool| -> Vec<_> {
+        AlgorithmData::<f64>::default()
+            .with_f64_upcast(upcast)
+            .unwrap()
+            .with_ref_lines(lines.iter())
+            .unwrap()
+            .compute()
+            .unwrap()
+            .collect()
+    };
+    assert_eq!(compute(true), compute(false));
+}
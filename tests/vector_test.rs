@@ -0,0 +1,26 @@
+use intersect2d::vector::{cross_z, div, dot, lerp, norm, perp, scale_to_coordinate};
+
+#[test]
+fn vector_1() {
+    let a = geo::Coordinate { x: 3., y: 4. };
+    let b = geo::Coordinate { x: -2., y: 1. };
+    assert_eq!(dot(&a, &b), -2.0);
+    assert_eq!(cross_z(&a, &b), 11.0);
+    assert_eq!(cross_z(&b, &a), -11.0);
+    assert_eq!(norm(&a), 5.0);
+    assert_eq!(div(&a, 2.0), geo::Coordinate { x: 1.5, y: 2. });
+    assert_eq!(perp(&a), geo::Coordinate { x: -4., y: 3. });
+    // perp is orthogonal, and to the left
+    assert_eq!(dot(&a, &perp(&a)), 0.0);
+    assert!(cross_z(&a, &perp(&a)) > 0.0);
+    assert_eq!(
+        scale_to_coordinate(&a, &b, 2.0),
+        geo::Coordinate { x: -1., y: 6. }
+    );
+    assert_eq!(lerp(&a, &b, 0.5), geo::Coordinate { x: 0.5, y: 2.5 });
+    // the crate root re-export is the same function
+    assert_eq!(
+        intersect2d::scale_to_coordinate(&a, &b, 2.0),
+        scale_to_coordinate(&a, &b, 2.0)
+    );
+}
@@ -0,0 +1,63 @@
+use intersect2d::algorithm::AlgorithmData;
+use intersect2d::generators::{collinear_stack, lattice};
+use intersect2d::verify::{verify_results, Discrepancy};
+use intersect2d::{to_lines, Tolerance};
+
+fn check(lines: &[geo::Line<f64>], ignore_end_point_intersections: bool) {
+    let mut ad = AlgorithmData::<f64>::default();
+    let results: Vec<_> = ad
+        .with_ignore_end_point_intersections(ignore_end_point_intersections)
+        .unwrap()
+        .with_ref_lines(lines.iter())
+        .unwrap()
+        .compute()
+        .unwrap()
+        .collect();
+    assert_eq!(ad.verify_results(&results, 1000), vec![]);
+}
+
+#[test]
+fn verify_1() {
+    let mut seed = 0x0bad_cafe_dead_beef_u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 10_000) as f64 / 100.0
+    };
+    let coords: Vec<[f64; 4]> = (0..60).map(|_| [next(), next(), next(), next()]).collect();
+    let lines = to_lines::<_, f64>(&coords);
+    check(&lines, false);
+    check(&lines, true);
+    check(&lattice::<f64>(4, 1), false);
+    check(&lattice::<f64>(4, 1), true);
+    check(&collinear_stack::<f64>(6, 1), false);
+    check(&collinear_stack::<f64>(6, 1), true);
+}
+
+#[test]
+fn verify_2() {
+    let lines = to_lines::<_, f64>(&[[0., 0., 10., 10.], [0., 10., 10., 0.], [5., 0., 5., 10.]]);
+    let tolerance = Tolerance::default();
+    let position = geo::Coordinate { x: 5., y: 5. };
+    // a missed line
+    let rv = verify_results(&lines, &[(position, vec![0, 1])], false, &tolerance, 10);
+    assert_eq!(rv.len(), 2);
+    assert!(rv.contains(&Discrepancy::Missed {
+        lines: (0, 2),
+        position
+    }));
+    // the brute force pass is skipped for large inputs
+    assert!(verify_results(&lines, &[(position, vec![0, 1])], false, &tolerance, 2).is_empty());
+    // a bad index
+    let rv = verify_results(
+        &lines,
+        &[(position, vec![0, 1, 2, 3])],
+        false,
+        &tolerance,
+        10,
+    );
+    assert_eq!(rv, vec![Discrepancy::InvalidLine { position, line: 3 }]);
+    // nothing reported at all
+    assert_eq!(verify_results(&lines, &[], true, &tolerance, 10).len(), 3);
+}
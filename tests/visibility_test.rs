@@ -0,0 +1,74 @@
+use geo::algorithm::area::Area;
+use intersect2d::visibility::visibility_polygon;
+use intersect2d::IntersectError;
+
+fn room() -> Vec<geo::Line<f64>> {
+    intersect2d::to_lines(&[
+        [0., 0., 10., 0.],
+        [10., 0., 10., 10.],
+        [10., 10., 0., 10.],
+        [0., 10., 0., 0.],
+    ])
+}
+
+#[test]
+fn visibility_1() {
+    let mut walls = room();
+    walls.push(geo::Line::new(
+        geo::Coordinate { x: 0., y: 5. },
+        geo::Coordinate { x: 5., y: 5. },
+    ));
+    let visible = visibility_polygon(geo::Coordinate { x: 8., y: 2. }, &walls, 100.).unwrap();
+    // the shadow of the wall is the triangle (0,5) (5,5) (0,10)
+    assert!(approx::ulps_eq!(
+        visible.unsigned_area(),
+        87.5,
+        epsilon = 1e-6
+    ));
+
+    // from above the wall the room below it is partly hidden, the wall ends at (5,5)
+    let visible = visibility_polygon(geo::Coordinate { x: 2., y: 8. }, &walls, 100.).unwrap();
+    assert!(visible.unsigned_area() < 50.0 + 37.5);
+}
+
+#[test]
+fn visibility_2() {
+    // nothing in the way, the axis directions span a diamond
+    let visible = visibility_polygon(geo::Coordinate { x: 1., y: 1. }, &[], 1.).unwrap();
+    assert!(approx::ulps_eq!(
+        visible.unsigned_area(),
+        2.0,
+        epsilon = 1e-9
+    ));
+    assert_eq!(visible.exterior().0.len(), 5);
+}
+
+#[test]
+fn visibility_max_distance_1() {
+    let origin = geo::Coordinate { x: 1., y: 1. };
+    for max_distance in [0., -1., f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            visibility_polygon(origin, &room(), max_distance),
+            Err(IntersectError::InvalidParameter(m)) if m.contains("maximum distance")
+        ));
+    }
+}
+
+#[test]
+fn visibility_non_finite_1() {
+    let origin = geo::Coordinate { x: 1., y: 1. };
+    let mut lines = room();
+    lines.push([(5., 5.), (f64::NAN, 5.)].into());
+    assert!(matches!(
+        visibility_polygon(origin, &lines, 1.),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+    let origin = geo::Coordinate {
+        x: f64::INFINITY,
+        y: 1.,
+    };
+    assert!(matches!(
+        visibility_polygon(origin, &room(), 1.),
+        Err(IntersectError::InvalidData(m)) if m.contains("query point")
+    ));
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "writer")]
+use intersect2d::streaming::{MemoryRunStorage, StreamingSweep};
+use intersect2d::writer::{GeoJsonSeqWriter, IntersectionSink};
+use intersect2d::{to_lines, IntersectError};
+
+#[test]
+fn writer_1() -> Result<(), IntersectError> {
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [10., 0., 0., 10.],
+        [0., 8., 10., 8.],
+        [20., 0., 20., 10.],
+    ]);
+    let names = ["a", "b\"quoted\"", "c", "d"];
+    let mut buffer = Vec::<u8>::new();
+    let mut writer =
+        GeoJsonSeqWriter::new(&mut buffer).with_attribute("name", |i| names[i].to_string());
+    StreamingSweep::new(MemoryRunStorage::default())
+        .with_slab_capacity(2)?
+        .with_ignore_end_point_intersections(true)?
+        .with_lines(lines.iter().copied())?
+        .compute_into(&mut writer)?;
+    assert_eq!(writer.written(), 3);
+    drop(writer);
+    let text = String::from_utf8(buffer).unwrap();
+    let rows: Vec<&str> = text.lines().collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        rows[0],
+        r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[5,5]},"properties":{"lines":[0,1],"name":["a","b\"quoted\""]}}"#
+    );
+    assert!(rows[1].contains(r#""coordinates":[2,8]"#));
+    assert!(rows[2].contains(r#""coordinates":[8,8]"#));
+    Ok(())
+}
+
+/// Fails on the write after `fail_after` successful writes
+struct FailingSink {
+    fail_after: usize,
+    written: usize,
+    finished: bool,
+}
+
+impl IntersectionSink<f64> for FailingSink {
+    fn write(&mut self, _: geo::Coordinate<f64>, _: &[usize]) -> Result<(), IntersectError> {
+        if self.written == self.fail_after {
+            return Err(IntersectError::IoError("disk full".to_string()));
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), IntersectError> {
+        self.finished = true;
+        Ok(())
+    }
+}
+
+#[test]
+fn writer_sink_error_1() {
+    // three intersections, the sink fails on the second one
+    let lines = to_lines::<_, f64>(&[
+        [0., 0., 10., 10.],
+        [10., 0., 0., 10.],
+        [20., 0., 30., 10.],
+        [30., 0., 20., 10.],
+        [40., 0., 50., 10.],
+        [50., 0., 40., 10.],
+    ]);
+    let mut sink = FailingSink {
+        fail_after: 1,
+        written: 0,
+        finished: false,
+    };
+    let rv = StreamingSweep::new(MemoryRunStorage::default())
+        .with_lines(lines.iter().copied())
+        .unwrap()
+        .compute_into(&mut sink);
+    assert!(matches!(rv, Err(IntersectError::IoError(m)) if m == "disk full"));
+    // nothing is written after the first error, and the sink is not finished
+    assert_eq!(sink.written, 1);
+    assert!(!sink.finished);
+}
+
+struct FailingWrite;
+
+impl std::io::Write for FailingWrite {
+    fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writer_io_error_1() {
+    let mut writer = GeoJsonSeqWriter::new(FailingWrite);
+    assert!(matches!(
+        writer.write(geo::Coordinate { x: 1., y: 2. }, &[0, 1]),
+        Err(IntersectError::IoError(m)) if m.contains("broken pipe")
+    ));
+    assert_eq!(writer.written(), 0);
+}
+
+#[test]
+fn writer_non_finite_1() {
+    let mut out = Vec::<u8>::new();
+    let mut writer = GeoJsonSeqWriter::new(&mut out);
+    assert!(matches!(
+        writer.write(geo::Coordinate { x: f64::NAN, y: 2. }, &[0, 1]),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+    assert!(matches!(
+        writer.write(geo::Coordinate { x: 1., y: f64::INFINITY }, &[0, 1]),
+        Err(IntersectError::InvalidData(m)) if m.contains("non-finite")
+    ));
+    assert_eq!(writer.written(), 0);
+    drop(writer);
+    assert!(out.is_empty());
+}